@@ -1,6 +1,9 @@
 use criterion::{Criterion, criterion_group, criterion_main};
 use std::hint::black_box;
+use std::time::Instant;
 
+use rusty_chess::attacks::database::{AttacksDB, SliderBackend};
+use rusty_chess::attacks::rays::{bishop_attacks_slow, rook_attacks_slow};
 use rusty_chess::board::{Board, ChessMove};
 use rusty_chess::search::ChessEngine;
 
@@ -57,6 +60,85 @@ fn criterion_benchmark(c: &mut Criterion) {
     }
 }
 
+/// Occupancies of every benchmark position, for the sliding attack lookups
+fn benchmark_occupancies() -> Vec<u64> {
+    BENCHMARK_POSITIONS
+        .iter()
+        .map(|(_, fen)| Board::from_fen(fen).occ_all)
+        .chain([Board::startpos().occ_all])
+        .collect()
+}
+
+/// Rook and bishop attacks from every square for every occupancy.
+fn slider_lookups(
+    occupancies: &[u64],
+    rook: impl Fn(usize, u64) -> u64,
+    bishop: impl Fn(usize, u64) -> u64,
+) -> u64 {
+    let mut sum = 0u64;
+    for &occ in occupancies {
+        for square in 0..64 {
+            sum = sum.wrapping_add(rook(square, occ) ^ bishop(square, occ));
+        }
+    }
+    sum
+}
+
+fn slider_benchmark(c: &mut Criterion) {
+    let occupancies = benchmark_occupancies();
+    let mut backends = vec![
+        AttacksDB::embedded()
+            .with_backend(SliderBackend::Magic)
+            .unwrap(),
+    ];
+    if SliderBackend::Pext.is_supported() {
+        backends.push(
+            AttacksDB::embedded()
+                .with_backend(SliderBackend::Pext)
+                .unwrap(),
+        );
+    }
+
+    let mut group = c.benchmark_group("Slider attacks");
+    for tables in &backends {
+        group.bench_function(format!("{:?}", tables.backend()), |b| {
+            b.iter(|| {
+                black_box(slider_lookups(
+                    black_box(&occupancies),
+                    |sq, occ| tables.rook_attacks(sq, occ),
+                    |sq, occ| tables.bishop_attacks(sq, occ),
+                ))
+            });
+        });
+    }
+    group.bench_function("Ray walk", |b| {
+        b.iter(|| {
+            black_box(slider_lookups(
+                black_box(&occupancies),
+                rook_attacks_slow,
+                bishop_attacks_slow,
+            ))
+        });
+    });
+    group.finish();
+
+    // Criterion reports each backend separately; also print the ratio directly
+    if let [magic, pext] = backends.as_slice() {
+        let time = |tables: &AttacksDB| {
+            let start = Instant::now();
+            for _ in 0..20_000 {
+                black_box(slider_lookups(
+                    black_box(&occupancies),
+                    |sq, occ| tables.rook_attacks(sq, occ),
+                    |sq, occ| tables.bishop_attacks(sq, occ),
+                ));
+            }
+            start.elapsed().as_secs_f64()
+        };
+        println!("PEXT speedup over magic: {:.2}x", time(magic) / time(pext));
+    }
+}
+
 // 4. Configure the Criterion Groups
-criterion_group!(benches, criterion_benchmark);
+criterion_group!(benches, criterion_benchmark, slider_benchmark);
 criterion_main!(benches);
@@ -1,4 +1,4 @@
-use once_cell::sync::Lazy;
+use once_cell::sync::{Lazy, OnceCell};
 use std::sync::Arc;
 
 use crate::board::Color;
@@ -8,9 +8,9 @@ use super::knights::KNIGHT_ATTACKS;
 use super::magics::{BISHOP_MAGICS, ROOK_MAGICS};
 use super::masks::{BISHOP_MASKS, ROOK_MASKS};
 use super::pawns::{BLACK_PAWN_ATTACKS, WHITE_PAWN_ATTACKS};
+use super::rays::{bishop_attacks_slow, rook_attacks_slow};
 use super::relevant_bits::{BISHOP_RELEVANT_BITS, ROOK_RELEVANT_BITS};
-use std::fs::File;
-use std::io::{BufReader, Read};
+use std::io::{Error, ErrorKind, Read};
 
 // =========
 // CONSTANTS
@@ -18,69 +18,271 @@ use std::io::{BufReader, Read};
 
 pub const ATTACKS_DB_BIN: &str = "attack_tables.bin";
 
+/// The rook and bishop tables from `ATTACKS_DB_BIN`, embedded at compile time
+const EMBEDDED_ATTACK_TABLES: &[u8] = include_bytes!("../../attack_tables.bin");
+
 pub const BOARD_SIZE: usize = 64;
 
 // ================
 // ATTACKS DATABASE
 // ================
 
+/// How rook and bishop attacks are looked up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SliderBackend {
+    /// Magic multiplication into the tables from `ATTACKS_DB_BIN`
+    Magic,
+    /// The BMI2 `pext` instruction (x86_64 only) into tables built at startup
+    Pext,
+}
+
+impl SliderBackend {
+    /// The fastest backend the CPU supports.
+    pub fn detect() -> Self {
+        if Self::Pext.is_supported() {
+            Self::Pext
+        } else {
+            Self::Magic
+        }
+    }
+
+    pub fn is_supported(self) -> bool {
+        match self {
+            Self::Magic => true,
+            #[cfg(target_arch = "x86_64")]
+            Self::Pext => std::is_x86_feature_detected!("bmi2"),
+            #[cfg(not(target_arch = "x86_64"))]
+            Self::Pext => false,
+        }
+    }
+}
+
+/// Attack sets of one slider type, with every square's table in a single allocation.
+struct SliderTable {
+    masks: &'static [u64; BOARD_SIZE],
+    magics: &'static [u64; BOARD_SIZE],
+    shifts: [u32; BOARD_SIZE],
+    // Start of each square's entries; both layouts have 2^relevant_bits per square
+    offsets: [usize; BOARD_SIZE],
+    magic_attacks: Vec<u64>,
+    // Indexed by `pext(occupancy, mask)`; empty until the PEXT backend is selected
+    pext_attacks: Vec<u64>,
+}
+
+impl SliderTable {
+    /// Reads the magic-indexed tables for every square from `reader`.
+    fn read(
+        reader: &mut &[u8],
+        masks: &'static [u64; BOARD_SIZE],
+        magics: &'static [u64; BOARD_SIZE],
+        relevant_bits: &[u32; BOARD_SIZE],
+    ) -> std::io::Result<Self> {
+        let mut offsets = [0; BOARD_SIZE];
+        let mut total = 0;
+        for (offset, &bits) in offsets.iter_mut().zip(relevant_bits) {
+            *offset = total;
+            total += 1 << bits;
+        }
+
+        let mut magic_attacks = Vec::with_capacity(total);
+        for _ in 0..total {
+            let mut buf = [0u8; 8];
+            reader.read_exact(&mut buf)?;
+            magic_attacks.push(u64::from_le_bytes(buf));
+        }
+
+        Ok(Self {
+            masks,
+            magics,
+            shifts: relevant_bits.map(|bits| 64 - bits),
+            offsets,
+            magic_attacks,
+            pext_attacks: Vec::new(),
+        })
+    }
+
+    /// Fills the PEXT tables from the ray-walking reference.
+    fn build_pext(&mut self, attacks_slow: fn(usize, u64) -> u64) {
+        let mut pext_attacks = vec![0; self.magic_attacks.len()];
+        for square in 0..BOARD_SIZE {
+            let mask = self.masks[square];
+            for index in 0..1usize << mask.count_ones() {
+                // Entry `index` holds the occupancy whose mask bits spell out `index`
+                pext_attacks[self.offsets[square] + index] =
+                    attacks_slow(square, deposit(index as u64, mask));
+            }
+        }
+        self.pext_attacks = pext_attacks;
+    }
+
+    #[inline]
+    fn magic(&self, square: usize, occ_all: u64) -> u64 {
+        let index = ((occ_all & self.masks[square]).wrapping_mul(self.magics[square]))
+            >> self.shifts[square];
+        self.magic_attacks[self.offsets[square] + index as usize]
+    }
+
+    #[inline]
+    fn pext(&self, square: usize, occ_all: u64) -> u64 {
+        // SAFETY: the PEXT backend is only selected after BMI2 support was detected
+        let index = unsafe { pext(occ_all, self.masks[square]) };
+        self.pext_attacks[self.offsets[square] + index as usize]
+    }
+}
+
+// Inline assembly rather than the `_pext_u64` intrinsic: a `#[target_feature]` function
+// cannot be inlined into callers compiled without BMI2, and the call overhead costs more
+// than the lookup saves.
+#[cfg(target_arch = "x86_64")]
+#[inline(always)]
+unsafe fn pext(value: u64, mask: u64) -> u64 {
+    let result: u64;
+    // SAFETY: callers guarantee the CPU supports BMI2
+    unsafe {
+        std::arch::asm!(
+            "pext {0}, {1}, {2}",
+            out(reg) result,
+            in(reg) value,
+            in(reg) mask,
+            options(pure, nomem, nostack),
+        );
+    }
+    result
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+unsafe fn pext(_value: u64, _mask: u64) -> u64 {
+    unreachable!("the PEXT backend is only available on x86_64")
+}
+
+/// Scatters the low bits of `index` onto the set bits of `mask` (software PDEP).
+fn deposit(index: u64, mask: u64) -> u64 {
+    let mut result = 0;
+    let mut remaining = mask;
+    let mut bit = 0;
+    while remaining != 0 {
+        let lowest = remaining & remaining.wrapping_neg();
+        if index & (1 << bit) != 0 {
+            result |= lowest;
+        }
+        remaining &= remaining - 1;
+        bit += 1;
+    }
+    result
+}
+
 pub struct AttacksDB {
-    pub rook: Vec<Vec<u64>>,
-    pub bishop: Vec<Vec<u64>>,
+    rook: SliderTable,
+    bishop: SliderTable,
+    backend: SliderBackend,
     pub white_pawn: [u64; 64],
     pub black_pawn: [u64; 64],
     pub knight: [u64; 64],
     pub king: [u64; 64],
 }
 
-// The AttacksDB global static reference for attack table queries
+// The tables in use: set by `install_attacks_db`, or to the embedded ones on the first
+// lookup. Both go through this one cell, so installing can't race with a lookup.
+static CHOSEN_ATTACKS_DB: OnceCell<Arc<AttacksDB>> = OnceCell::new();
+
+// The AttacksDB global static reference for attack table queries.
 pub static ATTACKS_DB: Lazy<Arc<AttacksDB>> = Lazy::new(|| {
-    let tables = AttacksDB::load_from_bin(ATTACKS_DB_BIN).expect("Failed to load AttacksDB bin!");
-    Arc::new(tables)
+    CHOSEN_ATTACKS_DB
+        .get_or_init(|| Arc::new(AttacksDB::embedded()))
+        .clone()
 });
 
+/// Loads the attack tables from a file instead of the embedded copy.
+///
+/// Must be called before the first attack lookup (i.e. before any board is created);
+/// returns an `AlreadyExists` error once the tables are in use or were already set.
+pub fn init_attacks_db(path: &str) -> std::io::Result<()> {
+    install_attacks_db(AttacksDB::load_from_bin(path)?)
+}
+
+/// Makes `tables` the global attack tables, e.g. to force a `SliderBackend`:
+///
+/// ```no_run
+/// use rusty_chess::attacks::database::{AttacksDB, SliderBackend, install_attacks_db};
+///
+/// let tables = AttacksDB::embedded().with_backend(SliderBackend::Magic).unwrap();
+/// install_attacks_db(tables).unwrap();
+/// ```
+///
+/// Has the same restrictions as `init_attacks_db`.
+pub fn install_attacks_db(tables: AttacksDB) -> std::io::Result<()> {
+    CHOSEN_ATTACKS_DB.set(Arc::new(tables)).map_err(|_| {
+        Error::new(
+            ErrorKind::AlreadyExists,
+            "attack tables are already initialized",
+        )
+    })
+}
+
 // ===============================
 // ATTACKS DATABASE IMPLEMENTATION
 // ===============================
 
 impl AttacksDB {
+    /// The tables compiled into the library; no file access needed.
+    pub fn embedded() -> Self {
+        Self::from_bytes(EMBEDDED_ATTACK_TABLES).expect("Embedded attack tables are invalid!")
+    }
+
     pub fn load_from_bin(path: &str) -> std::io::Result<Self> {
-        let file: File = File::open(path)?;
-        let mut reader: BufReader<File> = BufReader::new(file);
-
-        let mut rook: Vec<Vec<u64>> = Vec::with_capacity(BOARD_SIZE);
-        let mut bishop: Vec<Vec<u64>> = Vec::with_capacity(BOARD_SIZE);
-
-        for &relevant_bits in ROOK_RELEVANT_BITS.iter().take(BOARD_SIZE) {
-            let size = 1 << relevant_bits;
-            let mut table = Vec::with_capacity(size);
-            for _ in 0..size {
-                let mut buf = [0u8; 8];
-                reader.read_exact(&mut buf)?;
-                table.push(u64::from_le_bytes(buf));
-            }
-            rook.push(table);
-        }
+        Self::from_bytes(&std::fs::read(path)?)
+    }
 
-        for &relevant_bits in BISHOP_RELEVANT_BITS.iter().take(BOARD_SIZE) {
-            let size = 1 << relevant_bits;
-            let mut table = Vec::with_capacity(size);
-            for _ in 0..size {
-                let mut buf = [0u8; 8];
-                reader.read_exact(&mut buf)?;
-                table.push(u64::from_le_bytes(buf));
-            }
-            bishop.push(table);
+    /// Parses tables in the `ATTACKS_DB_BIN` layout: every rook table, then every bishop
+    /// table, as little-endian u64s. Uses the backend from `SliderBackend::detect`.
+    pub fn from_bytes(bytes: &[u8]) -> std::io::Result<Self> {
+        let mut reader = bytes;
+        let rook = SliderTable::read(&mut reader, &ROOK_MASKS, &ROOK_MAGICS, &ROOK_RELEVANT_BITS)?;
+        let bishop = SliderTable::read(
+            &mut reader,
+            &BISHOP_MASKS,
+            &BISHOP_MAGICS,
+            &BISHOP_RELEVANT_BITS,
+        )?;
+
+        if !reader.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "unexpected data after the attack tables",
+            ));
         }
 
-        Ok(Self {
+        let tables = Self {
             rook,
             bishop,
+            backend: SliderBackend::Magic,
             white_pawn: WHITE_PAWN_ATTACKS,
             black_pawn: BLACK_PAWN_ATTACKS,
             knight: KNIGHT_ATTACKS,
             king: KING_ATTACKS,
-        })
+        };
+        tables.with_backend(SliderBackend::detect())
+    }
+
+    /// Switches the sliding attack backend, building its tables if needed.
+    /// Fails with `Unsupported` if the CPU cannot run it.
+    pub fn with_backend(mut self, backend: SliderBackend) -> std::io::Result<Self> {
+        if !backend.is_supported() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                format!("{:?} attacks are not supported on this CPU", backend),
+            ));
+        }
+        if backend == SliderBackend::Pext && self.rook.pext_attacks.is_empty() {
+            self.rook.build_pext(rook_attacks_slow);
+            self.bishop.build_pext(bishop_attacks_slow);
+        }
+        self.backend = backend;
+        Ok(self)
+    }
+
+    pub fn backend(&self) -> SliderBackend {
+        self.backend
     }
 
     #[inline]
@@ -93,14 +295,10 @@ impl AttacksDB {
 
     #[inline]
     pub fn rook_attacks(&self, square: usize, occ_all: u64) -> u64 {
-        Self::lookup(
-            square,
-            occ_all,
-            ROOK_MASKS[square],
-            ROOK_MAGICS[square],
-            ROOK_RELEVANT_BITS[square],
-            &self.rook,
-        )
+        match self.backend {
+            SliderBackend::Magic => self.rook.magic(square, occ_all),
+            SliderBackend::Pext => self.rook.pext(square, occ_all),
+        }
     }
 
     #[inline]
@@ -110,14 +308,10 @@ impl AttacksDB {
 
     #[inline]
     pub fn bishop_attacks(&self, square: usize, occ_all: u64) -> u64 {
-        Self::lookup(
-            square,
-            occ_all,
-            BISHOP_MASKS[square],
-            BISHOP_MAGICS[square],
-            BISHOP_RELEVANT_BITS[square],
-            &self.bishop,
-        )
+        match self.backend {
+            SliderBackend::Magic => self.bishop.magic(square, occ_all),
+            SliderBackend::Pext => self.bishop.pext(square, occ_all),
+        }
     }
 
     #[inline]
@@ -129,19 +323,6 @@ impl AttacksDB {
     pub fn king_attacks(&self, square: usize, _occ_all: u64) -> u64 {
         self.king[square]
     }
-
-    #[inline]
-    fn lookup(
-        square: usize,
-        occ_all: u64,
-        mask: u64,
-        magic: u64,
-        relevant_bits: u32,
-        table: &[Vec<u64>],
-    ) -> u64 {
-        let index: u64 = ((occ_all & mask).wrapping_mul(magic)) >> (64 - relevant_bits);
-        table[square][index as usize]
-    }
 }
 
 #[cfg(test)]
@@ -352,6 +533,89 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_embedded_tables_match_file() {
+        let embedded = AttacksDB::embedded();
+        let from_file = AttacksDB::load_from_bin(ATTACKS_DB_BIN).unwrap();
+        assert_eq!(embedded.rook.magic_attacks, from_file.rook.magic_attacks);
+        assert_eq!(
+            embedded.bishop.magic_attacks,
+            from_file.bishop.magic_attacks
+        );
+    }
+
+    #[test]
+    fn test_backends_match_ray_walk() {
+        let mut backends = vec![
+            AttacksDB::embedded()
+                .with_backend(SliderBackend::Magic)
+                .unwrap(),
+        ];
+        if SliderBackend::Pext.is_supported() {
+            backends.push(
+                AttacksDB::embedded()
+                    .with_backend(SliderBackend::Pext)
+                    .unwrap(),
+            );
+        }
+
+        // Every blocker arrangement on each square's rays, plus noise off the rays
+        let noise = 0x8100_0000_0000_0081;
+        for tables in &backends {
+            for square in 0..BOARD_SIZE {
+                for (mask, slow, lookup) in [
+                    (
+                        ROOK_MASKS[square],
+                        rook_attacks_slow as fn(usize, u64) -> u64,
+                        AttacksDB::rook_attacks as fn(&AttacksDB, usize, u64) -> u64,
+                    ),
+                    (
+                        BISHOP_MASKS[square],
+                        bishop_attacks_slow,
+                        AttacksDB::bishop_attacks,
+                    ),
+                ] {
+                    for index in 0..1u64 << mask.count_ones() {
+                        let occ = deposit(index, mask) | (noise & !mask & !(1 << square));
+                        assert_eq!(
+                            lookup(tables, square, occ),
+                            slow(square, occ),
+                            "{:?} square {} occupancy {:#018X}",
+                            tables.backend(),
+                            square,
+                            occ
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_detected_backend_is_supported() {
+        let backend = SliderBackend::detect();
+        assert!(backend.is_supported());
+        assert_eq!(AttacksDB::embedded().backend(), backend);
+        assert_eq!(deposit(0b101, 0b1110), 0b1010);
+    }
+
+    #[test]
+    fn test_from_bytes_rejects_bad_length() {
+        let truncated = &EMBEDDED_ATTACK_TABLES[..EMBEDDED_ATTACK_TABLES.len() - 8];
+        assert!(AttacksDB::from_bytes(truncated).is_err());
+
+        let mut extended = EMBEDDED_ATTACK_TABLES.to_vec();
+        extended.extend_from_slice(&[0; 8]);
+        assert!(AttacksDB::from_bytes(&extended).is_err());
+    }
+
+    #[test]
+    fn test_init_after_first_use_fails() {
+        let _ = ATTACKS_DB.clone();
+        let err = init_attacks_db(ATTACKS_DB_BIN).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+    }
+
     /// Print a bitboard visually for debugging
     fn print_bitboard(bb: u64) {
         println!("Bitboard: 0x{:016X}", bb);
@@ -4,4 +4,5 @@ pub mod knights;
 pub mod magics;
 pub mod masks;
 pub mod pawns;
+pub mod rays;
 pub mod relevant_bits;
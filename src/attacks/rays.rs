@@ -0,0 +1,36 @@
+//! Slow sliding attack generation by walking rays square by square.
+//!
+//! Used to build the PEXT tables and as the reference the table lookups are tested against.
+
+const ROOK_DIRECTIONS: [(i32, i32); 4] = [(0, 1), (0, -1), (1, 0), (-1, 0)];
+const BISHOP_DIRECTIONS: [(i32, i32); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+pub fn rook_attacks_slow(square: usize, occ_all: u64) -> u64 {
+    sliding_attacks(square, occ_all, &ROOK_DIRECTIONS)
+}
+
+pub fn bishop_attacks_slow(square: usize, occ_all: u64) -> u64 {
+    sliding_attacks(square, occ_all, &BISHOP_DIRECTIONS)
+}
+
+/// Attacked squares along each (rank, file) direction, up to and including the first blocker.
+fn sliding_attacks(square: usize, occ_all: u64, directions: &[(i32, i32)]) -> u64 {
+    let mut attacks: u64 = 0;
+    let rank = (square / 8) as i32;
+    let file = (square % 8) as i32;
+
+    for &(dr, df) in directions {
+        let (mut r, mut f) = (rank + dr, file + df);
+        while (0..8).contains(&r) && (0..8).contains(&f) {
+            let bit = 1u64 << (r * 8 + f);
+            attacks |= bit;
+            if occ_all & bit != 0 {
+                break;
+            }
+            r += dr;
+            f += df;
+        }
+    }
+
+    attacks
+}
@@ -0,0 +1,326 @@
+use rusty_chess::board::{Board, ChessMove, Color};
+use rusty_chess::engine::{
+    Coach, GameAnalysis, MoveQuality, format_score, recording_moves, recording_tags,
+};
+use rusty_chess::eval::{EvalConfig, EvalParams};
+use rusty_chess::metrics::GameRecording;
+use rusty_chess::pgn::parse_pgn;
+use rusty_chess::search::SearchParams;
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process;
+
+const DEFAULT_NODES: u64 = 200_000;
+const DEFAULT_MAX_DEPTH: u8 = 32;
+
+/// The report lists this many of the moves that lost the most
+const CRITICAL_MOMENTS: usize = 5;
+
+/// Narrowest the White and Black columns of the summary table get
+const MIN_COLUMN_WIDTH: usize = 16;
+
+fn print_usage(program_name: &str) {
+    eprintln!("Usage: {} <game> [options]", program_name);
+    eprintln!();
+    eprintln!("Re-analyzes every move of a game: centipawn loss, accuracy per side, the");
+    eprintln!("biggest mistakes and missed mates, and an annotated PGN with the better lines.");
+    eprintln!("<game> is a JSON recording from game_recordings/ or a PGN file.");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!(
+        "  --nodes <n>              Node limit per position (default: {})",
+        DEFAULT_NODES
+    );
+    eprintln!(
+        "  --depth <n>              Maximum search depth per position (default: {})",
+        DEFAULT_MAX_DEPTH
+    );
+    eprintln!("  --game <n>               Which game of a PGN file to analyze (default: 1)");
+    eprintln!("  --output <path>          Where to write the annotated PGN");
+    eprintln!("                           (default: the game's path ending in _annotated.pgn)");
+    eprintln!("  --eval-config <path>     JSON eval config selecting evaluators and their weights");
+    eprintln!("  --eval-params <path>     JSON file of tuned evaluation constants");
+    eprintln!("  --help                   Show this help message");
+    eprintln!();
+    eprintln!("Examples:");
+    eprintln!(
+        "  {} game_recordings/game_2026-10-18_18-04-51.json",
+        program_name
+    );
+    eprintln!("  {} match.pgn --game 3 --nodes 1000000", program_name);
+}
+
+struct Config {
+    input_path: String,
+    output_path: String,
+    nodes: u64,
+    max_depth: u8,
+    game: usize,
+    eval_config_path: Option<String>,
+    eval_params_path: Option<String>,
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value for {}: {}", flag, value))
+}
+
+fn parse_args() -> Result<Config, String> {
+    let args: Vec<String> = env::args().collect();
+    let program_name = &args[0];
+
+    if args.len() < 2 || args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        print_usage(program_name);
+        process::exit(0);
+    }
+
+    let mut input_path = None;
+    let mut output_path = None;
+    let mut config = Config {
+        input_path: String::new(),
+        output_path: String::new(),
+        nodes: DEFAULT_NODES,
+        max_depth: DEFAULT_MAX_DEPTH,
+        game: 1,
+        eval_config_path: None,
+        eval_params_path: None,
+    };
+
+    let mut i = 1;
+    while i < args.len() {
+        let flag = args[i].as_str();
+        let mut value = || -> Result<String, String> {
+            i += 1;
+            args.get(i)
+                .cloned()
+                .ok_or_else(|| format!("{} requires a value", flag))
+        };
+        match flag {
+            "--nodes" => config.nodes = parse_number(flag, &value()?)?,
+            "--depth" => config.max_depth = parse_number(flag, &value()?)?,
+            "--game" => config.game = parse_number(flag, &value()?)?,
+            "--output" => output_path = Some(value()?),
+            "--eval-config" => config.eval_config_path = Some(value()?),
+            "--eval-params" => config.eval_params_path = Some(value()?),
+            _ if flag.starts_with("--") => return Err(format!("Unknown option: {}", flag)),
+            _ if input_path.is_none() => input_path = Some(flag.to_string()),
+            _ => return Err(format!("Unexpected argument: {}", flag)),
+        }
+        i += 1;
+    }
+
+    if config.nodes == 0 || config.max_depth == 0 || config.game == 0 {
+        return Err("--nodes, --depth and --game must be positive".to_string());
+    }
+    config.input_path = input_path.ok_or("No game file given")?;
+    config.output_path = output_path.unwrap_or_else(|| {
+        let stem = Path::new(&config.input_path)
+            .with_extension("")
+            .to_string_lossy()
+            .into_owned();
+        format!("{}_annotated.pgn", stem)
+    });
+    Ok(config)
+}
+
+/// A game to analyze: its start position, moves and PGN tags.
+struct LoadedGame {
+    start_fen: String,
+    moves: Vec<ChessMove>,
+    tags: Vec<(String, String)>,
+}
+
+/// Reads a JSON game recording, or the `game`-th game (from 1) of a PGN file.
+fn load_game(path: &str, game: usize) -> Result<LoadedGame, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("Failed to read '{}': {}", path, e))?;
+
+    if text.trim_start().starts_with('{') {
+        let recording: GameRecording = serde_json::from_str(&text)
+            .map_err(|e| format!("Invalid game recording '{}': {}", path, e))?;
+        let (start_fen, moves) = recording_moves(&recording)?;
+        let tags = recording_tags(&recording, &start_fen);
+        return Ok(LoadedGame {
+            start_fen,
+            moves,
+            tags,
+        });
+    }
+
+    let games = parse_pgn(&text);
+    let pgn = games.get(game - 1).ok_or_else(|| {
+        format!(
+            "'{}' has {} game(s); can't analyze game {}",
+            path,
+            games.len(),
+            game
+        )
+    })?;
+    let moves = pgn
+        .replay(None)
+        .map_err(|e| format!("Game {} of '{}': {}", game, path, e))?
+        .into_iter()
+        .map(|(_, chess_move)| chess_move)
+        .collect();
+    let start_fen = match pgn.tag("FEN") {
+        Some(fen) => fen.to_string(),
+        None => Board::startpos().to_fen(),
+    };
+    let mut tags = pgn.tags.clone();
+    tags.retain(|(name, _)| name != "Annotator");
+    Ok(LoadedGame {
+        start_fen,
+        moves,
+        tags,
+    })
+}
+
+fn create_coach(config: &Config) -> Result<Coach, String> {
+    let mut coach = Coach::new(SearchParams::fixed_nodes(config.max_depth, config.nodes));
+    if let Some(path) = &config.eval_config_path {
+        let eval_config = EvalConfig::load(path)
+            .map_err(|e| format!("Failed to load eval config '{}': {}", path, e))?;
+        coach.engine_mut().set_eval_config(&eval_config)?;
+    }
+    if let Some(path) = &config.eval_params_path {
+        let eval_params = EvalParams::load(path)
+            .map_err(|e| format!("Failed to load eval params '{}': {}", path, e))?;
+        coach.engine_mut().set_eval_params(eval_params);
+    }
+    Ok(coach)
+}
+
+fn print_summary(analysis: &GameAnalysis, tags: &[(String, String)]) {
+    let tag = |name: &str| {
+        tags.iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+            .unwrap_or("?")
+    };
+    let per_side = |f: &dyn Fn(Color) -> String| [f(Color::White), f(Color::Black)];
+    let rows: Vec<(&str, [String; 2])> = vec![
+        (
+            "Player",
+            per_side(&|color| tag(&format!("{:?}", color)).to_string()),
+        ),
+        (
+            "Accuracy",
+            per_side(&|color| match analysis.accuracy(color) {
+                Some(accuracy) => format!("{:.1}%", accuracy),
+                None => "-".to_string(),
+            }),
+        ),
+        (
+            "Avg centipawn loss",
+            per_side(&|color| match analysis.average_centipawn_loss(color) {
+                Some(loss) => format!("{:.0}", loss),
+                None => "-".to_string(),
+            }),
+        ),
+        (
+            "Inaccuracies",
+            per_side(&|color| analysis.count(color, MoveQuality::Inaccuracy).to_string()),
+        ),
+        (
+            "Mistakes",
+            per_side(&|color| analysis.count(color, MoveQuality::Mistake).to_string()),
+        ),
+        (
+            "Blunders",
+            per_side(&|color| analysis.count(color, MoveQuality::Blunder).to_string()),
+        ),
+        (
+            "Missed mates",
+            per_side(&|color| {
+                analysis
+                    .moves_by(color)
+                    .filter(|m| m.missed_mate)
+                    .count()
+                    .to_string()
+            }),
+        ),
+    ];
+
+    let width = rows
+        .iter()
+        .flat_map(|(_, cells)| cells.iter().map(|cell| cell.chars().count()))
+        .max()
+        .unwrap_or(0)
+        .max(MIN_COLUMN_WIDTH);
+    println!();
+    println!("{:<20} {:>width$} {:>width$}", "", "White", "Black");
+    println!("{}", "-".repeat(22 + 2 * width));
+    for (label, [white, black]) in rows {
+        println!("{:<20} {:>width$} {:>width$}", label, white, black);
+    }
+
+    let moments = analysis.critical_moments(CRITICAL_MOMENTS);
+    if moments.is_empty() {
+        println!("\nNo inaccuracies, mistakes or blunders. Well played!");
+        return;
+    }
+    println!("\nCritical moments:");
+    for moment in moments {
+        println!(
+            "  {:<14} {} ({} -> {}), best was {}{}",
+            moment.label(),
+            moment.review.quality,
+            format_score(moment.review.best.score),
+            format_score(moment.review.played_score),
+            moment.best_line.join(" "),
+            if moment.missed_mate {
+                ", which forced mate"
+            } else {
+                ""
+            }
+        );
+    }
+    println!("  (scores from the mover's point of view)");
+}
+
+fn main() {
+    let config = parse_args().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    });
+    let game = load_game(&config.input_path, config.game).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    });
+    let mut coach = create_coach(&config).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    });
+
+    println!(
+        "Analyzing {} moves of {} ({} nodes per position)...",
+        game.moves.len(),
+        config.input_path,
+        config.nodes
+    );
+    let analysis =
+        GameAnalysis::analyze(&game.start_fen, &game.moves, &mut coach, |done, total| {
+            print!("\r  {}/{}", done, total);
+            io::stdout().flush().ok();
+        })
+        .unwrap_or_else(|e| {
+            eprintln!("\nError: {}", e);
+            process::exit(1);
+        });
+    println!();
+
+    print_summary(&analysis, &game.tags);
+
+    let mut tags = game.tags;
+    tags.push((
+        "Annotator".to_string(),
+        "Rusty Chess analyze-game".to_string(),
+    ));
+    if let Err(e) = fs::write(&config.output_path, analysis.to_annotated_pgn(tags)) {
+        eprintln!("Error: Failed to write '{}': {}", config.output_path, e);
+        process::exit(1);
+    }
+    println!("\nAnnotated PGN written to {}", config.output_path);
+}
@@ -0,0 +1,227 @@
+use rusty_chess::board::Color;
+use rusty_chess::opening::{BookResult, OpeningBook};
+use rusty_chess::pgn::{PgnGame, parse_pgn};
+use std::env;
+use std::process;
+
+const DEFAULT_PLIES: usize = 16;
+const DEFAULT_MIN_COUNT: u32 = 2;
+const DEFAULT_OUTPUT: &str = "opening_book.bin";
+
+fn print_usage(program_name: &str) {
+    eprintln!("Usage: {} [options] <pgn files...>", program_name);
+    eprintln!();
+    eprintln!("Builds an opening book from PGN game collections. Every move in the first");
+    eprintln!("plies of each game is added to the book; a move's weight is how often it was");
+    eprintln!("played, and the game results are stored as its win/draw/loss record.");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!(
+        "  --plies <n>             Plies of each game to add to the book (default: {})",
+        DEFAULT_PLIES
+    );
+    eprintln!(
+        "  --min-count <n>         Drop moves played fewer than n times (default: {})",
+        DEFAULT_MIN_COUNT
+    );
+    eprintln!("  --min-elo <n>           Only use games where both players are rated n or more");
+    eprintln!("  --results <list>        Comma-separated results to include, e.g. 1-0,0-1");
+    eprintln!("                          (default: 1-0,0-1,1/2-1/2)");
+    eprintln!(
+        "  --output <path>         Output book file (default: {})",
+        DEFAULT_OUTPUT
+    );
+    eprintln!("  --help                  Show this help message");
+    eprintln!();
+    eprintln!("Examples:");
+    eprintln!("  {} games.pgn", program_name);
+    eprintln!(
+        "  {} --plies 20 --min-elo 2400 --min-count 5 masters1.pgn masters2.pgn",
+        program_name
+    );
+}
+
+struct Config {
+    plies: usize,
+    min_count: u32,
+    min_elo: Option<u32>,
+    results: Vec<String>,
+    output_path: String,
+    pgn_paths: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            plies: DEFAULT_PLIES,
+            min_count: DEFAULT_MIN_COUNT,
+            min_elo: None,
+            results: vec!["1-0".into(), "0-1".into(), "1/2-1/2".into()],
+            output_path: DEFAULT_OUTPUT.to_string(),
+            pgn_paths: Vec::new(),
+        }
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value for {}: {}", flag, value))
+}
+
+fn parse_args() -> Result<Config, String> {
+    let args: Vec<String> = env::args().collect();
+    let program_name = &args[0];
+
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        print_usage(program_name);
+        process::exit(0);
+    }
+
+    let mut config = Config::default();
+
+    let mut i = 1;
+    while i < args.len() {
+        let flag = args[i].as_str();
+        let mut value = || -> Result<String, String> {
+            i += 1;
+            args.get(i)
+                .cloned()
+                .ok_or_else(|| format!("{} requires a value", flag))
+        };
+
+        match flag {
+            "--plies" => config.plies = parse_number(flag, &value()?)?,
+            "--min-count" => config.min_count = parse_number(flag, &value()?)?,
+            "--min-elo" => config.min_elo = Some(parse_number(flag, &value()?)?),
+            "--results" => {
+                config.results = value()?.split(',').map(|r| r.trim().to_string()).collect();
+                if let Some(bad) = config
+                    .results
+                    .iter()
+                    .find(|r| !matches!(r.as_str(), "1-0" | "0-1" | "1/2-1/2"))
+                {
+                    return Err(format!("Unknown result: {}", bad));
+                }
+            }
+            "--output" => config.output_path = value()?,
+            _ if flag.starts_with("--") => return Err(format!("Unknown option: {}", flag)),
+            _ => config.pgn_paths.push(flag.to_string()),
+        }
+        i += 1;
+    }
+
+    if config.pgn_paths.is_empty() {
+        print_usage(program_name);
+        return Err("No PGN files provided".to_string());
+    }
+
+    Ok(config)
+}
+
+/// Whether a game passes the result and rating filters.
+fn accept_game(game: &PgnGame, config: &Config) -> bool {
+    let result_ok = game
+        .tag("Result")
+        .is_some_and(|result| config.results.iter().any(|r| r == result));
+
+    let elo_ok = match config.min_elo {
+        Some(min_elo) => [Color::White, Color::Black]
+            .iter()
+            .all(|&color| game.elo(color).is_some_and(|elo| elo >= min_elo)),
+        None => true,
+    };
+
+    result_ok && elo_ok
+}
+
+/// Result of the game for the side that made a move.
+fn result_for(color: Color, white_score: f64) -> BookResult {
+    let score = match color {
+        Color::White => white_score,
+        Color::Black => 1.0 - white_score,
+    };
+    if score > 0.5 {
+        BookResult::Win
+    } else if score < 0.5 {
+        BookResult::Loss
+    } else {
+        BookResult::Draw
+    }
+}
+
+fn main() {
+    let config = match parse_args() {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let mut book = OpeningBook::new();
+    let mut games_used = 0usize;
+    let mut games_skipped = 0usize;
+    let mut games_invalid = 0usize;
+
+    for path in &config.pgn_paths {
+        let data = match std::fs::read(path) {
+            Ok(data) => data,
+            Err(e) => {
+                eprintln!("Error: Failed to read '{}': {}", path, e);
+                process::exit(1);
+            }
+        };
+        // Older databases are often Latin-1; tags may be mangled but moves are ASCII
+        let text = String::from_utf8_lossy(&data);
+
+        for game in parse_pgn(&text) {
+            // Book positions are keyed from the standard starting position
+            if game.tag("FEN").is_some() || !accept_game(&game, &config) {
+                games_skipped += 1;
+                continue;
+            }
+            let Some(white_score) = game.white_score() else {
+                games_skipped += 1;
+                continue;
+            };
+
+            let plies = match game.replay(Some(config.plies)) {
+                Ok(plies) => plies,
+                Err(e) => {
+                    eprintln!("Warning: Skipping game in '{}': {}", path, e);
+                    games_invalid += 1;
+                    continue;
+                }
+            };
+
+            for (board, chess_move) in plies {
+                book.add_move(board.hash, chess_move);
+                book.record_result(
+                    board.hash,
+                    chess_move,
+                    result_for(board.side_to_move, white_score),
+                );
+            }
+            games_used += 1;
+        }
+    }
+
+    book.prune(config.min_count);
+    book.finalize();
+
+    if let Err(e) = book.save(&config.output_path) {
+        eprintln!("Error: Failed to write '{}': {}", config.output_path, e);
+        process::exit(1);
+    }
+
+    println!(
+        "Used {} games ({} filtered out, {} with illegal moves)",
+        games_used, games_skipped, games_invalid
+    );
+    println!(
+        "Wrote {} positions to {}",
+        book.num_positions(),
+        config.output_path
+    );
+}
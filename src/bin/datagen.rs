@@ -0,0 +1,390 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rusty_chess::board::castling::CastlingSide;
+use rusty_chess::board::{Board, ChessMove, Color};
+use rusty_chess::engine::game_outcome;
+use rusty_chess::eval::{EvalConfig, EvalParams};
+use rusty_chess::movegen::MoveGenerator;
+use rusty_chess::search::{ChessEngine, SearchParams};
+use std::env;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::process;
+use std::time::Instant;
+
+const DEFAULT_GAMES: usize = 100;
+const DEFAULT_NODES: u64 = 5_000;
+const DEFAULT_MAX_DEPTH: u8 = 32;
+const DEFAULT_RANDOM_PLIES: usize = 8;
+const DEFAULT_MAX_PLIES: usize = 400;
+
+/// Scores at or beyond this are mate scores, which make poor training labels
+const MATE_THRESHOLD: i32 = 90_000;
+
+/// Size of one record in the binary format
+const RECORD_SIZE: usize = 32;
+
+fn print_usage(program_name: &str) {
+    eprintln!("Usage: {} [options]", program_name);
+    eprintln!();
+    eprintln!("Plays engine-vs-engine games from randomized openings at a fixed node limit");
+    eprintln!("and writes quiet positions labeled with the search score and game result.");
+    eprintln!("The same seed and options always produce the same output.");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!(
+        "  --games <n>             Number of games to play (default: {})",
+        DEFAULT_GAMES
+    );
+    eprintln!(
+        "  --nodes <n>             Node limit per move (default: {})",
+        DEFAULT_NODES
+    );
+    eprintln!(
+        "  --depth <n>             Maximum search depth per move (default: {})",
+        DEFAULT_MAX_DEPTH
+    );
+    eprintln!(
+        "  --random-plies <n>      Random moves played at the start of each game (default: {})",
+        DEFAULT_RANDOM_PLIES
+    );
+    eprintln!(
+        "  --max-plies <n>         Adjudicate a draw after this many plies (default: {})",
+        DEFAULT_MAX_PLIES
+    );
+    eprintln!("  --seed <n>              Random seed (default: 0)");
+    eprintln!("  --format <text|binary>  Output format (default: text)");
+    eprintln!("  --output <path>         Output file (default: datagen.txt or datagen.bin)");
+    eprintln!("  --eval-config <path>    JSON eval config selecting evaluators and their weights");
+    eprintln!("  --eval-params <path>    JSON file of tuned evaluation constants");
+    eprintln!("  --help                  Show this help message");
+    eprintln!();
+    eprintln!("Text format, one position per line (readable by the tune binary):");
+    eprintln!("  <fen> | <score> | <result>");
+    eprintln!("  score is in centipawns and result is 1.0, 0.5 or 0.0, both from White's view.");
+    eprintln!();
+    eprintln!(
+        "Binary format, {} bytes per position, little-endian:",
+        RECORD_SIZE
+    );
+    eprintln!("  u64     occupancy bitboard");
+    eprintln!("  [u8;16] one nibble per occupied square in ascending square order,");
+    eprintln!("          low nibble first: color << 3 | piece (P=0 N=1 B=2 R=3 Q=4 K=5)");
+    eprintln!("  u8      bit 7: side to move (1 = Black), bits 0-3: castling KQkq");
+    eprintln!("  u8      en passant square (64 = none)");
+    eprintln!("  u8      halfmove clock");
+    eprintln!("  i16     score from White's view, clamped to the i16 range");
+    eprintln!("  u8      result: 0 = Black won, 1 = draw, 2 = White won");
+    eprintln!("  [u8;3]  reserved (zero)");
+    eprintln!();
+    eprintln!("Examples:");
+    eprintln!("  {} --games 1000 --nodes 10000 --seed 42", program_name);
+    eprintln!("  {} --format binary --output selfplay.bin", program_name);
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    Text,
+    Binary,
+}
+
+struct Config {
+    games: usize,
+    nodes: u64,
+    max_depth: u8,
+    random_plies: usize,
+    max_plies: usize,
+    seed: u64,
+    format: Format,
+    output_path: Option<String>,
+    eval_config_path: Option<String>,
+    eval_params_path: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            games: DEFAULT_GAMES,
+            nodes: DEFAULT_NODES,
+            max_depth: DEFAULT_MAX_DEPTH,
+            random_plies: DEFAULT_RANDOM_PLIES,
+            max_plies: DEFAULT_MAX_PLIES,
+            seed: 0,
+            format: Format::Text,
+            output_path: None,
+            eval_config_path: None,
+            eval_params_path: None,
+        }
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value for {}: {}", flag, value))
+}
+
+fn parse_args() -> Result<Config, String> {
+    let args: Vec<String> = env::args().collect();
+    let program_name = &args[0];
+
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        print_usage(program_name);
+        process::exit(0);
+    }
+
+    let mut config = Config::default();
+
+    let mut i = 1;
+    while i < args.len() {
+        let flag = args[i].as_str();
+        let mut value = || -> Result<String, String> {
+            i += 1;
+            args.get(i)
+                .cloned()
+                .ok_or_else(|| format!("{} requires a value", flag))
+        };
+
+        match flag {
+            "--games" => config.games = parse_number(flag, &value()?)?,
+            "--nodes" => {
+                config.nodes = parse_number(flag, &value()?)?;
+                if config.nodes == 0 {
+                    return Err("--nodes must be positive".to_string());
+                }
+            }
+            "--depth" => {
+                config.max_depth = parse_number(flag, &value()?)?;
+                if config.max_depth == 0 {
+                    return Err("--depth must be positive".to_string());
+                }
+            }
+            "--random-plies" => config.random_plies = parse_number(flag, &value()?)?,
+            "--max-plies" => config.max_plies = parse_number(flag, &value()?)?,
+            "--seed" => config.seed = parse_number(flag, &value()?)?,
+            "--format" => {
+                config.format = match value()?.as_str() {
+                    "text" => Format::Text,
+                    "binary" => Format::Binary,
+                    other => return Err(format!("Unknown format: {}", other)),
+                }
+            }
+            "--output" => config.output_path = Some(value()?),
+            "--eval-config" => config.eval_config_path = Some(value()?),
+            "--eval-params" => config.eval_params_path = Some(value()?),
+            _ => return Err(format!("Unknown option: {}", flag)),
+        }
+        i += 1;
+    }
+
+    Ok(config)
+}
+
+/// A position picked for training, scored by the search from White's perspective.
+struct Sample {
+    board: Board,
+    score: i32,
+}
+
+/// Plays one game and returns its quiet positions and the result from White's view.
+fn play_game(engine: &mut ChessEngine, rng: &mut StdRng, config: &Config) -> (Vec<Sample>, f64) {
+    let search_params = SearchParams::fixed_nodes(config.max_depth, config.nodes);
+    let (mut board, mut history) = random_opening(rng, config.random_plies);
+    let mut samples = Vec::new();
+
+    engine.new_game();
+
+    let result = loop {
+        if let Some(outcome) = game_outcome(&board, &history) {
+            break outcome.white_score();
+        }
+        if history.len() > config.max_plies {
+            break 0.5;
+        }
+
+        let Some(mv) = engine.find_best_move_iterative(&board, &search_params) else {
+            break 0.5;
+        };
+        let metrics = engine
+            .get_last_search_metrics()
+            .expect("search always records metrics");
+
+        let score = match board.side_to_move {
+            Color::White => metrics.score,
+            Color::Black => -metrics.score,
+        };
+        if is_quiet(&board, mv) && score.abs() < MATE_THRESHOLD {
+            samples.push(Sample { board, score });
+        }
+
+        board.make_move(mv);
+        history.push(board.hash);
+    };
+
+    (samples, result)
+}
+
+/// Plays random legal moves from the start position, retrying until the game is still in progress.
+fn random_opening(rng: &mut StdRng, plies: usize) -> (Board, Vec<u64>) {
+    let mut moves = Vec::with_capacity(64);
+
+    'attempt: loop {
+        let mut board = Board::startpos();
+        let mut history = vec![board.hash];
+
+        for _ in 0..plies {
+            MoveGenerator::generate_legal_moves(&board, &mut moves);
+            if moves.is_empty() {
+                continue 'attempt;
+            }
+            board.make_move(moves[rng.random_range(0..moves.len())]);
+            history.push(board.hash);
+        }
+
+        if game_outcome(&board, &history).is_none() {
+            return (board, history);
+        }
+    }
+}
+
+/// Positions in check or whose best move captures or promotes are too tactical
+/// for a static evaluation to learn from.
+fn is_quiet(board: &Board, mv: ChessMove) -> bool {
+    !board.in_check(board.side_to_move) && !mv.is_promotion() && !board.is_capture(mv)
+}
+
+fn write_text(writer: &mut impl Write, sample: &Sample, result: f64) -> std::io::Result<()> {
+    writeln!(
+        writer,
+        "{} | {} | {:.1}",
+        sample.board.to_fen(),
+        sample.score,
+        result
+    )
+}
+
+fn write_binary(writer: &mut impl Write, sample: &Sample, result: f64) -> std::io::Result<()> {
+    let board = &sample.board;
+    let mut record = [0u8; RECORD_SIZE];
+
+    record[0..8].copy_from_slice(&board.occ_all.to_le_bytes());
+
+    let mut occupied = board.occ_all;
+    let mut index = 0;
+    while occupied != 0 {
+        let sq = occupied.trailing_zeros() as u8;
+        occupied &= occupied - 1;
+        if let Some((color, piece)) = board.piece_on(sq) {
+            let nibble = ((color as u8) << 3) | piece as u8;
+            record[8 + index / 2] |= nibble << (4 * (index % 2));
+        }
+        index += 1;
+    }
+
+    let mut flags = 0u8;
+    for (bit, (color, side)) in [
+        (Color::White, CastlingSide::KingSide),
+        (Color::White, CastlingSide::QueenSide),
+        (Color::Black, CastlingSide::KingSide),
+        (Color::Black, CastlingSide::QueenSide),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        if board.castling.has(color, side) {
+            flags |= 1 << bit;
+        }
+    }
+    if board.side_to_move == Color::Black {
+        flags |= 1 << 7;
+    }
+
+    record[24] = flags;
+    record[25] = board.en_passant;
+    record[26] = board.halfmove_clock;
+    let score = sample.score.clamp(i16::MIN as i32, i16::MAX as i32) as i16;
+    record[27..29].copy_from_slice(&score.to_le_bytes());
+    record[29] = (result * 2.0).round() as u8;
+
+    writer.write_all(&record)
+}
+
+fn main() {
+    let config = match parse_args() {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let eval_config = match &config.eval_config_path {
+        Some(path) => EvalConfig::load(path).unwrap_or_else(|e| {
+            eprintln!("Error: Failed to load eval config '{}': {}", path, e);
+            process::exit(1);
+        }),
+        None => EvalConfig::default(),
+    };
+    let params = match &config.eval_params_path {
+        Some(path) => EvalParams::load(path).unwrap_or_else(|e| {
+            eprintln!("Error: Failed to load eval params '{}': {}", path, e);
+            process::exit(1);
+        }),
+        None => EvalParams::default(),
+    };
+
+    let output_path = config.output_path.clone().unwrap_or_else(|| {
+        match config.format {
+            Format::Text => "datagen.txt",
+            Format::Binary => "datagen.bin",
+        }
+        .to_string()
+    });
+    let file = File::create(&output_path).unwrap_or_else(|e| {
+        eprintln!("Error: Failed to create '{}': {}", output_path, e);
+        process::exit(1);
+    });
+    let mut writer = BufWriter::new(file);
+
+    let mut engine = ChessEngine::with_eval_config(&eval_config).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    });
+    engine.set_eval_params(params);
+    engine.set_verbose(false);
+
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let start = Instant::now();
+    let mut total_positions = 0;
+    let mut wins = [0usize; 3]; // Black, draw, White
+
+    for game in 1..=config.games {
+        let (samples, result) = play_game(&mut engine, &mut rng, &config);
+
+        let written = samples.iter().try_for_each(|sample| match config.format {
+            Format::Text => write_text(&mut writer, sample, result),
+            Format::Binary => write_binary(&mut writer, sample, result),
+        });
+        if let Err(e) = written.and_then(|_| writer.flush()) {
+            eprintln!("Error: Failed to write '{}': {}", output_path, e);
+            process::exit(1);
+        }
+
+        total_positions += samples.len();
+        wins[(result * 2.0).round() as usize] += 1;
+        println!(
+            "Game {}/{}: {} positions, result {:.1} (total {}, +{} ={} -{}, {:.1}s)",
+            game,
+            config.games,
+            samples.len(),
+            result,
+            total_positions,
+            wins[2],
+            wins[1],
+            wins[0],
+            start.elapsed().as_secs_f64()
+        );
+    }
+
+    println!("Wrote {} positions to {}", total_positions, output_path);
+}
@@ -29,7 +29,13 @@ fn main() {
 
     // Step 1: Verify FEN parsing and hash consistency
     println!("--- Step 1: FEN Identity Verification ---");
-    let board = Board::from_fen(fen);
+    let board = match Board::try_from_fen(fen) {
+        Ok(board) => board,
+        Err(e) => {
+            eprintln!("Error: Invalid FEN '{}': {}", fen, e);
+            std::process::exit(1);
+        }
+    };
     let regenerated_fen = board.to_fen();
     let reloaded_board = Board::from_fen(&regenerated_fen);
 
@@ -122,7 +128,7 @@ fn main() {
         println!(
             "Best move (fresh TT): {}{}",
             square_name(mv.from()),
-            square_name(mv.to())
+            square_name(mv.destination())
         );
     } else {
         println!("Best move (fresh TT): None");
@@ -142,7 +148,7 @@ fn main() {
         println!(
             "Best move (warm TT): {}{}",
             square_name(mv.from()),
-            square_name(mv.to())
+            square_name(mv.destination())
         );
     } else {
         println!("Best move (warm TT): None");
@@ -164,12 +170,12 @@ fn main() {
                 println!(
                     "  Fresh TT: {}{}",
                     square_name(fresh.from()),
-                    square_name(fresh.to())
+                    square_name(fresh.destination())
                 );
                 println!(
                     "  Warm TT:  {}{}",
                     square_name(warm.from()),
-                    square_name(warm.to())
+                    square_name(warm.destination())
                 );
                 println!();
                 println!("DIAGNOSIS: The transposition table affects move selection.");
@@ -196,7 +202,7 @@ fn main() {
         println!(
             "Best move (cleared TT): {}{}",
             square_name(mv.from()),
-            square_name(mv.to())
+            square_name(mv.destination())
         );
     }
 
@@ -222,7 +228,7 @@ fn main() {
         println!(
             "Best move (new engine): {}{}",
             square_name(mv.from()),
-            square_name(mv.to())
+            square_name(mv.destination())
         );
     }
 
@@ -244,7 +250,7 @@ fn main() {
         println!(
             "Best move (with book): {}{}",
             square_name(mv.from()),
-            square_name(mv.to())
+            square_name(mv.destination())
         );
     } else {
         println!("Best move (with book): None");
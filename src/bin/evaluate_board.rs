@@ -1,11 +1,6 @@
-use rusty_chess::board::{Board, Color, print_board};
-use rusty_chess::eval::{
-    BoardEvaluator, bishop_pair::BishopPairEvaluator, central_control::CentralControlEvaluator,
-    fork::ForkEvaluator, king_safety::KingSafetyEvaluator, knight_outpost::KnightOutpostEvaluator,
-    line_pressure::LinePressureEvaluator, material::MaterialEvaluator, mobility::MobilityEvaluator,
-    pawn_structure::PawnStructureEvaluator, position::PositionEvaluator,
-    rook_file_evaluator::RookFileEvaluator, tempo::TempoEvaluator, threat::ThreatEvaluator,
-};
+use rusty_chess::board::{Board, Color, render_board};
+use rusty_chess::eval::{EvalConfig, EvalParams, Evaluator};
+use rusty_chess::terminal::DisplaySettings;
 use std::env;
 use std::process;
 
@@ -21,8 +16,15 @@ fn print_usage(program_name: &str) {
     eprintln!("  <fen>                   Chess position in FEN notation (required)");
     eprintln!();
     eprintln!("Options:");
+    eprintln!("  --eval-config <path>    JSON eval config selecting evaluators and their weights");
+    eprintln!("  --eval-params <path>    Tuned evaluation constants written by the tune binary");
     eprintln!("  --quiet                 Only output the final score, no breakdown");
     eprintln!("  --json                  Output results in JSON format");
+    eprintln!("  --ascii                 Draw pieces as letters (K, q) instead of chess symbols");
+    eprintln!("  --no-color              Draw the board without colors (also set by NO_COLOR)");
+    eprintln!("  --flip                  Draw the board from Black's side");
+    eprintln!("  --no-coordinates        Hide the file letters and rank numbers");
+    eprintln!("  --captured              Show the extra material of the side that's ahead");
     eprintln!("  --help                  Show this help message");
     eprintln!();
     eprintln!("Examples:");
@@ -45,6 +47,9 @@ struct Config {
     fen: String,
     quiet: bool,
     json: bool,
+    eval_config_path: Option<String>,
+    eval_params_path: Option<String>,
+    display: DisplaySettings,
 }
 
 fn parse_args() -> Result<Config, String> {
@@ -56,7 +61,10 @@ fn parse_args() -> Result<Config, String> {
         return Err("No FEN position provided".to_string());
     }
 
-    let mut config = Config::default();
+    let mut config = Config {
+        display: DisplaySettings::from_env(),
+        ..Config::default()
+    };
     let mut i = 1;
 
     // Check for help flag first
@@ -83,6 +91,21 @@ fn parse_args() -> Result<Config, String> {
             "--json" => {
                 config.json = true;
             }
+            "--eval-config" => {
+                i += 1;
+                if i >= args.len() {
+                    return Err("--eval-config requires a path".to_string());
+                }
+                config.eval_config_path = Some(args[i].clone());
+            }
+            "--eval-params" => {
+                i += 1;
+                if i >= args.len() {
+                    return Err("--eval-params requires a path".to_string());
+                }
+                config.eval_params_path = Some(args[i].clone());
+            }
+            flag if config.display.parse_board_flag(flag) => {}
             _ => {
                 return Err(format!("Unknown option: {}", args[i]));
             }
@@ -93,18 +116,6 @@ fn parse_args() -> Result<Config, String> {
     Ok(config)
 }
 
-/// Named evaluator for display purposes
-struct NamedEvaluator {
-    name: &'static str,
-    evaluator: Box<dyn BoardEvaluator>,
-}
-
-impl NamedEvaluator {
-    fn new(name: &'static str, evaluator: Box<dyn BoardEvaluator>) -> Self {
-        Self { name, evaluator }
-    }
-}
-
 /// Holds the result of a single evaluator
 struct EvaluatorResult {
     name: &'static str,
@@ -139,34 +150,14 @@ impl EvaluationResult {
     }
 }
 
-fn create_evaluators() -> Vec<NamedEvaluator> {
-    vec![
-        NamedEvaluator::new("Material", Box::new(MaterialEvaluator)),
-        NamedEvaluator::new("Position", Box::new(PositionEvaluator)),
-        NamedEvaluator::new("Pawn Structure", Box::new(PawnStructureEvaluator)),
-        NamedEvaluator::new("Mobility", Box::new(MobilityEvaluator)),
-        NamedEvaluator::new("King Safety", Box::new(KingSafetyEvaluator)),
-        NamedEvaluator::new("Tempo", Box::new(TempoEvaluator)),
-        NamedEvaluator::new("Bishop Pair", Box::new(BishopPairEvaluator)),
-        NamedEvaluator::new("Knight Outpost", Box::new(KnightOutpostEvaluator)),
-        NamedEvaluator::new("Rook Files", Box::new(RookFileEvaluator)),
-        NamedEvaluator::new("Central Control", Box::new(CentralControlEvaluator)),
-        NamedEvaluator::new("Threats", Box::new(ThreatEvaluator)),
-        NamedEvaluator::new("Line Pressure", Box::new(LinePressureEvaluator)),
-        NamedEvaluator::new("Forks", Box::new(ForkEvaluator)),
-    ]
-}
-
-fn evaluate_board(board: &Board) -> EvaluationResult {
-    let evaluators = create_evaluators();
+fn evaluate_board(board: &Board, evaluator: &Evaluator) -> EvaluationResult {
     let mut sub_evaluations = Vec::new();
     let mut total_score: i32 = 0;
 
-    for named_eval in evaluators {
-        let score = named_eval.evaluator.evaluate(board);
+    for (kind, score) in evaluator.evaluate_components(board) {
         total_score += score;
         sub_evaluations.push(EvaluatorResult {
-            name: named_eval.name,
+            name: kind.name(),
             score,
         });
     }
@@ -279,11 +270,45 @@ fn main() {
         }
     };
 
+    let eval_config = match &config.eval_config_path {
+        Some(path) => match EvalConfig::load(path) {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("Error: Failed to load eval config '{}': {}", path, e);
+                process::exit(1);
+            }
+        },
+        None => EvalConfig::default(),
+    };
+    let eval_params = match &config.eval_params_path {
+        Some(path) => match EvalParams::load(path) {
+            Ok(p) => p,
+            Err(e) => {
+                eprintln!("Error: Failed to load eval params '{}': {}", path, e);
+                process::exit(1);
+            }
+        },
+        None => EvalParams::default(),
+    };
+
     // Parse the FEN and create the board
-    let board = Board::from_fen(&config.fen);
+    let board = match Board::try_from_fen(&config.fen) {
+        Ok(board) => board,
+        Err(e) => {
+            eprintln!("Error: Invalid FEN '{}': {}", config.fen, e);
+            process::exit(1);
+        }
+    };
 
     // Evaluate the board
-    let result = evaluate_board(&board);
+    let evaluator = match Evaluator::from_config(&eval_config) {
+        Ok(evaluator) => evaluator.with_params(eval_params),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+    let result = evaluate_board(&board, &evaluator);
 
     if config.json {
         print_json(&config.fen, &board, &result);
@@ -292,7 +317,10 @@ fn main() {
     } else {
         println!();
         println!("Position:");
-        print_board(&board);
+        print!(
+            "{}",
+            render_board(&board, &config.display.board_style(None), None)
+        );
         println!();
         println!("Side to move: {:?}", board.side_to_move);
         println!();
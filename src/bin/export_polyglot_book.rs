@@ -0,0 +1,50 @@
+use rusty_chess::opening::{
+    OpeningBook, PolyglotBook, create_colle_system_opening_book, create_london_system_opening_book,
+};
+use std::env;
+use std::process;
+
+fn print_usage(program_name: &str) {
+    eprintln!("Usage: {} <book> <output>", program_name);
+    eprintln!();
+    eprintln!("Exports an opening book in the Polyglot .bin format used by most chess GUIs.");
+    eprintln!();
+    eprintln!("Arguments:");
+    eprintln!("  <book>                  london, colle, or the path to a native book file");
+    eprintln!("  <output>                Where to write the Polyglot book");
+    eprintln!();
+    eprintln!("Examples:");
+    eprintln!("  {} london london.bin", program_name);
+    eprintln!("  {} ./opening_book.bin book-polyglot.bin", program_name);
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let program_name = &args[0];
+
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        print_usage(program_name);
+        process::exit(0);
+    }
+    if args.len() != 3 {
+        print_usage(program_name);
+        process::exit(1);
+    }
+
+    let native = match args[1].as_str() {
+        "london" => create_london_system_opening_book(),
+        "colle" => create_colle_system_opening_book(),
+        path => OpeningBook::load(path).unwrap_or_else(|e| {
+            eprintln!("Error: Failed to load opening book '{}': {}", path, e);
+            process::exit(1);
+        }),
+    };
+
+    let book = PolyglotBook::from_opening_book(&native);
+    if let Err(e) = book.save(&args[2]) {
+        eprintln!("Error: Failed to write '{}': {}", args[2], e);
+        process::exit(1);
+    }
+
+    println!("Wrote {} entries to {}", book.len(), args[2]);
+}
@@ -1,8 +1,12 @@
-use rusty_chess::board::{Board, print_board};
-use rusty_chess::eval::Evaluator;
+use rusty_chess::board::{Board, render_board};
+use rusty_chess::eval::{EvalConfig, EvalParams, Evaluator};
+use rusty_chess::opening::{BookSelection, SelectionPolicy};
 use rusty_chess::search::{ChessEngine, SearchParams};
+use rusty_chess::tablebase::{SyzygyTablebase, Tablebase};
+use rusty_chess::terminal::DisplaySettings;
 use std::env;
 use std::process;
+use std::sync::Arc;
 
 const DEFAULT_MAX_DEPTH: u8 = 5;
 const DEFAULT_MIN_SEARCH_TIME_MS: u64 = 16000;
@@ -26,7 +30,19 @@ fn print_usage(program_name: &str) {
     eprintln!(
         "  --book <path>           Path to opening book file (falls back to built-in London System)"
     );
+    eprintln!("                          Native and Polyglot .bin books are both accepted");
+    eprintln!("  --book-random           Pick book moves at random, weighted by frequency");
+    eprintln!("  --book-seed <n>         Seed for random book move selection (default: 0)");
+    eprintln!("  --book-min-weight <n>   Never play book moves with a weight below n");
+    eprintln!("  --syzygy-path <dir>     Directory of Syzygy endgame tablebase files");
+    eprintln!("  --eval-config <path>    JSON eval config selecting evaluators and their weights");
+    eprintln!("  --eval-params <path>    Tuned evaluation constants written by the tune binary");
     eprintln!("  --quiet                 Only output the best move, no statistics");
+    eprintln!("  --ascii                 Draw pieces as letters (K, q) instead of chess symbols");
+    eprintln!("  --no-color              Draw the board without colors (also set by NO_COLOR)");
+    eprintln!("  --flip                  Draw the board from Black's side");
+    eprintln!("  --no-coordinates        Hide the file letters and rank numbers");
+    eprintln!("  --captured              Show the extra material of the side that's ahead");
     eprintln!("  --help                  Show this help message");
     eprintln!();
     eprintln!("Examples:");
@@ -60,7 +76,13 @@ struct Config {
     min_search_time_ms: u64,
     use_opening_book: bool,
     opening_book_path: String,
+    book_selection: BookSelection,
+    book_seed: u64,
+    syzygy_path: Option<String>,
+    eval_config_path: Option<String>,
+    eval_params_path: Option<String>,
     quiet: bool,
+    display: DisplaySettings,
 }
 
 impl Default for Config {
@@ -71,7 +93,13 @@ impl Default for Config {
             min_search_time_ms: DEFAULT_MIN_SEARCH_TIME_MS,
             use_opening_book: true,
             opening_book_path: "./opening_book.bin".to_string(),
+            book_selection: BookSelection::best_only(),
+            book_seed: 0,
+            syzygy_path: None,
+            eval_config_path: None,
+            eval_params_path: None,
             quiet: false,
+            display: DisplaySettings::from_env(),
         }
     }
 }
@@ -137,9 +165,52 @@ fn parse_args() -> Result<Config, String> {
                 }
                 config.opening_book_path = args[i].clone();
             }
+            "--book-random" => {
+                config.book_selection.policy = SelectionPolicy::WeightedRandom;
+            }
+            "--book-seed" => {
+                i += 1;
+                if i >= args.len() {
+                    return Err("--book-seed requires a value".to_string());
+                }
+                config.book_seed = args[i]
+                    .parse()
+                    .map_err(|_| format!("Invalid seed value: {}", args[i]))?;
+            }
+            "--book-min-weight" => {
+                i += 1;
+                if i >= args.len() {
+                    return Err("--book-min-weight requires a value".to_string());
+                }
+                config.book_selection.min_weight = args[i]
+                    .parse()
+                    .map_err(|_| format!("Invalid weight value: {}", args[i]))?;
+            }
+            "--syzygy-path" => {
+                i += 1;
+                if i >= args.len() {
+                    return Err("--syzygy-path requires a directory".to_string());
+                }
+                config.syzygy_path = Some(args[i].clone());
+            }
+            "--eval-config" => {
+                i += 1;
+                if i >= args.len() {
+                    return Err("--eval-config requires a path".to_string());
+                }
+                config.eval_config_path = Some(args[i].clone());
+            }
+            "--eval-params" => {
+                i += 1;
+                if i >= args.len() {
+                    return Err("--eval-params requires a path".to_string());
+                }
+                config.eval_params_path = Some(args[i].clone());
+            }
             "--quiet" => {
                 config.quiet = true;
             }
+            flag if config.display.parse_board_flag(flag) => {}
             _ => {
                 return Err(format!("Unknown option: {}", args[i]));
             }
@@ -159,12 +230,42 @@ fn main() {
         }
     };
 
+    let eval_config = match &config.eval_config_path {
+        Some(path) => match EvalConfig::load(path) {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("Error: Failed to load eval config '{}': {}", path, e);
+                process::exit(1);
+            }
+        },
+        None => EvalConfig::default(),
+    };
+    let eval_params = match &config.eval_params_path {
+        Some(path) => match EvalParams::load(path) {
+            Ok(p) => p,
+            Err(e) => {
+                eprintln!("Error: Failed to load eval params '{}': {}", path, e);
+                process::exit(1);
+            }
+        },
+        None => EvalParams::default(),
+    };
+
     // Parse the FEN and create the board
-    let board = Board::from_fen(&config.fen);
+    let board = match Board::try_from_fen(&config.fen) {
+        Ok(board) => board,
+        Err(e) => {
+            eprintln!("Error: Invalid FEN '{}': {}", config.fen, e);
+            process::exit(1);
+        }
+    };
 
     if !config.quiet {
         println!("Position:");
-        print_board(&board);
+        print!(
+            "{}",
+            render_board(&board, &config.display.board_style(None), None)
+        );
         println!();
         println!("Side to move: {:?}", board.side_to_move);
         println!(
@@ -189,30 +290,60 @@ fn main() {
     } else {
         ChessEngine::new()
     };
+    engine.set_book_selection(config.book_selection);
+    engine.set_book_seed(config.book_seed);
+    if let Err(e) = engine.set_eval_config(&eval_config) {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+    engine.set_eval_params(eval_params.clone());
+    if let Some(path) = &config.syzygy_path {
+        match SyzygyTablebase::open(path) {
+            Ok(tablebase) => {
+                if !config.quiet {
+                    println!(
+                        "Syzygy: {} WDL and {} DTZ tables, up to {} pieces",
+                        tablebase.num_wdl_tables(),
+                        tablebase.num_dtz_tables(),
+                        tablebase.max_pieces()
+                    );
+                }
+                engine.set_tablebase(Some(Arc::new(tablebase)));
+            }
+            Err(e) => {
+                eprintln!("Error: Failed to open Syzygy tables in '{}': {}", path, e);
+                process::exit(1);
+            }
+        }
+    }
 
     // Set up search parameters
     let search_params = SearchParams {
         max_depth: config.max_depth,
         min_search_time_ms: config.min_search_time_ms,
+        max_nodes: None,
     };
 
     // Find the best move
     match engine.find_best_move_iterative(&board, &search_params) {
         Some(best_move) => {
             let from = square_to_notation(best_move.from());
-            let to = square_to_notation(best_move.to());
+            let to = square_to_notation(best_move.destination());
 
             if config.quiet {
                 println!("{}{}", from, to);
             } else {
                 println!("Best move: {}{}", from, to);
-                if board.piece_on(best_move.to() as u8).is_some() || best_move.is_en_passant() {
+                if board.is_capture(best_move) {
                     println!("Move type: Capture");
                 }
                 println!();
 
                 // Show evaluation breakdown
-                let evaluator = Evaluator::new();
+                // Built from the same config as the engine's, so it can't fail here
+                let evaluator = Evaluator::from_config(&eval_config)
+                    .expect("the engine's evaluator was built from this config")
+                    .with_params(eval_params);
 
                 // Current position evaluation
                 println!("=== Current Position Evaluation ===");
@@ -224,6 +355,14 @@ fn main() {
                 let mut board_after = board;
                 board_after.make_move(best_move);
 
+                print!(
+                    "{}",
+                    render_board(
+                        &board_after,
+                        &config.display.board_style(None),
+                        Some(best_move)
+                    )
+                );
                 println!("=== After {}{} Evaluation ===", from, to);
                 let after_eval = evaluator.evaluate_detailed(&board_after);
                 println!("{}", after_eval);
@@ -0,0 +1,691 @@
+use chrono::Local;
+use rusty_chess::board::{Board, Color};
+use rusty_chess::engine::{GameOutcome, MatchScore, Sprt, SprtStatus, game_outcome};
+use rusty_chess::eval::{EvalConfig, EvalParams};
+use rusty_chess::opening::Book;
+use rusty_chess::pgn::{PgnGame, parse_pgn, parse_san, to_san};
+use rusty_chess::search::{ChessEngine, MAX_SKILL_LEVEL, SearchParams, Skill};
+use std::env;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::process;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Instant;
+
+const DEFAULT_GAMES: usize = 100;
+const DEFAULT_NODES: u64 = 10_000;
+const DEFAULT_MAX_DEPTH: u8 = 32;
+const DEFAULT_MAX_PLIES: usize = 400;
+const DEFAULT_PGN_PATH: &str = "match.pgn";
+const DEFAULT_HASH_MB: usize = 64;
+
+/// Balanced opening lines used when no --openings file is given
+const DEFAULT_OPENINGS: &[&str] = &[
+    "e4 e5 Nf3 Nc6 Bb5 a6",
+    "e4 e5 Nf3 Nc6 Bc4 Bc5",
+    "e4 c5 Nf3 d6 d4 cxd4 Nxd4 Nf6 Nc3",
+    "e4 c5 Nc3 Nc6 g3 g6",
+    "e4 e6 d4 d5 Nc3 Nf6",
+    "e4 c6 d4 d5 e5 Bf5",
+    "e4 d5 exd5 Qxd5 Nc3 Qa5",
+    "e4 g6 d4 Bg7 Nc3 d6",
+    "d4 d5 c4 e6 Nc3 Nf6",
+    "d4 d5 c4 c6 Nf3 Nf6",
+    "d4 Nf6 c4 g6 Nc3 Bg7 e4 d6",
+    "d4 Nf6 c4 e6 Nc3 Bb4",
+    "d4 Nf6 Bf4 d5 e3 c5",
+    "d4 f5 g3 Nf6 Bg2 e6",
+    "c4 e5 Nc3 Nf6 Nf3 Nc6",
+    "Nf3 d5 g3 Nf6 Bg2 c6",
+];
+
+fn print_usage(program_name: &str) {
+    eprintln!("Usage: {} [options]", program_name);
+    eprintln!();
+    eprintln!("Plays two engine configurations against each other. Every opening is played");
+    eprintln!("twice with colors swapped, and the result is reported from engine A's view.");
+    eprintln!();
+    eprintln!("Match options:");
+    eprintln!(
+        "  --games <n>              Number of games to play (default: {})",
+        DEFAULT_GAMES
+    );
+    eprintln!("  --threads <n>            Games played in parallel (default: all cores)");
+    eprintln!("  --openings <path>        Opening suite: a PGN file, or one FEN or SAN move");
+    eprintln!("                           sequence per line (default: built-in suite)");
+    eprintln!(
+        "  --max-plies <n>          Adjudicate a draw after this many plies (default: {})",
+        DEFAULT_MAX_PLIES
+    );
+    eprintln!(
+        "  --pgn <path>             Where to write the games (default: {})",
+        DEFAULT_PGN_PATH
+    );
+    eprintln!("  --sprt <elo0>,<elo1>     Stop once an SPRT of H0: elo0 vs H1: elo1 decides");
+    eprintln!("  --alpha <p>              SPRT false positive rate (default: 0.05)");
+    eprintln!("  --beta <p>               SPRT false negative rate (default: 0.05)");
+    eprintln!("  --seed <n>               Seed for opening book choices (default: 0)");
+    eprintln!("  --help                   Show this help message");
+    eprintln!();
+    eprintln!("Engine options, suffixed with -a or -b for one engine or unsuffixed for both:");
+    eprintln!("  --name <name>            Name used in the PGN and report");
+    eprintln!("  --eval-config <path>     JSON eval config selecting evaluators and their weights");
+    eprintln!("  --eval-params <path>     JSON file of tuned evaluation constants");
+    eprintln!("  --book <path>            Opening book (native or Polyglot)");
+    eprintln!(
+        "  --nodes <n>              Node limit per move (default: {})",
+        DEFAULT_NODES
+    );
+    eprintln!(
+        "  --depth <n>              Maximum search depth per move (default: {})",
+        DEFAULT_MAX_DEPTH
+    );
+    eprintln!("  --time <ms>              Search time per move instead of a node limit");
+    eprintln!(
+        "  --hash <mb>              Transposition table size per engine (default: {})",
+        DEFAULT_HASH_MB
+    );
+    eprintln!(
+        "  --skill <level>          Weakened play, 0-{} (default: {}, full strength)",
+        MAX_SKILL_LEVEL, MAX_SKILL_LEVEL
+    );
+    eprintln!("  --elo <elo>              Weakened play at the skill level nearest an Elo");
+    eprintln!();
+    eprintln!("Examples:");
+    eprintln!(
+        "  {} --eval-params-a tuned.json --games 400 --sprt 0,10",
+        program_name
+    );
+    eprintln!(
+        "  {} --depth-a 6 --depth-b 5 --nodes 1000000 --threads 8",
+        program_name
+    );
+}
+
+/// One side of the match, as given on the command line.
+#[derive(Clone)]
+struct EngineSpec {
+    name: String,
+    eval_config_path: Option<String>,
+    eval_params_path: Option<String>,
+    book_path: Option<String>,
+    nodes: u64,
+    max_depth: u8,
+    time_ms: Option<u64>,
+    hash_mb: usize,
+    skill: Skill,
+}
+
+impl EngineSpec {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            eval_config_path: None,
+            eval_params_path: None,
+            book_path: None,
+            nodes: DEFAULT_NODES,
+            max_depth: DEFAULT_MAX_DEPTH,
+            time_ms: None,
+            hash_mb: DEFAULT_HASH_MB,
+            skill: Skill::full_strength(),
+        }
+    }
+}
+
+struct Config {
+    games: usize,
+    threads: usize,
+    openings_path: Option<String>,
+    max_plies: usize,
+    pgn_path: String,
+    sprt: Option<Sprt>,
+    seed: u64,
+    engines: [EngineSpec; 2],
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            games: DEFAULT_GAMES,
+            threads: thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1),
+            openings_path: None,
+            max_plies: DEFAULT_MAX_PLIES,
+            pgn_path: DEFAULT_PGN_PATH.to_string(),
+            sprt: None,
+            seed: 0,
+            engines: [EngineSpec::new("Engine A"), EngineSpec::new("Engine B")],
+        }
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value for {}: {}", flag, value))
+}
+
+fn parse_args() -> Result<Config, String> {
+    let args: Vec<String> = env::args().collect();
+    let program_name = &args[0];
+
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        print_usage(program_name);
+        process::exit(0);
+    }
+
+    let mut config = Config::default();
+    let mut alpha = None;
+    let mut beta = None;
+
+    let mut i = 1;
+    while i < args.len() {
+        let flag = args[i].as_str();
+        let mut value = || -> Result<String, String> {
+            i += 1;
+            args.get(i)
+                .cloned()
+                .ok_or_else(|| format!("{} requires a value", flag))
+        };
+
+        // Engine options apply to one engine with a -a/-b suffix, otherwise to both
+        let (option, engines) = match (flag.strip_suffix("-a"), flag.strip_suffix("-b")) {
+            (Some(option), _) => (option, 0..1),
+            (_, Some(option)) => (option, 1..2),
+            _ => (flag, 0..2),
+        };
+        if matches!(
+            option,
+            "--name"
+                | "--eval-config"
+                | "--eval-params"
+                | "--book"
+                | "--nodes"
+                | "--depth"
+                | "--time"
+                | "--hash"
+                | "--skill"
+                | "--elo"
+        ) {
+            let value = value()?;
+            for spec in &mut config.engines[engines] {
+                match option {
+                    "--name" => spec.name = value.clone(),
+                    "--eval-config" => spec.eval_config_path = Some(value.clone()),
+                    "--eval-params" => spec.eval_params_path = Some(value.clone()),
+                    "--book" => spec.book_path = Some(value.clone()),
+                    "--nodes" => spec.nodes = parse_number(flag, &value)?,
+                    "--depth" => spec.max_depth = parse_number(flag, &value)?,
+                    "--hash" => spec.hash_mb = parse_number(flag, &value)?,
+                    "--skill" => {
+                        let level: u8 = parse_number(flag, &value)?;
+                        if level > MAX_SKILL_LEVEL {
+                            return Err(format!(
+                                "{} must be between 0 and {}",
+                                flag, MAX_SKILL_LEVEL
+                            ));
+                        }
+                        spec.skill = Skill::from_level(level);
+                    }
+                    "--elo" => spec.skill = Skill::from_elo(parse_number(flag, &value)?),
+                    _ => spec.time_ms = Some(parse_number(flag, &value)?),
+                }
+                if spec.nodes == 0
+                    || spec.max_depth == 0
+                    || spec.time_ms == Some(0)
+                    || spec.hash_mb == 0
+                {
+                    return Err(format!("{} must be positive", flag));
+                }
+            }
+            i += 1;
+            continue;
+        }
+
+        match flag {
+            "--games" => config.games = parse_number(flag, &value()?)?,
+            "--threads" => {
+                config.threads = parse_number(flag, &value()?)?;
+                if config.threads == 0 {
+                    return Err("--threads must be positive".to_string());
+                }
+            }
+            "--openings" => config.openings_path = Some(value()?),
+            "--max-plies" => config.max_plies = parse_number(flag, &value()?)?,
+            "--pgn" => config.pgn_path = value()?,
+            "--sprt" => {
+                let v = value()?;
+                let (elo0, elo1) = v
+                    .split_once(',')
+                    .ok_or_else(|| format!("--sprt expects <elo0>,<elo1>, got {}", v))?;
+                let (elo0, elo1): (f64, f64) = (
+                    parse_number(flag, elo0.trim())?,
+                    parse_number(flag, elo1.trim())?,
+                );
+                if elo0 >= elo1 {
+                    return Err("--sprt requires elo0 < elo1".to_string());
+                }
+                config.sprt = Some(Sprt {
+                    elo0,
+                    elo1,
+                    ..Sprt::default()
+                });
+            }
+            "--alpha" => alpha = Some(parse_probability(flag, &value()?)?),
+            "--beta" => beta = Some(parse_probability(flag, &value()?)?),
+            "--seed" => config.seed = parse_number(flag, &value()?)?,
+            _ => return Err(format!("Unknown option: {}", flag)),
+        }
+        i += 1;
+    }
+
+    if let Some(sprt) = &mut config.sprt {
+        sprt.alpha = alpha.unwrap_or(sprt.alpha);
+        sprt.beta = beta.unwrap_or(sprt.beta);
+    } else if alpha.is_some() || beta.is_some() {
+        return Err("--alpha and --beta require --sprt".to_string());
+    }
+
+    Ok(config)
+}
+
+fn parse_probability(flag: &str, value: &str) -> Result<f64, String> {
+    let p: f64 = parse_number(flag, value)?;
+    if p > 0.0 && p < 0.5 {
+        Ok(p)
+    } else {
+        Err(format!("{} must be between 0 and 0.5", flag))
+    }
+}
+
+/// An engine configuration with its files loaded, ready to build engines from.
+struct EngineSetup {
+    name: String,
+    eval_config: EvalConfig,
+    eval_params: EvalParams,
+    book: Option<Book>,
+    search: SearchParams,
+    hash_mb: usize,
+    skill: Skill,
+}
+
+impl EngineSetup {
+    fn load(spec: &EngineSpec) -> Result<Self, String> {
+        let eval_config = match &spec.eval_config_path {
+            Some(path) => EvalConfig::load(path)
+                .map_err(|e| format!("Failed to load eval config '{}': {}", path, e))?,
+            None => EvalConfig::default(),
+        };
+        let eval_params = match &spec.eval_params_path {
+            Some(path) => EvalParams::load(path)
+                .map_err(|e| format!("Failed to load eval params '{}': {}", path, e))?,
+            None => EvalParams::default(),
+        };
+        let book = match &spec.book_path {
+            Some(path) => Some(
+                Book::load(path).map_err(|e| format!("Failed to load book '{}': {}", path, e))?,
+            ),
+            None => None,
+        };
+        let search = match spec.time_ms {
+            Some(ms) => SearchParams::new(spec.max_depth, ms),
+            None => SearchParams::fixed_nodes(spec.max_depth, spec.nodes),
+        };
+
+        Ok(Self {
+            name: spec.name.clone(),
+            eval_config,
+            eval_params,
+            book,
+            search,
+            hash_mb: spec.hash_mb,
+            skill: spec.skill,
+        })
+    }
+
+    fn build(&self) -> ChessEngine {
+        let mut engine = ChessEngine::with_hash_size_mb(self.hash_mb);
+        match &self.book {
+            Some(Book::Native(book)) => engine.set_opening_book(Some(book.clone())),
+            Some(Book::Polyglot(book)) => engine.set_polyglot_book(Some(book.clone())),
+            None => {}
+        }
+        engine
+            .set_eval_config(&self.eval_config)
+            .expect("EvalConfig::load checked that the evaluators build");
+        engine.set_eval_params(self.eval_params.clone());
+        engine.set_skill(self.skill);
+        engine.set_verbose(false);
+        engine
+    }
+}
+
+/// A position games start from.
+struct Opening {
+    board: Board,
+    history: Vec<u64>,
+}
+
+impl Opening {
+    /// Plays SAN moves from a starting position.
+    fn from_moves(mut board: Board, moves: &[String]) -> Result<Self, String> {
+        let mut history = vec![board.hash];
+        for san in moves {
+            let chess_move = parse_san(&board, san)?;
+            board.make_move(chess_move);
+            history.push(board.hash);
+        }
+        if game_outcome(&board, &history).is_some() {
+            return Err("Opening ends the game".to_string());
+        }
+        Ok(Self { board, history })
+    }
+}
+
+fn load_openings(path: Option<&str>) -> Result<Vec<Opening>, String> {
+    let Some(path) = path else {
+        return DEFAULT_OPENINGS
+            .iter()
+            .map(|line| Opening::from_moves(Board::startpos(), &san_moves(line)))
+            .collect();
+    };
+
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read openings '{}': {}", path, e))?;
+    let openings: Vec<Opening> = if path.ends_with(".pgn") {
+        parse_pgn(&text)
+            .iter()
+            .enumerate()
+            .map(|(i, game)| {
+                game.start_board()
+                    .map_err(|e| format!("Invalid FEN tag: {}", e))
+                    .and_then(|board| Opening::from_moves(board, &game.moves))
+                    .map_err(|e| format!("{} game {}: {}", path, i + 1, e))
+            })
+            .collect::<Result<_, _>>()?
+    } else {
+        text.lines()
+            .enumerate()
+            .map(|(i, line)| (i, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(i, line)| {
+                let opening = if line.contains('/') {
+                    Board::try_from_fen(line)
+                        .map_err(|e| e.to_string())
+                        .and_then(|board| Opening::from_moves(board, &[]))
+                } else {
+                    Opening::from_moves(Board::startpos(), &san_moves(line))
+                };
+                opening.map_err(|e| format!("{} line {}: {}", path, i + 1, e))
+            })
+            .collect::<Result<_, _>>()?
+    };
+
+    if openings.is_empty() {
+        return Err(format!("No openings in '{}'", path));
+    }
+    Ok(openings)
+}
+
+/// Splits a move list such as "1. e4 e5 2. Nf3" into SAN moves.
+fn san_moves(line: &str) -> Vec<String> {
+    line.split_whitespace()
+        .map(|token| token.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.'))
+        .filter(|san| !san.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// A finished game and its score for engine A.
+struct GameRecord {
+    index: usize,
+    a_score: f64,
+    reason: &'static str,
+    pgn: PgnGame,
+}
+
+fn describe(outcome: Option<GameOutcome>) -> &'static str {
+    match outcome {
+        Some(GameOutcome::Checkmate { .. }) => "checkmate",
+        Some(GameOutcome::Stalemate) => "stalemate",
+        Some(GameOutcome::FiftyMoveRule) => "fifty-move rule",
+        Some(GameOutcome::ThreefoldRepetition) => "threefold repetition",
+        Some(GameOutcome::InsufficientMaterial) => "insufficient material",
+        None => "adjudicated draw",
+    }
+}
+
+/// Plays game `index`: opening `index / 2`, with engine A White on even indices.
+fn play_game(
+    index: usize,
+    opening: &Opening,
+    engines: &mut [ChessEngine; 2],
+    setups: &[EngineSetup; 2],
+    config: &Config,
+) -> GameRecord {
+    let a_white = index.is_multiple_of(2);
+    let mut board = opening.board;
+    let mut history = opening.history.clone();
+    let mut moves = Vec::new();
+
+    for (i, engine) in engines.iter_mut().enumerate() {
+        engine.new_game();
+        let seed = config.seed ^ ((index as u64) << 1 | i as u64);
+        engine.set_book_seed(seed);
+        engine.set_skill_seed(seed);
+    }
+
+    let outcome = loop {
+        if let Some(outcome) = game_outcome(&board, &history) {
+            break Some(outcome);
+        }
+        if moves.len() >= config.max_plies {
+            break None;
+        }
+        let side = if (board.side_to_move == Color::White) == a_white {
+            0
+        } else {
+            1
+        };
+        let Some(chess_move) = engines[side].find_best_move_iterative(&board, &setups[side].search)
+        else {
+            break None;
+        };
+        moves.push(to_san(&board, chess_move));
+        board.make_move(chess_move);
+        history.push(board.hash);
+    };
+
+    let result = outcome.map(|o| o.result_str()).unwrap_or("1/2-1/2");
+    let white_score = outcome.map(|o| o.white_score()).unwrap_or(0.5);
+    let (white, black) = if a_white {
+        (&setups[0].name, &setups[1].name)
+    } else {
+        (&setups[1].name, &setups[0].name)
+    };
+    let mut tags = vec![
+        ("Event".to_string(), "Engine match".to_string()),
+        ("Site".to_string(), "rusty-chess".to_string()),
+        (
+            "Date".to_string(),
+            Local::now().format("%Y.%m.%d").to_string(),
+        ),
+        ("Round".to_string(), (index + 1).to_string()),
+        ("White".to_string(), white.clone()),
+        ("Black".to_string(), black.clone()),
+        ("Result".to_string(), result.to_string()),
+    ];
+    let start_fen = opening.board.to_fen();
+    if start_fen != Board::startpos().to_fen() {
+        tags.push(("SetUp".to_string(), "1".to_string()));
+        tags.push(("FEN".to_string(), start_fen));
+    }
+    let termination = if outcome.is_some() {
+        "normal"
+    } else {
+        "adjudication"
+    };
+    tags.push(("Termination".to_string(), termination.to_string()));
+
+    GameRecord {
+        index,
+        a_score: if a_white {
+            white_score
+        } else {
+            1.0 - white_score
+        },
+        reason: describe(outcome),
+        pgn: PgnGame { tags, moves },
+    }
+}
+
+fn format_elo(score: &MatchScore) -> String {
+    match score.elo() {
+        Some((elo, margin)) => format!("{:+.1} +/- {:.1}", elo, margin),
+        None => "n/a".to_string(),
+    }
+}
+
+fn main() {
+    let config = match parse_args() {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let setups = match (
+        EngineSetup::load(&config.engines[0]),
+        EngineSetup::load(&config.engines[1]),
+    ) {
+        (Ok(a), Ok(b)) => [a, b],
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+    let openings = load_openings(config.openings_path.as_deref()).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    });
+    let file = File::create(&config.pgn_path).unwrap_or_else(|e| {
+        eprintln!("Error: Failed to create '{}': {}", config.pgn_path, e);
+        process::exit(1);
+    });
+    let mut pgn_writer = BufWriter::new(file);
+
+    println!(
+        "{} vs {}: {} games from {} openings on {} threads",
+        setups[0].name,
+        setups[1].name,
+        config.games,
+        openings.len(),
+        config.threads
+    );
+
+    let next_game = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let (sender, receiver) = mpsc::channel();
+    let start = Instant::now();
+    let mut score = MatchScore::default();
+    let mut sprt_status = SprtStatus::Continue;
+
+    thread::scope(|scope| {
+        for _ in 0..config.threads.min(config.games) {
+            let sender = sender.clone();
+            let (next_game, stop, setups, openings, config) =
+                (&next_game, &stop, &setups, &openings, &config);
+            scope.spawn(move || {
+                let mut engines = [setups[0].build(), setups[1].build()];
+                while !stop.load(Ordering::Relaxed) {
+                    let index = next_game.fetch_add(1, Ordering::Relaxed);
+                    if index >= config.games {
+                        break;
+                    }
+                    let opening = &openings[(index / 2) % openings.len()];
+                    let record = play_game(index, opening, &mut engines, setups, config);
+                    if sender.send(record).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        for record in receiver {
+            score.add(record.a_score);
+            if let Err(e) = pgn_writer
+                .write_all(record.pgn.to_pgn().as_bytes())
+                .and_then(|_| pgn_writer.flush())
+            {
+                eprintln!("Error: Failed to write '{}': {}", config.pgn_path, e);
+                process::exit(1);
+            }
+
+            let mut line = format!(
+                "Game {} ({} vs {}): {} by {} | {} - {} - {} | Elo {}",
+                record.index + 1,
+                record.pgn.tag("White").unwrap_or("?"),
+                record.pgn.tag("Black").unwrap_or("?"),
+                record.pgn.tag("Result").unwrap_or("*"),
+                record.reason,
+                score.wins,
+                score.draws,
+                score.losses,
+                format_elo(&score)
+            );
+            if let Some(sprt) = &config.sprt {
+                let (lower, upper) = sprt.bounds();
+                line.push_str(&format!(
+                    " | LLR {:.2} ({:.2}, {:.2})",
+                    sprt.llr(&score),
+                    lower,
+                    upper
+                ));
+                // The verdict stands once reached; games still in flight can't undo it
+                if sprt_status == SprtStatus::Continue {
+                    sprt_status = sprt.status(&score);
+                    if sprt_status != SprtStatus::Continue {
+                        stop.store(true, Ordering::Relaxed);
+                    }
+                }
+            }
+            println!("{}", line);
+        }
+    });
+
+    println!();
+    println!(
+        "Score of {} vs {}: {} - {} - {} [{:.3}] {} games in {:.1}s",
+        setups[0].name,
+        setups[1].name,
+        score.wins,
+        score.draws,
+        score.losses,
+        score.score().unwrap_or(0.5),
+        score.games(),
+        start.elapsed().as_secs_f64()
+    );
+    println!("Elo difference: {}", format_elo(&score));
+    if let Some(sprt) = &config.sprt {
+        let verdict = match sprt_status {
+            SprtStatus::Accept => "H1 accepted",
+            SprtStatus::Reject => "H0 accepted (H1 rejected)",
+            SprtStatus::Continue => "inconclusive",
+        };
+        println!(
+            "SPRT (elo0 {}, elo1 {}, alpha {}, beta {}): LLR {:.2}, {}",
+            sprt.elo0,
+            sprt.elo1,
+            sprt.alpha,
+            sprt.beta,
+            sprt.llr(&score),
+            verdict
+        );
+    }
+    println!("Games written to {}", config.pgn_path);
+}
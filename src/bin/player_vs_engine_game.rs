@@ -1,33 +1,85 @@
 // Chess Engine - MIT License, 2026 James Wallace
 
 use rusty_chess::board::Color;
-use rusty_chess::engine::AiGame;
+use rusty_chess::engine::{AiGame, SavedGame};
 use rusty_chess::terminal::{
-    ChessEngineSettings, DisplaySettings, get_chess_engine_settings, print_instructions,
-    print_introduction,
+    ChessEngineSettings, DisplaySettings, book_learning_path_from_args, eval_config_from_args,
+    eval_params_from_args, get_chess_engine_settings, print_instructions, print_introduction,
+    resume_path_from_args,
 };
 
 fn main() {
     // Parse command-line arguments for display settings
     let display_settings: DisplaySettings = DisplaySettings::from_args();
+    let eval_config = eval_config_from_args().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    });
+    let eval_params = eval_params_from_args().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    });
+    let book_learning_path = book_learning_path_from_args().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    });
+
+    let resume_path = resume_path_from_args().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    });
 
     print_introduction();
-    let settings: ChessEngineSettings = get_chess_engine_settings();
-    print_instructions(&settings, &display_settings);
-
-    let mut game: AiGame = AiGame::new(
-        settings.player_color,
-        settings.search_depth,
-        settings.starting_position,
-        display_settings,
-        settings.white_opening_book,
-        settings.black_opening_book,
-    );
-
-    // If player chose black and it's white's turn, AI makes the first move
-    if settings.player_color == Color::Black && game.side_to_move() == Color::White {
+    let (settings, mut game) = match &resume_path {
+        // A resumed game keeps its own settings, including the display options
+        Some(path) => {
+            let (settings, game) = resume_game(path).unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            });
+            println!("Resuming the game saved in {}\n", path);
+            (settings, game)
+        }
+        None => {
+            let settings: ChessEngineSettings = get_chess_engine_settings();
+            let game: AiGame =
+                AiGame::from_settings(&settings, display_settings).unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
+            (settings, game)
+        }
+    };
+    print_instructions(&settings, game.display_settings());
+
+    if let Some(config) = &eval_config
+        && let Err(e) = game.set_eval_config(config)
+    {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+    if let Some(params) = &eval_params {
+        game.set_eval_params(params);
+    }
+    if let Some(path) = &book_learning_path
+        && let Err(e) = game.set_book_learning_path(path)
+    {
+        eprintln!("Error: Failed to load opening book '{}': {}", path, e);
+        std::process::exit(1);
+    }
+
+    // If the player chose black and it's white's turn, the engine makes the first move
+    if resume_path.is_none()
+        && settings.human_color() == Some(Color::Black)
+        && game.side_to_move() == Color::White
+    {
         println!("\nAI will make the first move as White.\n");
     }
 
     game.run();
 }
+
+fn resume_game(path: &str) -> Result<(ChessEngineSettings, AiGame), String> {
+    let saved = SavedGame::load(path)?;
+    Ok((saved.settings()?, AiGame::from_saved(&saved)?))
+}
@@ -0,0 +1,476 @@
+use rusty_chess::board::Board;
+use rusty_chess::eval::{EvalConfig, EvalParams, Evaluator, EvaluatorKind, ParamSpec};
+use rusty_chess::fen::FENParser;
+use std::env;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::process;
+use std::thread;
+use std::time::Instant;
+
+const DEFAULT_OUTPUT_PATH: &str = "tuned_params.json";
+const DEFAULT_ITERATIONS: usize = 10;
+const DEFAULT_STEP: i32 = 1;
+
+fn print_usage(program_name: &str) {
+    eprintln!("Usage: {} <positions-file> [options]", program_name);
+    eprintln!();
+    eprintln!("Texel-tunes the evaluation constants against labeled positions by minimizing");
+    eprintln!("the logistic error between static evaluations and game results.");
+    eprintln!();
+    eprintln!("Arguments:");
+    eprintln!("  <positions-file>        One quiet position per line: a FEN followed by the game");
+    eprintln!("                          result from White's point of view, e.g.");
+    eprintln!("                            <fen> | 1-0");
+    eprintln!("                            <fen> | <score> | 0.5      (datagen output)");
+    eprintln!("                            <fen> [0.0]");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!(
+        "  --output <path>         Where to write the tuned parameters (default: {})",
+        DEFAULT_OUTPUT_PATH
+    );
+    eprintln!("  --params <path>         Start from an existing parameter file");
+    eprintln!("  --eval-config <path>    JSON eval config selecting evaluators and their weights");
+    eprintln!(
+        "  --iterations <n>        Maximum local-search passes (default: {})",
+        DEFAULT_ITERATIONS
+    );
+    eprintln!(
+        "  --step <n>              Amount each parameter is nudged per trial (default: {})",
+        DEFAULT_STEP
+    );
+    eprintln!("  --k <value>             Fix the logistic scaling constant instead of fitting it");
+    eprintln!("  --only <prefixes>       Comma-separated parameter name prefixes to tune,");
+    eprintln!("                          e.g. material,pst.knight,king_safety");
+    eprintln!("  --limit <n>             Only use the first n positions");
+    eprintln!("  --threads <n>           Worker threads (default: all cores)");
+    eprintln!("  --help                  Show this help message");
+    eprintln!();
+    eprintln!("Examples:");
+    eprintln!("  {} quiet-labeled.txt --only material", program_name);
+    eprintln!(
+        "  {} datagen.txt --params tuned_params.json --iterations 50",
+        program_name
+    );
+}
+
+struct Config {
+    positions_path: String,
+    output_path: String,
+    params_path: Option<String>,
+    eval_config_path: Option<String>,
+    iterations: usize,
+    step: i32,
+    k: Option<f64>,
+    only: Vec<String>,
+    limit: Option<usize>,
+    threads: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            positions_path: String::new(),
+            output_path: DEFAULT_OUTPUT_PATH.to_string(),
+            params_path: None,
+            eval_config_path: None,
+            iterations: DEFAULT_ITERATIONS,
+            step: DEFAULT_STEP,
+            k: None,
+            only: Vec::new(),
+            limit: None,
+            threads: thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1),
+        }
+    }
+}
+
+fn parse_args() -> Result<Config, String> {
+    let args: Vec<String> = env::args().collect();
+    let program_name = &args[0];
+
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        print_usage(program_name);
+        process::exit(0);
+    }
+
+    if args.len() < 2 || args[1].starts_with("--") {
+        print_usage(program_name);
+        return Err("No positions file provided".to_string());
+    }
+
+    let mut config = Config {
+        positions_path: args[1].clone(),
+        ..Config::default()
+    };
+
+    let mut i = 2;
+    while i < args.len() {
+        let flag = args[i].as_str();
+        let mut value = || -> Result<String, String> {
+            i += 1;
+            args.get(i)
+                .cloned()
+                .ok_or_else(|| format!("{} requires a value", flag))
+        };
+
+        match flag {
+            "--output" => config.output_path = value()?,
+            "--params" => config.params_path = Some(value()?),
+            "--eval-config" => config.eval_config_path = Some(value()?),
+            "--iterations" => {
+                let v = value()?;
+                config.iterations = v
+                    .parse()
+                    .map_err(|_| format!("Invalid iteration count: {}", v))?;
+            }
+            "--step" => {
+                let v = value()?;
+                config.step = v
+                    .parse()
+                    .ok()
+                    .filter(|&s: &i32| s > 0)
+                    .ok_or_else(|| format!("Invalid step: {}", v))?;
+            }
+            "--k" => {
+                let v = value()?;
+                config.k = Some(
+                    v.parse()
+                        .ok()
+                        .filter(|&k: &f64| k > 0.0)
+                        .ok_or_else(|| format!("Invalid K: {}", v))?,
+                );
+            }
+            "--only" => {
+                config.only = value()?
+                    .split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect();
+            }
+            "--limit" => {
+                let v = value()?;
+                config.limit = Some(v.parse().map_err(|_| format!("Invalid limit: {}", v))?);
+            }
+            "--threads" => {
+                let v = value()?;
+                config.threads = v
+                    .parse()
+                    .ok()
+                    .filter(|&n: &usize| n > 0)
+                    .ok_or_else(|| format!("Invalid thread count: {}", v))?;
+            }
+            _ => return Err(format!("Unknown option: {}", flag)),
+        }
+        i += 1;
+    }
+
+    Ok(config)
+}
+
+/// A labeled training position with its cached per-evaluator scores.
+struct TuningPosition {
+    board: Board,
+    /// Game result from White's perspective: 1.0 win, 0.5 draw, 0.0 loss
+    result: f64,
+    /// Weighted score of each evaluator, indexed like `EvaluatorKind::ALL`
+    components: [i32; 13],
+    /// Sum of `components` (White's perspective)
+    total: i32,
+}
+
+fn kind_index(kind: EvaluatorKind) -> usize {
+    EvaluatorKind::ALL
+        .iter()
+        .position(|&k| k == kind)
+        .expect("every evaluator kind is listed in ALL")
+}
+
+fn parse_result(token: &str) -> Option<f64> {
+    let token = token
+        .trim()
+        .trim_matches(|c| c == '[' || c == ']' || c == '"' || c == ';');
+    match token {
+        "1-0" | "1" | "1.0" => Some(1.0),
+        "0-1" | "0" | "0.0" => Some(0.0),
+        "1/2-1/2" | "0.5" | "½-½" => Some(0.5),
+        _ => None,
+    }
+}
+
+/// Splits a training line into FEN and result. Accepts `fen | result`,
+/// `fen | score | result`, `fen; result` and `fen [result]` / `fen result`.
+fn parse_line(line: &str) -> Option<(String, f64)> {
+    let (fen, result) = if line.contains('|') || line.contains(';') {
+        let fields: Vec<&str> = line.split(['|', ';']).collect();
+        (fields[0].trim(), parse_result(fields[fields.len() - 1])?)
+    } else {
+        let (fen, result) = line.trim().rsplit_once(char::is_whitespace)?;
+        (fen.trim(), parse_result(result)?)
+    };
+
+    // EPD-style lines omit the move counters
+    let fen = if fen.split_whitespace().count() == 4 {
+        format!("{} 0 1", fen)
+    } else {
+        fen.to_string()
+    };
+
+    FENParser::parse(&fen).ok()?;
+    Some((fen, result))
+}
+
+fn load_positions(path: &str, limit: Option<usize>) -> Result<Vec<(Board, f64)>, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open '{}': {}", path, e))?;
+    let mut positions = Vec::new();
+    let mut skipped = 0;
+
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(|e| format!("Failed to read '{}': {}", path, e))?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        match parse_line(line) {
+            Some((fen, result)) => positions.push((Board::from_fen(&fen), result)),
+            None => skipped += 1,
+        }
+
+        if limit.is_some_and(|n| positions.len() >= n) {
+            break;
+        }
+    }
+
+    if skipped > 0 {
+        eprintln!("Warning: skipped {} unparseable lines", skipped);
+    }
+
+    Ok(positions)
+}
+
+/// Evaluates one evaluator on every position, split across worker threads.
+fn component_scores(
+    positions: &[TuningPosition],
+    evaluator: &Evaluator,
+    kind: EvaluatorKind,
+    threads: usize,
+) -> Vec<i32> {
+    let chunk_size = positions.len().div_ceil(threads).max(1);
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = positions
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|p| evaluator.evaluate_component(&p.board, kind))
+                        .collect::<Vec<i32>>()
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().expect("tuning worker panicked"))
+            .collect()
+    })
+}
+
+fn sigmoid(k: f64, score: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-k * score / 400.0))
+}
+
+/// Mean squared error between game results and predicted win probabilities.
+fn mean_error(positions: &[TuningPosition], k: f64) -> f64 {
+    let sum: f64 = positions
+        .iter()
+        .map(|p| {
+            let diff = p.result - sigmoid(k, p.total as f64);
+            diff * diff
+        })
+        .sum();
+    sum / positions.len() as f64
+}
+
+/// Mean squared error if `kind`'s cached scores were replaced with `scores`.
+fn mean_error_with(
+    positions: &[TuningPosition],
+    k: f64,
+    kind: EvaluatorKind,
+    scores: &[i32],
+) -> f64 {
+    let idx = kind_index(kind);
+    let sum: f64 = positions
+        .iter()
+        .zip(scores)
+        .map(|(p, &score)| {
+            let total = p.total - p.components[idx] + score;
+            let diff = p.result - sigmoid(k, total as f64);
+            diff * diff
+        })
+        .sum();
+    sum / positions.len() as f64
+}
+
+/// Finds the scaling constant K that best maps centipawns to results.
+fn fit_k(positions: &[TuningPosition]) -> f64 {
+    let mut best_k = 1.0;
+    let mut best_error = f64::MAX;
+    let mut step = 0.1;
+    let (mut low, mut high) = (0.1, 3.0);
+
+    // Coarse scan, then refine around the best value
+    for _ in 0..3 {
+        let mut k = low;
+        while k <= high + 1e-9 {
+            let error = mean_error(positions, k);
+            if error < best_error {
+                best_error = error;
+                best_k = k;
+            }
+            k += step;
+        }
+        low = (best_k - step).max(0.01);
+        high = best_k + step;
+        step /= 10.0;
+    }
+
+    best_k
+}
+
+fn is_selected(spec: &ParamSpec, only: &[String]) -> bool {
+    only.is_empty() || only.iter().any(|prefix| spec.name.starts_with(prefix))
+}
+
+fn main() {
+    let config = match parse_args() {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let eval_config = match &config.eval_config_path {
+        Some(path) => EvalConfig::load(path).unwrap_or_else(|e| {
+            eprintln!("Error: Failed to load eval config '{}': {}", path, e);
+            process::exit(1);
+        }),
+        None => EvalConfig::default(),
+    };
+    let mut params = match &config.params_path {
+        Some(path) => EvalParams::load(path).unwrap_or_else(|e| {
+            eprintln!("Error: Failed to load eval params '{}': {}", path, e);
+            process::exit(1);
+        }),
+        None => EvalParams::default(),
+    };
+
+    let labeled = load_positions(&config.positions_path, config.limit).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    });
+    if labeled.is_empty() {
+        eprintln!("Error: No usable positions in '{}'", config.positions_path);
+        process::exit(1);
+    }
+    println!("Loaded {} positions", labeled.len());
+
+    let mut evaluator = Evaluator::from_config(&eval_config)
+        .unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        })
+        .with_params(params.clone());
+
+    let mut positions: Vec<TuningPosition> = labeled
+        .into_iter()
+        .map(|(board, result)| TuningPosition {
+            board,
+            result,
+            components: [0; 13],
+            total: 0,
+        })
+        .collect();
+
+    for kind in EvaluatorKind::ALL {
+        let scores = component_scores(&positions, &evaluator, kind, config.threads);
+        for (p, score) in positions.iter_mut().zip(scores) {
+            p.components[kind_index(kind)] = score;
+            p.total += score;
+        }
+    }
+
+    let k = config.k.unwrap_or_else(|| fit_k(&positions));
+    let mut best_error = mean_error(&positions, k);
+    println!("K = {:.3}, initial error = {:.6}", k, best_error);
+
+    let specs = EvalParams::specs();
+    let tunable: Vec<usize> = (0..specs.len())
+        .filter(|&i| is_selected(&specs[i], &config.only))
+        .filter(|&i| eval_config.weight_of(specs[i].owner).is_some())
+        .collect();
+    if tunable.is_empty() {
+        eprintln!("Error: No enabled parameters match the --only filter");
+        process::exit(1);
+    }
+    println!("Tuning {} of {} parameters\n", tunable.len(), specs.len());
+
+    let start = Instant::now();
+
+    for pass in 1..=config.iterations {
+        let mut changed = 0;
+
+        for &i in &tunable {
+            let kind = specs[i].owner;
+            let original = params.get(i);
+
+            for delta in [config.step, -config.step] {
+                params.set(i, original + delta);
+                evaluator.set_params(params.clone());
+
+                let scores = component_scores(&positions, &evaluator, kind, config.threads);
+                let error = mean_error_with(&positions, k, kind, &scores);
+
+                if error < best_error {
+                    best_error = error;
+                    let idx = kind_index(kind);
+                    for (p, score) in positions.iter_mut().zip(scores) {
+                        p.total += score - p.components[idx];
+                        p.components[idx] = score;
+                    }
+                    changed += 1;
+                    break;
+                }
+
+                params.set(i, original);
+                evaluator.set_params(params.clone());
+            }
+        }
+
+        println!(
+            "Pass {:>3}: error = {:.6}, {} parameters changed ({:.1}s)",
+            pass,
+            best_error,
+            changed,
+            start.elapsed().as_secs_f64()
+        );
+
+        // Checkpoint after every pass so long runs can be interrupted
+        if let Err(e) = params.save(&config.output_path) {
+            eprintln!("Error: Failed to write '{}': {}", config.output_path, e);
+            process::exit(1);
+        }
+
+        if changed == 0 {
+            break;
+        }
+    }
+
+    println!("\nFinal error = {:.6}", best_error);
+    println!("Tuned parameters written to {}", config.output_path);
+}
@@ -1,5 +1,5 @@
 use super::Board;
-use crate::board::{Color, Piece};
+use crate::board::{ChessMove, Color, Piece};
 
 impl Board {
     /// Return the pieces as a `u64` bitboard of the given color
@@ -37,6 +37,13 @@ impl Board {
         !self.occ_all
     }
 
+    /// Return true if the move captures a piece, including en passant.
+    /// Castling is never a capture, even though it is encoded as the
+    /// king moving onto its own rook.
+    pub fn is_capture(&self, mv: ChessMove) -> bool {
+        mv.is_en_passant() || (!mv.is_castle() && self.piece_on(mv.to() as u8).is_some())
+    }
+
     /// Return the piece, if one exists, on the given chess board
     /// square.
     pub fn piece_on(&self, sq: u8) -> Option<(Color, Piece)> {
@@ -108,7 +115,7 @@ impl Board {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::board::{Color, Piece};
+    use crate::board::{ChessMove, Color, Piece};
 
     // ── pieces_of ──────────────────────────────────────────────
 
@@ -326,4 +333,15 @@ mod tests {
         assert_eq!(board.count_pieces(Color::White, Piece::Pawn), 0);
         assert_eq!(board.count_pieces(Color::White, Piece::Queen), 0);
     }
+
+    // ── is_capture ─────────────────────────────────────────────
+
+    #[test]
+    fn is_capture_excludes_castling() {
+        let board = Board::from_fen("r3k3/8/8/3pP3/8/8/8/R3K2R w KQ d6 0 1");
+        assert!(board.is_capture(ChessMove::new(0, 56)));
+        assert!(board.is_capture(ChessMove::new_en_passant(36, 43)));
+        assert!(!board.is_capture(ChessMove::new_castle(4, 7)));
+        assert!(!board.is_capture(ChessMove::new(4, 5)));
+    }
 }
@@ -0,0 +1,158 @@
+use crate::board::{Board, Color, Piece};
+use crate::eval::EvalParams;
+
+/// Game phase weight of each piece type: minors count 1, rooks 2, queens 4 (24 at the start)
+const PHASE_WEIGHTS: [i32; 6] = [0, 1, 1, 2, 4, 0];
+
+static DEFAULT_PARAMS: EvalParams = EvalParams::DEFAULT;
+
+/// Material and piece-square terms maintained incrementally by `make_move`/`unmake_move`,
+/// so leaf evaluation doesn't have to rescan the bitboards.
+///
+/// Scores use the default evaluation parameters. Kings are excluded from `pst`
+/// because their table is blended by game phase when the score is read.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct EvalAccumulator {
+    /// Material per color
+    pub material: [i32; 2],
+    /// Piece-square score per color, excluding the king
+    pub pst: [i32; 2],
+    /// Sum of piece phase weights on the board
+    pub phase: i32,
+}
+
+impl EvalAccumulator {
+    /// Computes the accumulator from scratch.
+    pub fn from_board(board: &Board) -> Self {
+        let mut acc = Self::default();
+
+        for color in [Color::White, Color::Black] {
+            for piece in [
+                Piece::Pawn,
+                Piece::Knight,
+                Piece::Bishop,
+                Piece::Rook,
+                Piece::Queen,
+            ] {
+                let mut bb = board.pieces[color as usize][piece as usize];
+                while bb != 0 {
+                    let sq = bb.trailing_zeros() as usize;
+                    acc.add_piece(color, piece, sq);
+                    bb &= bb - 1;
+                }
+            }
+        }
+
+        acc
+    }
+
+    #[inline]
+    pub fn add_piece(&mut self, color: Color, piece: Piece, sq: usize) {
+        if piece == Piece::King {
+            return;
+        }
+        self.material[color as usize] += DEFAULT_PARAMS.piece_values[piece as usize];
+        self.pst[color as usize] += Self::pst_value(color, piece, sq);
+        self.phase += PHASE_WEIGHTS[piece as usize];
+    }
+
+    #[inline]
+    pub fn remove_piece(&mut self, color: Color, piece: Piece, sq: usize) {
+        if piece == Piece::King {
+            return;
+        }
+        self.material[color as usize] -= DEFAULT_PARAMS.piece_values[piece as usize];
+        self.pst[color as usize] -= Self::pst_value(color, piece, sq);
+        self.phase -= PHASE_WEIGHTS[piece as usize];
+    }
+
+    #[inline]
+    pub fn move_piece(&mut self, color: Color, piece: Piece, from: usize, to: usize) {
+        if piece == Piece::King {
+            return;
+        }
+        self.pst[color as usize] +=
+            Self::pst_value(color, piece, to) - Self::pst_value(color, piece, from);
+    }
+
+    #[inline]
+    fn pst_value(color: Color, piece: Piece, sq: usize) -> i32 {
+        // Tables are from White's perspective; flip the rank for Black
+        let normalized = match color {
+            Color::White => sq,
+            Color::Black => sq ^ 56,
+        };
+        DEFAULT_PARAMS.piece_square_tables[piece as usize][normalized]
+    }
+}
+
+impl Board {
+    /// Recomputes the incremental evaluation terms from the bitboards.
+    ///
+    /// `from_fen` and `make_move`/`unmake_move` keep them in sync; this is only
+    /// needed after editing the bitboards directly.
+    pub fn refresh_accumulator(&mut self) {
+        self.accumulator = EvalAccumulator::from_board(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::movegen::MoveGenerator;
+
+    #[test]
+    fn test_startpos_accumulator_is_balanced() {
+        let board = Board::startpos();
+        let acc = board.accumulator;
+
+        assert_eq!(acc.material[0], acc.material[1]);
+        assert_eq!(acc.pst[0], acc.pst[1]);
+        assert_eq!(acc.phase, 24);
+    }
+
+    #[test]
+    fn test_accumulator_tracks_make_and_unmake() {
+        // Exercises captures, castling, en passant and promotions
+        let fens = [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        ];
+
+        for fen in fens {
+            let board = Board::from_fen(fen);
+            let mut moves = Vec::new();
+            MoveGenerator::generate_legal_moves(&board, &mut moves);
+
+            for mv in moves {
+                let mut next = board;
+                let undo = next.make_move(mv);
+                assert_eq!(
+                    next.accumulator,
+                    EvalAccumulator::from_board(&next),
+                    "accumulator out of sync after {} in {}",
+                    mv.to_uci(),
+                    fen
+                );
+
+                next.unmake_move(undo);
+                assert_eq!(next.accumulator, board.accumulator);
+            }
+        }
+    }
+
+    #[test]
+    fn test_refresh_accumulator_after_manual_edit() {
+        let mut board = Board::startpos();
+        board.pieces[Color::Black as usize][Piece::Queen as usize] = 0;
+        board.refresh_accumulator();
+
+        assert_eq!(
+            board.accumulator.material[0] - board.accumulator.material[1],
+            900
+        );
+        assert_eq!(board.accumulator.phase, 20);
+    }
+}
@@ -5,10 +5,12 @@ use super::CastlingSide;
 
 impl CastlingRights {
     /// Individual bit flags
-    const WHITE_KING_SIDE: u8 = 0b0001;
-    const WHITE_QUEEN_SIDE: u8 = 0b0010;
-    const BLACK_KING_SIDE: u8 = 0b0100;
-    const BLACK_QUEEN_SIDE: u8 = 0b1000;
+    const WHITE_KING_SIDE: u16 = 0b0001;
+    const WHITE_QUEEN_SIDE: u16 = 0b0010;
+    const BLACK_KING_SIDE: u16 = 0b0100;
+    const BLACK_QUEEN_SIDE: u16 = 0b1000;
+
+    const FILE_SHIFT: u16 = 4;
 
     /// No castling rights
     pub const fn empty() -> Self {
@@ -20,37 +22,100 @@ impl CastlingRights {
         CastlingRights(0b1111)
     }
 
-    /// Check if a particular color + side is available
-    pub fn has(&self, color: Color, side: CastlingSide) -> bool {
-        let flag: u8 = match (color, side) {
+    fn flag(color: Color, side: CastlingSide) -> u16 {
+        match (color, side) {
             (Color::White, CastlingSide::KingSide) => Self::WHITE_KING_SIDE,
             (Color::White, CastlingSide::QueenSide) => Self::WHITE_QUEEN_SIDE,
             (Color::Black, CastlingSide::KingSide) => Self::BLACK_KING_SIDE,
             (Color::Black, CastlingSide::QueenSide) => Self::BLACK_QUEEN_SIDE,
-        };
-        (self.0 & flag) != 0
+        }
+    }
+
+    /// Position of the three rook file bits for a right
+    fn file_shift(color: Color, side: CastlingSide) -> u16 {
+        Self::FILE_SHIFT + 3 * Self::flag(color, side).trailing_zeros() as u16
+    }
+
+    /// Check if a particular color + side is available
+    pub fn has(&self, color: Color, side: CastlingSide) -> bool {
+        (self.0 & Self::flag(color, side)) != 0
     }
 
     /// Remove a specific castling right
     pub fn remove(&mut self, color: Color, side: CastlingSide) {
-        let flag: u8 = match (color, side) {
-            (Color::White, CastlingSide::KingSide) => Self::WHITE_KING_SIDE,
-            (Color::White, CastlingSide::QueenSide) => Self::WHITE_QUEEN_SIDE,
-            (Color::Black, CastlingSide::KingSide) => Self::BLACK_KING_SIDE,
-            (Color::Black, CastlingSide::QueenSide) => Self::BLACK_QUEEN_SIDE,
-        };
-        self.0 &= !flag;
+        self.0 &= !Self::flag(color, side);
+        self.0 &= !(0b111 << Self::file_shift(color, side));
     }
 
-    /// Add a specific castling right
+    /// Add a specific castling right, with the rook on its standard file
     pub fn add(&mut self, color: Color, side: CastlingSide) {
-        let flag: u8 = match (color, side) {
-            (Color::White, CastlingSide::KingSide) => Self::WHITE_KING_SIDE,
-            (Color::White, CastlingSide::QueenSide) => Self::WHITE_QUEEN_SIDE,
-            (Color::Black, CastlingSide::KingSide) => Self::BLACK_KING_SIDE,
-            (Color::Black, CastlingSide::QueenSide) => Self::BLACK_QUEEN_SIDE,
+        self.add_with_rook_file(color, side, side.default_rook_file());
+    }
+
+    /// Add a castling right whose rook starts on `file` (0 = a-file), as in Chess960
+    pub fn add_with_rook_file(&mut self, color: Color, side: CastlingSide, file: u8) {
+        self.remove(color, side);
+        let file_bits = ((file ^ side.default_rook_file()) & 0b111) as u16;
+        self.0 |= Self::flag(color, side) | (file_bits << Self::file_shift(color, side));
+    }
+
+    /// File the castling rook starts on (meaningful only while the right is held)
+    pub fn rook_file(&self, color: Color, side: CastlingSide) -> u8 {
+        let file_bits = (self.0 >> Self::file_shift(color, side)) as u8 & 0b111;
+        file_bits ^ side.default_rook_file()
+    }
+
+    /// Square the castling rook starts on
+    pub fn rook_square(&self, color: Color, side: CastlingSide) -> u8 {
+        let back_rank = match color {
+            Color::White => 0,
+            Color::Black => 56,
         };
-        self.0 |= flag;
+        back_rank + self.rook_file(color, side)
+    }
+
+    /// Remove the right that uses the rook starting on `square`, if any
+    pub fn remove_rook(&mut self, color: Color, square: u8) {
+        for side in [CastlingSide::KingSide, CastlingSide::QueenSide] {
+            if self.has(color, side) && self.rook_square(color, side) == square {
+                self.remove(color, side);
+            }
+        }
+    }
+
+    /// Whether every held right uses a rook on the standard a- or h-file
+    pub fn has_standard_rooks(&self) -> bool {
+        self.0 >> Self::FILE_SHIFT == 0
+    }
+
+    /// The FEN castling field: `KQkq` when every rook is on a standard file,
+    /// Shredder-FEN rook files (e.g. `HFhf`) otherwise.
+    pub fn to_fen_field(&self) -> String {
+        let standard = self.has_standard_rooks();
+        let mut field = String::new();
+        for color in [Color::White, Color::Black] {
+            for (side, letter) in [
+                (CastlingSide::KingSide, 'K'),
+                (CastlingSide::QueenSide, 'Q'),
+            ] {
+                if !self.has(color, side) {
+                    continue;
+                }
+                let c = if standard {
+                    letter
+                } else {
+                    (b'A' + self.rook_file(color, side)) as char
+                };
+                field.push(match color {
+                    Color::White => c,
+                    Color::Black => c.to_ascii_lowercase(),
+                });
+            }
+        }
+        if field.is_empty() {
+            field.push('-');
+        }
+        field
     }
 }
 
@@ -155,6 +220,48 @@ mod tests {
         assert_eq!(rights, CastlingRights::empty());
     }
 
+    // ── rook files ───────────────────────────────────────────────
+
+    #[test]
+    fn default_rook_files_are_standard() {
+        let rights: CastlingRights = CastlingRights::full();
+        assert_eq!(rights.rook_square(Color::White, CastlingSide::KingSide), 7);
+        assert_eq!(
+            rights.rook_square(Color::Black, CastlingSide::QueenSide),
+            56
+        );
+        assert!(rights.has_standard_rooks());
+        assert_eq!(rights.to_fen_field(), "KQkq");
+    }
+
+    #[test]
+    fn chess960_rook_files_round_trip() {
+        let mut rights: CastlingRights = CastlingRights::empty();
+        rights.add_with_rook_file(Color::White, CastlingSide::KingSide, 6);
+        rights.add_with_rook_file(Color::Black, CastlingSide::QueenSide, 1);
+        rights.add(Color::Black, CastlingSide::KingSide);
+        assert_eq!(rights.rook_square(Color::White, CastlingSide::KingSide), 6);
+        assert_eq!(
+            rights.rook_square(Color::Black, CastlingSide::QueenSide),
+            57
+        );
+        assert!(!rights.has_standard_rooks());
+        assert_eq!(rights.to_fen_field(), "Ghb");
+
+        rights.remove_rook(Color::White, 6);
+        rights.remove_rook(Color::Black, 57);
+        assert!(rights.has_standard_rooks());
+        assert_eq!(rights.to_fen_field(), "k");
+    }
+
+    #[test]
+    fn removing_chess960_rights_compares_equal_to_empty() {
+        let mut rights: CastlingRights = CastlingRights::empty();
+        rights.add_with_rook_file(Color::White, CastlingSide::QueenSide, 2);
+        rights.remove(Color::White, CastlingSide::QueenSide);
+        assert_eq!(rights, CastlingRights::empty());
+    }
+
     // ── independence ─────────────────────────────────────────────
 
     #[test]
@@ -6,5 +6,36 @@ pub enum CastlingSide {
     QueenSide,
 }
 
+impl CastlingSide {
+    /// File the king lands on, in standard chess and Chess960 alike (g or c).
+    pub const fn king_destination_file(self) -> u8 {
+        match self {
+            CastlingSide::KingSide => 6,
+            CastlingSide::QueenSide => 2,
+        }
+    }
+
+    /// File the rook lands on (f or d).
+    pub const fn rook_destination_file(self) -> u8 {
+        match self {
+            CastlingSide::KingSide => 5,
+            CastlingSide::QueenSide => 3,
+        }
+    }
+
+    /// File the rook starts on in standard chess (h or a).
+    pub const fn default_rook_file(self) -> u8 {
+        match self {
+            CastlingSide::KingSide => 7,
+            CastlingSide::QueenSide => 0,
+        }
+    }
+}
+
+/// Castling availability, plus the file each castling rook starts on.
+///
+/// The low four bits are the rights; above them each right has three bits holding its
+/// rook file XOR the standard file, so standard positions only use the low bits and
+/// Chess960 rook files survive moves without growing the struct.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
-pub struct CastlingRights(u8);
+pub struct CastlingRights(u16);
@@ -0,0 +1,235 @@
+//! Chess960 (Fischer Random) starting positions.
+//!
+//! Positions are numbered 0-959 with the standard Scharnagl scheme, in which
+//! [`STANDARD_INDEX`] is the normal chess starting position.
+
+use super::castling::CastlingSide;
+use super::{Board, CastlingRights, Color, Piece};
+
+/// Number of distinct Chess960 starting positions
+pub const POSITION_COUNT: u16 = 960;
+
+/// Scharnagl index of the standard starting position
+pub const STANDARD_INDEX: u16 = 518;
+
+/// Files (from the five left after bishops and queen) taken by the knights
+const KNIGHT_PLACEMENTS: [(usize, usize); 10] = [
+    (0, 1),
+    (0, 2),
+    (0, 3),
+    (0, 4),
+    (1, 2),
+    (1, 3),
+    (1, 4),
+    (2, 3),
+    (2, 4),
+    (3, 4),
+];
+
+/// White's back rank for a start position, a-file first, or None if the index is out of range.
+pub fn back_rank(index: u16) -> Option<[Piece; 8]> {
+    if index >= POSITION_COUNT {
+        return None;
+    }
+    let mut rank: [Option<Piece>; 8] = [None; 8];
+    let mut n = index as usize;
+
+    // Bishops on opposite colors: the light-squared one on b/d/f/h, the dark one on a/c/e/g
+    rank[2 * (n % 4) + 1] = Some(Piece::Bishop);
+    n /= 4;
+    rank[2 * (n % 4)] = Some(Piece::Bishop);
+    n /= 4;
+
+    let empty_files = |rank: &[Option<Piece>; 8]| -> Vec<usize> {
+        (0..8).filter(|&file| rank[file].is_none()).collect()
+    };
+
+    rank[empty_files(&rank)[n % 6]] = Some(Piece::Queen);
+    n /= 6;
+
+    let (first, second) = KNIGHT_PLACEMENTS[n];
+    let empty = empty_files(&rank);
+    rank[empty[first]] = Some(Piece::Knight);
+    rank[empty[second]] = Some(Piece::Knight);
+
+    // The last three squares hold rook, king, rook so the king sits between the rooks
+    for (file, piece) in empty_files(&rank)
+        .into_iter()
+        .zip([Piece::Rook, Piece::King, Piece::Rook])
+    {
+        rank[file] = Some(piece);
+    }
+
+    Some(rank.map(|piece| piece.unwrap_or(Piece::Pawn)))
+}
+
+/// FEN of a start position with full castling rights, or None if the index is out of range.
+pub fn start_fen(index: u16) -> Option<String> {
+    let rank = back_rank(index)?;
+    let white: String = rank.iter().map(|&piece| piece_letter(piece)).collect();
+    let black = white.to_ascii_lowercase();
+
+    let rook_files: Vec<u8> = (0..8u8)
+        .filter(|&file| rank[file as usize] == Piece::Rook)
+        .collect();
+    let mut castling = CastlingRights::empty();
+    for color in [Color::White, Color::Black] {
+        castling.add_with_rook_file(color, CastlingSide::QueenSide, rook_files[0]);
+        castling.add_with_rook_file(color, CastlingSide::KingSide, rook_files[1]);
+    }
+
+    Some(format!(
+        "{}/pppppppp/8/8/8/8/PPPPPPPP/{} w {} - 0 1",
+        black,
+        white,
+        castling.to_fen_field()
+    ))
+}
+
+fn piece_letter(piece: Piece) -> char {
+    match piece {
+        Piece::Pawn => 'P',
+        Piece::Knight => 'N',
+        Piece::Bishop => 'B',
+        Piece::Rook => 'R',
+        Piece::Queen => 'Q',
+        Piece::King => 'K',
+    }
+}
+
+impl Board {
+    /// Create a board in the Chess960 start position with the given index (0-959).
+    pub fn chess960(index: u16) -> Option<Self> {
+        start_fen(index).map(|fen| Self::from_fen(&fen))
+    }
+
+    /// Whether castling here needs Chess960 rules: a side that may still castle has
+    /// its king off the e-file or a castling rook off the a- and h-files.
+    pub fn has_chess960_castling(&self) -> bool {
+        !self.castling.has_standard_rooks()
+            || [Color::White, Color::Black].into_iter().any(|color| {
+                let may_castle = [CastlingSide::KingSide, CastlingSide::QueenSide]
+                    .into_iter()
+                    .any(|side| self.castling.has(color, side));
+                may_castle && self.king_square(color) % 8 != 4
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::ChessMove;
+    use std::collections::HashSet;
+
+    fn perft(board: &mut Board, depth: u32) -> u64 {
+        let mut moves = Vec::new();
+        board.generate_moves(&mut moves);
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+        let mut nodes = 0;
+        for mv in moves {
+            let undo = board.make_move(mv);
+            nodes += perft(board, depth - 1);
+            board.unmake_move(undo);
+        }
+        nodes
+    }
+
+    #[test]
+    fn test_standard_index_is_the_normal_start_position() {
+        assert_eq!(
+            start_fen(STANDARD_INDEX).unwrap(),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+        );
+        assert_eq!(start_fen(0).unwrap().split(' ').nth(2), Some("HFhf"));
+        assert!(start_fen(POSITION_COUNT).is_none());
+    }
+
+    #[test]
+    fn test_detects_chess960_castling() {
+        assert!(!Board::startpos().has_chess960_castling());
+        assert!(
+            !Board::chess960(STANDARD_INDEX)
+                .unwrap()
+                .has_chess960_castling()
+        );
+        // Castling rooks on the f- and h-files
+        assert!(Board::chess960(0).unwrap().has_chess960_castling());
+        // Rooks on the a- and h-files, but the kings start on b1/b8
+        assert!(
+            Board::from_fen("rkbbnnqr/pppppppp/8/8/8/8/PPPPPPPP/RKBBNNQR w KQkq - 0 1")
+                .has_chess960_castling()
+        );
+        assert!(
+            !Board::from_fen("bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w - - 0 1")
+                .has_chess960_castling()
+        );
+    }
+
+    #[test]
+    fn test_all_start_positions_are_distinct_and_valid() {
+        let mut seen = HashSet::new();
+        for index in 0..POSITION_COUNT {
+            let rank = back_rank(index).unwrap();
+            assert!(
+                seen.insert(start_fen(index)),
+                "duplicate position {}",
+                index
+            );
+
+            let files = |piece| (0..8).filter(move |&file| rank[file] == piece);
+            let bishops: Vec<usize> = files(Piece::Bishop).collect();
+            let rooks: Vec<usize> = files(Piece::Rook).collect();
+            let king = files(Piece::King).next().unwrap();
+            assert_ne!(bishops[0] % 2, bishops[1] % 2);
+            assert!(rooks[0] < king && king < rooks[1]);
+        }
+    }
+
+    #[test]
+    fn test_chess960_perft() {
+        // Reference counts from the standard Chess960 perft suite
+        for (fen, expected) in [
+            (
+                "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+                [21, 528, 12189],
+            ),
+            (
+                "2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9",
+                [21, 807, 18002],
+            ),
+        ] {
+            let mut board = Board::from_fen(fen);
+            for (depth, &nodes) in expected.iter().enumerate() {
+                assert_eq!(perft(&mut board, depth as u32 + 1), nodes, "{}", fen);
+            }
+        }
+    }
+
+    #[test]
+    fn test_castling_with_king_and_rook_swapping_squares() {
+        // King f1 and rook g1: kingside castling puts the king on g1 and the rook on f1
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/R4KR1 w GA - 0 1");
+        let original_hash = board.hash;
+        let castle = ChessMove::new_castle(5, 6);
+
+        let mut moves = Vec::new();
+        board.generate_moves(&mut moves);
+        assert!(moves.contains(&castle));
+        assert!(moves.contains(&ChessMove::new_castle(5, 0)));
+
+        let undo = board.make_move(castle);
+        assert_eq!(board.piece_on(6), Some((Color::White, Piece::King)));
+        assert_eq!(board.piece_on(5), Some((Color::White, Piece::Rook)));
+        assert_eq!(board.hash, crate::search::compute_hash_board(&board));
+        assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/8/R4RK1 b - - 1 1");
+
+        board.unmake_move(undo);
+        assert_eq!(board.piece_on(5), Some((Color::White, Piece::King)));
+        assert_eq!(board.piece_on(6), Some((Color::White, Piece::Rook)));
+        assert_eq!(board.hash, original_hash);
+        assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/8/R4KR1 w GA - 0 1");
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    board::{Board, CastlingRights, Color, Piece},
+    board::{Board, CastlingRights, Color, EvalAccumulator, Piece},
     fen::{FENParser, ParsedFEN},
     search::compute_hash_board,
 };
@@ -17,6 +17,7 @@ impl Board {
             halfmove_clock: 0,
             king_sq: [64; 2],
             hash: 0u64,
+            accumulator: EvalAccumulator::default(),
         }
     }
 
@@ -26,9 +27,16 @@ impl Board {
     }
 
     /// Create a Chess board from FEN (Forsyth–Edwards Notation)
+    ///
+    /// Panics on malformed FEN and does not check the position is legal;
+    /// use `try_from_fen` for untrusted input.
     pub fn from_fen(fen: &str) -> Self {
         let parsed: ParsedFEN = FENParser::parse(fen).expect("Invalid FEN string");
+        Self::from_parsed(&parsed)
+    }
 
+    /// Build a board from an already parsed FEN without checking the position
+    pub(crate) fn from_parsed(parsed: &ParsedFEN) -> Self {
         let mut board: Board = Self::new_empty();
 
         // Set pieces from parsed board
@@ -61,6 +69,9 @@ impl Board {
         // Compute the Zobrist hash
         board.hash = compute_hash_board(&board);
 
+        // Seed the incremental evaluation terms
+        board.refresh_accumulator();
+
         board
     }
 
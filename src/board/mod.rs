@@ -1,6 +1,8 @@
 pub mod accessors;
+pub mod accumulator;
 pub mod attacks;
 pub mod castling;
+pub mod chess960;
 pub mod color;
 pub mod default;
 pub mod init;
@@ -8,10 +10,13 @@ pub mod model;
 pub mod moves;
 pub mod piece;
 pub mod utils;
+pub mod validate;
 
+pub use accumulator::EvalAccumulator;
 pub use castling::CastlingRights;
 pub use color::Color;
 pub use model::Board;
 pub use moves::{ChessMove, MoveUndo};
 pub use piece::Piece;
-pub use utils::print_board;
+pub use utils::{BoardStyle, print_board, render_board};
+pub use validate::{BoardFromFenError, PositionError};
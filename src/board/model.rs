@@ -1,4 +1,4 @@
-use super::{CastlingRights, Color};
+use super::{CastlingRights, Color, EvalAccumulator};
 
 #[derive(Copy, Clone)]
 pub struct Board {
@@ -23,4 +23,7 @@ pub struct Board {
     // Hash (represented as 64-bit unsigned integer)
     // The hash of the board state
     pub hash: u64,
+
+    // Material and piece-square terms, updated incrementally by make/unmake
+    pub accumulator: EvalAccumulator,
 }
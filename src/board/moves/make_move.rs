@@ -13,12 +13,19 @@ impl Board {
     pub fn make_move(&mut self, mv: ChessMove) -> MoveUndo {
         let from = mv.from();
         let to = mv.to();
+        // Castling moves encode the rook square in `to`; the king lands elsewhere
+        let dest = mv.destination();
         let from_mask: u64 = 1u64 << from;
         let to_mask: u64 = 1u64 << to;
+        let dest_mask: u64 = 1u64 << dest;
 
         // Get the piece being moved and optional captured piece at destination
         let moved_piece: Option<(Color, Piece)> = self.piece_on(from as u8);
-        let captured_piece_at_to: Option<(Color, Piece)> = self.piece_on(to as u8);
+        let captured_piece_at_to: Option<(Color, Piece)> = if mv.is_castle() {
+            None
+        } else {
+            self.piece_on(to as u8)
+        };
 
         // Determine captured piece (EP captures a pawn not at the to square)
         let captured_piece: Option<Piece> = if mv.is_en_passant() {
@@ -67,13 +74,7 @@ impl Board {
 
         // 3. XOR out rook if castling (will be moved to new square)
         if mv.is_castle() {
-            match to {
-                6 => self.hash ^= zobrist.piece(Piece::Rook, Color::White, 7), // h1
-                2 => self.hash ^= zobrist.piece(Piece::Rook, Color::White, 0), // a1
-                62 => self.hash ^= zobrist.piece(Piece::Rook, Color::Black, 63), // h8
-                58 => self.hash ^= zobrist.piece(Piece::Rook, Color::Black, 56), // a8
-                _ => {}
-            }
+            self.hash ^= zobrist.piece(Piece::Rook, self.side_to_move, to);
         }
 
         // 4. XOR out old castling rights
@@ -106,6 +107,8 @@ impl Board {
             // Remove piece from source square
             self.pieces[moving_color as usize][moving_piece as usize] &= !from_mask;
             self.occ[moving_color as usize] &= !from_mask;
+            self.accumulator
+                .remove_piece(moving_color, moving_piece, from);
 
             // Handle captures
             if is_capture {
@@ -119,13 +122,24 @@ impl Board {
                     self.pieces[moving_color.opponent() as usize][Piece::Pawn as usize] &=
                         !captured_mask;
                     self.occ[moving_color.opponent() as usize] &= !captured_mask;
+                    self.accumulator.remove_piece(
+                        moving_color.opponent(),
+                        Piece::Pawn,
+                        captured_pawn_sq,
+                    );
                 } else if let Some((cap_color, cap_piece)) = captured_piece_at_to {
                     // Normal capture - remove piece from destination
                     self.pieces[cap_color as usize][cap_piece as usize] &= !to_mask;
                     self.occ[cap_color as usize] &= !to_mask;
+                    self.accumulator.remove_piece(cap_color, cap_piece, to);
                 }
             }
 
+            // Lift the castling rook before placing the king, which may land on its square
+            if mv.is_castle() {
+                self.move_castling_rook(moving_color, to, castling_rook_destination(mv));
+            }
+
             // Handle promotions
             let final_piece = if let Some(promo_piece) = mv.promotion_piece() {
                 promo_piece
@@ -134,47 +148,13 @@ impl Board {
             };
 
             // Place piece on destination square
-            self.pieces[moving_color as usize][final_piece as usize] |= to_mask;
-            self.occ[moving_color as usize] |= to_mask;
+            self.pieces[moving_color as usize][final_piece as usize] |= dest_mask;
+            self.occ[moving_color as usize] |= dest_mask;
+            self.accumulator.add_piece(moving_color, final_piece, dest);
 
             // Update king position
             if moving_piece == Piece::King {
-                self.king_sq[moving_color as usize] = to as u8;
-            }
-
-            // Handle castling rook move
-            if mv.is_castle() {
-                match to {
-                    6 => {
-                        // White kingside: move rook from h1 to f1
-                        self.pieces[Color::White as usize][Piece::Rook as usize] &= !(1u64 << 7);
-                        self.pieces[Color::White as usize][Piece::Rook as usize] |= 1u64 << 5;
-                        self.occ[Color::White as usize] &= !(1u64 << 7);
-                        self.occ[Color::White as usize] |= 1u64 << 5;
-                    }
-                    2 => {
-                        // White queenside: move rook from a1 to d1
-                        self.pieces[Color::White as usize][Piece::Rook as usize] &= !(1u64 << 0);
-                        self.pieces[Color::White as usize][Piece::Rook as usize] |= 1u64 << 3;
-                        self.occ[Color::White as usize] &= !(1u64 << 0);
-                        self.occ[Color::White as usize] |= 1u64 << 3;
-                    }
-                    62 => {
-                        // Black kingside: move rook from h8 to f8
-                        self.pieces[Color::Black as usize][Piece::Rook as usize] &= !(1u64 << 63);
-                        self.pieces[Color::Black as usize][Piece::Rook as usize] |= 1u64 << 61;
-                        self.occ[Color::Black as usize] &= !(1u64 << 63);
-                        self.occ[Color::Black as usize] |= 1u64 << 61;
-                    }
-                    58 => {
-                        // Black queenside: move rook from a8 to d8
-                        self.pieces[Color::Black as usize][Piece::Rook as usize] &= !(1u64 << 56);
-                        self.pieces[Color::Black as usize][Piece::Rook as usize] |= 1u64 << 59;
-                        self.occ[Color::Black as usize] &= !(1u64 << 56);
-                        self.occ[Color::Black as usize] |= 1u64 << 59;
-                    }
-                    _ => {}
-                }
+                self.king_sq[moving_color as usize] = dest as u8;
             }
 
             // Update castling rights
@@ -182,25 +162,12 @@ impl Board {
                 self.castling.remove(moving_color, CastlingSide::KingSide);
                 self.castling.remove(moving_color, CastlingSide::QueenSide);
             } else if moving_piece == Piece::Rook {
-                // Check which rook moved
-                match from {
-                    0 => self.castling.remove(Color::White, CastlingSide::QueenSide), // a1
-                    7 => self.castling.remove(Color::White, CastlingSide::KingSide),  // h1
-                    56 => self.castling.remove(Color::Black, CastlingSide::QueenSide), // a8
-                    63 => self.castling.remove(Color::Black, CastlingSide::KingSide), // h8
-                    _ => {}
-                }
+                self.castling.remove_rook(moving_color, from as u8);
             }
 
             // If a rook is captured, remove castling rights
-            if let Some((_, Piece::Rook)) = captured_piece_at_to {
-                match to {
-                    0 => self.castling.remove(Color::White, CastlingSide::QueenSide), // a1
-                    7 => self.castling.remove(Color::White, CastlingSide::KingSide),  // h1
-                    56 => self.castling.remove(Color::Black, CastlingSide::QueenSide), // a8
-                    63 => self.castling.remove(Color::Black, CastlingSide::KingSide), // h8
-                    _ => {}
-                }
+            if let Some((cap_color, Piece::Rook)) = captured_piece_at_to {
+                self.castling.remove_rook(cap_color, to as u8);
             }
 
             // Update combined occupancy
@@ -239,17 +206,12 @@ impl Board {
             } else {
                 moving_piece
             };
-            self.hash ^= zobrist.piece(final_piece, moving_color, to);
+            self.hash ^= zobrist.piece(final_piece, moving_color, dest);
 
             // 8. XOR in rook at new position if castling
             if mv.is_castle() {
-                match to {
-                    6 => self.hash ^= zobrist.piece(Piece::Rook, Color::White, 5), // f1
-                    2 => self.hash ^= zobrist.piece(Piece::Rook, Color::White, 3), // d1
-                    62 => self.hash ^= zobrist.piece(Piece::Rook, Color::Black, 61), // f8
-                    58 => self.hash ^= zobrist.piece(Piece::Rook, Color::Black, 59), // d8
-                    _ => {}
-                }
+                self.hash ^=
+                    zobrist.piece(Piece::Rook, moving_color, castling_rook_destination(mv));
             }
 
             // 9. XOR in new castling rights
@@ -292,8 +254,25 @@ impl Board {
         self.side_to_move = self.side_to_move.opponent();
         let moving_color = self.side_to_move;
 
-        // Find the piece at the destination square
-        if let Some((_, piece_on_dest)) = self.piece_on(to as u8) {
+        if mv.is_castle() {
+            // Lift both pieces first: the king may have landed on the rook's start square
+            let king_to = mv.destination();
+            let rook_to = castling_rook_destination(mv);
+            let king_mask = 1u64 << king_to;
+            self.pieces[moving_color as usize][Piece::King as usize] &= !king_mask;
+            self.occ[moving_color as usize] &= !king_mask;
+            self.accumulator
+                .remove_piece(moving_color, Piece::King, king_to);
+            self.move_castling_rook(moving_color, rook_to, to);
+
+            self.pieces[moving_color as usize][Piece::King as usize] |= from_mask;
+            self.occ[moving_color as usize] |= from_mask;
+            self.accumulator.add_piece(moving_color, Piece::King, from);
+            self.king_sq[moving_color as usize] = from as u8;
+
+            self.occ_all = self.occ[Color::White as usize] | self.occ[Color::Black as usize];
+        } else if let Some((_, piece_on_dest)) = self.piece_on(to as u8) {
+            // Find the piece at the destination square
             // For promotion, the original piece was a Pawn
             let original_piece = if mv.is_promotion() {
                 Piece::Pawn
@@ -304,51 +283,20 @@ impl Board {
             // Remove piece from destination square
             self.pieces[moving_color as usize][piece_on_dest as usize] &= !to_mask;
             self.occ[moving_color as usize] &= !to_mask;
+            self.accumulator
+                .remove_piece(moving_color, piece_on_dest, to);
 
             // Restore piece to source square
             self.pieces[moving_color as usize][original_piece as usize] |= from_mask;
             self.occ[moving_color as usize] |= from_mask;
+            self.accumulator
+                .add_piece(moving_color, original_piece, from);
 
             // Restore king position if king was moved
             if original_piece == Piece::King {
                 self.king_sq[moving_color as usize] = from as u8;
             }
 
-            // Handle castling undo
-            if mv.is_castle() {
-                match to {
-                    6 => {
-                        // White kingside: move rook back from f1 to h1
-                        self.pieces[Color::White as usize][Piece::Rook as usize] &= !(1u64 << 5);
-                        self.pieces[Color::White as usize][Piece::Rook as usize] |= 1u64 << 7;
-                        self.occ[Color::White as usize] &= !(1u64 << 5);
-                        self.occ[Color::White as usize] |= 1u64 << 7;
-                    }
-                    2 => {
-                        // White queenside: move rook back from d1 to a1
-                        self.pieces[Color::White as usize][Piece::Rook as usize] &= !(1u64 << 3);
-                        self.pieces[Color::White as usize][Piece::Rook as usize] |= 1u64 << 0;
-                        self.occ[Color::White as usize] &= !(1u64 << 3);
-                        self.occ[Color::White as usize] |= 1u64 << 0;
-                    }
-                    62 => {
-                        // Black kingside: move rook back from f8 to h8
-                        self.pieces[Color::Black as usize][Piece::Rook as usize] &= !(1u64 << 61);
-                        self.pieces[Color::Black as usize][Piece::Rook as usize] |= 1u64 << 63;
-                        self.occ[Color::Black as usize] &= !(1u64 << 61);
-                        self.occ[Color::Black as usize] |= 1u64 << 63;
-                    }
-                    58 => {
-                        // Black queenside: move rook back from d8 to a8
-                        self.pieces[Color::Black as usize][Piece::Rook as usize] &= !(1u64 << 59);
-                        self.pieces[Color::Black as usize][Piece::Rook as usize] |= 1u64 << 56;
-                        self.occ[Color::Black as usize] &= !(1u64 << 59);
-                        self.occ[Color::Black as usize] |= 1u64 << 56;
-                    }
-                    _ => {}
-                }
-            }
-
             // Restore captured piece
             if let Some(captured) = undo.captured_piece {
                 if mv.is_en_passant() {
@@ -361,10 +309,17 @@ impl Board {
                     self.pieces[moving_color.opponent() as usize][Piece::Pawn as usize] |=
                         captured_mask;
                     self.occ[moving_color.opponent() as usize] |= captured_mask;
+                    self.accumulator.add_piece(
+                        moving_color.opponent(),
+                        Piece::Pawn,
+                        captured_pawn_sq,
+                    );
                 } else {
                     // Restore normal captured piece
                     self.pieces[moving_color.opponent() as usize][captured as usize] |= to_mask;
                     self.occ[moving_color.opponent() as usize] |= to_mask;
+                    self.accumulator
+                        .add_piece(moving_color.opponent(), captured, to);
                 }
             }
 
@@ -378,4 +333,18 @@ impl Board {
         self.halfmove_clock = undo.previous_halfmove_clock;
         self.hash = undo.previous_zobrist_hash;
     }
+
+    /// Moves a castling rook between squares, leaving occ_all to the caller.
+    fn move_castling_rook(&mut self, color: Color, from: usize, to: usize) {
+        let mask = (1u64 << from) ^ (1u64 << to);
+        self.pieces[color as usize][Piece::Rook as usize] ^= mask;
+        self.occ[color as usize] ^= mask;
+        self.accumulator.move_piece(color, Piece::Rook, from, to);
+    }
+}
+
+/// Square the rook lands on when castling (f- or d-file of the king's rank).
+fn castling_rook_destination(mv: ChessMove) -> usize {
+    let side = mv.castling_side().unwrap_or(CastlingSide::KingSide);
+    (mv.from() & !7) + side.rook_destination_file() as usize
 }
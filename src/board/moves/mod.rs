@@ -1,5 +1,6 @@
 mod make_move;
 
+use super::castling::CastlingSide;
 use super::{CastlingRights, Piece};
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -50,12 +51,13 @@ impl ChessMove {
         )
     }
 
-    /// Castling move.
+    /// Castling move, encoded as the king capturing its own rook so that Chess960
+    /// castles stay unambiguous: `to` is the rook's starting square.
     #[inline(always)]
-    pub const fn new_castle(from: usize, to: usize) -> Self {
+    pub const fn new_castle(king_from: usize, rook_from: usize) -> Self {
         Self(
-            (from as u16 & FROM_MASK)
-                | ((to as u16) << TO_SHIFT & TO_MASK)
+            (king_from as u16 & FROM_MASK)
+                | ((rook_from as u16) << TO_SHIFT & TO_MASK)
                 | (FLAG_CASTLE << FLAG_SHIFT),
         )
     }
@@ -103,6 +105,28 @@ impl ChessMove {
         ((self.0 & TO_MASK) >> TO_SHIFT) as usize
     }
 
+    /// Square the moving piece ends on: the king's g- or c-file square for castling,
+    /// `to()` otherwise.
+    #[inline(always)]
+    pub const fn destination(self) -> usize {
+        match self.castling_side() {
+            Some(side) => (self.from() & !7) + side.king_destination_file() as usize,
+            None => self.to(),
+        }
+    }
+
+    /// Which way a castling move goes, or `None` for other moves.
+    #[inline(always)]
+    pub const fn castling_side(self) -> Option<CastlingSide> {
+        if !self.is_castle() {
+            None
+        } else if self.to() > self.from() {
+            Some(CastlingSide::KingSide)
+        } else {
+            Some(CastlingSide::QueenSide)
+        }
+    }
+
     /// Raw 2-bit flag field.
     #[inline(always)]
     const fn flags(self) -> u16 {
@@ -160,19 +184,23 @@ impl ChessMove {
     // ── Display helpers ─────────────────────────────────────────────
 
     /// UCI notation (e.g. "e2e4", "e7e8q").
+    ///
+    /// Castling from the standard king and rook squares prints the king's move
+    /// ("e1g1"); other Chess960 castles print king-takes-rook ("b1a1").
     pub fn to_uci(&self) -> String {
-        let from_file = (self.from() % 8) as u8;
-        let from_rank = (self.from() / 8) as u8;
-        let to_file = (self.to() % 8) as u8;
-        let to_rank = (self.to() / 8) as u8;
-
-        let mut uci = format!(
-            "{}{}{}{}",
-            (b'a' + from_file) as char,
-            (b'1' + from_rank) as char,
-            (b'a' + to_file) as char,
-            (b'1' + to_rank) as char,
-        );
+        let standard_castle =
+            self.is_castle() && self.from() % 8 == 4 && matches!(self.to() % 8, 0 | 7);
+        if standard_castle {
+            uci_squares(self.from(), self.destination())
+        } else {
+            self.to_uci_chess960()
+        }
+    }
+
+    /// UCI notation with every castle written king-takes-rook ("e1h1"), as used
+    /// by engines in `UCI_Chess960` mode.
+    pub fn to_uci_chess960(&self) -> String {
+        let mut uci = uci_squares(self.from(), self.to());
 
         if let Some(piece) = self.promotion_piece() {
             let ch = match piece {
@@ -190,14 +218,9 @@ impl ChessMove {
 
     /// Standard algebraic castling notation ("O-O" or "O-O-O"), or `None`.
     pub fn to_castling_notation(&self) -> Option<&'static str> {
-        if !self.is_castle() {
-            return None;
-        }
-        let to_file = self.to() % 8;
-        match to_file {
-            6 => Some("O-O"),
-            2 => Some("O-O-O"),
-            _ => None,
+        match self.castling_side()? {
+            CastlingSide::KingSide => Some("O-O"),
+            CastlingSide::QueenSide => Some("O-O-O"),
         }
     }
 
@@ -211,6 +234,15 @@ impl ChessMove {
     }
 }
 
+fn uci_squares(from: usize, to: usize) -> String {
+    let square = |sq: usize| {
+        let file = (b'a' + (sq % 8) as u8) as char;
+        let rank = (b'1' + (sq / 8) as u8) as char;
+        format!("{}{}", file, rank)
+    };
+    format!("{}{}", square(from), square(to))
+}
+
 // ── Debug ───────────────────────────────────────────────────────────
 
 impl fmt::Debug for ChessMove {
@@ -267,13 +299,23 @@ mod tests {
 
     #[test]
     fn castle_move_round_trip() {
-        let mv = ChessMove::new_castle(4, 6); // e1 → g1 (white kingside)
+        let mv = ChessMove::new_castle(4, 7); // e1 takes h1 (white kingside)
         assert_eq!(mv.from(), 4);
-        assert_eq!(mv.to(), 6);
+        assert_eq!(mv.to(), 7);
+        assert_eq!(mv.destination(), 6);
+        assert_eq!(mv.castling_side(), Some(CastlingSide::KingSide));
         assert!(mv.is_castle());
         assert!(!mv.is_quiet());
     }
 
+    #[test]
+    fn chess960_castle_destination() {
+        let mv = ChessMove::new_castle(57, 56); // b8 takes a8 (black queenside)
+        assert_eq!(mv.destination(), 58);
+        assert_eq!(mv.castling_side(), Some(CastlingSide::QueenSide));
+        assert_eq!(ChessMove::new(12, 28).destination(), 28);
+    }
+
     #[test]
     fn en_passant_round_trip() {
         let mv = ChessMove::new_en_passant(33, 40); // b5 → a6
@@ -351,17 +393,27 @@ mod tests {
         assert_eq!(mv.to_uci(), "a7a8n");
     }
 
+    #[test]
+    fn uci_castling() {
+        let standard = ChessMove::new_castle(4, 7);
+        assert_eq!(standard.to_uci(), "e1g1");
+        assert_eq!(standard.to_uci_chess960(), "e1h1");
+
+        let chess960 = ChessMove::new_castle(1, 0); // king b1, rook a1
+        assert_eq!(chess960.to_uci(), "b1a1");
+    }
+
     // ── Castling notation ───────────────────────────────────────────
 
     #[test]
     fn castling_notation_kingside() {
-        let mv = ChessMove::new_castle(4, 6); // e1 → g1
+        let mv = ChessMove::new_castle(4, 7); // e1 → g1
         assert_eq!(mv.to_castling_notation(), Some("O-O"));
     }
 
     #[test]
     fn castling_notation_queenside() {
-        let mv = ChessMove::new_castle(4, 2); // e1 → c1
+        let mv = ChessMove::new_castle(4, 0); // e1 → c1
         assert_eq!(mv.to_castling_notation(), Some("O-O-O"));
     }
 
@@ -375,7 +427,7 @@ mod tests {
 
     #[test]
     fn display_castle_uses_notation() {
-        let mv = ChessMove::new_castle(4, 6);
+        let mv = ChessMove::new_castle(4, 7);
         assert_eq!(mv.to_display(), "O-O");
     }
 
@@ -737,7 +737,7 @@ fn test_make_unmake_castling() {
     let original_castling = board.castling;
 
     // Make castling move
-    let mv = ChessMove::new_castle(4, 6);
+    let mv = ChessMove::new_castle(4, 7);
 
     let state = board.make_move(mv);
 
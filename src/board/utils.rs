@@ -1,46 +1,257 @@
-use crate::board::{Board, Color, Piece};
+use crate::board::{Board, ChessMove, Color, Piece};
+use std::fmt::Write;
 
-/// Print the Chess board to the console
-pub fn print_board(board: &Board) {
-    // Unicode chess symbols
-    fn unicode_symbol(piece: Piece, color: Color) -> char {
-        match (piece, color) {
-            (Piece::Pawn, Color::White) => '♙',
-            (Piece::Knight, Color::White) => '♘',
-            (Piece::Bishop, Color::White) => '♗',
-            (Piece::Rook, Color::White) => '♖',
-            (Piece::Queen, Color::White) => '♕',
-            (Piece::King, Color::White) => '♔',
-            (Piece::Pawn, Color::Black) => '♟',
-            (Piece::Knight, Color::Black) => '♞',
-            (Piece::Bishop, Color::Black) => '♝',
-            (Piece::Rook, Color::Black) => '♜',
-            (Piece::Queen, Color::Black) => '♛',
-            (Piece::King, Color::Black) => '♚',
+// ANSI color codes for board squares
+const LIGHT_SQUARE: &str = "\x1b[48;5;230m"; // beige
+const DARK_SQUARE: &str = "\x1b[48;5;94m"; // brown
+const LIGHT_LAST_MOVE: &str = "\x1b[48;5;187m"; // pale olive
+const DARK_LAST_MOVE: &str = "\x1b[48;5;143m"; // olive
+const CHECK_SQUARE: &str = "\x1b[48;5;167m"; // red
+const RESET: &str = "\x1b[0m";
+
+/// Pieces in the order the captured-material bar lists them
+const BAR_PIECES: [Piece; 5] = [
+    Piece::Queen,
+    Piece::Rook,
+    Piece::Bishop,
+    Piece::Knight,
+    Piece::Pawn,
+];
+
+/// How `render_board` draws a board.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BoardStyle {
+    /// Letters (K for a white king, k for a black one) instead of chess symbols
+    pub ascii: bool,
+    /// ANSI colored squares and highlights; without them, highlighted squares are
+    /// bracketed: [e4] for the last move and !K! for a king in check
+    pub color: bool,
+    /// Black's side at the bottom
+    pub flipped: bool,
+    /// File letters and rank numbers around the board
+    pub coordinates: bool,
+    /// A bar above or below the board with the extra material of the side that's ahead
+    pub captured_material: bool,
+}
+
+impl Default for BoardStyle {
+    fn default() -> Self {
+        Self {
+            ascii: false,
+            color: true,
+            flipped: false,
+            coordinates: true,
+            captured_material: false,
+        }
+    }
+}
+
+fn piece_symbol(piece: Piece, color: Color, ascii: bool) -> char {
+    if ascii {
+        let letter = match piece {
+            Piece::Pawn => 'P',
+            Piece::Knight => 'N',
+            Piece::Bishop => 'B',
+            Piece::Rook => 'R',
+            Piece::Queen => 'Q',
+            Piece::King => 'K',
+        };
+        return match color {
+            Color::White => letter,
+            Color::Black => letter.to_ascii_lowercase(),
+        };
+    }
+    match (piece, color) {
+        (Piece::Pawn, Color::White) => '♙',
+        (Piece::Knight, Color::White) => '♘',
+        (Piece::Bishop, Color::White) => '♗',
+        (Piece::Rook, Color::White) => '♖',
+        (Piece::Queen, Color::White) => '♕',
+        (Piece::King, Color::White) => '♔',
+        (Piece::Pawn, Color::Black) => '♟',
+        (Piece::Knight, Color::Black) => '♞',
+        (Piece::Bishop, Color::Black) => '♝',
+        (Piece::Rook, Color::Black) => '♜',
+        (Piece::Queen, Color::Black) => '♛',
+        (Piece::King, Color::Black) => '♚',
+    }
+}
+
+fn piece_points(piece: Piece) -> i32 {
+    match piece {
+        Piece::Pawn => 1,
+        Piece::Knight | Piece::Bishop => 3,
+        Piece::Rook => 5,
+        Piece::Queen => 9,
+        Piece::King => 0,
+    }
+}
+
+/// The pieces `color` has beyond the opponent's, drawn as the opponent's pieces it
+/// has taken, and its lead in points, e.g. "♞♟ +4". Empty when it isn't ahead.
+fn material_bar(board: &Board, color: Color, ascii: bool) -> String {
+    let mut bar = String::new();
+    let mut lead = 0;
+    for piece in BAR_PIECES {
+        let extra = board.count_pieces(color, piece) as i32
+            - board.count_pieces(color.opponent(), piece) as i32;
+        lead += extra * piece_points(piece);
+        for _ in 0..extra.max(0) {
+            bar.push(piece_symbol(piece, color.opponent(), ascii));
         }
     }
+    if lead > 0 {
+        write!(bar, " +{}", lead).unwrap();
+    }
+    bar
+}
 
-    // ANSI color codes for board squares
-    const LIGHT_SQUARE: &str = "\x1b[48;5;230m"; // beige
-    const DARK_SQUARE: &str = "\x1b[48;5;94m"; // brown
-    const RESET: &str = "\x1b[0m";
+/// Draws `board` as text in `style`, highlighting `last_move` and a king in check.
+pub fn render_board(board: &Board, style: &BoardStyle, last_move: Option<ChessMove>) -> String {
+    let (ranks, files): (Vec<u8>, Vec<u8>) = if style.flipped {
+        ((0..8).collect(), (0..8).rev().collect())
+    } else {
+        ((0..8).rev().collect(), (0..8).collect())
+    };
+    let (top, bottom) = if style.flipped {
+        (Color::White, Color::Black)
+    } else {
+        (Color::Black, Color::White)
+    };
+    let moved = last_move.map(|m| [m.from() as u8, m.destination() as u8]);
+    let checked_king = board
+        .in_check(board.side_to_move)
+        .then(|| board.king_square(board.side_to_move));
 
-    println!("\n  a b c d e f g h");
-    for rank in (0..8).rev() {
-        print!("{} ", rank + 1);
-        for file in 0..8 {
+    let mut out = String::from("\n");
+    let margin = if style.coordinates { "  " } else { "" };
+    let file_labels: String = files
+        .iter()
+        .map(|&file| {
+            let letter = (b'a' + file) as char;
+            if style.color {
+                format!("{} ", letter)
+            } else {
+                format!(" {} ", letter)
+            }
+        })
+        .collect();
+    let top_bar = material_bar(board, top, style.ascii);
+    if style.captured_material && !top_bar.is_empty() {
+        writeln!(out, "{}{}", margin, top_bar).unwrap();
+    }
+    if style.coordinates {
+        writeln!(out, "{}{}", margin, file_labels).unwrap();
+    }
+    for &rank in &ranks {
+        if style.coordinates {
+            write!(out, "{} ", rank + 1).unwrap();
+        }
+        for &file in &files {
             let sq = rank * 8 + file;
             let is_light = (rank + file) % 2 == 1;
-            let bg_color = if is_light { LIGHT_SQUARE } else { DARK_SQUARE };
+            let is_last_move = moved.is_some_and(|squares| squares.contains(&sq));
+            let is_check = checked_king == Some(sq);
+            let symbol = match board.piece_on(sq) {
+                Some((color, piece)) => piece_symbol(piece, color, style.ascii),
+                None if style.color => ' ',
+                None => '.',
+            };
 
-            if let Some((color, piece)) = board.piece_on(sq) {
-                let symbol = unicode_symbol(piece, color);
-                print!("{}{} {}", bg_color, symbol, RESET);
+            if style.color {
+                let bg_color = match (is_check, is_last_move, is_light) {
+                    (true, _, _) => CHECK_SQUARE,
+                    (false, true, true) => LIGHT_LAST_MOVE,
+                    (false, true, false) => DARK_LAST_MOVE,
+                    (false, false, true) => LIGHT_SQUARE,
+                    (false, false, false) => DARK_SQUARE,
+                };
+                write!(out, "{}{} {}", bg_color, symbol, RESET).unwrap();
             } else {
-                print!("{}  {}", bg_color, RESET);
+                let (left, right) = if is_check {
+                    ('!', '!')
+                } else if is_last_move {
+                    ('[', ']')
+                } else {
+                    (' ', ' ')
+                };
+                write!(out, "{}{}{}", left, symbol, right).unwrap();
             }
         }
-        println!(" {}", rank + 1);
+        if style.coordinates {
+            write!(out, " {}", rank + 1).unwrap();
+        }
+        out.push('\n');
+    }
+    if style.coordinates {
+        writeln!(out, "{}{}", margin, file_labels).unwrap();
+    }
+    let bottom_bar = material_bar(board, bottom, style.ascii);
+    if style.captured_material && !bottom_bar.is_empty() {
+        writeln!(out, "{}{}", margin, bottom_bar).unwrap();
+    }
+    out.push('\n');
+    out
+}
+
+/// Whether the `NO_COLOR` environment variable asks for output without colors
+/// (<https://no-color.org>).
+pub fn no_color_requested() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+/// Print the Chess board to the console
+pub fn print_board(board: &Board) {
+    let style = BoardStyle {
+        color: !no_color_requested(),
+        ..BoardStyle::default()
+    };
+    print!("{}", render_board(board, &style, None));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAIN: BoardStyle = BoardStyle {
+        ascii: true,
+        color: false,
+        flipped: false,
+        coordinates: false,
+        captured_material: false,
+    };
+
+    #[test]
+    fn test_plain_rendering_marks_last_move_and_check() {
+        // 1. f3 e5 2. g4 Qh4#
+        let board =
+            Board::from_fen("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3");
+        let text = render_board(&board, &PLAIN, Some(ChessMove::new(59, 31)));
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[1], " r  n  b [.] k  b  n  r ");
+        assert_eq!(lines[5], " .  .  .  .  .  .  P [q]");
+        assert_eq!(lines[8], " R  N  B  Q !K! B  N  R ");
+    }
+
+    #[test]
+    fn test_flipped_board_with_coordinates_and_material() {
+        // White is a knight and a pawn up
+        let board = Board::from_fen("4k3/8/8/8/8/8/4P3/4KN2 w - - 0 1");
+        let style = BoardStyle {
+            flipped: true,
+            coordinates: true,
+            captured_material: true,
+            ..PLAIN
+        };
+        let text = render_board(&board, &style, None);
+        let lines: Vec<&str> = text.lines().collect();
+        // White's side, and its bar, are on top
+        assert_eq!(lines[1], "  np +4");
+        assert_eq!(lines[2], "   h  g  f  e  d  c  b  a ");
+        assert_eq!(lines[3], "1  .  .  N  K  .  .  .  .  1");
+        // Black isn't ahead, so no bar follows the coordinates, only the closing blank line
+        assert_eq!(lines[11], lines[2]);
+        assert_eq!(lines[12], "");
+        assert_eq!(lines.len(), 13);
     }
-    println!("  a b c d e f g h\n");
 }
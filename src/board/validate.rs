@@ -0,0 +1,271 @@
+//! Legality checks for positions built from untrusted input.
+//!
+//! FEN parsing only checks syntax; [`Board::validate`] catches positions the rest of
+//! the engine assumes can't happen, and [`Board::try_from_fen`] refuses to build them.
+
+use super::castling::CastlingSide;
+use super::{Board, Color, Piece};
+use crate::fen::{FENParseError, FENParser};
+use std::fmt;
+
+/// A rule of chess a position breaks
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PositionError {
+    /// The color has no king
+    MissingKing(Color),
+    /// The color has more than one king
+    TooManyKings(Color),
+    /// A pawn stands on the first or eighth rank
+    PawnOnBackRank(u8),
+    /// The side that just moved was left in check
+    OpponentInCheck,
+    /// A castling right without the king and rook on their starting squares
+    InvalidCastlingRight(Color, CastlingSide),
+    /// An en passant square no double pawn push could have produced
+    InvalidEnPassant(u8),
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingKing(color) => write!(f, "{:?} has no king", color),
+            Self::TooManyKings(color) => write!(f, "{:?} has more than one king", color),
+            Self::PawnOnBackRank(sq) => write!(f, "Pawn on back rank square {}", square_name(*sq)),
+            Self::OpponentInCheck => write!(f, "The side not to move is in check"),
+            Self::InvalidCastlingRight(color, side) => write!(
+                f,
+                "{:?} {:?} castling right without king and rook on their starting squares",
+                color, side
+            ),
+            Self::InvalidEnPassant(sq) => {
+                write!(f, "Impossible en passant square {}", square_name(*sq))
+            }
+        }
+    }
+}
+
+impl std::error::Error for PositionError {}
+
+/// Why a FEN string could not be turned into a board
+#[derive(Debug, PartialEq, Eq)]
+pub enum BoardFromFenError {
+    /// The FEN string is malformed
+    Parse(FENParseError),
+    /// The FEN string is well formed but describes an illegal position
+    Invalid(Vec<PositionError>),
+}
+
+impl fmt::Display for BoardFromFenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(e) => write!(f, "{}", e),
+            Self::Invalid(errors) => {
+                let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+                write!(f, "Illegal position: {}", messages.join("; "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for BoardFromFenError {}
+
+fn square_name(sq: u8) -> String {
+    format!("{}{}", (b'a' + sq % 8) as char, (b'1' + sq / 8) as char)
+}
+
+const BACK_RANKS: u64 = 0xFF00_0000_0000_00FF;
+
+impl Board {
+    /// Create a board from FEN, rejecting malformed strings and illegal positions.
+    pub fn try_from_fen(fen: &str) -> Result<Self, BoardFromFenError> {
+        let parsed = FENParser::parse(fen).map_err(BoardFromFenError::Parse)?;
+        let board = Self::from_parsed(&parsed);
+        board.validate().map_err(BoardFromFenError::Invalid)?;
+        Ok(board)
+    }
+
+    /// Check the position for everything a legal game could not reach in one move
+    /// order or another, returning every violation found.
+    pub fn validate(&self) -> Result<(), Vec<PositionError>> {
+        let mut errors = Vec::new();
+
+        let mut kings_ok = true;
+        for color in [Color::White, Color::Black] {
+            match self.count_pieces(color, Piece::King) {
+                0 => errors.push(PositionError::MissingKing(color)),
+                1 => continue,
+                _ => errors.push(PositionError::TooManyKings(color)),
+            }
+            kings_ok = false;
+        }
+
+        let mut pawns = (self.pieces_of(Color::White, Piece::Pawn)
+            | self.pieces_of(Color::Black, Piece::Pawn))
+            & BACK_RANKS;
+        while pawns != 0 {
+            errors.push(PositionError::PawnOnBackRank(pawns.trailing_zeros() as u8));
+            pawns &= pawns - 1;
+        }
+
+        // Check and castling tests need exactly one king per side
+        if kings_ok {
+            if self.in_check(self.side_to_move.opponent()) {
+                errors.push(PositionError::OpponentInCheck);
+            }
+            for color in [Color::White, Color::Black] {
+                for side in [CastlingSide::KingSide, CastlingSide::QueenSide] {
+                    if self.castling.has(color, side) && !self.castling_pieces_home(color, side) {
+                        errors.push(PositionError::InvalidCastlingRight(color, side));
+                    }
+                }
+            }
+        }
+
+        if self.en_passant < 64 && !self.en_passant_possible() {
+            errors.push(PositionError::InvalidEnPassant(self.en_passant));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Whether the king and the castling rook for a right stand where castling needs them.
+    fn castling_pieces_home(&self, color: Color, side: CastlingSide) -> bool {
+        let back_rank = match color {
+            Color::White => 0,
+            Color::Black => 56,
+        };
+        let king = self.king_sq[color as usize];
+        let rook = self.castling.rook_square(color, side);
+        let rook_on_side = match side {
+            CastlingSide::KingSide => rook > king,
+            CastlingSide::QueenSide => rook < king,
+        };
+        king & !7 == back_rank
+            && rook_on_side
+            && self.pieces_of(color, Piece::Rook) & (1u64 << rook) != 0
+    }
+
+    /// Whether the opponent's last move could have been a double push past the en passant square.
+    fn en_passant_possible(&self) -> bool {
+        let ep = self.en_passant as usize;
+        let (rank, pushed_to, pushed_from) = match self.side_to_move {
+            Color::White => (5, ep.wrapping_sub(8), ep + 8),
+            Color::Black => (2, ep + 8, ep.wrapping_sub(8)),
+        };
+        ep / 8 == rank
+            && self.occ_all & ((1u64 << ep) | (1u64 << pushed_from)) == 0
+            && self.pieces_of(self.side_to_move.opponent(), Piece::Pawn) & (1u64 << pushed_to) != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn violations(fen: &str) -> Vec<PositionError> {
+        Board::from_fen(fen).validate().err().unwrap_or_default()
+    }
+
+    #[test]
+    fn test_legal_positions_validate() {
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 3",
+            "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+        ] {
+            assert_eq!(violations(fen), vec![], "{}", fen);
+        }
+    }
+
+    #[test]
+    fn test_kings_and_pawns() {
+        assert_eq!(
+            violations("8/8/8/8/8/8/8/4K3 w - - 0 1"),
+            vec![PositionError::MissingKing(Color::Black)]
+        );
+        assert_eq!(
+            violations("4k3/8/8/8/8/8/8/K3K3 w - - 0 1"),
+            vec![PositionError::TooManyKings(Color::White)]
+        );
+        assert_eq!(
+            violations("P3k3/8/8/8/8/8/8/4K2p w - - 0 1"),
+            vec![
+                PositionError::PawnOnBackRank(7),
+                PositionError::PawnOnBackRank(56)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_side_not_to_move_in_check() {
+        assert_eq!(
+            violations("4k3/8/8/8/8/8/8/4RK2 w - - 0 1"),
+            vec![PositionError::OpponentInCheck]
+        );
+    }
+
+    #[test]
+    fn test_castling_rights_need_pieces_at_home() {
+        assert_eq!(
+            violations("r3k3/8/8/8/8/8/8/R3K3 w KQq - 0 1"),
+            vec![PositionError::InvalidCastlingRight(
+                Color::White,
+                CastlingSide::KingSide
+            )]
+        );
+        // Chess960: X-FEN "q" finds the a8 rook left of a king on f8
+        assert_eq!(violations("r4k2/8/8/8/8/8/8/4K3 b q - 0 1"), vec![]);
+        assert_eq!(
+            violations("4k3/r7/8/8/8/8/8/4K3 w q - 0 1"),
+            vec![PositionError::InvalidCastlingRight(
+                Color::Black,
+                CastlingSide::QueenSide
+            )]
+        );
+    }
+
+    #[test]
+    fn test_en_passant_needs_a_double_push() {
+        // Right rank but no pawn that could have just moved two squares
+        assert_eq!(
+            violations("4k3/8/8/8/8/8/8/4K3 b - e3 0 1"),
+            vec![PositionError::InvalidEnPassant(20)]
+        );
+        // Pawn present but the wrong side to move
+        assert_eq!(
+            violations("4k3/8/8/8/4P3/8/8/4K3 w - e3 0 1"),
+            vec![PositionError::InvalidEnPassant(20)]
+        );
+    }
+
+    #[test]
+    fn test_try_from_fen_reports_errors() {
+        assert!(
+            Board::try_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").is_ok()
+        );
+        assert!(matches!(
+            Board::try_from_fen("not a fen"),
+            Err(BoardFromFenError::Parse(FENParseError::InsufficientParts {
+                found: 3
+            }))
+        ));
+        let err = Board::try_from_fen("8/8/8/8/8/8/8/8 w - - 0 1")
+            .err()
+            .unwrap();
+        assert_eq!(
+            err,
+            BoardFromFenError::Invalid(vec![
+                PositionError::MissingKing(Color::White),
+                PositionError::MissingKing(Color::Black)
+            ])
+        );
+        assert_eq!(
+            err.to_string(),
+            "Illegal position: White has no king; Black has no king"
+        );
+    }
+}
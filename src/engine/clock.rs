@@ -0,0 +1,278 @@
+//! Chess clocks: time controls with Fischer increment or Bronstein delay, each
+//! side's remaining time, and how much of it an engine should spend on a move.
+
+use crate::board::Color;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// An engine plans to spread its remaining time over this many more moves
+const MOVES_TO_GO: u32 = 30;
+
+/// Engines never plan to spend more than this fraction of their remaining time
+const MAX_TIME_FRACTION: u32 = 2;
+
+/// Shortest time an engine is given for a move, so it always completes a search
+const MIN_MOVE_TIME: Duration = Duration::from_millis(10);
+
+/// Time given back after each move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimeBonus {
+    None,
+    /// Fischer increment: added after every move
+    Increment(Duration),
+    /// Bronstein delay: the time spent on a move is given back, up to this much
+    Delay(Duration),
+}
+
+/// Starting time for each side and the per-move bonus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeControl {
+    pub base: Duration,
+    pub bonus: TimeBonus,
+}
+
+impl TimeControl {
+    /// 90 minutes plus a 30 second increment
+    pub fn classical() -> Self {
+        Self::fischer(Duration::from_secs(90 * 60), Duration::from_secs(30))
+    }
+
+    /// 15 minutes plus a 10 second increment
+    pub fn rapid() -> Self {
+        Self::fischer(Duration::from_secs(15 * 60), Duration::from_secs(10))
+    }
+
+    /// 5 minutes plus a 3 second increment
+    pub fn blitz() -> Self {
+        Self::fischer(Duration::from_secs(5 * 60), Duration::from_secs(3))
+    }
+
+    pub fn fischer(base: Duration, increment: Duration) -> Self {
+        let bonus = if increment.is_zero() {
+            TimeBonus::None
+        } else {
+            TimeBonus::Increment(increment)
+        };
+        Self { base, bonus }
+    }
+
+    pub fn bronstein(base: Duration, delay: Duration) -> Self {
+        let bonus = if delay.is_zero() {
+            TimeBonus::None
+        } else {
+            TimeBonus::Delay(delay)
+        };
+        Self { base, bonus }
+    }
+
+    /// The Fischer increment, or zero for other bonuses.
+    pub fn increment(&self) -> Duration {
+        match self.bonus {
+            TimeBonus::Increment(increment) => increment,
+            _ => Duration::ZERO,
+        }
+    }
+}
+
+/// Formats as "15+10" (minutes plus increment seconds), "5+3 delay" or "5 min".
+impl fmt::Display for TimeControl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let secs = self.base.as_secs();
+        let base = if secs.is_multiple_of(60) {
+            format!("{}", secs / 60)
+        } else {
+            format!("{:.1}", secs as f64 / 60.0)
+        };
+        match self.bonus {
+            TimeBonus::None => write!(f, "{} min", base),
+            TimeBonus::Increment(increment) => write!(f, "{}+{}", base, increment.as_secs()),
+            TimeBonus::Delay(delay) => write!(f, "{}+{} delay", base, delay.as_secs()),
+        }
+    }
+}
+
+/// A two-sided chess clock. Only one side's clock runs at a time.
+#[derive(Debug, Clone)]
+pub struct Clock {
+    control: TimeControl,
+    /// White's remaining time, then Black's, as of the last time their clock stopped
+    remaining: [Duration; 2],
+    /// The side whose clock is running, and since when
+    running: Option<(Color, Instant)>,
+}
+
+impl Clock {
+    pub fn new(control: TimeControl) -> Self {
+        Self {
+            control,
+            remaining: [control.base; 2],
+            running: None,
+        }
+    }
+
+    /// A clock part way through a game, with `remaining` time for White and Black.
+    pub fn with_remaining(control: TimeControl, remaining: [Duration; 2]) -> Self {
+        Self {
+            control,
+            remaining,
+            running: None,
+        }
+    }
+
+    pub fn control(&self) -> &TimeControl {
+        &self.control
+    }
+
+    /// Starts `color`'s clock, stopping the other side's without a bonus.
+    /// Does nothing if `color`'s clock is already running.
+    pub fn start(&mut self, color: Color) {
+        match self.running {
+            Some((running, _)) if running == color => return,
+            Some(_) => self.stop(),
+            None => {}
+        }
+        self.running = Some((color, Instant::now()));
+    }
+
+    /// Stops whichever clock is running, charging the time used but giving no bonus.
+    pub fn stop(&mut self) {
+        if let Some((color, started)) = self.running.take() {
+            let remaining = &mut self.remaining[color as usize];
+            *remaining = remaining.saturating_sub(started.elapsed());
+        }
+    }
+
+    /// Ends `color`'s move: stops its clock, charges the time spent and adds the bonus.
+    /// Returns the time spent on the move.
+    pub fn press(&mut self, color: Color) -> Duration {
+        let spent = match self.running.take() {
+            Some((running, started)) if running == color => started.elapsed(),
+            other => {
+                self.running = other;
+                Duration::ZERO
+            }
+        };
+        self.spend(color, spent);
+        spent
+    }
+
+    /// Charges `color` for a move that took `spent` and adds the bonus, unless
+    /// the move took all of its remaining time.
+    pub fn spend(&mut self, color: Color, spent: Duration) {
+        let remaining = &mut self.remaining[color as usize];
+        if spent >= *remaining {
+            *remaining = Duration::ZERO;
+            return;
+        }
+        *remaining -= spent;
+        *remaining += match self.control.bonus {
+            TimeBonus::None => Duration::ZERO,
+            TimeBonus::Increment(increment) => increment,
+            TimeBonus::Delay(delay) => spent.min(delay),
+        };
+    }
+
+    /// Time left on `color`'s clock, counting down while it runs.
+    pub fn remaining(&self, color: Color) -> Duration {
+        let remaining = self.remaining[color as usize];
+        match self.running {
+            Some((running, started)) if running == color => {
+                remaining.saturating_sub(started.elapsed())
+            }
+            _ => remaining,
+        }
+    }
+
+    /// Whether `color` has run out of time.
+    pub fn is_flagged(&self, color: Color) -> bool {
+        self.remaining(color).is_zero()
+    }
+
+    /// How long `color` should think about its next move: an even share of the
+    /// remaining time plus most of the bonus, never more than half of what is left.
+    pub fn time_for_move(&self, color: Color) -> Duration {
+        let remaining = self.remaining(color);
+        let bonus = match self.control.bonus {
+            TimeBonus::None => Duration::ZERO,
+            TimeBonus::Increment(bonus) | TimeBonus::Delay(bonus) => bonus,
+        };
+        let planned = remaining / MOVES_TO_GO + bonus * 3 / 4;
+        planned
+            .min(remaining / MAX_TIME_FRACTION)
+            .max(MIN_MOVE_TIME)
+    }
+}
+
+/// Formats a clock reading as "1:05:00", "4:59" or, under 20 seconds, "12.3".
+pub fn format_clock_time(time: Duration) -> String {
+    let secs = time.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else if secs >= 20 {
+        format!("{}:{:02}", secs / 60, secs % 60)
+    } else {
+        format!("{:.1}", time.as_secs_f64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(secs: u64) -> Duration {
+        Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn test_increment_and_delay() {
+        let mut fischer = Clock::new(TimeControl::fischer(secs(60), secs(5)));
+        fischer.spend(Color::White, secs(2));
+        assert_eq!(fischer.remaining(Color::White), secs(63));
+        assert_eq!(fischer.remaining(Color::Black), secs(60));
+
+        let mut bronstein = Clock::new(TimeControl::bronstein(secs(60), secs(5)));
+        bronstein.spend(Color::White, secs(2));
+        assert_eq!(bronstein.remaining(Color::White), secs(60));
+        bronstein.spend(Color::White, secs(10));
+        assert_eq!(bronstein.remaining(Color::White), secs(55));
+    }
+
+    #[test]
+    fn test_running_out_of_time_flags() {
+        let mut clock = Clock::new(TimeControl::fischer(secs(10), secs(30)));
+        clock.spend(Color::Black, secs(10));
+        assert!(clock.is_flagged(Color::Black));
+        assert!(!clock.is_flagged(Color::White));
+
+        // The increment doesn't rescue a flagged side
+        assert_eq!(clock.remaining(Color::Black), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_time_for_move() {
+        let clock = Clock::new(TimeControl::fischer(secs(300), secs(4)));
+        assert_eq!(clock.time_for_move(Color::White), secs(10) + secs(3));
+
+        // Low on time: never plan to use more than half of it
+        let mut short = Clock::new(TimeControl::fischer(secs(300), secs(4)));
+        short.spend(Color::White, secs(298));
+        assert_eq!(short.time_for_move(Color::White), secs(3));
+    }
+
+    #[test]
+    fn test_formatting() {
+        assert_eq!(TimeControl::rapid().to_string(), "15+10");
+        assert_eq!(
+            TimeControl::bronstein(secs(300), secs(3)).to_string(),
+            "5+3 delay"
+        );
+        assert_eq!(
+            TimeControl::fischer(secs(600), secs(0)).to_string(),
+            "10 min"
+        );
+        assert_eq!(format_clock_time(secs(3_900)), "1:05:00");
+        assert_eq!(format_clock_time(secs(299)), "4:59");
+        assert_eq!(format_clock_time(Duration::from_millis(12_340)), "12.3");
+    }
+}
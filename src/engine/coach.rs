@@ -0,0 +1,228 @@
+//! Coaching in the terminal game: hints from a short engine search, and grading a
+//! move by how much of the evaluation it gave up compared with the engine's best move.
+
+use crate::board::{Board, ChessMove};
+use crate::pgn::to_san;
+use crate::search::{ChessEngine, SearchParams};
+use crate::tablebase::TB_WIN_SCORE;
+use std::fmt;
+
+/// Hash table size of the coach's engine, kept small beside the players' engines
+const COACH_HASH_SIZE_MB: usize = 64;
+
+/// Longest line shown after a suggested move or a refutation
+const MAX_LINE_LENGTH: usize = 6;
+
+/// Scores are capped at this many centipawns when measuring a loss, so missing a
+/// mate costs as much as dropping a lot of material rather than 100,000 centipawns
+const MAX_SCORE: i32 = 1_000;
+
+/// Scores beyond this are forced wins: mates or tablebase wins
+const DECISIVE_SCORE: i32 = TB_WIN_SCORE - 1_000;
+
+/// Largest centipawn loss graded as each quality, best to worst
+const GOOD_MAX_LOSS: i32 = 50;
+const INACCURACY_MAX_LOSS: i32 = 100;
+const MISTAKE_MAX_LOSS: i32 = 300;
+
+/// How a move compares with the engine's best move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MoveQuality {
+    Best,
+    Good,
+    Inaccuracy,
+    Mistake,
+    Blunder,
+}
+
+impl MoveQuality {
+    /// Grades a move by the centipawns it loses against the best move.
+    pub fn from_centipawn_loss(loss: i32) -> Self {
+        if loss <= 0 {
+            Self::Best
+        } else if loss <= GOOD_MAX_LOSS {
+            Self::Good
+        } else if loss <= INACCURACY_MAX_LOSS {
+            Self::Inaccuracy
+        } else if loss <= MISTAKE_MAX_LOSS {
+            Self::Mistake
+        } else {
+            Self::Blunder
+        }
+    }
+}
+
+impl fmt::Display for MoveQuality {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::Best => "the best move",
+            Self::Good => "a good move",
+            Self::Inaccuracy => "an inaccuracy",
+            Self::Mistake => "a mistake",
+            Self::Blunder => "a blunder",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Centipawns lost by playing a move scored `played` instead of one scored `best`,
+/// both from the mover's perspective.
+pub fn centipawn_loss(best: i32, played: i32) -> i32 {
+    let cap = |score: i32| score.clamp(-MAX_SCORE, MAX_SCORE);
+    (cap(best) - cap(played)).max(0)
+}
+
+/// Formats a score from the mover's perspective in pawns, e.g. "+0.35", or as a
+/// forced win or loss.
+pub fn format_score(score: i32) -> String {
+    if score >= DECISIVE_SCORE {
+        "winning by force".to_string()
+    } else if score <= -DECISIVE_SCORE {
+        "losing by force".to_string()
+    } else {
+        format!("{:+.2}", score as f64 / 100.0)
+    }
+}
+
+/// Writes `moves` played from `board` in SAN, separated by spaces.
+pub fn san_line(board: &Board, moves: &[ChessMove]) -> String {
+    let mut board = *board;
+    let mut line = Vec::with_capacity(moves.len());
+    for &chess_move in moves {
+        line.push(to_san(&board, chess_move));
+        board.make_move(chess_move);
+    }
+    line.join(" ")
+}
+
+/// A move the engine recommends, with its score and the line it expects to follow.
+#[derive(Debug, Clone)]
+pub struct Suggestion {
+    pub chess_move: ChessMove,
+    /// From the mover's perspective
+    pub score: i32,
+    /// The suggested move and the expected continuation
+    pub line: Vec<ChessMove>,
+}
+
+/// A move graded against the engine's best move in the same position.
+#[derive(Debug, Clone)]
+pub struct MoveReview {
+    pub played: ChessMove,
+    /// From the mover's perspective
+    pub played_score: i32,
+    pub best: Suggestion,
+    pub loss: i32,
+    pub quality: MoveQuality,
+    /// The opponent's best reply to the played move and what follows
+    pub refutation: Vec<ChessMove>,
+}
+
+/// An engine kept apart from the players to suggest and grade moves.
+pub struct Coach {
+    engine: ChessEngine,
+    params: SearchParams,
+}
+
+impl Coach {
+    /// A coach searching each position within `params`.
+    pub fn new(params: SearchParams) -> Self {
+        let mut engine = ChessEngine::with_hash_size_mb(COACH_HASH_SIZE_MB);
+        engine.set_verbose(false);
+        Self { engine, params }
+    }
+
+    /// The engine behind the coach, to give it the players' eval settings.
+    pub fn engine_mut(&mut self) -> &mut ChessEngine {
+        &mut self.engine
+    }
+
+    /// The best move in `board`, or None when there are no legal moves.
+    pub fn hint(&mut self, board: &Board) -> Option<Suggestion> {
+        let root_moves = self.engine.score_root_moves(board, &self.params);
+        let &(chess_move, score) = root_moves.first()?;
+        Some(self.suggestion(board, chess_move, score))
+    }
+
+    /// Grades `played` against the best move in `board`. None if the search was cut
+    /// short before it scored the played move.
+    pub fn review(&mut self, board: &Board, played: ChessMove) -> Option<MoveReview> {
+        let root_moves = self.engine.score_root_moves(board, &self.params);
+        let &(best_move, best_score) = root_moves.first()?;
+        let &(_, played_score) = root_moves.iter().find(|&&(m, _)| m == played)?;
+
+        let loss = centipawn_loss(best_score, played_score);
+        let mut after = *board;
+        after.make_move(played);
+        Some(MoveReview {
+            played,
+            played_score,
+            best: self.suggestion(board, best_move, best_score),
+            loss,
+            quality: MoveQuality::from_centipawn_loss(loss),
+            refutation: self.engine.principal_variation(&after, MAX_LINE_LENGTH),
+        })
+    }
+
+    fn suggestion(&mut self, board: &Board, chess_move: ChessMove, score: i32) -> Suggestion {
+        let mut after = *board;
+        after.make_move(chess_move);
+        let mut line = vec![chess_move];
+        line.extend(self.engine.principal_variation(&after, MAX_LINE_LENGTH - 1));
+        Suggestion {
+            chess_move,
+            score,
+            line,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quality_thresholds() {
+        assert_eq!(MoveQuality::from_centipawn_loss(0), MoveQuality::Best);
+        assert_eq!(MoveQuality::from_centipawn_loss(50), MoveQuality::Good);
+        assert_eq!(
+            MoveQuality::from_centipawn_loss(51),
+            MoveQuality::Inaccuracy
+        );
+        assert_eq!(MoveQuality::from_centipawn_loss(300), MoveQuality::Mistake);
+        assert_eq!(MoveQuality::from_centipawn_loss(301), MoveQuality::Blunder);
+    }
+
+    #[test]
+    fn test_centipawn_loss_caps_decisive_scores() {
+        assert_eq!(centipawn_loss(120, 20), 100);
+        assert_eq!(centipawn_loss(20, 120), 0);
+        // Two different mates lose nothing; missing a mate loses a lot, but not 100,000
+        assert_eq!(centipawn_loss(100_003, 100_001), 0);
+        assert_eq!(centipawn_loss(100_003, 300), 700);
+        assert_eq!(format_score(-35), "-0.35");
+        assert_eq!(format_score(100_003), "winning by force");
+    }
+
+    #[test]
+    fn test_reviews_a_hanging_queen() {
+        // White can take Black's undefended queen
+        let board = Board::from_fen("4k3/8/8/3q4/8/8/8/3QK3 w - - 0 1");
+        let take_queen = ChessMove::new(3, 35);
+        let mut coach = Coach::new(SearchParams::fixed_nodes(3, 200_000));
+
+        let hint = coach.hint(&board).unwrap();
+        assert_eq!(hint.chess_move, take_queen);
+        assert_eq!(hint.line[0], take_queen);
+        assert_eq!(san_line(&board, &hint.line[..1]), "Qxd5");
+
+        let review = coach.review(&board, take_queen).unwrap();
+        assert_eq!(review.quality, MoveQuality::Best);
+
+        // Kf2 leaves White's queen undefended, so Black takes it instead
+        let review = coach.review(&board, ChessMove::new(4, 13)).unwrap();
+        assert_eq!(review.quality, MoveQuality::Blunder);
+        assert_eq!(review.best.chess_move, take_queen);
+        assert_eq!(review.refutation.first(), Some(&ChessMove::new(35, 3)));
+    }
+}
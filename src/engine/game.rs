@@ -1,30 +1,68 @@
-use crate::board::{Board, ChessMove, Color, MoveUndo, print_board};
-use crate::eval::Evaluator;
-use crate::metrics::{AiMoveMetrics, GameRecorder, GameResult};
+use crate::board::{Board, ChessMove, Color, MoveUndo, render_board};
+use crate::engine::clock::{Clock, TimeControl, format_clock_time};
+use crate::engine::coach::{Coach, MoveQuality, MoveReview, format_score, san_line};
+use crate::engine::game_tree::GameTree;
+use crate::engine::move_input::parse_move_input;
+use crate::engine::outcome::{GameOutcome, game_outcome, has_mating_material};
+use crate::engine::player::{EnginePlayer, HumanPlayer, Player, PlayerAction, UciPlayer};
+use crate::engine::saved_game::SavedGame;
+use crate::eval::{EvalConfig, EvalParams, Evaluator};
+use crate::metrics::{AiMoveMetrics, ClockRecord, GameRecorder, GameResult};
 use crate::movegen::MoveGenerator;
-use crate::opening::{create_colle_system_opening_book, create_london_system_opening_book};
-use crate::search::{ChessEngine, SearchParams};
-use crate::terminal::{BlackOpeningBook, DisplaySettings, WhiteOpeningBook};
-use std::io::{self, Write};
-
-pub enum PlayerAction {
-    Continue,
-    Quit,
-    Resign,
-}
+use crate::opening::{
+    BookResult, BookSelection, OpeningBook, create_colle_system_opening_book,
+    create_kings_indian_defense_opening_book, create_london_system_opening_book,
+    create_sicilian_defense_opening_book,
+};
+use crate::search::{ChessEngine, SearchParams, Skill};
+use crate::terminal::{
+    BlackOpeningBook, ChessEngineSettings, DisplaySettings, PlayerSettings, WhiteOpeningBook,
+};
+use std::io;
+use std::thread;
+use std::time::Duration;
+
+/// Transposition table size for each built-in engine when two of them play each other,
+/// so the pair uses no more memory than a single engine with the default table
+const SHARED_HASH_SIZE_MB: usize = 2048;
+
+/// Hash table entries the engines keep while `load` sets up the loaded game
+const SHRUNK_HASH_ENTRIES: usize = 1024;
+
+/// Hints and coach mode search each position this deep, for at most about this long
+const COACH_SEARCH_DEPTH: u8 = 8;
+const COACH_SEARCH_TIME_MS: u64 = 1000;
 
 pub struct AiGame {
     board: Board,
+    start_board: Board,
     move_history: Vec<MoveUndo>,
-    player_color: Color,
-    search_params: SearchParams,
-    engine: ChessEngine,
+    /// Every move tried, including those stepped back over, with the cursor on `board`
+    tree: GameTree,
+    /// Set while the human steps through earlier moves; engines wait until a move is played
+    browsing: bool,
+    /// White's player, then Black's
+    players: [Box<dyn Player>; 2],
     evaluator: Evaluator,
     game_recorder: GameRecorder,
     move_counter: u16,
     display: DisplaySettings,
+    /// Pause after each move when no human is playing
+    move_delay: Duration,
+    /// Both sides' clocks, or None for an untimed game
+    clock: Option<Clock>,
     /// Move ledger: Vec of (white_move, optional black_move) for display
     move_ledger: Vec<(String, Option<String>)>,
+    /// Book moves the engines played as (ply, color, position hash, move), for book learning
+    book_moves: Vec<(usize, Color, u64, ChessMove)>,
+    /// The engine whose learned opening book is saved after the game, and where
+    book_learning: Option<(Color, String)>,
+    /// The terminal settings the game was created from, needed to save it
+    settings: Option<ChessEngineSettings>,
+    /// The eval config applied to the engines, kept so a loaded game gets it too
+    eval_config: Option<EvalConfig>,
+    /// The engine behind hints and coach mode, created when first needed
+    coach: Option<Coach>,
 }
 
 impl AiGame {
@@ -32,129 +70,449 @@ impl AiGame {
         self.board.side_to_move
     }
 
+    pub fn display_settings(&self) -> &DisplaySettings {
+        &self.display
+    }
+
     pub fn new(
-        player_color: Color,
-        ai_depth: u8,
+        white: Box<dyn Player>,
+        black: Box<dyn Player>,
         starting_board: Board,
         display: DisplaySettings,
-        white_opening_book: WhiteOpeningBook,
-        black_opening_book: BlackOpeningBook,
     ) -> Self {
-        // Create search parameters with time based on depth
-        // Higher depths get more time: depth * 1000ms
-        let min_search_time_ms: u64 = (ai_depth as u64) * 2000;
-        let search_params: SearchParams = SearchParams {
-            max_depth: ai_depth,
-            min_search_time_ms,
+        let human_color = match (white.is_human(), black.is_human()) {
+            (true, false) => Some(Color::White),
+            (false, true) => Some(Color::Black),
+            _ => None,
         };
-
-        // Create engine with the appropriate opening book based on AI's color
-        let engine = Self::create_engine_with_opening_book(
-            player_color,
-            white_opening_book,
-            black_opening_book,
-        );
+        let mut game_recorder = GameRecorder::new(white.name(), black.name(), human_color);
+        game_recorder.set_start_fen(starting_board.to_fen());
 
         Self {
             board: starting_board,
+            start_board: starting_board,
             move_history: Vec::new(),
-            player_color,
-            search_params,
-            engine,
+            tree: GameTree::new(),
+            browsing: false,
+            players: [white, black],
             evaluator: Evaluator::new(),
-            game_recorder: GameRecorder::new(player_color, ai_depth),
+            game_recorder,
             move_counter: 0,
             display,
+            move_delay: Duration::ZERO,
+            clock: None,
             move_ledger: Vec::new(),
+            book_moves: Vec::new(),
+            book_learning: None,
+            settings: None,
+            eval_config: None,
+            coach: None,
         }
     }
 
-    fn create_engine_with_opening_book(
-        player_color: Color,
-        white_opening_book: WhiteOpeningBook,
-        black_opening_book: BlackOpeningBook,
-    ) -> ChessEngine {
-        let mut engine = ChessEngine::new();
+    /// Creates the game described by the terminal settings, starting any external engines.
+    pub fn from_settings(
+        settings: &ChessEngineSettings,
+        display: DisplaySettings,
+    ) -> io::Result<Self> {
+        let two_engines = matches!(settings.white, PlayerSettings::Engine { .. })
+            && matches!(settings.black, PlayerSettings::Engine { .. });
+        let human_name = match settings.human_color() {
+            Some(_) => "You",
+            None => "Human",
+        };
 
-        // AI plays the opposite color of the player
-        match player_color {
-            Color::White => {
-                // AI plays Black
-                match black_opening_book {
-                    BlackOpeningBook::None => {
-                        // No opening book for Black
-                    } // Future opening books can be added here
+        let mut players = Vec::with_capacity(2);
+        for color in [Color::White, Color::Black] {
+            let player: Box<dyn Player> = match settings.player(color) {
+                PlayerSettings::Human => Box::new(HumanPlayer::new(human_name)),
+                PlayerSettings::Engine { search_depth } => {
+                    let engine = if two_engines {
+                        ChessEngine::with_hash_size_mb(SHARED_HASH_SIZE_MB)
+                    } else {
+                        ChessEngine::new()
+                    };
+                    Box::new(Self::create_engine_player(
+                        engine,
+                        *search_depth,
+                        Self::create_opening_book(color, settings),
+                    ))
                 }
+                PlayerSettings::Uci {
+                    command,
+                    move_time_ms,
+                } => Box::new(UciPlayer::spawn(command, *move_time_ms).map_err(|e| {
+                    io::Error::new(
+                        e.kind(),
+                        format!("Failed to start UCI engine '{}': {}", command, e),
+                    )
+                })?),
+            };
+            players.push(player);
+        }
+        let black = players.pop().unwrap();
+        let white = players.pop().unwrap();
+
+        let mut game = Self::new(white, black, settings.starting_position, display);
+        game.move_delay = Duration::from_millis(settings.move_delay_ms);
+        if let Some(time_control) = settings.time_control {
+            game.set_time_control(time_control);
+        }
+        game.settings = Some(settings.clone());
+        Ok(game)
+    }
+
+    /// Continues a saved game: sets it up from its settings, then replays its moves
+    /// and restores the clocks.
+    pub fn from_saved(saved: &SavedGame) -> Result<Self, String> {
+        let settings = saved.settings()?;
+        let moves = saved.replay()?;
+        let mut game =
+            Self::from_settings(&settings, saved.display.clone()).map_err(|e| e.to_string())?;
+        for chess_move in moves {
+            game.play_move(chess_move);
+        }
+        if let (Some(time_control), Some([white_ms, black_ms])) =
+            (saved.time_control, saved.clock_remaining_ms)
+        {
+            game.clock = Some(Clock::with_remaining(
+                time_control,
+                [
+                    Duration::from_millis(white_ms),
+                    Duration::from_millis(black_ms),
+                ],
+            ));
+        }
+        Ok(game)
+    }
+
+    /// Loads a game saved with `save`.
+    pub fn load(path: &str) -> Result<Self, String> {
+        Self::from_saved(&SavedGame::load(path)?)
+    }
+
+    /// Saves the settings, moves and clocks so the game can be continued with `load`.
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        self.saved_game()?.save(path)
+    }
+
+    fn saved_game(&self) -> Result<SavedGame, String> {
+        let settings = self
+            .settings
+            .as_ref()
+            .ok_or("Only games set up from the terminal settings can be saved")?;
+        Ok(SavedGame {
+            mode: settings.mode,
+            white: settings.white.clone(),
+            black: settings.black.clone(),
+            white_opening_book: settings.white_opening_book,
+            black_opening_book: settings.black_opening_book,
+            move_delay_ms: self.move_delay.as_millis() as u64,
+            time_control: self.clock.as_ref().map(|clock| *clock.control()),
+            clock_remaining_ms: self.clock.as_ref().map(|clock| {
+                [Color::White, Color::Black].map(|color| clock.remaining(color).as_millis() as u64)
+            }),
+            display: self.display.clone(),
+            start_fen: self.start_board.to_fen(),
+            // Castling as king takes rook can't be mistaken for a king move
+            moves: self
+                .move_history
+                .iter()
+                .map(|undo| undo.chess_move.to_uci_chess960())
+                .collect(),
+        })
+    }
+
+    /// Replaces this game with one loaded from `path`, keeping the eval settings and
+    /// book learning given on the command line.
+    fn load_game(&mut self, path: &str) -> Result<(), String> {
+        let saved = SavedGame::load(path)?;
+        // Free the engines' hash tables before the loaded game allocates its own. The
+        // players themselves, with their books and book learning, stay until it's set up.
+        let hash_entries = self.shrink_hash_tables();
+        let loaded = Self::from_saved(&saved).and_then(|mut loaded| {
+            self.copy_eval_settings(&mut loaded)?;
+            Ok(loaded)
+        });
+        let mut loaded = match loaded {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                self.restore_hash_tables(&hash_entries);
+                return Err(e);
             }
-            Color::Black => {
-                // AI plays White
-                match white_opening_book {
-                    WhiteOpeningBook::None => {
-                        // No opening book for White
-                    }
-                    WhiteOpeningBook::LondonSystem => {
-                        engine.set_opening_book(Some(create_london_system_opening_book()));
-                    }
-                    WhiteOpeningBook::ColleSystem => {
-                        engine.set_opening_book(Some(create_colle_system_opening_book()));
-                    }
-                }
+        };
+        if let Some((_, learning_path)) = &self.book_learning
+            && let Err(e) = loaded.set_book_learning_path(learning_path)
+        {
+            println!("Book learning is off for the loaded game: {}", e);
+        }
+        *self = loaded;
+        Ok(())
+    }
+
+    /// Swaps each built-in engine's hash table for a tiny one, returning the old sizes.
+    fn shrink_hash_tables(&mut self) -> Vec<Option<usize>> {
+        self.players
+            .iter_mut()
+            .map(|player| {
+                let engine = player.engine_mut()?;
+                let num_entries = engine.get_tt_num_entries();
+                engine.set_tt_num_entries(SHRUNK_HASH_ENTRIES);
+                Some(num_entries)
+            })
+            .collect()
+    }
+
+    /// Gives the engines empty hash tables of the sizes `shrink_hash_tables` returned.
+    fn restore_hash_tables(&mut self, hash_entries: &[Option<usize>]) {
+        for (player, num_entries) in self.players.iter_mut().zip(hash_entries) {
+            if let (Some(engine), Some(num_entries)) = (player.engine_mut(), num_entries) {
+                engine.set_tt_num_entries(*num_entries);
+            }
+        }
+    }
+
+    fn create_engine_player(
+        mut engine: ChessEngine,
+        depth: u8,
+        opening_book: Option<OpeningBook>,
+    ) -> EnginePlayer {
+        // Create search parameters with time based on depth
+        // Higher depths get more time: depth * 2000ms
+        let min_search_time_ms: u64 = (depth as u64) * 2000;
+        let search_params: SearchParams = SearchParams {
+            max_depth: depth,
+            min_search_time_ms,
+            max_nodes: None,
+        };
+
+        if opening_book.is_some() {
+            engine.set_opening_book(opening_book);
+        }
+        // Vary the book line from game to game
+        engine.set_book_selection(BookSelection::weighted_random());
+        engine.set_book_seed(rand::random());
+
+        let skill = Self::skill_for_difficulty(depth);
+        engine.set_skill(skill);
+        engine.set_skill_seed(rand::random());
+        let name = if skill.is_limited() {
+            format!("Rusty Chess (depth {}, ~{} Elo)", depth, skill.elo())
+        } else {
+            format!("Rusty Chess (depth {})", depth)
+        };
+
+        EnginePlayer::new(&name, engine, search_params)
+    }
+
+    /// Beginner difficulties also weaken play, since even a depth 1 search
+    /// sees every capture through quiescence.
+    fn skill_for_difficulty(depth: u8) -> Skill {
+        match depth {
+            1 => Skill::from_level(0),
+            2 => Skill::from_level(3),
+            3 => Skill::from_level(6),
+            _ => Skill::full_strength(),
+        }
+    }
+
+    /// The book chosen for the built-in engine playing `color`.
+    fn create_opening_book(color: Color, settings: &ChessEngineSettings) -> Option<OpeningBook> {
+        match color {
+            Color::White => match settings.white_opening_book {
+                WhiteOpeningBook::None => None,
+                WhiteOpeningBook::LondonSystem => Some(create_london_system_opening_book()),
+                WhiteOpeningBook::ColleSystem => Some(create_colle_system_opening_book()),
+            },
+            Color::Black => match settings.black_opening_book {
+                BlackOpeningBook::None => None,
+                BlackOpeningBook::Sicilian => Some(create_sicilian_defense_opening_book()),
+                BlackOpeningBook::KingsIndian => Some(create_kings_indian_defense_opening_book()),
+            },
+        }
+    }
+
+    /// Enables book learning for the game's only built-in engine: its book is loaded from
+    /// `path` if the file exists, and saved there with the game's result once the game ends.
+    pub fn set_book_learning_path(&mut self, path: &str) -> io::Result<()> {
+        let engine_colors: Vec<Color> = [Color::White, Color::Black]
+            .into_iter()
+            .filter(|&color| self.player(color).engine().is_some())
+            .collect();
+        let [color] = engine_colors[..] else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "book learning needs exactly one built-in engine in the game",
+            ));
+        };
+
+        if std::path::Path::new(path).exists() {
+            let book = OpeningBook::load(path)?;
+            if let Some(engine) = self.player_mut(color).engine_mut() {
+                engine.set_opening_book(Some(book));
+            }
+        }
+        self.book_learning = Some((color, path.to_string()));
+        Ok(())
+    }
+
+    /// Pauses for `delay` after each move when no human is playing.
+    pub fn set_move_delay(&mut self, delay: Duration) {
+        self.move_delay = delay;
+    }
+
+    /// Plays the game with clocks. A side whose clock runs out loses on time, and
+    /// engines budget their thinking time from what is left on their clock.
+    pub fn set_time_control(&mut self, time_control: TimeControl) {
+        self.clock = Some(Clock::new(time_control));
+        self.game_recorder
+            .set_time_control(time_control.to_string());
+    }
+
+    /// Gives `other` the eval config and constants this game's engines use.
+    fn copy_eval_settings(&self, other: &mut AiGame) -> Result<(), String> {
+        if let Some(config) = &self.eval_config {
+            other.set_eval_config(config)?;
+        }
+        other.set_eval_params(self.evaluator.params());
+        Ok(())
+    }
+
+    /// Applies an eval config to the built-in engines' searches and the displayed evaluations.
+    /// Nothing changes if an enabled evaluator can't be built.
+    pub fn set_eval_config(&mut self, config: &EvalConfig) -> Result<(), String> {
+        let params = self.evaluator.params().clone();
+        let evaluator = Evaluator::from_config(config)?.with_params(params.clone());
+        for player in &mut self.players {
+            if let Some(engine) = player.engine_mut() {
+                engine.set_eval_config(config)?;
+                engine.set_eval_params(params.clone());
+            }
+        }
+        self.evaluator = evaluator;
+        self.eval_config = Some(config.clone());
+        // Rebuilt with the new settings when next needed
+        self.coach = None;
+        Ok(())
+    }
+
+    /// Applies tuned evaluation constants to the built-in engines' searches and the displayed evaluations.
+    pub fn set_eval_params(&mut self, params: &EvalParams) {
+        for player in &mut self.players {
+            if let Some(engine) = player.engine_mut() {
+                engine.set_eval_params(params.clone());
             }
         }
+        self.evaluator.set_params(params.clone());
+        self.coach = None;
+    }
+
+    fn player(&self, color: Color) -> &dyn Player {
+        self.players[color as usize].as_ref()
+    }
+
+    fn player_mut(&mut self, color: Color) -> &mut dyn Player {
+        self.players[color as usize].as_mut()
+    }
+
+    fn human_color(&self) -> Option<Color> {
+        match (
+            self.player(Color::White).is_human(),
+            self.player(Color::Black).is_human(),
+        ) {
+            (true, false) => Some(Color::White),
+            (false, true) => Some(Color::Black),
+            _ => None,
+        }
+    }
+
+    fn any_human(&self) -> bool {
+        self.players.iter().any(|player| player.is_human())
+    }
 
-        engine
+    /// Prints the board in the display settings' style, highlighting the last move.
+    fn print_board(&self) {
+        let style = self.display.board_style(self.human_color());
+        let last_move = self.move_history.last().map(|state| state.chess_move);
+        print!("{}", render_board(&self.board, &style, last_move));
     }
 
     pub fn run(&mut self) {
-        let mut game_result = GameResult::InProgress;
+        let mut winner = None;
         let mut player_quit = false;
 
         loop {
-            print_board(&self.board);
-
-            // Check for checkmate
-            if self.is_checkmate() {
-                let winner = self.board.side_to_move.opponent();
-                println!("\nCheckmate! {:?} wins!", winner);
-
-                game_result = if winner == self.player_color {
-                    GameResult::PlayerWin
-                } else {
-                    GameResult::AIWin
-                };
+            self.print_board();
+            self.print_clocks();
+
+            if let Some(outcome) = self.outcome() {
+                match outcome {
+                    GameOutcome::Checkmate { winner: side } => {
+                        println!("\nCheckmate! {:?} wins!", side);
+                        winner = Some(side);
+                    }
+                    GameOutcome::Stalemate => println!("\nStalemate! The game is a draw."),
+                    GameOutcome::FiftyMoveRule => {
+                        println!("\nDraw by the fifty-move rule.")
+                    }
+                    GameOutcome::ThreefoldRepetition => {
+                        println!("\nDraw by threefold repetition.")
+                    }
+                    GameOutcome::InsufficientMaterial => {
+                        println!("\nDraw by insufficient material.")
+                    }
+                }
                 break;
             }
 
-            // Check for stalemate
-            if self.is_stalemate() {
-                println!("\nStalemate! The game is a draw.");
-                game_result = GameResult::Draw;
+            if let Some(loser) = self.flagged_side() {
+                let name = self.player(loser).name();
+                if !has_mating_material(&self.board, loser.opponent()) {
+                    println!(
+                        "\n{:?} ({}) ran out of time, but {:?} has no material to mate. The game is a draw.",
+                        loser,
+                        name,
+                        loser.opponent()
+                    );
+                } else {
+                    println!(
+                        "\n{:?} ({}) ran out of time. {:?} wins on time!",
+                        loser,
+                        name,
+                        loser.opponent()
+                    );
+                    winner = Some(loser.opponent());
+                }
                 break;
             }
 
-            // Determine if it's the player's turn or AI's turn
-            if self.board.side_to_move == self.player_color {
-                // Player's turn
-                match self.handle_player_turn() {
-                    PlayerAction::Continue => {}
-                    PlayerAction::Quit => {
-                        player_quit = true;
-                        break;
-                    }
-                    PlayerAction::Resign => {
-                        println!("\nYou have resigned. AI wins!");
-                        game_result = GameResult::AIWin;
-                        break;
-                    }
+            let color = self.board.side_to_move;
+            match self.play_turn(color) {
+                PlayerAction::Quit => {
+                    player_quit = true;
+                    break;
                 }
-            } else {
-                // AI's turn
-                self.handle_ai_turn();
+                PlayerAction::Resign => {
+                    println!(
+                        "\n{:?} ({}) resigns. {:?} wins!",
+                        color,
+                        self.player(color).name(),
+                        color.opponent()
+                    );
+                    winner = Some(color.opponent());
+                    break;
+                }
+                _ => {}
             }
         }
 
+        if !player_quit {
+            self.learn_from_game(winner);
+        }
+
         // Save game recording
         if !player_quit {
+            let game_result = self.game_result(winner);
             match self.game_recorder.finalize_and_save(game_result) {
                 Ok(filename) => {
                     println!("\nGame recorded successfully: {}", filename);
@@ -166,16 +524,179 @@ impl AiGame {
         }
     }
 
-    fn handle_player_turn(&mut self) -> PlayerAction {
-        print!("{:?} to move (You): ", self.board.side_to_move);
-        io::stdout().flush().unwrap();
+    /// How the game has ended, or None while it is still in progress.
+    fn outcome(&self) -> Option<GameOutcome> {
+        let mut history: Vec<u64> = self
+            .move_history
+            .iter()
+            .map(|undo| undo.previous_zobrist_hash)
+            .collect();
+        history.push(self.board.hash);
+        game_outcome(&self.board, &history)
+    }
+
+    /// The side whose clock has run out, if any.
+    fn flagged_side(&self) -> Option<Color> {
+        let clock = self.clock.as_ref()?;
+        [Color::White, Color::Black]
+            .into_iter()
+            .find(|&color| clock.is_flagged(color))
+    }
 
-        let mut input = String::new();
-        io::stdin()
-            .read_line(&mut input)
-            .expect("Failed to read input");
+    fn game_result(&self, winner: Option<Color>) -> GameResult {
+        match (winner, self.human_color()) {
+            (None, _) => GameResult::Draw,
+            (Some(side), Some(human)) if side == human => GameResult::PlayerWin,
+            (Some(_), Some(_)) => GameResult::AIWin,
+            (Some(Color::White), None) => GameResult::WhiteWin,
+            (Some(Color::Black), None) => GameResult::BlackWin,
+        }
+    }
 
-        let input = input.trim();
+    /// Feeds the game result back into the engines' opening books and saves the
+    /// learning engine's book if learning is enabled.
+    fn learn_from_game(&mut self, winner: Option<Color>) {
+        for color in [Color::White, Color::Black] {
+            let result = match winner {
+                Some(side) if side == color => BookResult::Win,
+                Some(_) => BookResult::Loss,
+                None => BookResult::Draw,
+            };
+            let book_moves: Vec<(u64, ChessMove)> = self
+                .book_moves
+                .iter()
+                .filter(|&&(_, side, _, _)| side == color)
+                .map(|&(_, _, hash, chess_move)| (hash, chess_move))
+                .collect();
+            if let Some(engine) = self.players[color as usize].engine_mut() {
+                engine.record_book_result(&book_moves, result);
+            }
+        }
+
+        if let Some((color, path)) = &self.book_learning
+            && let Some(engine) = self.player(*color).engine()
+            && engine.opening_book().is_some()
+        {
+            match engine.save_opening_book(path) {
+                Ok(()) => println!("Opening book learning saved: {}", path),
+                Err(e) => eprintln!("Error saving opening book: {}", e),
+            }
+        }
+    }
+
+    /// Asks the side to move's player for an action and carries it out. While the
+    /// human is browsing earlier moves, they are asked instead of an engine.
+    fn play_turn(&mut self, color: Color) -> PlayerAction {
+        let actor = match self.human_color() {
+            Some(human) if self.browsing => human,
+            _ => color,
+        };
+
+        // The clock keeps running while a human enters commands
+        if let Some(clock) = self.clock.as_mut() {
+            clock.start(color);
+            self.players[color as usize].update_clock(clock, color);
+        }
+
+        if actor != color {
+            println!(
+                "{} waits while you browse: play a move for {:?}, or use back, forward or goto.",
+                self.player(color).name(),
+                color
+            );
+        } else if let Some(params) = self.player(color).search_params() {
+            println!(
+                "{:?} to move ({}): Thinking (max depth: {}, min time: {}ms)...",
+                color,
+                self.player(color).name(),
+                params.max_depth,
+                params.min_search_time_ms
+            );
+        } else if !self.player(color).is_human() {
+            println!(
+                "{:?} to move ({}): Thinking...",
+                color,
+                self.player(color).name()
+            );
+        }
+
+        let moves: Vec<ChessMove> = self
+            .move_history
+            .iter()
+            .map(|undo| undo.chess_move)
+            .collect();
+        let board = self.board;
+        let start_board = self.start_board;
+        let action = match self
+            .player_mut(actor)
+            .next_action(&board, &start_board, &moves)
+        {
+            PlayerAction::Input(input) => self.handle_input(&input),
+            action => action,
+        };
+
+        let mut time_spent = None;
+        if let Some(clock) = self.clock.as_mut()
+            && !matches!(action, PlayerAction::Quit)
+        {
+            if let PlayerAction::Move(_) = action {
+                time_spent = Some(clock.press(color));
+            }
+            if clock.is_flagged(color) {
+                // Too late: the move doesn't count and the game is lost on time
+                clock.stop();
+                return PlayerAction::Continue;
+            }
+        }
+
+        if let PlayerAction::Move(chess_move) = action {
+            if self.player(actor).is_human() {
+                let review = self.review_move(chess_move);
+                let board = self.board;
+                self.process_move(chess_move);
+                if let Some(review) = review {
+                    self.print_review(&board, &review);
+                }
+            } else {
+                self.handle_engine_move(chess_move);
+                if !self.any_human() && !self.move_delay.is_zero() {
+                    thread::sleep(self.move_delay);
+                }
+            }
+            if let (Some(time_spent), Some(clock)) = (time_spent, &self.clock) {
+                self.game_recorder.record_clock(ClockRecord {
+                    time_spent_ms: time_spent.as_millis() as u64,
+                    white_remaining_ms: clock.remaining(Color::White).as_millis() as u64,
+                    black_remaining_ms: clock.remaining(Color::Black).as_millis() as u64,
+                });
+            }
+        }
+        action
+    }
+
+    /// Reads a human's input as a command or a move.
+    fn handle_input(&mut self, input: &str) -> PlayerAction {
+        if let Some(path) = input.strip_prefix("save ") {
+            match self.save(path.trim()) {
+                Ok(()) => println!("Game saved to {}", path.trim()),
+                Err(e) => println!("Error: {}", e),
+            }
+            return PlayerAction::Continue;
+        }
+        if let Some(ply) = input.strip_prefix("goto ") {
+            match ply.trim().parse() {
+                Ok(ply) => self.go_to_ply(ply),
+                Err(_) => println!("Error: '{}' is not a ply number", ply.trim()),
+            }
+            return PlayerAction::Continue;
+        }
+        if let Some(path) = input.strip_prefix("load ") {
+            match self.load_game(path.trim()) {
+                Ok(()) => println!("Game loaded from {}", path.trim()),
+                Err(e) => println!("Error: {}", e),
+            }
+            return PlayerAction::Continue;
+        }
 
         match input {
             "quit" => {
@@ -192,15 +713,60 @@ impl AiGame {
                     println!("No moves to undo.");
                 }
             }
+            "back" => {
+                if self.can_browse() {
+                    if self.step_back() {
+                        self.print_line_position();
+                    } else {
+                        println!("Already at the start of the game.");
+                    }
+                }
+            }
+            "forward" => {
+                if self.can_browse() {
+                    if self.step_forward() {
+                        self.print_line_position();
+                    } else {
+                        println!("No moves to replay.");
+                    }
+                }
+            }
+            "goto" => {
+                println!("Usage: goto <ply>");
+            }
             "moves" => {
                 self.print_legal_moves();
             }
+            "hint" => {
+                self.print_hint();
+            }
+            "coach" => {
+                self.display.coach_mode = !self.display.coach_mode;
+                println!(
+                    "Coach mode: {}",
+                    if self.display.coach_mode { "ON" } else { "OFF" }
+                );
+            }
+            "flip" => {
+                self.display.flip_board = !self.display.flip_board;
+                self.print_board();
+            }
             "fen" => {
                 println!("FEN: {}", self.board.to_fen());
             }
             "eval" => {
                 self.print_evaluation();
             }
+            "save" | "load" => {
+                println!("Usage: {} <file>", input);
+            }
+            "clock" => {
+                if self.clock.is_some() {
+                    self.print_clocks();
+                } else {
+                    println!("This game is untimed.");
+                }
+            }
             "stats" => {
                 self.display.show_search_stats = !self.display.show_search_stats;
                 println!(
@@ -252,24 +818,17 @@ impl AiGame {
             "display" => {
                 self.print_display_status();
             }
-            _ => {
-                if let Err(e) = self.process_move(input) {
-                    println!("Error: {}", e);
-                }
-            }
+            _ => match self.parse_move(input) {
+                Ok(chess_move) => return PlayerAction::Move(chess_move),
+                Err(e) => println!("Error: {}", e),
+            },
         }
 
         PlayerAction::Continue
     }
 
-    fn handle_ai_turn(&mut self) {
-        println!(
-            "{:?} to move (AI): Thinking (max depth: {}, min time: {}ms)...",
-            self.board.side_to_move,
-            self.search_params.max_depth,
-            self.search_params.min_search_time_ms
-        );
-
+    /// Announces and plays a move chosen by an engine player, with the requested statistics.
+    fn handle_engine_move(&mut self, best_move: ChessMove) {
         let ai_color = self.board.side_to_move;
 
         // Capture evaluation before the move
@@ -279,231 +838,429 @@ impl AiGame {
             None
         };
 
-        match self
-            .engine
-            .find_best_move_iterative(&self.board, &self.search_params)
-        {
-            Some(best_move) => {
-                let from_notation = square_to_notation(best_move.from());
-                let to_notation = square_to_notation(best_move.to());
-                let move_notation = format!("{}-{}", from_notation, to_notation);
-
-                println!("AI plays: {},{}", from_notation, to_notation);
-                if self.board.piece_on(best_move.to() as u8).is_some() || best_move.is_en_passant()
-                {
-                    println!("  (capture)");
-                }
-                println!();
+        let from_notation = square_to_notation(best_move.from());
+        let to_notation = square_to_notation(best_move.destination());
+        let move_notation = recorded_notation(best_move);
 
-                // Capture AI metrics
-                if let Some(search_metrics) = self.engine.get_last_search_metrics() {
-                    self.move_counter += 1;
+        println!(
+            "{} plays: {},{}",
+            self.player(ai_color).name(),
+            from_notation,
+            to_notation
+        );
+        if self.board.is_capture(best_move) {
+            println!("  (capture)");
+        }
+        println!();
 
-                    let nps = if search_metrics.search_time.as_secs_f64() > 0.0 {
-                        (search_metrics.nodes_explored as f64
-                            / search_metrics.search_time.as_secs_f64())
-                            as u64
-                    } else {
-                        0
-                    };
+        self.move_counter += 1;
+        match self.ai_move_metrics(ai_color) {
+            Some(ai_metrics) => self.game_recorder.record_ai_move(
+                self.move_counter,
+                ai_color,
+                move_notation,
+                ai_metrics,
+            ),
+            // Book moves and external engines have no search metrics
+            None => {
+                self.game_recorder
+                    .record_player_move(self.move_counter, ai_color, move_notation)
+            }
+        }
 
-                    let beta_cutoff_percentage = if search_metrics.nodes_explored > 0 {
-                        (search_metrics.beta_cutoffs as f64 / search_metrics.nodes_explored as f64)
-                            * 100.0
-                    } else {
-                        0.0
-                    };
+        // Make the move on a copy first to get after evaluation
+        let mut board_after = self.board;
+        board_after.make_move(best_move);
 
-                    let tt_hits = self.engine.get_tt_hits();
-                    let tt_misses = self.engine.get_tt_misses();
-                    let tt_hit_rate = if tt_hits + tt_misses > 0 {
-                        (tt_hits as f64 / (tt_hits + tt_misses) as f64) * 100.0
-                    } else {
-                        0.0
-                    };
+        // Show evaluation breakdown if enabled
+        if let Some(ref before) = before_eval {
+            let after_eval = self.evaluator.evaluate_detailed(&board_after);
 
-                    let ai_metrics = AiMoveMetrics {
-                        search_time_ms: search_metrics.search_time.as_millis(),
-                        nodes_explored: search_metrics.nodes_explored,
-                        nodes_per_second: nps,
-                        beta_cutoffs: search_metrics.beta_cutoffs,
-                        beta_cutoff_percentage,
-                        max_depth_reached: search_metrics.max_depth_reached,
-                        tt_size_bytes: self.engine.get_tt_size_bytes(),
-                        tt_num_entries: self.engine.get_tt_num_entries(),
-                        tt_hits,
-                        tt_misses,
-                        tt_hit_rate_percentage: tt_hit_rate,
-                    };
+            if self.display.show_eval {
+                println!(
+                    "=== Before {},{} Evaluation ===",
+                    from_notation, to_notation
+                );
+                println!("{}", before);
+                println!();
 
-                    self.game_recorder.record_ai_move(
-                        self.move_counter,
-                        ai_color,
-                        move_notation,
-                        ai_metrics,
-                    );
+                println!("=== After {},{} Evaluation ===", from_notation, to_notation);
+                println!("{}", after_eval);
+                println!();
+            }
 
-                    // Display search statistics
-                    if self.display.show_search_stats {
-                        println!("=== Search Statistics ===");
-                        println!("  Time: {:.2}s", search_metrics.search_time.as_secs_f64());
-                        println!("  Nodes: {} ({} n/s)", search_metrics.nodes_explored, nps);
-                        println!("  Depth reached: {}", search_metrics.max_depth_reached);
-                        println!();
-                    }
+            // Show move analysis if enabled
+            if self.display.show_move_analysis {
+                let delta = after_eval.total - before.total;
+                let improvement = match ai_color {
+                    Color::White => delta,
+                    Color::Black => -delta,
+                };
+                println!("=== Move Analysis ===");
+                println!(
+                    "  Position change: {:+} cp (for {:?})",
+                    improvement, ai_color
+                );
 
-                    // Display transposition table info
-                    if self.display.show_tt_info {
-                        println!("=== Transposition Table ===");
-                        println!("  Entries: {}", self.engine.get_tt_num_entries());
-                        println!("  Size: {} KB", self.engine.get_tt_size_bytes() / 1024);
-                        println!("  Hits: {} | Misses: {}", tt_hits, tt_misses);
-                        println!("  Hit rate: {:.1}%", tt_hit_rate);
-                        println!();
-                    }
+                // Highlight significant changes
+                let mat_delta = after_eval.material - before.material;
+                if mat_delta != 0 {
+                    println!("  Material change: {:+} cp", mat_delta);
+                }
+                let threat_delta = after_eval.threat - before.threat;
+                if threat_delta.abs() >= 30 {
+                    println!("  Threat change:   {:+} cp", threat_delta);
+                }
+                let mobility_delta = after_eval.mobility - before.mobility;
+                if mobility_delta.abs() >= 20 {
+                    println!("  Mobility change: {:+} cp", mobility_delta);
+                }
+                let king_safety_delta = after_eval.king_safety - before.king_safety;
+                if king_safety_delta.abs() >= 30 {
+                    println!("  King safety:     {:+} cp", king_safety_delta);
                 }
+                println!();
+            }
+        }
 
-                // Make the move on a copy first to get after evaluation
-                let mut board_after = self.board;
-                board_after.make_move(best_move);
-
-                // Show evaluation breakdown if enabled
-                if let Some(ref before) = before_eval {
-                    let after_eval = self.evaluator.evaluate_detailed(&board_after);
-
-                    if self.display.show_eval {
-                        println!(
-                            "=== Before {},{} Evaluation ===",
-                            from_notation, to_notation
-                        );
-                        println!("{}", before);
-                        println!();
-
-                        println!("=== After {},{} Evaluation ===", from_notation, to_notation);
-                        println!("{}", after_eval);
-                        println!();
-                    }
+        if self
+            .player(ai_color)
+            .engine()
+            .is_some_and(|engine| engine.last_move_from_book())
+        {
+            self.book_moves.push((
+                self.move_history.len(),
+                ai_color,
+                self.board.hash,
+                best_move,
+            ));
+        }
 
-                    // Show move analysis if enabled
-                    if self.display.show_move_analysis {
-                        let delta = after_eval.total - before.total;
-                        let improvement = match ai_color {
-                            Color::White => delta,
-                            Color::Black => -delta,
-                        };
-                        println!("=== Move Analysis ===");
-                        println!(
-                            "  Position change: {:+} cp (for {:?})",
-                            improvement, ai_color
-                        );
-
-                        // Highlight significant changes
-                        let mat_delta = after_eval.material - before.material;
-                        if mat_delta != 0 {
-                            println!("  Material change: {:+} cp", mat_delta);
-                        }
-                        let threat_delta = after_eval.threat - before.threat;
-                        if threat_delta.abs() >= 30 {
-                            println!("  Threat change:   {:+} cp", threat_delta);
-                        }
-                        let mobility_delta = after_eval.mobility - before.mobility;
-                        if mobility_delta.abs() >= 20 {
-                            println!("  Mobility change: {:+} cp", mobility_delta);
-                        }
-                        let king_safety_delta = after_eval.king_safety - before.king_safety;
-                        if king_safety_delta.abs() >= 30 {
-                            println!("  King safety:     {:+} cp", king_safety_delta);
-                        }
-                        println!();
-                    }
-                }
+        self.push_move(best_move);
 
-                let state = self.board.make_move(best_move);
-                self.move_history.push(state);
+        // Record move and display FEN and ledger
+        let ledger_notation = format!("{},{}", from_notation, to_notation);
+        self.record_move_to_ledger(ai_color, &ledger_notation);
+        println!("FEN: {}", self.board.to_fen());
+        self.print_move_ledger();
+    }
 
-                // Record move and display FEN and ledger
-                let ledger_notation = format!("{},{}", from_notation, to_notation);
-                self.record_move_to_ledger(ai_color, &ledger_notation);
-                println!("FEN: {}", self.board.to_fen());
-                self.print_move_ledger();
-            }
-            None => {
-                println!("AI has no legal moves!");
-            }
+    /// Collects the built-in engine's statistics for its last search and prints
+    /// them if enabled. None for players without a built-in engine, and for book moves.
+    fn ai_move_metrics(&self, color: Color) -> Option<AiMoveMetrics> {
+        let engine = self.player(color).engine()?;
+        if engine.last_move_from_book() {
+            return None;
         }
-    }
+        let search_metrics = engine.get_last_search_metrics()?;
+
+        let nps = if search_metrics.search_time.as_secs_f64() > 0.0 {
+            (search_metrics.nodes_explored as f64 / search_metrics.search_time.as_secs_f64()) as u64
+        } else {
+            0
+        };
 
-    fn process_move(&mut self, input: &str) -> Result<(), String> {
-        // Parse the move
-        let chess_move = self.parse_move(input)?;
+        let beta_cutoff_percentage = if search_metrics.nodes_explored > 0 {
+            (search_metrics.beta_cutoffs as f64 / search_metrics.nodes_explored as f64) * 100.0
+        } else {
+            0.0
+        };
+
+        let tt_hits = engine.get_tt_hits();
+        let tt_misses = engine.get_tt_misses();
+        let tt_hit_rate = if tt_hits + tt_misses > 0 {
+            (tt_hits as f64 / (tt_hits + tt_misses) as f64) * 100.0
+        } else {
+            0.0
+        };
 
+        // Display search statistics
+        if self.display.show_search_stats {
+            println!("=== Search Statistics ===");
+            println!("  Time: {:.2}s", search_metrics.search_time.as_secs_f64());
+            println!("  Nodes: {} ({} n/s)", search_metrics.nodes_explored, nps);
+            println!("  Depth reached: {}", search_metrics.max_depth_reached);
+            println!();
+        }
+
+        // Display transposition table info
+        if self.display.show_tt_info {
+            println!("=== Transposition Table ===");
+            println!("  Entries: {}", engine.get_tt_num_entries());
+            println!("  Size: {} KB", engine.get_tt_size_bytes() / 1024);
+            println!("  Hits: {} | Misses: {}", tt_hits, tt_misses);
+            println!("  Hit rate: {:.1}%", tt_hit_rate);
+            println!();
+        }
+
+        Some(AiMoveMetrics {
+            search_time_ms: search_metrics.search_time.as_millis(),
+            nodes_explored: search_metrics.nodes_explored,
+            nodes_per_second: nps,
+            beta_cutoffs: search_metrics.beta_cutoffs,
+            beta_cutoff_percentage,
+            max_depth_reached: search_metrics.max_depth_reached,
+            tt_size_bytes: engine.get_tt_size_bytes(),
+            tt_num_entries: engine.get_tt_num_entries(),
+            tt_hits,
+            tt_misses,
+            tt_hit_rate_percentage: tt_hit_rate,
+        })
+    }
+
+    /// Plays a move that needs no announcement, recording it and adding it to the ledger.
+    fn play_move(&mut self, chess_move: ChessMove) {
         // Capture player move notation
         let player_color = self.board.side_to_move;
         let from_notation = square_to_notation(chess_move.from());
-        let to_notation = square_to_notation(chess_move.to());
-        let move_notation = format!("{}-{}", from_notation, to_notation);
+        let to_notation = square_to_notation(chess_move.destination());
+        let move_notation = recorded_notation(chess_move);
 
         self.move_counter += 1;
         self.game_recorder
             .record_player_move(self.move_counter, player_color, move_notation);
 
         // Apply the move
-        let state = self.board.make_move(chess_move);
-        self.move_history.push(state);
+        self.push_move(chess_move);
 
-        // Record move and display FEN and ledger
+        // Record move to the ledger
         let ledger_notation = format!("{},{}", from_notation, to_notation);
         self.record_move_to_ledger(player_color, &ledger_notation);
+    }
+
+    fn process_move(&mut self, chess_move: ChessMove) {
+        self.play_move(chess_move);
+
+        // Display FEN and ledger
         println!("FEN: {}", self.board.to_fen());
         self.print_move_ledger();
+    }
 
-        Ok(())
+    /// Makes a move on the board and adds it to the game tree, ending any browsing.
+    fn push_move(&mut self, chess_move: ChessMove) {
+        let state = self.board.make_move(chess_move);
+        self.move_history.push(state);
+        if self.tree.play(chess_move) {
+            println!("Started a new variation at move {}.", self.tree.ply());
+        }
+        self.browsing = false;
     }
 
-    fn parse_move(&self, input: &str) -> Result<ChessMove, String> {
-        let parts: Vec<&str> = input.split(',').collect();
-        if parts.len() != 2 {
-            return Err("Invalid format. Use: e2,e4".to_string());
+    /// Takes back the last move, keeping it and its recording in the game tree
+    /// so `step_forward` can replay it.
+    fn step_back(&mut self) -> bool {
+        let Some(state) = self.move_history.pop() else {
+            return false;
+        };
+        self.board.unmake_move(state);
+        let record = self.game_recorder.take_back_move();
+        self.tree.back(record);
+        self.remove_from_ledger(self.board.side_to_move);
+        self.move_counter -= 1;
+
+        // Taken back book moves shouldn't count towards learning
+        let ply = self.move_history.len();
+        self.book_moves
+            .retain(|&(move_ply, _, _, _)| move_ply < ply);
+        true
+    }
+
+    /// Replays the next move of the current line with the recording it had.
+    fn step_forward(&mut self) -> bool {
+        let Some((chess_move, record)) = self.tree.forward() else {
+            return false;
+        };
+        let color = self.board.side_to_move;
+        let from_notation = square_to_notation(chess_move.from());
+        let to_notation = square_to_notation(chess_move.destination());
+
+        let state = self.board.make_move(chess_move);
+        self.move_history.push(state);
+        self.move_counter += 1;
+        match record {
+            Some(record) => self.game_recorder.restore_move(record),
+            None => self.game_recorder.record_player_move(
+                self.move_counter,
+                color,
+                recorded_notation(chess_move),
+            ),
         }
+        self.record_move_to_ledger(color, &format!("{},{}", from_notation, to_notation));
+        true
+    }
 
-        let from = parse_square(parts[0].trim())?;
-        let to = parse_square(parts[1].trim())?;
+    /// Steps back or forward along the current line to the position after `ply` moves.
+    fn go_to_ply(&mut self, ply: usize) {
+        if !self.can_browse() {
+            return;
+        }
+        let length = self.tree.line_length();
+        if ply > length {
+            println!("Error: the current line has {} moves", length);
+            return;
+        }
+        while self.tree.ply() > ply && self.step_back() {}
+        while self.tree.ply() < ply && self.step_forward() {}
+        self.print_line_position();
+    }
 
-        // Find the matching legal move
-        let mut legal_moves = Vec::with_capacity(128);
-        MoveGenerator::generate_legal_moves(&self.board, &mut legal_moves);
-        legal_moves
-            .into_iter()
-            .find(|m| m.from() == from && m.to() == to)
-            .ok_or_else(|| "Invalid or illegal move".to_string())
+    /// Whether back, forward and goto are allowed. Clocks can't be wound back, so
+    /// timed games only allow `undo`.
+    fn can_browse(&self) -> bool {
+        if self.clock.is_some() {
+            println!("Moving through the game is off in timed games; use 'undo' instead.");
+            return false;
+        }
+        true
+    }
+
+    /// Prints where the cursor is in the current line, the move `forward` replays
+    /// and the other moves tried from here. Engines wait until the end of the line.
+    fn print_line_position(&mut self) {
+        let ply = self.tree.ply();
+        println!(
+            "Position after move {} of {}.",
+            ply,
+            self.tree.line_length()
+        );
+        let notation = |chess_move: ChessMove| {
+            format!(
+                "{},{}",
+                square_to_notation(chess_move.from()),
+                square_to_notation(chess_move.destination())
+            )
+        };
+        let next = self.tree.next_move();
+        if let Some(next) = next {
+            println!("Next: {} (forward)", notation(next));
+        }
+        self.browsing = next.is_some();
+        let tried = self.tree.moves_tried();
+        if tried.len() > 1 {
+            let tried: Vec<String> = tried.into_iter().map(notation).collect();
+            println!("Moves tried here: {}", tried.join(" "));
+        }
+        self.print_move_ledger();
+    }
+
+    fn parse_move(&self, input: &str) -> Result<ChessMove, String> {
+        parse_move_input(&self.board, input)
     }
 
+    /// Takes back moves until it is a human's turn again, so in a game against an
+    /// engine the engine's reply is taken back along with the human's move.
     fn undo_move(&mut self) -> bool {
-        // Check if we have at least 2 moves (one player move + one AI move)
-        if self.move_history.len() < 2 {
+        let mut count = 1;
+        let mut side = self.board.side_to_move.opponent();
+        while count <= self.move_history.len() && !self.player(side).is_human() {
+            count += 1;
+            side = side.opponent();
+        }
+        if count > self.move_history.len() {
             return false;
         }
 
-        // Undo the last move (AI's move)
-        if let Some(ai_state) = self.move_history.pop() {
-            self.board.unmake_move(ai_state);
+        for _ in 0..count {
+            self.step_back();
+        }
+        true
+    }
 
-            // Also undo the player's move before that
-            if let Some(player_state) = self.move_history.pop() {
-                self.board.unmake_move(player_state);
-                return true;
+    /// The coach, created with the engines' eval settings when first needed.
+    fn coach(&mut self) -> &mut Coach {
+        self.coach.get_or_insert_with(|| {
+            let mut coach = Coach::new(SearchParams::new(COACH_SEARCH_DEPTH, COACH_SEARCH_TIME_MS));
+            if let Some(config) = &self.eval_config
+                && let Err(e) = coach.engine_mut().set_eval_config(config)
+            {
+                println!("The coach uses the default evaluation: {}", e);
             }
-        }
+            coach
+                .engine_mut()
+                .set_eval_params(self.evaluator.params().clone());
+            coach
+        })
+    }
 
-        false
+    fn print_hint(&mut self) {
+        let board = self.board;
+        match self.coach().hint(&board) {
+            Some(hint) => {
+                println!(
+                    "Hint: {} ({},{}), eval {}",
+                    san_line(&board, &hint.line[..1]),
+                    square_to_notation(hint.chess_move.from()),
+                    square_to_notation(hint.chess_move.destination()),
+                    format_score(hint.score)
+                );
+                println!("  Expected line: {}", san_line(&board, &hint.line));
+            }
+            None => println!("No legal moves available."),
+        }
     }
 
-    fn is_checkmate(&self) -> bool {
-        MoveGenerator::is_checkmate(&self.board)
+    /// Grades a human's move before it is played, if coach mode is on.
+    fn review_move(&mut self, chess_move: ChessMove) -> Option<MoveReview> {
+        if !self.display.coach_mode {
+            return None;
+        }
+        let board = self.board;
+        self.coach().review(&board, chess_move)
     }
 
-    fn is_stalemate(&self) -> bool {
-        MoveGenerator::is_stalemate(&self.board)
+    /// Prints how a move played from `board` compares with the best move, and for
+    /// worse than good moves, the line that punishes it.
+    fn print_review(&self, board: &Board, review: &MoveReview) {
+        let played = san_line(board, &[review.played]);
+        let best = san_line(board, &review.best.line[..1]);
+        match review.quality {
+            MoveQuality::Best => println!(
+                "Coach: {} is {} ({}).",
+                played,
+                review.quality,
+                format_score(review.played_score)
+            ),
+            MoveQuality::Good => println!(
+                "Coach: {} is {} ({}); {} was slightly better ({}).",
+                played,
+                review.quality,
+                format_score(review.played_score),
+                best,
+                format_score(review.best.score)
+            ),
+            _ => {
+                println!(
+                    "Coach: {} is {}, losing {:.2} pawns ({} instead of {}). Best was {}.",
+                    played,
+                    review.quality,
+                    review.loss as f64 / 100.0,
+                    format_score(review.played_score),
+                    format_score(review.best.score),
+                    best
+                );
+                let mut after = *board;
+                after.make_move(review.played);
+                if !review.refutation.is_empty() {
+                    println!("  Refutation: {}", san_line(&after, &review.refutation));
+                }
+                println!("  Best line:  {}", san_line(board, &review.best.line));
+
+                // What the refutation wins, in material
+                let mut end = after;
+                for &chess_move in &review.refutation {
+                    end.make_move(chess_move);
+                }
+                let material = |board: &Board| self.evaluator.evaluate_detailed(board).material;
+                let delta = match board.side_to_move {
+                    Color::White => material(&end) - material(board),
+                    Color::Black => material(board) - material(&end),
+                };
+                if delta != 0 {
+                    println!("  Material after the refutation: {:+} cp", delta);
+                }
+            }
+        }
+        println!();
     }
 
     fn print_evaluation(&self) {
@@ -543,7 +1300,7 @@ impl AiGame {
                 format!(
                     "{},{}",
                     square_to_notation(m.from()),
-                    square_to_notation(m.to())
+                    square_to_notation(m.destination())
                 )
             })
             .collect();
@@ -567,6 +1324,31 @@ impl AiGame {
         println!("Total: {} legal moves\n", legal_moves.len());
     }
 
+    /// Prints both clocks, marking the side whose turn it is. Nothing for an untimed game.
+    fn print_clocks(&self) {
+        let Some(clock) = &self.clock else {
+            return;
+        };
+        let reading = |color: Color| {
+            let marker = if color == self.board.side_to_move {
+                "▶"
+            } else {
+                " "
+            };
+            format!(
+                "{} {:?} {}",
+                marker,
+                color,
+                format_clock_time(clock.remaining(color))
+            )
+        };
+        println!(
+            "Clock: {}   {}",
+            reading(Color::White),
+            reading(Color::Black)
+        );
+    }
+
     fn print_display_status(&self) {
         println!("\n=== Display Settings ===");
         println!(
@@ -619,6 +1401,23 @@ impl AiGame {
         }
     }
 
+    /// Removes `color`'s move from the end of the ledger.
+    fn remove_from_ledger(&mut self, color: Color) {
+        match color {
+            Color::White => {
+                self.move_ledger.pop();
+            }
+            Color::Black => {
+                if let Some(last) = self.move_ledger.last_mut() {
+                    last.1 = None;
+                    if last.0 == "..." {
+                        self.move_ledger.pop();
+                    }
+                }
+            }
+        }
+    }
+
     /// Prints the move ledger in standard notation format
     fn print_move_ledger(&self) {
         if self.move_ledger.is_empty() {
@@ -639,6 +1438,21 @@ impl AiGame {
     }
 }
 
+/// How a move is written in game recordings: "e2-e4", or "e7-e8=N" for a promotion.
+/// Castling is written king-takes-rook ("e1-h1") so a Chess960 castle can't be read
+/// back as a king step to the same square.
+fn recorded_notation(chess_move: ChessMove) -> String {
+    let notation = format!(
+        "{}-{}",
+        square_to_notation(chess_move.from()),
+        square_to_notation(chess_move.to())
+    );
+    match chess_move.to_uci().chars().nth(4) {
+        Some(piece) => format!("{}={}", notation, piece.to_ascii_uppercase()),
+        None => notation,
+    }
+}
+
 pub fn parse_square(s: &str) -> Result<usize, String> {
     if s.len() != 2 {
         return Err(format!("Invalid square: {}", s));
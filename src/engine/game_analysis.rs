@@ -0,0 +1,340 @@
+//! Reviewing a finished game: every move is compared with the engine's best move
+//! at a fixed search budget, giving each side's centipawn loss and accuracy, the
+//! critical moments and an annotated PGN.
+
+use crate::board::{Board, ChessMove, Color};
+use crate::engine::coach::{Coach, MoveQuality, MoveReview, format_score};
+use crate::engine::move_input::parse_move_input;
+use crate::fen::FENParser;
+use crate::metrics::{GameRecording, GameResult};
+use crate::pgn::{MoveAnnotation, PgnGame, to_san};
+use crate::search::MATE_SCORE;
+
+/// NAGs for "?!", "?" and "??"
+const NAG_DUBIOUS: u8 = 6;
+const NAG_MISTAKE: u8 = 2;
+const NAG_BLUNDER: u8 = 4;
+
+/// One move of the game with the engine's verdict on it.
+#[derive(Debug, Clone)]
+pub struct AnalyzedMove {
+    pub color: Color,
+    /// Fullmove number, as in "12." or "12..."
+    pub number: u16,
+    pub san: String,
+    pub review: MoveReview,
+    /// The engine's best line from the position before the move, in SAN
+    pub best_line: Vec<String>,
+    /// 0 to 100, how much of its winning chances the move kept
+    pub accuracy: f64,
+    /// The mover had a forced mate and played a move without one
+    pub missed_mate: bool,
+}
+
+impl AnalyzedMove {
+    /// The move with its number, e.g. "12. Nf3" or "12... Nf6".
+    pub fn label(&self) -> String {
+        match self.color {
+            Color::White => format!("{}. {}", self.number, self.san),
+            Color::Black => format!("{}... {}", self.number, self.san),
+        }
+    }
+}
+
+/// Every move of a game, analyzed.
+#[derive(Clone)]
+pub struct GameAnalysis {
+    pub start: Board,
+    pub moves: Vec<AnalyzedMove>,
+}
+
+impl GameAnalysis {
+    /// Analyzes `moves` played from `start_fen` with `coach`, calling `progress` with
+    /// the number of moves done and the total after each one.
+    pub fn analyze(
+        start_fen: &str,
+        moves: &[ChessMove],
+        coach: &mut Coach,
+        mut progress: impl FnMut(usize, usize),
+    ) -> Result<Self, String> {
+        let start = Board::try_from_fen(start_fen)
+            .map_err(|e| format!("Invalid start position '{}': {}", start_fen, e))?;
+        let mut number = FENParser::parse(start_fen)
+            .map(|parsed| parsed.fullmove_number)
+            .unwrap_or(1);
+
+        let mut board = start;
+        let mut analyzed = Vec::with_capacity(moves.len());
+        for (i, &chess_move) in moves.iter().enumerate() {
+            let color = board.side_to_move;
+            let san = to_san(&board, chess_move);
+            let review = coach.review(&board, chess_move).ok_or_else(|| {
+                format!(
+                    "Move {} ({}) couldn't be scored; raise the node budget",
+                    i + 1,
+                    san
+                )
+            })?;
+            analyzed.push(AnalyzedMove {
+                color,
+                number,
+                san,
+                best_line: san_moves(&board, &review.best.line),
+                accuracy: move_accuracy(review.best.score, review.played_score),
+                missed_mate: review.best.score >= MATE_SCORE && review.played_score < MATE_SCORE,
+                review,
+            });
+
+            board.make_move(chess_move);
+            if color == Color::Black {
+                number += 1;
+            }
+            progress(i + 1, moves.len());
+        }
+        Ok(Self {
+            start,
+            moves: analyzed,
+        })
+    }
+
+    /// `color`'s moves.
+    pub fn moves_by(&self, color: Color) -> impl Iterator<Item = &AnalyzedMove> {
+        self.moves.iter().filter(move |m| m.color == color)
+    }
+
+    /// The average accuracy of `color`'s moves, or None if it made none.
+    pub fn accuracy(&self, color: Color) -> Option<f64> {
+        average(self.moves_by(color).map(|m| m.accuracy))
+    }
+
+    /// The average centipawns `color` lost per move, or None if it made none.
+    pub fn average_centipawn_loss(&self, color: Color) -> Option<f64> {
+        average(self.moves_by(color).map(|m| m.review.loss as f64))
+    }
+
+    /// How many of `color`'s moves were graded `quality`.
+    pub fn count(&self, color: Color, quality: MoveQuality) -> usize {
+        self.moves_by(color)
+            .filter(|m| m.review.quality == quality)
+            .count()
+    }
+
+    /// The `max` moves that lost the most, worst first; only inaccuracies or worse.
+    pub fn critical_moments(&self, max: usize) -> Vec<&AnalyzedMove> {
+        let mut moments: Vec<&AnalyzedMove> = self
+            .moves
+            .iter()
+            .filter(|m| m.review.quality >= MoveQuality::Inaccuracy)
+            .collect();
+        // Stable, so equal losses stay in game order
+        moments.sort_by_key(|m| std::cmp::Reverse(m.review.loss));
+        moments.truncate(max);
+        moments
+    }
+
+    /// NAGs, comments and the better line for each move, for `to_annotated_pgn`.
+    pub fn annotations(&self) -> Vec<MoveAnnotation> {
+        self.moves.iter().map(annotate).collect()
+    }
+
+    /// The game as PGN with `tags`, annotated with the analysis.
+    pub fn to_annotated_pgn(&self, tags: Vec<(String, String)>) -> String {
+        let game = PgnGame {
+            tags,
+            moves: self.moves.iter().map(|m| m.san.clone()).collect(),
+        };
+        game.to_annotated_pgn(&self.annotations())
+    }
+}
+
+fn annotate(analyzed: &AnalyzedMove) -> MoveAnnotation {
+    let review = &analyzed.review;
+    let nag = match review.quality {
+        MoveQuality::Best | MoveQuality::Good => return MoveAnnotation::default(),
+        MoveQuality::Inaccuracy => NAG_DUBIOUS,
+        MoveQuality::Mistake => NAG_MISTAKE,
+        MoveQuality::Blunder => NAG_BLUNDER,
+    };
+    // Comments give scores from White's perspective, as PGN readers expect
+    let white_score = |score: i32| match analyzed.color {
+        Color::White => score,
+        Color::Black => -score,
+    };
+    let quality = match review.quality {
+        MoveQuality::Inaccuracy => "Inaccuracy",
+        MoveQuality::Mistake => "Mistake",
+        _ => "Blunder",
+    };
+    let mut comment = format!(
+        "{}. {} -> {}. Best was {}",
+        quality,
+        format_score(white_score(review.best.score)),
+        format_score(white_score(review.played_score)),
+        analyzed.best_line[0]
+    );
+    if analyzed.missed_mate {
+        comment.push_str("; it forced mate");
+    }
+    MoveAnnotation {
+        nag: Some(nag),
+        comment: Some(comment),
+        variation: analyzed.best_line.clone(),
+    }
+}
+
+/// The moves of a game recording and the position they start from. Recordings made
+/// before start positions were saved are assumed to start from the standard position.
+pub fn recording_moves(recording: &GameRecording) -> Result<(String, Vec<ChessMove>), String> {
+    let start_fen = recording
+        .metadata
+        .start_fen
+        .clone()
+        .unwrap_or_else(|| Board::startpos().to_fen());
+    let mut board = Board::try_from_fen(&start_fen)
+        .map_err(|e| format!("Invalid start position '{}': {}", start_fen, e))?;
+
+    let mut moves = Vec::with_capacity(recording.moves.len());
+    for (i, record) in recording.moves.iter().enumerate() {
+        let chess_move = parse_move_input(&board, &record.move_notation).map_err(|_| {
+            format!(
+                "Recorded move {} ({}) is illegal",
+                i + 1,
+                record.move_notation
+            )
+        })?;
+        board.make_move(chess_move);
+        moves.push(chess_move);
+    }
+    Ok((start_fen, moves))
+}
+
+/// PGN tags describing a game recording.
+pub fn recording_tags(recording: &GameRecording, start_fen: &str) -> Vec<(String, String)> {
+    let metadata = &recording.metadata;
+    let result = match (&metadata.result, metadata.player_color.as_deref()) {
+        (GameResult::WhiteWin, _)
+        | (GameResult::PlayerWin, Some("White"))
+        | (GameResult::AIWin, Some("Black")) => "1-0",
+        (GameResult::BlackWin, _) | (GameResult::PlayerWin, _) | (GameResult::AIWin, _) => "0-1",
+        (GameResult::Draw, _) => "1/2-1/2",
+        (GameResult::InProgress, _) => "*",
+    };
+    let mut tags = vec![
+        ("Event".to_string(), "Rusty Chess game".to_string()),
+        ("Date".to_string(), metadata.date.replace('-', ".")),
+        ("White".to_string(), metadata.white_player.clone()),
+        ("Black".to_string(), metadata.black_player.clone()),
+        ("Result".to_string(), result.to_string()),
+    ];
+    if let Some(time_control) = &metadata.time_control {
+        tags.push(("TimeControl".to_string(), time_control.clone()));
+    }
+    if start_fen != Board::startpos().to_fen() {
+        tags.push(("SetUp".to_string(), "1".to_string()));
+        tags.push(("FEN".to_string(), start_fen.to_string()));
+    }
+    tags
+}
+
+/// Winning chances from 0 to 100 for a score in centipawns, on the logistic
+/// curve fitted to rated games.
+pub fn win_percent(score: i32) -> f64 {
+    50.0 + 50.0 * (2.0 / (1.0 + (-0.003_682_08 * score as f64).exp()) - 1.0)
+}
+
+/// Accuracy from 0 to 100 of a move scored `played` where the best scored `best`:
+/// 100 when it keeps all of the winning chances, falling off as it gives them up.
+pub fn move_accuracy(best: i32, played: i32) -> f64 {
+    let lost = (win_percent(best) - win_percent(played)).max(0.0);
+    (103.166_8 * (-0.043_54 * lost).exp() - 3.166_9).clamp(0.0, 100.0)
+}
+
+fn average(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), v| (sum + v, count + 1));
+    (count > 0).then(|| sum / count as f64)
+}
+
+fn san_moves(board: &Board, moves: &[ChessMove]) -> Vec<String> {
+    let mut board = *board;
+    moves
+        .iter()
+        .map(|&chess_move| {
+            let san = to_san(&board, chess_move);
+            board.make_move(chess_move);
+            san
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::{GameMetadata, MoveRecord};
+    use crate::search::SearchParams;
+
+    #[test]
+    fn test_accuracy_curve() {
+        assert!((win_percent(0) - 50.0).abs() < 1e-9);
+        assert!(win_percent(300) > 75.0 && win_percent(-300) < 25.0);
+        assert!((move_accuracy(50, 50) - 100.0).abs() < 0.01);
+        assert!(move_accuracy(50, -250) < 30.0);
+        // Already lost: throwing away more changes little
+        assert!(move_accuracy(-900, -1200) > 90.0);
+    }
+
+    #[test]
+    fn test_reads_recordings() {
+        let mut recording = GameRecording::new(GameMetadata::new(
+            "You".to_string(),
+            "Engine".to_string(),
+            Some(Color::Black),
+            "12:00:00".to_string(),
+            "2026-10-18".to_string(),
+        ));
+        recording.metadata.result = GameResult::PlayerWin;
+        for (i, (color, notation)) in [(Color::White, "e2-e4"), (Color::Black, "e7-e5")]
+            .into_iter()
+            .enumerate()
+        {
+            recording.add_move(MoveRecord::new_player_move(
+                i as u16 + 1,
+                color,
+                notation.to_string(),
+            ));
+        }
+
+        let (start_fen, moves) = recording_moves(&recording).unwrap();
+        assert_eq!(start_fen, Board::startpos().to_fen());
+        assert_eq!(moves, vec![ChessMove::new(12, 28), ChessMove::new(52, 36)]);
+
+        let tags = recording_tags(&recording, &start_fen);
+        assert!(tags.contains(&("Result".to_string(), "0-1".to_string())));
+        assert!(tags.contains(&("Date".to_string(), "2026.10.18".to_string())));
+        assert!(!tags.iter().any(|(name, _)| name == "FEN"));
+    }
+
+    #[test]
+    fn test_finds_the_blunder() {
+        // White's king steps away from its queen, and Black takes it
+        let fen = "4k3/8/8/3q4/8/8/8/3QK3 w - - 0 30";
+        let moves = [ChessMove::new(4, 13), ChessMove::new(35, 3)];
+        let mut coach = Coach::new(SearchParams::fixed_nodes(3, 200_000));
+        let analysis = GameAnalysis::analyze(fen, &moves, &mut coach, |_, _| {}).unwrap();
+
+        let blunder = &analysis.moves[0];
+        assert_eq!(blunder.label(), "30. Kf2");
+        assert_eq!(blunder.review.quality, MoveQuality::Blunder);
+        assert_eq!(analysis.moves[1].label(), "30... Qxd1");
+        assert_eq!(analysis.moves[1].review.quality, MoveQuality::Best);
+        assert_eq!(analysis.count(Color::White, MoveQuality::Blunder), 1);
+        assert!(
+            analysis.accuracy(Color::White).unwrap() < analysis.accuracy(Color::Black).unwrap()
+        );
+        assert_eq!(analysis.critical_moments(3).len(), 1);
+
+        let pgn = analysis.to_annotated_pgn(vec![("FEN".to_string(), fen.to_string())]);
+        assert!(pgn.contains("30. Kf2 $4 {Blunder."), "{}", pgn);
+        assert!(pgn.contains("(30. Qxd5"), "{}", pgn);
+        assert!(pgn.contains("30... Qxd1"), "{}", pgn);
+    }
+}
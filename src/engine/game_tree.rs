@@ -0,0 +1,180 @@
+//! The moves of a game as a tree. Stepping back keeps the moves stepped over so they
+//! can be replayed, and a different move from an earlier position starts a variation.
+
+use crate::board::ChessMove;
+use crate::metrics::MoveRecord;
+
+struct Node {
+    /// The move leading here; None for the start position
+    chess_move: Option<ChessMove>,
+    parent: usize,
+    children: Vec<usize>,
+    /// The child `forward` follows: the one played or visited last
+    selected: Option<usize>,
+    /// How the move was recorded, kept while the cursor is before it
+    record: Option<MoveRecord>,
+    ply: usize,
+}
+
+/// Every move tried in a game, with a cursor on the current position.
+pub struct GameTree {
+    nodes: Vec<Node>,
+    cursor: usize,
+}
+
+impl Default for GameTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameTree {
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![Node {
+                chess_move: None,
+                parent: 0,
+                children: Vec::new(),
+                selected: None,
+                record: None,
+                ply: 0,
+            }],
+            cursor: 0,
+        }
+    }
+
+    /// Number of moves from the start position to the cursor.
+    pub fn ply(&self) -> usize {
+        self.nodes[self.cursor].ply
+    }
+
+    /// Number of moves in the current line, including those after the cursor.
+    pub fn line_length(&self) -> usize {
+        let mut node = self.cursor;
+        while let Some(next) = self.nodes[node].selected {
+            node = next;
+        }
+        self.nodes[node].ply
+    }
+
+    /// Plays `chess_move` from the cursor, following it if it was tried before.
+    /// Returns true when it starts a new variation beside moves already tried here.
+    pub fn play(&mut self, chess_move: ChessMove) -> bool {
+        let children = &self.nodes[self.cursor].children;
+        if let Some(&child) = children
+            .iter()
+            .find(|&&child| self.nodes[child].chess_move == Some(chess_move))
+        {
+            self.nodes[self.cursor].selected = Some(child);
+            self.nodes[child].record = None;
+            self.cursor = child;
+            return false;
+        }
+
+        let branches = !children.is_empty();
+        let child = self.nodes.len();
+        self.nodes.push(Node {
+            chess_move: Some(chess_move),
+            parent: self.cursor,
+            children: Vec::new(),
+            selected: None,
+            record: None,
+            ply: self.ply() + 1,
+        });
+        let parent = &mut self.nodes[self.cursor];
+        parent.children.push(child);
+        parent.selected = Some(child);
+        self.cursor = child;
+        branches
+    }
+
+    /// Steps back over the last move, keeping its `record` for `forward`.
+    /// Returns the move, or None at the start position.
+    pub fn back(&mut self, record: Option<MoveRecord>) -> Option<ChessMove> {
+        let node = &mut self.nodes[self.cursor];
+        let chess_move = node.chess_move?;
+        node.record = record;
+        self.cursor = node.parent;
+        Some(chess_move)
+    }
+
+    /// Steps forward along the current line, returning the move and its record.
+    pub fn forward(&mut self) -> Option<(ChessMove, Option<MoveRecord>)> {
+        let child = self.nodes[self.cursor].selected?;
+        self.cursor = child;
+        let node = &mut self.nodes[child];
+        Some((node.chess_move?, node.record.take()))
+    }
+
+    /// The move `forward` would play.
+    pub fn next_move(&self) -> Option<ChessMove> {
+        let child = self.nodes[self.cursor].selected?;
+        self.nodes[child].chess_move
+    }
+
+    /// Every move tried from the cursor, in the order they were first played.
+    pub fn moves_tried(&self) -> Vec<ChessMove> {
+        self.nodes[self.cursor]
+            .children
+            .iter()
+            .filter_map(|&child| self.nodes[child].chess_move)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_back_and_forward_follow_the_line() {
+        let mut tree = GameTree::new();
+        let (e4, e5, nf3) = (
+            ChessMove::new(12, 28),
+            ChessMove::new(52, 36),
+            ChessMove::new(6, 21),
+        );
+        for chess_move in [e4, e5, nf3] {
+            assert!(!tree.play(chess_move));
+        }
+        assert_eq!(tree.back(None), Some(nf3));
+        assert_eq!(tree.back(None), Some(e5));
+        assert_eq!((tree.ply(), tree.line_length()), (1, 3));
+        assert_eq!(tree.next_move(), Some(e5));
+
+        assert_eq!(tree.forward().map(|(m, _)| m), Some(e5));
+        assert_eq!(tree.forward().map(|(m, _)| m), Some(nf3));
+        assert!(tree.forward().is_none());
+
+        tree.back(None);
+        tree.back(None);
+        tree.back(None);
+        assert!(tree.back(None).is_none());
+        assert_eq!(tree.ply(), 0);
+    }
+
+    #[test]
+    fn test_new_moves_start_variations() {
+        let mut tree = GameTree::new();
+        let (e4, d4, e5) = (
+            ChessMove::new(12, 28),
+            ChessMove::new(11, 27),
+            ChessMove::new(52, 36),
+        );
+        tree.play(e4);
+        tree.play(e5);
+        tree.back(None);
+        tree.back(None);
+
+        // The variation becomes the line forward follows
+        assert!(tree.play(d4));
+        assert_eq!(tree.line_length(), 1);
+        tree.back(None);
+        assert_eq!(tree.moves_tried(), vec![e4, d4]);
+        assert_eq!(tree.next_move(), Some(d4));
+
+        // Replaying a move already tried returns to its line
+        assert!(!tree.play(e4));
+        assert_eq!(tree.line_length(), 2);
+    }
+}
@@ -0,0 +1,185 @@
+//! Statistics for engine-vs-engine matches: Elo difference with error bars and a
+//! sequential probability ratio test (SPRT) deciding between two Elo hypotheses.
+
+/// Two-sided 95% confidence
+const Z_95: f64 = 1.959_964;
+
+/// Wins, draws and losses from one engine's point of view.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MatchScore {
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+}
+
+impl MatchScore {
+    /// Records one game scored 1.0, 0.5 or 0.0.
+    pub fn add(&mut self, score: f64) {
+        if score > 0.75 {
+            self.wins += 1;
+        } else if score < 0.25 {
+            self.losses += 1;
+        } else {
+            self.draws += 1;
+        }
+    }
+
+    pub fn games(&self) -> u32 {
+        self.wins + self.draws + self.losses
+    }
+
+    /// Average points per game, or None before any game.
+    pub fn score(&self) -> Option<f64> {
+        let games = self.games();
+        (games > 0).then(|| (self.wins as f64 + 0.5 * self.draws as f64) / games as f64)
+    }
+
+    /// Per-game variance of the score, or None before any game.
+    fn variance(&self) -> Option<f64> {
+        let mean = self.score()?;
+        let games = self.games() as f64;
+        let deviation = |result: f64, count: u32| count as f64 * (result - mean).powi(2);
+        Some(
+            (deviation(1.0, self.wins) + deviation(0.5, self.draws) + deviation(0.0, self.losses))
+                / games,
+        )
+    }
+
+    /// Elo difference and its 95% error margin, or None while the score is still 0% or 100%.
+    pub fn elo(&self) -> Option<(f64, f64)> {
+        let mean = self.score()?;
+        if mean <= 0.0 || mean >= 1.0 {
+            return None;
+        }
+        let margin = Z_95 * (self.variance()? / self.games() as f64).sqrt();
+        let low = score_to_elo((mean - margin).max(f64::EPSILON));
+        let high = score_to_elo((mean + margin).min(1.0 - f64::EPSILON));
+        Some((score_to_elo(mean), (high - low) / 2.0))
+    }
+}
+
+/// Expected score for an Elo advantage.
+pub fn elo_to_score(elo: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-elo / 400.0))
+}
+
+/// Elo advantage for an expected score strictly between 0 and 1.
+pub fn score_to_elo(score: f64) -> f64 {
+    400.0 * (score / (1.0 - score)).log10()
+}
+
+/// What an SPRT concludes from the games so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SprtStatus {
+    /// The engine is at least `elo1` stronger (H1 accepted)
+    Accept,
+    /// The engine is no more than `elo0` stronger (H0 accepted)
+    Reject,
+    /// More games are needed
+    Continue,
+}
+
+/// Sequential probability ratio test of H0: elo = `elo0` against H1: elo = `elo1`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sprt {
+    pub elo0: f64,
+    pub elo1: f64,
+    /// Probability of accepting H1 when H0 holds
+    pub alpha: f64,
+    /// Probability of accepting H0 when H1 holds
+    pub beta: f64,
+}
+
+impl Default for Sprt {
+    fn default() -> Self {
+        Self {
+            elo0: 0.0,
+            elo1: 5.0,
+            alpha: 0.05,
+            beta: 0.05,
+        }
+    }
+}
+
+impl Sprt {
+    /// Log-likelihood ratio of H1 over H0, using the normal approximation of the
+    /// score distribution (as fishtest and cutechess do).
+    pub fn llr(&self, score: &MatchScore) -> f64 {
+        let (Some(mean), Some(variance)) = (score.score(), score.variance()) else {
+            return 0.0;
+        };
+        if variance <= 0.0 {
+            return 0.0;
+        }
+        let s0 = elo_to_score(self.elo0);
+        let s1 = elo_to_score(self.elo1);
+        score.games() as f64 * (s1 - s0) * (2.0 * mean - s0 - s1) / (2.0 * variance)
+    }
+
+    /// The (lower, upper) LLR bounds for rejecting and accepting.
+    pub fn bounds(&self) -> (f64, f64) {
+        (
+            (self.beta / (1.0 - self.alpha)).ln(),
+            ((1.0 - self.beta) / self.alpha).ln(),
+        )
+    }
+
+    pub fn status(&self, score: &MatchScore) -> SprtStatus {
+        let llr = self.llr(score);
+        let (lower, upper) = self.bounds();
+        if llr >= upper {
+            SprtStatus::Accept
+        } else if llr <= lower {
+            SprtStatus::Reject
+        } else {
+            SprtStatus::Continue
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(wins: u32, draws: u32, losses: u32) -> MatchScore {
+        MatchScore {
+            wins,
+            draws,
+            losses,
+        }
+    }
+
+    #[test]
+    fn test_elo_conversions() {
+        assert_eq!(elo_to_score(0.0), 0.5);
+        assert!((score_to_elo(elo_to_score(100.0)) - 100.0).abs() < 1e-9);
+        assert!((score_to_elo(0.75) - 190.85).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_elo_with_error_bars() {
+        assert_eq!(score(0, 0, 0).elo(), None);
+        assert_eq!(score(10, 0, 0).elo(), None);
+
+        let (elo, margin) = score(60, 20, 20).elo().unwrap();
+        assert!((elo - score_to_elo(0.7)).abs() < 1e-9);
+        assert!(margin > 30.0 && margin < 100.0);
+
+        // Four times the games roughly halves the margin
+        let (_, wider) = score(30, 40, 30).elo().unwrap();
+        let (_, narrower) = score(120, 160, 120).elo().unwrap();
+        assert!((wider / narrower - 2.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_sprt_decisions() {
+        let sprt = Sprt::default();
+        let (lower, upper) = sprt.bounds();
+        assert!((upper - 2.944).abs() < 0.001);
+        assert!((lower + 2.944).abs() < 0.001);
+
+        assert_eq!(sprt.status(&score(5, 10, 5)), SprtStatus::Continue);
+        assert_eq!(sprt.status(&score(600, 300, 100)), SprtStatus::Accept);
+        assert_eq!(sprt.status(&score(100, 300, 600)), SprtStatus::Reject);
+    }
+}
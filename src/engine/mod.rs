@@ -1,3 +1,25 @@
+pub mod clock;
+pub mod coach;
 pub mod game;
+pub mod game_analysis;
+pub mod game_tree;
+pub mod match_stats;
+pub mod move_input;
+pub mod outcome;
+pub mod player;
+pub mod saved_game;
 
-pub use game::{AiGame, PlayerAction, parse_square, square_to_notation};
+pub use clock::{Clock, TimeBonus, TimeControl, format_clock_time};
+pub use coach::{
+    Coach, MoveQuality, MoveReview, Suggestion, centipawn_loss, format_score, san_line,
+};
+pub use game::{AiGame, parse_square, square_to_notation};
+pub use game_analysis::{
+    AnalyzedMove, GameAnalysis, move_accuracy, recording_moves, recording_tags, win_percent,
+};
+pub use game_tree::GameTree;
+pub use match_stats::{MatchScore, Sprt, SprtStatus};
+pub use move_input::parse_move_input;
+pub use outcome::{GameOutcome, game_outcome, has_mating_material};
+pub use player::{EnginePlayer, HumanPlayer, Player, PlayerAction, UciPlayer};
+pub use saved_game::SavedGame;
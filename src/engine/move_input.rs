@@ -0,0 +1,279 @@
+//! Reading moves typed at the terminal: SAN (`Nf3`, `exd5`, `O-O`, `e8=N`), UCI
+//! (`g1f3`, `e7e8n`) or from,to coordinates (`g1,f3`, `e7,e8=N`).
+
+use crate::board::{Board, ChessMove, Piece};
+use crate::engine::game::{parse_square, square_to_notation};
+use crate::movegen::MoveGenerator;
+use crate::pgn::{san_candidates, to_san};
+
+/// At most this many "did you mean" suggestions are shown
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Finds the legal move meant by `input`, in SAN, UCI or coordinate notation.
+///
+/// UCI and coordinate promotions without a piece promote to a queen; SAN needs the
+/// piece. Errors list the candidates of an ambiguous move, or suggest legal moves
+/// close to what was typed.
+pub fn parse_move_input(board: &Board, input: &str) -> Result<ChessMove, String> {
+    let text = input.trim().trim_end_matches(['+', '#', '!', '?']);
+    if text.is_empty() {
+        return Err("Enter a move, e.g. Nf3, g1f3 or g1,f3".to_string());
+    }
+
+    let mut legal_moves = Vec::with_capacity(128);
+    MoveGenerator::generate_legal_moves(board, &mut legal_moves);
+
+    if let Some((from, to, promotion)) = parse_coordinates(text)? {
+        let matches = |m: &&ChessMove| {
+            m.from() == from
+                && (m.to() == to || m.destination() == to)
+                && match promotion {
+                    Some(piece) => m.promotion_piece() == Some(piece),
+                    None => m
+                        .promotion_piece()
+                        .is_none_or(|piece| piece == Piece::Queen),
+                }
+        };
+        // A king step to the square a Chess960 castle also lands on is the step
+        let exact = legal_moves.iter().find(|m| matches(m) && m.to() == to);
+        return match exact.or_else(|| legal_moves.iter().find(matches)) {
+            Some(&chess_move) => Ok(chess_move),
+            None => Err(illegal_move_error(board, &legal_moves, input)),
+        };
+    }
+
+    // Input that isn't SAN either may still be close to a legal move
+    let candidates = san_candidates(board, &normalize_san(text)).unwrap_or_default();
+    match candidates[..] {
+        [chess_move] => Ok(chess_move),
+        [] => Err(illegal_move_error(board, &legal_moves, input)),
+        ref candidates => {
+            let names: Vec<String> = candidates.iter().map(|&m| to_san(board, m)).collect();
+            if candidates.iter().all(|m| m.is_promotion()) {
+                Err(format!("Choose a promotion piece: {}", names.join(", ")))
+            } else {
+                Err(format!(
+                    "Ambiguous move '{}': could be {}",
+                    input.trim(),
+                    names.join(" or ")
+                ))
+            }
+        }
+    }
+}
+
+/// Reads UCI (`e7e8n`) or coordinates (`e7,e8`, `e7-e8=N`, `e7 e8 n`) as (from, to,
+/// promotion). None when the text isn't in either form, so it can be tried as SAN.
+fn parse_coordinates(text: &str) -> Result<Option<(usize, usize, Option<Piece>)>, String> {
+    let compact: String = text
+        .chars()
+        .filter(|c| !matches!(c, ',' | '-' | ' ' | '='))
+        .collect();
+    let is_square = |s: &str| {
+        let bytes = s.as_bytes();
+        bytes.len() == 2 && (b'a'..=b'h').contains(&bytes[0]) && (b'1'..=b'8').contains(&bytes[1])
+    };
+    // Pawn captures like "exd5" also start with a file; a square must come first
+    if compact.len() < 4 || compact.len() > 5 || !compact.is_ascii() {
+        return Ok(None);
+    }
+    if !is_square(&compact[..2]) || !is_square(&compact[2..4]) {
+        return Ok(None);
+    }
+
+    let from = parse_square(&compact[..2])?;
+    let to = parse_square(&compact[2..4])?;
+    let promotion = match compact[4..].to_ascii_lowercase().as_str() {
+        "" => None,
+        "q" => Some(Piece::Queen),
+        "r" => Some(Piece::Rook),
+        "b" => Some(Piece::Bishop),
+        "n" => Some(Piece::Knight),
+        other => {
+            return Err(format!(
+                "Invalid promotion piece '{}': use q, r, b or n",
+                other
+            ));
+        }
+    };
+    Ok(Some((from, to, promotion)))
+}
+
+/// Accepts lowercase promotion pieces (`e8=n`), which SAN writes in capitals.
+fn normalize_san(text: &str) -> String {
+    match text.split_once('=') {
+        Some((head, piece)) => format!("{}={}", head, piece.to_ascii_uppercase()),
+        None => text.to_string(),
+    }
+}
+
+/// An error for input that matches no legal move, suggesting the closest ones.
+fn illegal_move_error(board: &Board, legal_moves: &[ChessMove], input: &str) -> String {
+    let input = input.trim();
+    let suggestions = suggestions(board, legal_moves, input);
+    if suggestions.is_empty() {
+        format!(
+            "Illegal or unrecognized move '{}'. Enter moves as SAN (Nf3), UCI (g1f3) or \
+             coordinates (g1,f3); type 'moves' to list them",
+            input
+        )
+    } else {
+        format!(
+            "Illegal move '{}'. Did you mean {}?",
+            input,
+            suggestions.join(" or ")
+        )
+    }
+}
+
+/// The legal moves written closest to `input`, each in the notation nearest to it.
+fn suggestions(board: &Board, legal_moves: &[ChessMove], input: &str) -> Vec<String> {
+    let typed = notation_key(input);
+    // Allow one typo in short moves, two in longer ones
+    let max_distance = if typed.len() >= 4 { 2 } else { 1 };
+
+    let mut close: Vec<(usize, String)> = legal_moves
+        .iter()
+        .filter_map(|&chess_move| {
+            let san = to_san(board, chess_move);
+            let san = san.trim_end_matches(['+', '#']).to_string();
+            let coordinates = format!(
+                "{},{}",
+                square_to_notation(chess_move.from()),
+                square_to_notation(chess_move.destination())
+            );
+            [san, chess_move.to_uci(), coordinates]
+                .into_iter()
+                .map(|notation| (edit_distance(&typed, &notation_key(&notation)), notation))
+                // On a tie, prefer the notation written most like the input
+                .min_by_key(|(distance, notation)| (*distance, edit_distance(input, notation)))
+        })
+        .filter(|&(distance, _)| distance <= max_distance)
+        .collect();
+    // Only the closest matches: "nf3" should suggest Nf3, not every knight move
+    let closest = close.iter().map(|&(distance, _)| distance).min();
+    close.retain(|&(distance, _)| Some(distance) == closest);
+    close.sort();
+    close.dedup_by(|a, b| a.1 == b.1);
+    close
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, notation)| notation)
+        .collect()
+}
+
+/// A move's letters and digits in lowercase, so "nf3", "Nf3" and "g1-f3" compare
+/// by what was meant rather than how it was written.
+fn notation_key(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_ascii_alphanumeric() && *c != 'x')
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Levenshtein distance: the fewest single-character insertions, deletions and
+/// substitutions turning `a` into `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_every_notation() {
+        let board = Board::startpos();
+        let knight = Ok(ChessMove::new(6, 21));
+        for input in ["Nf3", "g1f3", "g1,f3", "g1-f3", " g1 f3 "] {
+            assert_eq!(parse_move_input(&board, input), knight, "{}", input);
+        }
+        assert_eq!(parse_move_input(&board, "e4"), Ok(ChessMove::new(12, 28)));
+
+        let board =
+            Board::from_fen("rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2");
+        assert_eq!(parse_move_input(&board, "exd5"), Ok(ChessMove::new(28, 35)));
+
+        let board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+        assert_eq!(
+            parse_move_input(&board, "O-O"),
+            Ok(ChessMove::new_castle(4, 7))
+        );
+        assert_eq!(
+            parse_move_input(&board, "e1,g1"),
+            Ok(ChessMove::new_castle(4, 7))
+        );
+        assert_eq!(
+            parse_move_input(&board, "e1-h1"),
+            Ok(ChessMove::new_castle(4, 7))
+        );
+
+        // Chess960: king b1 castling with the a1 rook ends on c1, a square the king can step to
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/RK6 w A - 0 1");
+        assert_eq!(
+            parse_move_input(&board, "b1-a1"),
+            Ok(ChessMove::new_castle(1, 0))
+        );
+        assert_eq!(parse_move_input(&board, "b1-c1"), Ok(ChessMove::new(1, 2)));
+    }
+
+    #[test]
+    fn test_promotions_in_every_notation() {
+        let board = Board::from_fen("4k3/1P6/8/8/8/8/8/4K3 w - - 0 1");
+        let knight = Ok(ChessMove::new_promotion(49, 57, Piece::Knight));
+        for input in ["b8=N", "b8=n", "b7b8n", "b7,b8=N", "b7,b8n", "b7-b8 N"] {
+            assert_eq!(parse_move_input(&board, input), knight, "{}", input);
+        }
+
+        // Coordinates and UCI promote to a queen by default; SAN asks for the piece
+        let queen = Ok(ChessMove::new_promotion(49, 57, Piece::Queen));
+        assert_eq!(parse_move_input(&board, "b7b8"), queen);
+        assert_eq!(parse_move_input(&board, "b7,b8"), queen);
+        let error = parse_move_input(&board, "b8").unwrap_err();
+        assert!(error.starts_with("Choose a promotion piece"), "{}", error);
+        assert!(parse_move_input(&board, "b7b8k").is_err());
+    }
+
+    #[test]
+    fn test_ambiguous_moves_list_candidates() {
+        let board = Board::from_fen("4k3/8/8/8/R7/8/8/R4RK1 w - - 0 1");
+        assert_eq!(
+            parse_move_input(&board, "Rd1"),
+            Err("Ambiguous move 'Rd1': could be Rad1 or Rfd1".to_string())
+        );
+        assert_eq!(parse_move_input(&board, "Rfd1"), Ok(ChessMove::new(5, 3)));
+    }
+
+    #[test]
+    fn test_near_misses_get_suggestions() {
+        let board = Board::startpos();
+        let error = parse_move_input(&board, "nf3").unwrap_err();
+        assert!(error.ends_with("Did you mean Nf3?"), "{}", error);
+
+        let error = parse_move_input(&board, "g1f4").unwrap_err();
+        assert!(error.ends_with("Did you mean g1f3?"), "{}", error);
+        let error = parse_move_input(&board, "e2,e9").unwrap_err();
+        assert!(error.ends_with("Did you mean e2,e3 or e2,e4?"), "{}", error);
+
+        let error = parse_move_input(&board, "hello").unwrap_err();
+        assert!(error.contains("type 'moves'"), "{}", error);
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("nf3", "nf3"), 0);
+        assert_eq!(edit_distance("nf3", "nf4"), 1);
+        assert_eq!(edit_distance("e4", "e2e4"), 2);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+}
@@ -0,0 +1,210 @@
+use crate::board::{Board, Color, Piece};
+use crate::movegen::MoveGenerator;
+
+/// How a game ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameOutcome {
+    Checkmate { winner: Color },
+    Stalemate,
+    FiftyMoveRule,
+    ThreefoldRepetition,
+    InsufficientMaterial,
+}
+
+impl GameOutcome {
+    /// The winning side, or None for a draw.
+    pub fn winner(&self) -> Option<Color> {
+        match self {
+            GameOutcome::Checkmate { winner } => Some(*winner),
+            _ => None,
+        }
+    }
+
+    /// PGN-style result string: "1-0", "0-1" or "1/2-1/2".
+    pub fn result_str(&self) -> &'static str {
+        match self.winner() {
+            Some(Color::White) => "1-0",
+            Some(Color::Black) => "0-1",
+            None => "1/2-1/2",
+        }
+    }
+
+    /// Game result from White's perspective: 1.0 win, 0.5 draw, 0.0 loss.
+    pub fn white_score(&self) -> f64 {
+        match self.winner() {
+            Some(Color::White) => 1.0,
+            Some(Color::Black) => 0.0,
+            None => 0.5,
+        }
+    }
+}
+
+/// Returns how the game ended, or None if it's still in progress.
+///
+/// `history` holds the hashes of every position reached so far, including the current one.
+pub fn game_outcome(board: &Board, history: &[u64]) -> Option<GameOutcome> {
+    let mut moves = Vec::with_capacity(64);
+    MoveGenerator::generate_legal_moves(board, &mut moves);
+
+    if moves.is_empty() {
+        return Some(if board.in_check(board.side_to_move) {
+            GameOutcome::Checkmate {
+                winner: board.side_to_move.opponent(),
+            }
+        } else {
+            GameOutcome::Stalemate
+        });
+    }
+
+    if board.halfmove_clock >= 100 {
+        return Some(GameOutcome::FiftyMoveRule);
+    }
+
+    if history.iter().filter(|&&hash| hash == board.hash).count() >= 3 {
+        return Some(GameOutcome::ThreefoldRepetition);
+    }
+
+    if is_insufficient_material(board) {
+        return Some(GameOutcome::InsufficientMaterial);
+    }
+
+    None
+}
+
+/// True when neither side can possibly mate: bare kings or a single minor piece.
+pub fn is_insufficient_material(board: &Board) -> bool {
+    let mut minors = 0;
+
+    for color in [Color::White, Color::Black] {
+        if board.count_pieces(color, Piece::Pawn) > 0
+            || board.count_pieces(color, Piece::Rook) > 0
+            || board.count_pieces(color, Piece::Queen) > 0
+        {
+            return false;
+        }
+        minors +=
+            board.count_pieces(color, Piece::Knight) + board.count_pieces(color, Piece::Bishop);
+    }
+
+    minors <= 1
+}
+
+const DARK_SQUARES: u64 = 0xAA55_AA55_AA55_AA55;
+
+/// True when `color` could checkmate by some series of legal moves, so it wins on
+/// time when the opponent's flag falls; otherwise the game is drawn.
+///
+/// A lone minor piece can only mate with the opponent's own men blocking its king,
+/// and bishops that all share one square color can't mate with just bishops of that
+/// color left on the board.
+pub fn has_mating_material(board: &Board, color: Color) -> bool {
+    if board.count_pieces(color, Piece::Pawn) > 0
+        || board.count_pieces(color, Piece::Rook) > 0
+        || board.count_pieces(color, Piece::Queen) > 0
+    {
+        return true;
+    }
+
+    let knights = board.count_pieces(color, Piece::Knight);
+    let bishops = board.pieces[color as usize][Piece::Bishop as usize];
+    let opponent = color.opponent();
+    let opponent_men = board.occ[opponent as usize] & !(1u64 << board.king_sq[opponent as usize]);
+
+    match (knights, bishops.count_ones()) {
+        (0, 0) => false,
+        (1, 0) => opponent_men != 0,
+        (0, _) => {
+            let color_mask = if bishops & DARK_SQUARES != 0 {
+                DARK_SQUARES
+            } else {
+                !DARK_SQUARES
+            };
+            let same_colored_bishops = (board.pieces[Color::White as usize]
+                [Piece::Bishop as usize]
+                | board.pieces[Color::Black as usize][Piece::Bishop as usize])
+                & color_mask;
+            bishops & !color_mask != 0 || opponent_men & !same_colored_bishops != 0
+        }
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkmate() {
+        // Fool's mate
+        let board =
+            Board::from_fen("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3");
+        let outcome = game_outcome(&board, &[board.hash]);
+
+        assert_eq!(
+            outcome,
+            Some(GameOutcome::Checkmate {
+                winner: Color::Black
+            })
+        );
+        assert_eq!(outcome.unwrap().result_str(), "0-1");
+    }
+
+    #[test]
+    fn test_stalemate() {
+        let board = Board::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1");
+        assert_eq!(
+            game_outcome(&board, &[board.hash]),
+            Some(GameOutcome::Stalemate)
+        );
+    }
+
+    #[test]
+    fn test_draw_rules() {
+        let fifty = Board::from_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 100 80");
+        assert_eq!(
+            game_outcome(&fifty, &[fifty.hash]),
+            Some(GameOutcome::FiftyMoveRule)
+        );
+
+        let board = Board::from_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1");
+        assert_eq!(game_outcome(&board, &[board.hash, board.hash]), None);
+        assert_eq!(
+            game_outcome(&board, &[board.hash, 1, board.hash, 2, board.hash]),
+            Some(GameOutcome::ThreefoldRepetition)
+        );
+
+        let bare = Board::from_fen("4k3/8/8/8/8/8/8/2B1K3 w - - 0 1");
+        assert_eq!(
+            game_outcome(&bare, &[bare.hash]),
+            Some(GameOutcome::InsufficientMaterial)
+        );
+    }
+
+    #[test]
+    fn test_has_mating_material() {
+        let pawn = Board::from_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1");
+        assert!(has_mating_material(&pawn, Color::White));
+        assert!(!has_mating_material(&pawn, Color::Black));
+
+        // A lone minor piece can't mate a bare king
+        let knight = Board::from_fen("4k3/8/8/8/8/8/8/1N2K3 w - - 0 1");
+        assert!(!has_mating_material(&knight, Color::White));
+        let bishop = Board::from_fen("4k3/8/8/8/8/8/8/2B1K3 w - - 0 1");
+        assert!(!has_mating_material(&bishop, Color::White));
+
+        // ... but can once the defender has men to hem its own king in
+        let knight_vs_pawn = Board::from_fen("4k3/4p3/8/8/8/8/8/1N2K3 w - - 0 1");
+        assert!(has_mating_material(&knight_vs_pawn, Color::White));
+
+        // Bishops all on one square color never mate
+        let same_bishops = Board::from_fen("3bk3/8/8/8/8/8/8/2B1K3 w - - 0 1");
+        assert!(!has_mating_material(&same_bishops, Color::White));
+        let opposite_bishops = Board::from_fen("2b1k3/8/8/8/8/8/8/2B1K3 w - - 0 1");
+        assert!(has_mating_material(&opposite_bishops, Color::White));
+
+        let bishop_pair = Board::from_fen("4k3/8/8/8/8/8/8/2B1KB2 w - - 0 1");
+        assert!(has_mating_material(&bishop_pair, Color::White));
+        let two_knights = Board::from_fen("4k3/8/8/8/8/8/8/1N2K1N1 w - - 0 1");
+        assert!(has_mating_material(&two_knights, Color::White));
+    }
+}
@@ -0,0 +1,314 @@
+use crate::board::{Board, ChessMove, Color};
+use crate::engine::clock::Clock;
+use crate::movegen::MoveGenerator;
+use crate::search::{ChessEngine, SearchParams};
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+/// What a player does on its turn
+pub enum PlayerAction {
+    /// Play this move
+    Move(ChessMove),
+    /// A line typed by a human, to be read as a move or a command
+    Input(String),
+    /// Nothing was played (a command was handled); the same player moves again
+    Continue,
+    Quit,
+    Resign,
+}
+
+/// One side of a game: a human at the terminal, the built-in engine, or an external engine.
+pub trait Player {
+    /// Shown in prompts, move announcements and the game recording
+    fn name(&self) -> &str;
+
+    fn is_human(&self) -> bool {
+        false
+    }
+
+    /// Decides what to do in `board`, which was reached by playing `moves` from `start`.
+    fn next_action(&mut self, board: &Board, start: &Board, moves: &[ChessMove]) -> PlayerAction;
+
+    /// The built-in engine behind this player, if any.
+    fn engine(&self) -> Option<&ChessEngine> {
+        None
+    }
+
+    fn engine_mut(&mut self) -> Option<&mut ChessEngine> {
+        None
+    }
+
+    /// The limits this player searches with, if it searches.
+    fn search_params(&self) -> Option<&SearchParams> {
+        None
+    }
+
+    /// Called before each of `color`'s moves in a game with a clock, so engines
+    /// can budget their remaining time.
+    fn update_clock(&mut self, _clock: &Clock, _color: Color) {}
+}
+
+/// A human entering moves and commands at the terminal.
+pub struct HumanPlayer {
+    name: String,
+}
+
+impl HumanPlayer {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+        }
+    }
+}
+
+impl Player for HumanPlayer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn is_human(&self) -> bool {
+        true
+    }
+
+    fn next_action(&mut self, board: &Board, _start: &Board, _moves: &[ChessMove]) -> PlayerAction {
+        print!("{:?} to move ({}): ", board.side_to_move, self.name);
+        io::stdout().flush().unwrap();
+
+        let mut input = String::new();
+        match io::stdin().read_line(&mut input) {
+            // End of input (e.g. Ctrl-D) leaves the game like `quit`
+            Ok(0) | Err(_) => PlayerAction::Quit,
+            Ok(_) => PlayerAction::Input(input.trim().to_string()),
+        }
+    }
+}
+
+/// The built-in engine searching with its own limits and opening book.
+pub struct EnginePlayer {
+    name: String,
+    engine: ChessEngine,
+    /// The limits it was created with
+    base_params: SearchParams,
+    /// The limits for the next move, shortened to fit the clock when there is one
+    search_params: SearchParams,
+}
+
+impl EnginePlayer {
+    pub fn new(name: &str, engine: ChessEngine, search_params: SearchParams) -> Self {
+        Self {
+            name: name.to_string(),
+            engine,
+            base_params: search_params,
+            search_params,
+        }
+    }
+}
+
+impl Player for EnginePlayer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn next_action(&mut self, board: &Board, _start: &Board, _moves: &[ChessMove]) -> PlayerAction {
+        match self
+            .engine
+            .find_best_move_iterative(board, &self.search_params)
+        {
+            Some(best_move) => PlayerAction::Move(best_move),
+            None => PlayerAction::Resign,
+        }
+    }
+
+    fn engine(&self) -> Option<&ChessEngine> {
+        Some(&self.engine)
+    }
+
+    fn engine_mut(&mut self) -> Option<&mut ChessEngine> {
+        Some(&mut self.engine)
+    }
+
+    fn search_params(&self) -> Option<&SearchParams> {
+        Some(&self.search_params)
+    }
+
+    fn update_clock(&mut self, clock: &Clock, color: Color) {
+        let budget_ms = clock.time_for_move(color).as_millis() as u64;
+        self.search_params.min_search_time_ms = self.base_params.min_search_time_ms.min(budget_ms);
+    }
+}
+
+/// An external engine speaking the UCI protocol over its stdin and stdout.
+pub struct UciPlayer {
+    name: String,
+    process: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    move_time_ms: u64,
+    /// Arguments for `go` in a game with a clock, replacing the fixed move time
+    clock_limits: Option<String>,
+    /// `UCI_Chess960` has been switched on, so castles are sent king-takes-rook
+    chess960: bool,
+}
+
+impl UciPlayer {
+    /// Starts `command` (a program followed by its arguments) and completes the UCI handshake.
+    /// Each move is searched for `move_time_ms` milliseconds.
+    pub fn spawn(command: &str, move_time_ms: u64) -> io::Result<Self> {
+        let mut parts = command.split_whitespace();
+        let program = parts
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Empty UCI command"))?;
+        let mut process = Command::new(program)
+            .args(parts)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+
+        let stdin = process.stdin.take().expect("stdin is piped");
+        let stdout = BufReader::new(process.stdout.take().expect("stdout is piped"));
+        let mut player = Self {
+            name: program.to_string(),
+            process,
+            stdin,
+            stdout,
+            move_time_ms,
+            clock_limits: None,
+            chess960: false,
+        };
+
+        player.send("uci")?;
+        while let Some(line) = player.read_line()? {
+            if let Some(name) = line.strip_prefix("id name ") {
+                player.name = name.trim().to_string();
+            } else if line == "uciok" {
+                break;
+            }
+        }
+        player.send("ucinewgame")?;
+        player.wait_until_ready()?;
+
+        Ok(player)
+    }
+
+    fn wait_until_ready(&mut self) -> io::Result<()> {
+        self.send("isready")?;
+        while self.read_line()?.is_some_and(|line| line != "readyok") {}
+        Ok(())
+    }
+
+    /// Switches the engine to Chess960 castling the first time it sees a start
+    /// position that needs it.
+    fn enable_chess960(&mut self, start: &Board) -> io::Result<()> {
+        if self.chess960 || !start.has_chess960_castling() {
+            return Ok(());
+        }
+        self.send("setoption name UCI_Chess960 value true")?;
+        self.wait_until_ready()?;
+        self.chess960 = true;
+        Ok(())
+    }
+
+    fn send(&mut self, command: &str) -> io::Result<()> {
+        writeln!(self.stdin, "{}", command)?;
+        self.stdin.flush()
+    }
+
+    /// The next line the engine printed, or None once it has exited.
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        let mut line = String::new();
+        if self.stdout.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        Ok(Some(line.trim().to_string()))
+    }
+
+    fn best_move(&mut self, start: &Board, moves: &[ChessMove]) -> io::Result<Option<String>> {
+        self.enable_chess960(start)?;
+        let mut position = format!("position fen {}", start.to_fen());
+        if !moves.is_empty() {
+            position.push_str(" moves");
+            for chess_move in moves {
+                position.push(' ');
+                position.push_str(&if self.chess960 {
+                    chess_move.to_uci_chess960()
+                } else {
+                    chess_move.to_uci()
+                });
+            }
+        }
+        self.send(&position)?;
+        let go = match &self.clock_limits {
+            Some(limits) => format!("go {}", limits),
+            None => format!("go movetime {}", self.move_time_ms),
+        };
+        self.send(&go)?;
+
+        while let Some(line) = self.read_line()? {
+            if let Some(rest) = line.strip_prefix("bestmove") {
+                return Ok(rest.split_whitespace().next().map(str::to_string));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl Player for UciPlayer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn next_action(&mut self, board: &Board, start: &Board, moves: &[ChessMove]) -> PlayerAction {
+        let best_move = match self.best_move(start, moves) {
+            Ok(Some(best_move)) => best_move,
+            Ok(None) => {
+                eprintln!("{} stopped responding", self.name);
+                return PlayerAction::Resign;
+            }
+            Err(e) => {
+                eprintln!("Error talking to {}: {}", self.name, e);
+                return PlayerAction::Resign;
+            }
+        };
+
+        let mut legal_moves = Vec::with_capacity(128);
+        MoveGenerator::generate_legal_moves(board, &mut legal_moves);
+        match legal_moves
+            .into_iter()
+            .find(|m| m.to_uci() == best_move || m.to_uci_chess960() == best_move)
+        {
+            Some(chess_move) => PlayerAction::Move(chess_move),
+            None => {
+                eprintln!("{} played an illegal move: {}", self.name, best_move);
+                PlayerAction::Resign
+            }
+        }
+    }
+
+    /// UCI has no delay setting, so under a Bronstein delay the engine is sent no increment.
+    fn update_clock(&mut self, clock: &Clock, _color: Color) {
+        let increment_ms = clock.control().increment().as_millis();
+        self.clock_limits = Some(format!(
+            "wtime {} btime {} winc {} binc {}",
+            clock.remaining(Color::White).as_millis(),
+            clock.remaining(Color::Black).as_millis(),
+            increment_ms,
+            increment_ms
+        ));
+    }
+}
+
+impl Drop for UciPlayer {
+    fn drop(&mut self) {
+        // Give the engine a moment to exit on its own before killing it
+        if self.send("quit").is_ok() {
+            thread::sleep(Duration::from_millis(100));
+        }
+        if self.process.try_wait().ok().flatten().is_none() {
+            let _ = self.process.kill();
+        }
+        let _ = self.process.wait();
+    }
+}
@@ -0,0 +1,133 @@
+//! Games saved from the terminal app to be continued later: the settings they were
+//! started with, the moves played so far and the clocks.
+
+use crate::board::{Board, ChessMove};
+use crate::engine::clock::TimeControl;
+use crate::engine::move_input::parse_move_input;
+use crate::terminal::{
+    BlackOpeningBook, ChessEngineSettings, DisplaySettings, GameMode, PlayerSettings,
+    WhiteOpeningBook,
+};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// Everything needed to continue a terminal game, stored as JSON.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedGame {
+    pub mode: GameMode,
+    pub white: PlayerSettings,
+    pub black: PlayerSettings,
+    pub white_opening_book: WhiteOpeningBook,
+    pub black_opening_book: BlackOpeningBook,
+    pub move_delay_ms: u64,
+    pub time_control: Option<TimeControl>,
+    /// White's and Black's remaining time when the game was saved
+    pub clock_remaining_ms: Option<[u64; 2]>,
+    #[serde(default)]
+    pub display: DisplaySettings,
+    /// The position the game started from
+    pub start_fen: String,
+    /// The moves played from the start position, in UCI notation
+    pub moves: Vec<String>,
+}
+
+impl SavedGame {
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize game: {}", e))?;
+        fs::write(path, json).map_err(|e| format!("Failed to write '{}': {}", path, e))
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let json =
+            fs::read_to_string(path).map_err(|e| format!("Failed to read '{}': {}", path, e))?;
+        serde_json::from_str(&json).map_err(|e| format!("Invalid saved game '{}': {}", path, e))
+    }
+
+    /// The settings the game was started with.
+    pub fn settings(&self) -> Result<ChessEngineSettings, String> {
+        let starting_position = Board::try_from_fen(&self.start_fen)
+            .map_err(|e| format!("Invalid start position '{}': {}", self.start_fen, e))?;
+        Ok(ChessEngineSettings {
+            mode: self.mode,
+            white: self.white.clone(),
+            black: self.black.clone(),
+            starting_position,
+            white_opening_book: self.white_opening_book,
+            black_opening_book: self.black_opening_book,
+            move_delay_ms: self.move_delay_ms,
+            time_control: self.time_control,
+        })
+    }
+
+    /// Replays the saved moves from the start position, checking that each one is legal.
+    pub fn replay(&self) -> Result<Vec<ChessMove>, String> {
+        let mut board = self.settings()?.starting_position;
+        let mut moves = Vec::with_capacity(self.moves.len());
+        for (i, text) in self.moves.iter().enumerate() {
+            let chess_move = parse_move_input(&board, text)
+                .map_err(|_| format!("Saved move {} ({}) is illegal", i + 1, text))?;
+            board.make_move(chess_move);
+            moves.push(chess_move);
+        }
+        Ok(moves)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn saved(moves: &[&str]) -> SavedGame {
+        SavedGame {
+            mode: GameMode::HumanVsEngine,
+            white: PlayerSettings::Human,
+            black: PlayerSettings::Engine { search_depth: 3 },
+            white_opening_book: WhiteOpeningBook::None,
+            black_opening_book: BlackOpeningBook::Sicilian,
+            move_delay_ms: 0,
+            time_control: Some(TimeControl::rapid()),
+            clock_remaining_ms: Some([890_000, 900_000]),
+            display: DisplaySettings::default(),
+            start_fen: Board::startpos().to_fen(),
+            moves: moves.iter().map(|m| m.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_round_trips_through_json() {
+        let game = saved(&["e2e4", "c7c5"]);
+        let json = serde_json::to_string(&game).unwrap();
+        let loaded: SavedGame = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(loaded.moves, game.moves);
+        assert_eq!(loaded.black, PlayerSettings::Engine { search_depth: 3 });
+        let settings = loaded.settings().unwrap();
+        assert_eq!(settings.black_opening_book, BlackOpeningBook::Sicilian);
+        assert_eq!(
+            settings.time_control.map(|control| control.base),
+            Some(Duration::from_secs(900))
+        );
+    }
+
+    #[test]
+    fn test_replay_validates_moves() {
+        assert_eq!(
+            saved(&["e2e4", "c7c5", "g1f3"]).replay().unwrap(),
+            vec![
+                ChessMove::new(12, 28),
+                ChessMove::new(50, 34),
+                ChessMove::new(6, 21)
+            ]
+        );
+        assert_eq!(
+            saved(&["e2e4", "e2e4"]).replay(),
+            Err("Saved move 2 (e2e4) is illegal".to_string())
+        );
+
+        let mut bad_start = saved(&[]);
+        bad_start.start_fen = "8/8/8/8/8/8/8/8 w - - 0 1".to_string();
+        assert!(bad_start.replay().is_err());
+    }
+}
@@ -1,24 +1,28 @@
 use crate::{
     board::{Board, Color, Piece},
-    eval::evaluator::BoardEvaluator,
+    eval::{EvalParams, evaluator::BoardEvaluator},
 };
 
-const BISHOP_PAIR_BONUS: i32 = 30;
+pub(crate) const BISHOP_PAIR_BONUS: i32 = 30;
 
 pub struct BishopPairEvaluator;
 
 impl BoardEvaluator for BishopPairEvaluator {
     fn evaluate(&self, board: &Board) -> i32 {
+        self.evaluate_with_params(board, &EvalParams::DEFAULT)
+    }
+
+    fn evaluate_with_params(&self, board: &Board, params: &EvalParams) -> i32 {
         let white_bishops: u32 = board.count_pieces(Color::White, Piece::Bishop);
         let black_bishops: u32 = board.count_pieces(Color::Black, Piece::Bishop);
 
         let mut score: i32 = 0;
 
         if white_bishops >= 2 {
-            score += BISHOP_PAIR_BONUS;
+            score += params.bishop_pair_bonus;
         }
         if black_bishops >= 2 {
-            score -= BISHOP_PAIR_BONUS;
+            score -= params.bishop_pair_bonus;
         }
 
         score
@@ -1,10 +1,10 @@
 use crate::{
     board::{Board, Color, Piece},
-    eval::evaluator::BoardEvaluator,
+    eval::{EvalParams, evaluator::BoardEvaluator},
 };
 
 // Bonus per controlled central square
-const CENTER_BONUS: i32 = 5;
+pub(crate) const CENTER_BONUS: i32 = 5;
 
 // Central square indices in 0..63 board array
 const CENTRAL_SQUARES: [usize; 4] = [27, 28, 35, 36]; // d4=27, e4=28, d5=35, e5=36
@@ -15,6 +15,10 @@ pub struct CentralControlEvaluator;
 
 impl BoardEvaluator for CentralControlEvaluator {
     fn evaluate(&self, board: &Board) -> i32 {
+        self.evaluate_with_params(board, &EvalParams::DEFAULT)
+    }
+
+    fn evaluate_with_params(&self, board: &Board, params: &EvalParams) -> i32 {
         let mut score = 0;
 
         for &sq in &CENTRAL_SQUARES {
@@ -22,7 +26,7 @@ impl BoardEvaluator for CentralControlEvaluator {
             let white_control = Self::count_control(board, sq, Color::White);
             let black_control = Self::count_control(board, sq, Color::Black);
 
-            score += CENTER_BONUS * (white_control as i32 - black_control as i32);
+            score += params.center_control_bonus * (white_control as i32 - black_control as i32);
         }
 
         score
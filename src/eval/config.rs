@@ -0,0 +1,280 @@
+use crate::eval::{
+    BoardEvaluator, bishop_pair::BishopPairEvaluator, central_control::CentralControlEvaluator,
+    fork::ForkEvaluator, king_safety::KingSafetyEvaluator, knight_outpost::KnightOutpostEvaluator,
+    line_pressure::LinePressureEvaluator, material::MaterialEvaluator, mobility::MobilityEvaluator,
+    nnue::NnueEvaluator, pawn_structure::PawnStructureEvaluator, position::PositionEvaluator,
+    rook_file_evaluator::RookFileEvaluator, tempo::TempoEvaluator, threat::ThreatEvaluator,
+};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+
+/// Identifies one of the built-in board evaluators.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EvaluatorKind {
+    Material,
+    Position,
+    PawnStructure,
+    Mobility,
+    KingSafety,
+    Tempo,
+    BishopPair,
+    KnightOutpost,
+    RookFile,
+    CentralControl,
+    Threat,
+    LinePressure,
+    Fork,
+    /// Neural network evaluation, loaded from `nnue.bin`. Not enabled by default.
+    Nnue,
+}
+
+impl EvaluatorKind {
+    /// All hand-crafted evaluators in their default evaluation order.
+    pub const ALL: [EvaluatorKind; 13] = [
+        EvaluatorKind::Material,
+        EvaluatorKind::Position,
+        EvaluatorKind::PawnStructure,
+        EvaluatorKind::Mobility,
+        EvaluatorKind::KingSafety,
+        EvaluatorKind::Tempo,
+        EvaluatorKind::BishopPair,
+        EvaluatorKind::KnightOutpost,
+        EvaluatorKind::RookFile,
+        EvaluatorKind::CentralControl,
+        EvaluatorKind::Threat,
+        EvaluatorKind::LinePressure,
+        EvaluatorKind::Fork,
+    ];
+
+    /// Human-readable name used in evaluation tables.
+    pub fn name(&self) -> &'static str {
+        match self {
+            EvaluatorKind::Material => "Material",
+            EvaluatorKind::Position => "Position",
+            EvaluatorKind::PawnStructure => "Pawn Structure",
+            EvaluatorKind::Mobility => "Mobility",
+            EvaluatorKind::KingSafety => "King Safety",
+            EvaluatorKind::Tempo => "Tempo",
+            EvaluatorKind::BishopPair => "Bishop Pair",
+            EvaluatorKind::KnightOutpost => "Knight Outpost",
+            EvaluatorKind::RookFile => "Rook Files",
+            EvaluatorKind::CentralControl => "Central Control",
+            EvaluatorKind::Threat => "Threats",
+            EvaluatorKind::LinePressure => "Line Pressure",
+            EvaluatorKind::Fork => "Forks",
+            EvaluatorKind::Nnue => "NNUE",
+        }
+    }
+
+    /// Creates the evaluator instance for this kind.
+    ///
+    /// Only `Nnue` can fail, when its network file can't be loaded.
+    pub fn build(&self) -> Result<Box<dyn BoardEvaluator>, String> {
+        Ok(match self {
+            EvaluatorKind::Material => Box::new(MaterialEvaluator),
+            EvaluatorKind::Position => Box::new(PositionEvaluator),
+            EvaluatorKind::PawnStructure => Box::new(PawnStructureEvaluator),
+            EvaluatorKind::Mobility => Box::new(MobilityEvaluator),
+            EvaluatorKind::KingSafety => Box::new(KingSafetyEvaluator),
+            EvaluatorKind::Tempo => Box::new(TempoEvaluator),
+            EvaluatorKind::BishopPair => Box::new(BishopPairEvaluator),
+            EvaluatorKind::KnightOutpost => Box::new(KnightOutpostEvaluator),
+            EvaluatorKind::RookFile => Box::new(RookFileEvaluator),
+            EvaluatorKind::CentralControl => Box::new(CentralControlEvaluator),
+            EvaluatorKind::Threat => Box::new(ThreatEvaluator),
+            EvaluatorKind::LinePressure => Box::new(LinePressureEvaluator),
+            EvaluatorKind::Fork => Box::new(ForkEvaluator),
+            EvaluatorKind::Nnue => Box::new(NnueEvaluator::from_default_network()?),
+        })
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_weight() -> i32 {
+    1
+}
+
+/// A single evaluator entry in an `EvalConfig`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EvaluatorConfig {
+    pub kind: EvaluatorKind,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_weight")]
+    pub weight: i32,
+}
+
+/// Selects which evaluators the `Evaluator` runs and how heavily each one counts.
+///
+/// Stored as JSON so eval experiments don't require a recompile:
+///
+/// ```json
+/// {
+///   "evaluators": [
+///     { "kind": "material", "weight": 1 },
+///     { "kind": "mobility", "weight": 2 },
+///     { "kind": "threat", "enabled": false }
+///   ]
+/// }
+/// ```
+///
+/// Evaluators that are not listed are disabled.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EvalConfig {
+    pub evaluators: Vec<EvaluatorConfig>,
+}
+
+impl Default for EvalConfig {
+    /// Every built-in evaluator enabled with weight 1.
+    fn default() -> Self {
+        Self {
+            evaluators: EvaluatorKind::ALL
+                .iter()
+                .map(|&kind| EvaluatorConfig {
+                    kind,
+                    enabled: true,
+                    weight: 1,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl EvalConfig {
+    /// Returns the weight for an evaluator, or None if it is disabled or not listed.
+    pub fn weight_of(&self, kind: EvaluatorKind) -> Option<i32> {
+        self.evaluators
+            .iter()
+            .find(|entry| entry.kind == kind && entry.enabled)
+            .map(|entry| entry.weight)
+    }
+
+    /// Sets the weight for an evaluator, enabling it if needed.
+    pub fn set_weight(&mut self, kind: EvaluatorKind, weight: i32) {
+        match self.evaluators.iter_mut().find(|entry| entry.kind == kind) {
+            Some(entry) => {
+                entry.enabled = true;
+                entry.weight = weight;
+            }
+            None => self.evaluators.push(EvaluatorConfig {
+                kind,
+                enabled: true,
+                weight,
+            }),
+        }
+    }
+
+    /// Disables an evaluator.
+    pub fn disable(&mut self, kind: EvaluatorKind) {
+        for entry in self
+            .evaluators
+            .iter_mut()
+            .filter(|entry| entry.kind == kind)
+        {
+            entry.enabled = false;
+        }
+    }
+
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        let file = File::create(path)?;
+        let writer = BufWriter::new(file);
+        serde_json::to_writer_pretty(writer, self).map_err(std::io::Error::other)
+    }
+
+    /// Reads a config and checks that every enabled evaluator can be built,
+    /// so a missing NNUE network is reported here rather than mid-game.
+    pub fn load(path: &str) -> std::io::Result<Self> {
+        let file = File::open(path)?;
+        let reader = BufReader::new(file);
+        let config: Self = serde_json::from_reader(reader)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        config
+            .check()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        Ok(config)
+    }
+
+    /// Fails if an enabled evaluator can't be built (see `EvaluatorKind::build`).
+    pub fn check(&self) -> Result<(), String> {
+        for entry in self.evaluators.iter().filter(|entry| entry.enabled) {
+            entry.kind.build()?;
+        }
+        Ok(())
+    }
+
+    /// Parses a config from a JSON string.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| format!("Invalid eval config: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_enables_all_evaluators() {
+        let config = EvalConfig::default();
+        for kind in EvaluatorKind::ALL {
+            assert_eq!(config.weight_of(kind), Some(1));
+        }
+    }
+
+    #[test]
+    fn test_parse_config_with_defaults() {
+        let config = EvalConfig::from_json(
+            r#"{"evaluators": [
+                {"kind": "material"},
+                {"kind": "mobility", "weight": 3},
+                {"kind": "threat", "enabled": false}
+            ]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.weight_of(EvaluatorKind::Material), Some(1));
+        assert_eq!(config.weight_of(EvaluatorKind::Mobility), Some(3));
+        assert_eq!(config.weight_of(EvaluatorKind::Threat), None);
+        assert_eq!(config.weight_of(EvaluatorKind::Fork), None);
+    }
+
+    #[test]
+    fn test_unknown_evaluator_is_rejected() {
+        assert!(EvalConfig::from_json(r#"{"evaluators": [{"kind": "vibes"}]}"#).is_err());
+    }
+
+    #[test]
+    fn test_nnue_is_opt_in() {
+        assert_eq!(EvalConfig::default().weight_of(EvaluatorKind::Nnue), None);
+
+        let config = EvalConfig::from_json(r#"{"evaluators": [{"kind": "nnue"}]}"#).unwrap();
+        assert_eq!(config.weight_of(EvaluatorKind::Nnue), Some(1));
+    }
+
+    #[test]
+    fn test_missing_nnue_network_is_an_error() {
+        // Only checkable where no network file sits in the working directory
+        if std::path::Path::new(crate::eval::nnue::NNUE_BIN).exists() {
+            return;
+        }
+        let config = EvalConfig::from_json(r#"{"evaluators": [{"kind": "nnue"}]}"#).unwrap();
+
+        let error = config.check().unwrap_err();
+        assert!(error.contains("nnue.bin"), "{}", error);
+        assert!(crate::eval::Evaluator::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_config_round_trip() {
+        let mut config = EvalConfig::default();
+        config.set_weight(EvaluatorKind::KingSafety, 2);
+        config.disable(EvaluatorKind::Fork);
+
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(EvalConfig::from_json(&json).unwrap(), config);
+    }
+}
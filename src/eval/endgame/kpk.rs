@@ -0,0 +1,215 @@
+//! King and pawn versus king bitbase.
+//!
+//! Every KPK position is classified as a win or a draw by retrograde iteration the
+//! first time the table is used. Positions are normalized so that White has the
+//! pawn and the pawn stands on files a-d, which leaves 2 x 24 x 64 x 64 entries.
+
+use crate::attacks::{kings::KING_ATTACKS, pawns::WHITE_PAWN_ATTACKS};
+use crate::board::Color;
+use once_cell::sync::Lazy;
+
+/// Pawn squares on ranks 2-7, files a-d
+const PAWN_SQUARES: usize = 24;
+const TABLE_SIZE: usize = 2 * PAWN_SQUARES * 64 * 64;
+
+const INVALID: u8 = 0;
+const UNKNOWN: u8 = 1;
+const DRAW: u8 = 2;
+const WIN: u8 = 4;
+
+/// One bit per position: set when the pawn side wins
+static KPK_BITBASE: Lazy<Vec<u64>> = Lazy::new(generate);
+
+/// Whether the side with the pawn wins.
+///
+/// Squares are from White's point of view with White holding the pawn; callers
+/// with a black pawn flip the ranks (`sq ^ 56`) and the side to move first.
+pub fn probe(white_king: usize, pawn: usize, black_king: usize, side_to_move: Color) -> bool {
+    // The table only stores pawns on files a-d; mirror the rest
+    let mirror = if pawn % 8 > 3 { 7 } else { 0 };
+    let idx = index(
+        white_king ^ mirror,
+        pawn ^ mirror,
+        black_king ^ mirror,
+        side_to_move,
+    );
+    KPK_BITBASE[idx / 64] & (1 << (idx % 64)) != 0
+}
+
+fn index(white_king: usize, pawn: usize, black_king: usize, side_to_move: Color) -> usize {
+    let pawn_index = (pawn / 8 - 1) * 4 + pawn % 8;
+    ((pawn_index * 64 + white_king) * 64 + black_king) * 2 + side_to_move as usize
+}
+
+/// Decodes an index back into (white king, pawn, black king, side to move).
+fn decode(idx: usize) -> (usize, usize, usize, Color) {
+    let side_to_move = if idx.is_multiple_of(2) {
+        Color::White
+    } else {
+        Color::Black
+    };
+    let black_king = (idx / 2) % 64;
+    let white_king = (idx / 128) % 64;
+    let pawn_index = idx / 8192;
+    let pawn = (pawn_index / 4 + 1) * 8 + pawn_index % 4;
+    (white_king, pawn, black_king, side_to_move)
+}
+
+fn generate() -> Vec<u64> {
+    let mut table: Vec<u8> = (0..TABLE_SIZE).map(initial_result).collect();
+
+    // Keep resolving unknown positions from their successors until nothing changes
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for idx in 0..TABLE_SIZE {
+            if table[idx] == UNKNOWN {
+                let result = resolve(&table, idx);
+                if result != UNKNOWN {
+                    table[idx] = result;
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    let mut bits = vec![0u64; TABLE_SIZE / 64];
+    for (idx, &result) in table.iter().enumerate() {
+        if result == WIN {
+            bits[idx / 64] |= 1 << (idx % 64);
+        }
+    }
+    bits
+}
+
+/// Classifies the positions decided without looking ahead.
+fn initial_result(idx: usize) -> u8 {
+    let (white_king, pawn, black_king, side_to_move) = decode(idx);
+    let promotion = pawn + 8;
+
+    if king_distance(white_king, black_king) <= 1
+        || white_king == pawn
+        || black_king == pawn
+        || (side_to_move == Color::White && WHITE_PAWN_ATTACKS[pawn] & (1 << black_king) != 0)
+    {
+        return INVALID;
+    }
+
+    match side_to_move {
+        // A pawn on the seventh promotes safely unless the black king can take the new queen
+        Color::White
+            if pawn / 8 == 6
+                && white_king != promotion
+                && (king_distance(black_king, promotion) > 1
+                    || KING_ATTACKS[white_king] & (1 << promotion) != 0) =>
+        {
+            WIN
+        }
+        Color::Black => {
+            let guarded = KING_ATTACKS[white_king] | WHITE_PAWN_ATTACKS[pawn];
+            let stalemate = KING_ATTACKS[black_king] & !guarded == 0;
+            let takes_pawn =
+                KING_ATTACKS[black_king] & !KING_ATTACKS[white_king] & (1 << pawn) != 0;
+            if stalemate || takes_pawn {
+                DRAW
+            } else {
+                UNKNOWN
+            }
+        }
+        _ => UNKNOWN,
+    }
+}
+
+/// Combines the results of every move from an unknown position.
+fn resolve(table: &[u8], idx: usize) -> u8 {
+    let (white_king, pawn, black_king, side_to_move) = decode(idx);
+    let mut results = INVALID;
+
+    match side_to_move {
+        Color::White => {
+            let mut moves = KING_ATTACKS[white_king];
+            while moves != 0 {
+                let to = moves.trailing_zeros() as usize;
+                moves &= moves - 1;
+                results |= table[index(to, pawn, black_king, Color::Black)];
+            }
+            if pawn / 8 < 6 {
+                results |= table[index(white_king, pawn + 8, black_king, Color::Black)];
+            }
+            if pawn / 8 == 1 && pawn + 8 != white_king && pawn + 8 != black_king {
+                results |= table[index(white_king, pawn + 16, black_king, Color::Black)];
+            }
+            // White picks a win if there is one
+            if results & WIN != 0 {
+                WIN
+            } else if results & UNKNOWN != 0 {
+                UNKNOWN
+            } else {
+                DRAW
+            }
+        }
+        Color::Black => {
+            let mut moves = KING_ATTACKS[black_king];
+            while moves != 0 {
+                let to = moves.trailing_zeros() as usize;
+                moves &= moves - 1;
+                results |= table[index(white_king, pawn, to, Color::White)];
+            }
+            // Black picks a draw if there is one
+            if results & DRAW != 0 {
+                DRAW
+            } else if results & UNKNOWN != 0 {
+                UNKNOWN
+            } else {
+                WIN
+            }
+        }
+    }
+}
+
+fn king_distance(a: usize, b: usize) -> usize {
+    let files = (a % 8).abs_diff(b % 8);
+    let ranks = (a / 8).abs_diff(b / 8);
+    files.max(ranks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A1: usize = 0;
+    const E1: usize = 4;
+
+    fn sq(name: &str) -> usize {
+        let bytes = name.as_bytes();
+        (bytes[1] - b'1') as usize * 8 + (bytes[0] - b'a') as usize
+    }
+
+    #[test]
+    fn test_index_round_trips() {
+        for idx in [0, 1, 4097, TABLE_SIZE - 1] {
+            let (white_king, pawn, black_king, side_to_move) = decode(idx);
+            assert_eq!(index(white_king, pawn, black_king, side_to_move), idx);
+        }
+        assert_eq!(decode(index(E1, sq("d2"), A1, Color::Black)).1, sq("d2"));
+    }
+
+    #[test]
+    fn test_known_kpk_results() {
+        // Key square: king in front of the pawn wins with either side to move
+        assert!(probe(sq("e6"), sq("e5"), sq("e8"), Color::White));
+        assert!(probe(sq("e6"), sq("e5"), sq("e8"), Color::Black));
+
+        // Opposition decides when the king is in front of a pawn on the fourth rank
+        assert!(!probe(sq("e5"), sq("e4"), sq("e7"), Color::White));
+        assert!(probe(sq("e5"), sq("e4"), sq("e7"), Color::Black));
+
+        // Rook pawns draw when the defending king reaches the corner
+        assert!(!probe(sq("b6"), sq("a6"), sq("a8"), Color::White));
+        assert!(!probe(sq("g6"), sq("h5"), sq("h8"), Color::Black));
+
+        // Square rule: the black king is too far from the runaway pawn
+        assert!(probe(sq("a1"), sq("c5"), sq("h1"), Color::White));
+        assert!(!probe(sq("a1"), sq("c5"), sq("e6"), Color::White));
+    }
+}
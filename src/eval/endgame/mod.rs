@@ -0,0 +1,256 @@
+//! Specialized knowledge for endgames the general evaluation misjudges.
+//!
+//! [`adjust`] runs on the summed evaluation and replaces or scales it when the
+//! material matches a known pattern:
+//!
+//! - lone king against mating material: a known-win score that rewards driving the
+//!   defending king to the edge (or, for bishop and knight, the right corner)
+//! - lone king against too little material to mate: a draw
+//! - king and pawn against king: the result from the [`kpk`] bitbase
+//! - pawnless endgames where the stronger side is not a piece ahead, and
+//!   opposite-colored bishops: the score is scaled toward a draw
+
+pub mod kpk;
+
+use crate::board::{Board, Color, Piece};
+use crate::eval::material::PIECE_VALUES;
+
+/// Base score for endgames the stronger side wins by force
+pub const KNOWN_WIN: i32 = 10_000;
+
+const DARK_SQUARES: u64 = 0xAA55_AA55_AA55_AA55;
+
+/// Adjusts an evaluation (from White's perspective) using endgame knowledge.
+pub fn adjust(board: &Board, score: i32) -> i32 {
+    // Nothing to recognize while both sides still have several pieces
+    if board.occ_all.count_ones() > 8 {
+        return score;
+    }
+
+    for strong in [Color::White, Color::Black] {
+        let weak = strong.opponent();
+        if board.occ[weak as usize] == board.pieces_of(weak, Piece::King)
+            && let Some(result) = lone_king(board, strong)
+        {
+            return match strong {
+                Color::White => result,
+                Color::Black => -result,
+            };
+        }
+    }
+
+    let strong = if score >= 0 {
+        Color::White
+    } else {
+        Color::Black
+    };
+    score * scale_factor(board, strong) / SCALE_NORMAL
+}
+
+/// Score for `strong` when the other side has only its king, or None to keep the
+/// normal evaluation.
+fn lone_king(board: &Board, strong: Color) -> Option<i32> {
+    let weak = strong.opponent();
+    let count = |piece| board.count_pieces(strong, piece);
+    let pawns = count(Piece::Pawn);
+    let knights = count(Piece::Knight);
+    let bishops = board.pieces_of(strong, Piece::Bishop);
+    let heavy = count(Piece::Rook) + count(Piece::Queen);
+
+    let strong_king = board.king_sq[strong as usize] as usize;
+    let weak_king = board.king_sq[weak as usize] as usize;
+
+    if pawns == 1 && knights == 0 && bishops == 0 && heavy == 0 {
+        return Some(kpk_score(board, strong));
+    }
+    if pawns > 0 {
+        return None;
+    }
+
+    let material = non_pawn_material(board, strong);
+    let both_bishop_colors = bishops & DARK_SQUARES != 0 && bishops & !DARK_SQUARES != 0;
+    if heavy > 0 || both_bishop_colors || (knights > 0 && bishops != 0 && material > 650) {
+        let drive =
+            20 * edge_distance(weak_king) + 10 * (7 - king_distance(strong_king, weak_king));
+        return Some(KNOWN_WIN + material + drive);
+    }
+    if knights == 1 && bishops.count_ones() == 1 {
+        // Mate is only possible in a corner the bishop controls
+        let corners: [usize; 2] = if bishops & DARK_SQUARES != 0 {
+            [0, 63]
+        } else {
+            [7, 56]
+        };
+        let corner_distance = corners
+            .iter()
+            .map(|&corner| king_distance(weak_king, corner))
+            .min()
+            .unwrap_or(7);
+        let drive = 40 * (7 - corner_distance) + 10 * (7 - king_distance(strong_king, weak_king));
+        return Some(KNOWN_WIN + material + drive);
+    }
+
+    // A minor piece, two knights or same-colored bishops cannot force mate
+    Some(0)
+}
+
+fn kpk_score(board: &Board, strong: Color) -> i32 {
+    // The bitbase has White holding the pawn
+    let flip = |sq: usize| match strong {
+        Color::White => sq,
+        Color::Black => sq ^ 56,
+    };
+    let pawn = flip(board.pieces_of(strong, Piece::Pawn).trailing_zeros() as usize);
+    let strong_king = flip(board.king_sq[strong as usize] as usize);
+    let weak_king = flip(board.king_sq[strong.opponent() as usize] as usize);
+    let side_to_move = if board.side_to_move == strong {
+        Color::White
+    } else {
+        Color::Black
+    };
+
+    if kpk::probe(strong_king, pawn, weak_king, side_to_move) {
+        KNOWN_WIN + PIECE_VALUES[Piece::Pawn as usize] + 10 * (pawn / 8) as i32
+    } else {
+        0
+    }
+}
+
+/// Scale factors are out of this value
+const SCALE_NORMAL: i32 = 64;
+
+/// How much of the evaluation `strong` can expect to convert, out of `SCALE_NORMAL`.
+fn scale_factor(board: &Board, strong: Color) -> i32 {
+    let weak = strong.opponent();
+    let strong_material = non_pawn_material(board, strong);
+    let weak_material = non_pawn_material(board, weak);
+    let bishop = PIECE_VALUES[Piece::Bishop as usize];
+    let rook = PIECE_VALUES[Piece::Rook as usize];
+
+    // Without pawns, being at most a minor piece ahead is usually not enough to win
+    if board.pieces_of(strong, Piece::Pawn) == 0 && strong_material - weak_material <= bishop {
+        return if strong_material < rook {
+            0
+        } else if weak_material <= bishop {
+            4
+        } else {
+            14
+        };
+    }
+
+    let white_bishops = board.pieces_of(Color::White, Piece::Bishop);
+    let black_bishops = board.pieces_of(Color::Black, Piece::Bishop);
+    let opposite_bishops = white_bishops.count_ones() == 1
+        && black_bishops.count_ones() == 1
+        && (white_bishops & DARK_SQUARES != 0) != (black_bishops & DARK_SQUARES != 0);
+    if opposite_bishops {
+        // Pure bishop endings are the most drawish
+        return if strong_material == bishop && weak_material == bishop {
+            32
+        } else {
+            48
+        };
+    }
+
+    SCALE_NORMAL
+}
+
+fn non_pawn_material(board: &Board, color: Color) -> i32 {
+    [Piece::Knight, Piece::Bishop, Piece::Rook, Piece::Queen]
+        .iter()
+        .map(|&piece| board.count_pieces(color, piece) as i32 * PIECE_VALUES[piece as usize])
+        .sum()
+}
+
+/// How far a square is from the center: 0 in the middle four squares, 6 in a corner.
+fn edge_distance(sq: usize) -> i32 {
+    let file = sq % 8;
+    let rank = sq / 8;
+    let from_center = |x: usize| if x < 4 { 3 - x } else { x - 4 };
+    (from_center(file) + from_center(rank)) as i32
+}
+
+fn king_distance(a: usize, b: usize) -> i32 {
+    let files = (a % 8).abs_diff(b % 8);
+    let ranks = (a / 8).abs_diff(b / 8);
+    files.max(ranks) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::Evaluator;
+
+    /// Evaluation from White's perspective
+    fn evaluate(fen: &str) -> i32 {
+        let board = Board::from_fen(fen);
+        let score = Evaluator::new().evaluate(&board);
+        match board.side_to_move {
+            Color::White => score,
+            Color::Black => -score,
+        }
+    }
+
+    #[test]
+    fn test_known_wins() {
+        for fen in [
+            "8/8/8/4k3/8/8/8/R3K3 w - - 0 1",   // KR vs K
+            "8/8/8/4k3/8/8/8/3QK3 b - - 0 1",   // KQ vs K
+            "8/8/8/4k3/8/8/8/2B1KB2 w - - 0 1", // KBB vs K
+            "8/8/8/4k3/8/8/8/1NB1K3 w - - 0 1", // KBN vs K
+            "8/8/4k3/8/8/4K3/4P3/8 w - - 0 1",  // KPK, king in front of the pawn
+            "4k3/8/8/8/8/8/r7/5K2 b - - 0 1",   // KR vs K for Black
+            "8/4p3/8/4k3/8/8/8/4K3 b - - 0 1",  // KPK for Black
+        ] {
+            let board = Board::from_fen(fen);
+            let winner = if board.occ[Color::White as usize].count_ones() > 1 {
+                1
+            } else {
+                -1
+            };
+            assert!(evaluate(fen) * winner > KNOWN_WIN, "{}", fen);
+        }
+    }
+
+    #[test]
+    fn test_known_draws() {
+        for fen in [
+            "8/8/8/4k3/8/8/8/1N2K3 w - - 0 1",  // KN vs K
+            "8/8/8/4k3/8/8/8/2B1K3 w - - 0 1",  // KB vs K
+            "8/8/8/4k3/8/8/8/1N2KN2 w - - 0 1", // KNN vs K
+            "k7/8/1K6/P7/8/8/8/8 w - - 0 1",    // Rook pawn, defender in the corner
+            "8/4k3/8/4K3/4P3/8/8/8 w - - 0 1",  // KPK, defender has the opposition
+        ] {
+            assert_eq!(evaluate(fen), 0, "{}", fen);
+        }
+    }
+
+    #[test]
+    fn test_drawish_material_is_scaled_down() {
+        // KR vs KB: a rook up on material but a theoretical draw
+        let board = Board::from_fen("8/8/4kb2/8/8/8/8/R3K3 w - - 0 1");
+        let unscaled = 170;
+        assert!(adjust(&board, unscaled) < unscaled / 8);
+
+        // Opposite-colored bishops with an extra pawn
+        let board = Board::from_fen("8/5k2/8/3b4/8/2B5/4PP2/5K2 w - - 0 1");
+        assert_eq!(adjust(&board, 200), 100);
+
+        // Same-colored bishops are left alone
+        let board = Board::from_fen("8/5k2/8/4b3/8/2B5/4PP2/5K2 w - - 0 1");
+        assert_eq!(adjust(&board, 200), 200);
+    }
+
+    #[test]
+    fn test_mating_scores_drive_the_king_to_the_corner() {
+        // KBN with a dark-squared bishop: the h8 corner beats the light a8 corner
+        let right_corner = evaluate("7k/8/5K2/8/8/8/8/2B1N3 w - - 0 1");
+        let wrong_corner = evaluate("k7/8/2K5/8/8/8/8/2B1N3 w - - 0 1");
+        assert!(right_corner > wrong_corner);
+
+        // KR vs K: the edge is better than the center
+        let edge = evaluate("4k3/8/4K3/8/8/8/8/R7 w - - 0 1");
+        let center = evaluate("8/8/8/4k3/8/2K5/8/R7 w - - 0 1");
+        assert!(edge > center);
+    }
+}
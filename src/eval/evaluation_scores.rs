@@ -1,4 +1,6 @@
-#[derive(Debug, Clone)]
+use crate::eval::EvaluatorKind;
+
+#[derive(Debug, Clone, Default)]
 pub struct EvaluationScores {
     pub material: i32,
     pub position: i32,
@@ -13,9 +15,32 @@ pub struct EvaluationScores {
     pub threat: i32,
     pub line_pressure: i32,
     pub fork: i32,
+    pub nnue: i32,
     pub total: i32,
 }
 
+impl EvaluationScores {
+    /// Returns the field holding the score of the given evaluator.
+    pub fn component_mut(&mut self, kind: EvaluatorKind) -> &mut i32 {
+        match kind {
+            EvaluatorKind::Material => &mut self.material,
+            EvaluatorKind::Position => &mut self.position,
+            EvaluatorKind::PawnStructure => &mut self.pawn_structure,
+            EvaluatorKind::Mobility => &mut self.mobility,
+            EvaluatorKind::KingSafety => &mut self.king_safety,
+            EvaluatorKind::Tempo => &mut self.tempo,
+            EvaluatorKind::BishopPair => &mut self.bishop_pair,
+            EvaluatorKind::KnightOutpost => &mut self.knight_outpost,
+            EvaluatorKind::RookFile => &mut self.rook_file,
+            EvaluatorKind::CentralControl => &mut self.central_control,
+            EvaluatorKind::Threat => &mut self.threat,
+            EvaluatorKind::LinePressure => &mut self.line_pressure,
+            EvaluatorKind::Fork => &mut self.fork,
+            EvaluatorKind::Nnue => &mut self.nnue,
+        }
+    }
+}
+
 impl std::fmt::Display for EvaluationScores {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "  Material:        {:+5} cp", self.material)?;
@@ -31,6 +56,9 @@ impl std::fmt::Display for EvaluationScores {
         writeln!(f, "  Threats:         {:+5} cp", self.threat)?;
         writeln!(f, "  Line Pressure:   {:+5} cp", self.line_pressure)?;
         writeln!(f, "  Fork Potential:  {:+5} cp", self.fork)?;
+        if self.nnue != 0 {
+            writeln!(f, "  NNUE:            {:+5} cp", self.nnue)?;
+        }
         writeln!(f, "  ─────────────────────────")?;
         write!(f, "  TOTAL:           {:+5} cp", self.total)
     }
@@ -1,19 +1,22 @@
 use crate::{
     board::{Board, Color},
     eval::{
-        EvaluationScores, bishop_pair::BishopPairEvaluator,
-        central_control::CentralControlEvaluator, fork::ForkEvaluator,
-        king_safety::KingSafetyEvaluator, knight_outpost::KnightOutpostEvaluator,
-        line_pressure::LinePressureEvaluator, material::MaterialEvaluator,
-        mobility::MobilityEvaluator, pawn_structure::PawnStructureEvaluator,
-        position::PositionEvaluator, rook_file_evaluator::RookFileEvaluator, tempo::TempoEvaluator,
-        threat::ThreatEvaluator,
+        EvalConfig, EvalParams, EvaluationScores, EvaluatorKind, endgame,
+        material::MaterialEvaluator,
+        nnue::{NnueAccumulator, NnueEvaluator},
+        position::PositionEvaluator,
     },
 };
 
-pub trait BoardEvaluator {
+pub trait BoardEvaluator: Send + Sync {
     /// Returns the evaluation score from White's perspective
     fn evaluate(&self, board: &Board) -> i32;
+
+    /// Returns the evaluation score from White's perspective using the given
+    /// tunable parameters. Evaluators without tunable constants ignore them.
+    fn evaluate_with_params(&self, board: &Board, _params: &EvalParams) -> i32 {
+        self.evaluate(board)
+    }
 }
 
 /// Evaluates a chess board position to guide the minimax search algorithm.
@@ -35,9 +38,22 @@ pub trait BoardEvaluator {
 /// - **Knight Outpost**: Rewards knights on squares safe from enemy pawns and advanced into the enemy territory.
 /// - **Threat**: Penalizes hanging pieces and pieces attacked by lower-value pieces (e.g., pawns).
 ///
+/// Which sub-evaluators run, and their weights, can be configured with an `EvalConfig`.
+///
+/// The weighted sum is then adjusted by the `endgame` module, which recognizes basic
+/// mates, KPK and drawish material.
+///
 /// Positive scores favor White; negative scores favor Black.
 pub struct Evaluator {
-    evaluators: Vec<(Box<dyn BoardEvaluator>, i32)>, // evaluator + weight
+    evaluators: Vec<(EvaluatorKind, Box<dyn BoardEvaluator>, i32)>, // kind + evaluator + weight
+    params: EvalParams,
+    // Material and PST come from the board's accumulator when using the default tables
+    incremental: bool,
+    /// Set when NNUE is enabled, to score the accumulator the search keeps in step
+    nnue: Option<NnueEvaluator>,
+    /// Largest random offset added to evaluations, in centipawns (0 = none)
+    noise: i32,
+    noise_seed: u64,
 }
 
 impl Default for Evaluator {
@@ -47,34 +63,129 @@ impl Default for Evaluator {
 }
 
 impl Evaluator {
+    /// Creates an evaluator with every built-in evaluator enabled at weight 1.
     pub fn new() -> Self {
-        let evaluators: Vec<(Box<dyn BoardEvaluator>, i32)> = vec![
-            (Box::new(MaterialEvaluator), 1),
-            (Box::new(PositionEvaluator), 1),
-            (Box::new(PawnStructureEvaluator), 1),
-            (Box::new(MobilityEvaluator), 1),
-            (Box::new(KingSafetyEvaluator), 1),
-            (Box::new(TempoEvaluator), 1),
-            (Box::new(BishopPairEvaluator), 1),
-            (Box::new(KnightOutpostEvaluator), 1),
-            (Box::new(RookFileEvaluator), 1),
-            (Box::new(CentralControlEvaluator), 1),
-            (Box::new(ThreatEvaluator), 1),
-            (Box::new(LinePressureEvaluator), 1),
-            (Box::new(ForkEvaluator), 1),
-        ];
-
-        Self { evaluators }
+        // The default config has only hand-crafted evaluators, which load no files
+        Self::from_config(&EvalConfig::default()).expect("hand-crafted evaluators always build")
+    }
+
+    /// Creates an evaluator running only the evaluators enabled in `config`.
+    /// Fails if one of them can't be built, e.g. NNUE without its network file.
+    pub fn from_config(config: &EvalConfig) -> Result<Self, String> {
+        let evaluators = config
+            .evaluators
+            .iter()
+            .filter(|entry| entry.enabled)
+            .map(|entry| Ok((entry.kind, entry.kind.build()?, entry.weight)))
+            .collect::<Result<_, String>>()?;
+        let nnue = match config.weight_of(EvaluatorKind::Nnue) {
+            Some(_) => Some(NnueEvaluator::from_default_network()?),
+            None => None,
+        };
+
+        Ok(Self {
+            evaluators,
+            params: EvalParams::DEFAULT,
+            incremental: true,
+            nnue,
+            noise: 0,
+            noise_seed: 0,
+        })
+    }
+
+    /// Replaces the tunable evaluation constants (e.g. a file written by the `tune` binary).
+    pub fn with_params(mut self, params: EvalParams) -> Self {
+        self.set_params(params);
+        self
+    }
+
+    pub fn set_params(&mut self, params: EvalParams) {
+        // The board accumulator is built from the default tables
+        self.incremental = params.piece_values == EvalParams::DEFAULT.piece_values
+            && params.piece_square_tables == EvalParams::DEFAULT.piece_square_tables;
+        self.params = params;
+    }
+
+    pub fn params(&self) -> &EvalParams {
+        &self.params
+    }
+
+    /// Adds a pseudo-random offset of up to `amplitude` centipawns to every evaluation,
+    /// to weaken play on purpose. The offset depends only on the position and `seed`, so
+    /// a position always gets the same score and cached search results stay consistent.
+    pub fn set_noise(&mut self, amplitude: i32, seed: u64) {
+        self.noise = amplitude.max(0);
+        self.noise_seed = seed;
+    }
+
+    /// The noise amplitude and seed set with `set_noise`.
+    pub fn noise(&self) -> (i32, u64) {
+        (self.noise, self.noise_seed)
+    }
+
+    fn noise_for(&self, board: &Board) -> i32 {
+        // SplitMix64 finalizer spreads nearby hashes over the whole range
+        let mut z = board.hash ^ self.noise_seed;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z % (2 * self.noise as u64 + 1)) as i32 - self.noise
     }
 
     pub fn evaluate(&self, board: &Board) -> i32 {
+        self.evaluate_with_accumulator(board, None)
+    }
+
+    /// The NNUE accumulator of `board`, or None when NNUE is disabled. The search keeps
+    /// it in step with its moves through `update_accumulator` instead of rebuilding it.
+    pub fn new_accumulator(&self, board: &Board) -> Option<NnueAccumulator> {
+        self.nnue
+            .as_ref()
+            .map(|nnue| NnueAccumulator::new(nnue.network(), board))
+    }
+
+    /// Brings an accumulator from `new_accumulator` up to date after moving from
+    /// `before` to `after` (or back).
+    pub fn update_accumulator(
+        &self,
+        accumulator: &mut Option<NnueAccumulator>,
+        before: &Board,
+        after: &Board,
+    ) {
+        if let (Some(nnue), Some(accumulator)) = (&self.nnue, accumulator) {
+            accumulator.update(nnue.network(), before, after);
+        }
+    }
+
+    /// Like `evaluate`, scoring NNUE from `accumulator` when one is given instead of
+    /// building it from scratch.
+    pub fn evaluate_with_accumulator(
+        &self,
+        board: &Board,
+        accumulator: Option<&NnueAccumulator>,
+    ) -> i32 {
         // Sum weighted evaluator scores
         let mut total: i32 = 0;
 
-        for (evaluator, weight) in &self.evaluators {
-            let score: i32 = evaluator.evaluate(board);
+        for (kind, evaluator, weight) in &self.evaluators {
+            let score: i32 = match (kind, self.nnue.as_ref().zip(accumulator)) {
+                (EvaluatorKind::Material, _) if self.incremental => {
+                    MaterialEvaluator::evaluate_incremental(board)
+                }
+                (EvaluatorKind::Position, _) if self.incremental => {
+                    PositionEvaluator::evaluate_incremental(board)
+                }
+                (EvaluatorKind::Nnue, Some((nnue, accumulator))) => {
+                    nnue.evaluate_accumulator(board, accumulator)
+                }
+                _ => evaluator.evaluate_with_params(board, &self.params),
+            };
             total += score * weight;
         }
+        let mut total = endgame::adjust(board, total);
+        if self.noise > 0 {
+            total += self.noise_for(board);
+        }
 
         // Adjust for side to move
         match board.side_to_move {
@@ -83,52 +194,166 @@ impl Evaluator {
         }
     }
 
+    /// Returns the weighted score of each enabled evaluator, in evaluation order.
+    /// Scores are from White's perspective.
+    pub fn evaluate_components(&self, board: &Board) -> Vec<(EvaluatorKind, i32)> {
+        self.evaluators
+            .iter()
+            .map(|(kind, evaluator, weight)| {
+                (
+                    *kind,
+                    evaluator.evaluate_with_params(board, &self.params) * weight,
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the weighted score of a single evaluator from White's perspective,
+    /// or 0 if it is disabled.
+    pub fn evaluate_component(&self, board: &Board, kind: EvaluatorKind) -> i32 {
+        self.evaluators
+            .iter()
+            .filter(|(k, _, _)| *k == kind)
+            .map(|(_, evaluator, weight)| {
+                evaluator.evaluate_with_params(board, &self.params) * weight
+            })
+            .sum()
+    }
+
     /// Returns a detailed breakdown of all evaluation components.
-    /// All scores are from White's perspective (positive = White advantage).
+    /// All scores are weighted and from White's perspective (positive = White advantage).
+    /// Disabled evaluators report 0. The total includes endgame adjustments, so in
+    /// recognized endgames it differs from the sum of the components.
     pub fn evaluate_detailed(&self, board: &Board) -> EvaluationScores {
-        let material = MaterialEvaluator.evaluate(board);
-        let position = PositionEvaluator.evaluate(board);
-        let pawn_structure = PawnStructureEvaluator.evaluate(board);
-        let mobility = MobilityEvaluator.evaluate(board);
-        let king_safety = KingSafetyEvaluator.evaluate(board);
-        let tempo = TempoEvaluator.evaluate(board);
-        let bishop_pair = BishopPairEvaluator.evaluate(board);
-        let knight_outpost = KnightOutpostEvaluator.evaluate(board);
-        let rook_file = RookFileEvaluator.evaluate(board);
-        let central_control = CentralControlEvaluator.evaluate(board);
-        let threat = ThreatEvaluator.evaluate(board);
-        let line_pressure = LinePressureEvaluator.evaluate(board);
-        let fork = ForkEvaluator.evaluate(board);
-
-        let total = material
-            + position
-            + pawn_structure
-            + mobility
-            + king_safety
-            + tempo
-            + bishop_pair
-            + knight_outpost
-            + rook_file
-            + central_control
-            + threat
-            + line_pressure
-            + fork;
-
-        EvaluationScores {
-            material,
-            position,
-            pawn_structure,
-            mobility,
-            king_safety,
-            tempo,
-            bishop_pair,
-            knight_outpost,
-            rook_file,
-            central_control,
-            threat,
-            line_pressure,
-            fork,
-            total,
+        let mut scores = EvaluationScores::default();
+
+        for (kind, score) in self.evaluate_components(board) {
+            *scores.component_mut(kind) += score;
+            scores.total += score;
         }
+        scores.total = endgame::adjust(board, scores.total);
+
+        scores
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::nnue::NnueNetwork;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_default_evaluator_matches_detailed_total() {
+        let board =
+            Board::from_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3");
+        let evaluator = Evaluator::new();
+
+        assert_eq!(
+            evaluator.evaluate(&board),
+            evaluator.evaluate_detailed(&board).total
+        );
+    }
+
+    #[test]
+    fn test_detailed_scores_respect_weights() {
+        // White has a rook against a pawn (a lone king would be an endgame special case)
+        let board = Board::from_fen("4k3/p7/8/8/8/8/8/R3K3 w - - 0 1");
+
+        let mut config = EvalConfig::default();
+        config.set_weight(EvaluatorKind::Material, 3);
+        config.disable(EvaluatorKind::Mobility);
+
+        let baseline = Evaluator::new().evaluate_detailed(&board);
+        let weighted = Evaluator::from_config(&config)
+            .unwrap()
+            .evaluate_detailed(&board);
+
+        assert_eq!(weighted.material, baseline.material * 3);
+        assert_eq!(weighted.mobility, 0);
+        assert_eq!(
+            weighted.total,
+            baseline.total + 2 * baseline.material - baseline.mobility
+        );
+    }
+
+    #[test]
+    fn test_params_change_evaluation() {
+        // White is up a knight
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/1N2K3 w - - 0 1");
+
+        let mut params = EvalParams::default();
+        params.piece_values[1] = 400;
+        let evaluator = Evaluator::new().with_params(params);
+
+        assert_eq!(evaluator.evaluate_detailed(&board).material, 400);
+        assert_eq!(
+            evaluator.evaluate_component(&board, EvaluatorKind::Material),
+            400
+        );
+    }
+
+    #[test]
+    fn test_custom_params_bypass_accumulator() {
+        // White is up a knight, with pawns so the endgame scaling stays out of the way
+        let board = Board::from_fen("4k3/p7/8/8/8/8/P7/1N2K3 w - - 0 1");
+
+        let mut params = EvalParams::default();
+        params.piece_values[1] = 400;
+        let tuned = Evaluator::new().with_params(params);
+        let default = Evaluator::new();
+
+        assert_eq!(
+            tuned.evaluate(&board),
+            tuned.evaluate_detailed(&board).total
+        );
+        assert_eq!(tuned.evaluate(&board) - default.evaluate(&board), 80);
+    }
+
+    #[test]
+    fn test_updated_accumulator_scores_like_a_fresh_one() {
+        let network = Arc::new(NnueNetwork::random(16, 5));
+        let evaluator = Evaluator {
+            evaluators: vec![(
+                EvaluatorKind::Nnue,
+                Box::new(NnueEvaluator::new(Arc::clone(&network))),
+                1,
+            )],
+            params: EvalParams::DEFAULT,
+            incremental: true,
+            nnue: Some(NnueEvaluator::new(network)),
+            noise: 0,
+            noise_seed: 0,
+        };
+
+        // Siblings, a capture, a king move and castling, each taken back as in a search
+        let root =
+            Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1");
+        let mut accumulator = evaluator.new_accumulator(&root);
+        let mut moves = Vec::new();
+        root.generate_moves(&mut moves);
+        for uci in ["e5f7", "e5g6", "e2a6", "e1g1", "e1d1", "f3h3"] {
+            let mv = moves.iter().find(|mv| mv.to_uci() == uci).unwrap();
+            let mut board = root;
+            board.make_move(*mv);
+
+            evaluator.update_accumulator(&mut accumulator, &root, &board);
+            assert_eq!(
+                evaluator.evaluate_with_accumulator(&board, accumulator.as_ref()),
+                evaluator.evaluate(&board),
+                "{}",
+                uci
+            );
+            evaluator.update_accumulator(&mut accumulator, &board, &root);
+        }
+        assert_eq!(accumulator, evaluator.new_accumulator(&root));
+    }
+
+    #[test]
+    fn test_empty_config_evaluates_to_zero() {
+        let board = Board::startpos();
+        let evaluator = Evaluator::from_config(&EvalConfig { evaluators: vec![] }).unwrap();
+
+        assert_eq!(evaluator.evaluate(&board), 0);
     }
 }
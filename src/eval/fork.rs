@@ -1,16 +1,16 @@
 use crate::{
     board::{Board, Color, Piece},
-    eval::evaluator::BoardEvaluator,
+    eval::{EvalParams, evaluator::BoardEvaluator},
 };
 
 /// Base bonus for creating a fork (attacking 2+ pieces)
-const FORK_BASE_BONUS: i32 = 15;
+pub(crate) const FORK_BASE_BONUS: i32 = 15;
 
 /// Bonus multiplier based on the value of forked pieces
-const FORK_VALUE_SCALE: i32 = 30;
+pub(crate) const FORK_VALUE_SCALE: i32 = 30;
 
 /// Extra bonus when forking with a lower-value piece
-const FORK_ATTACKER_BONUS: i32 = 10;
+pub(crate) const FORK_ATTACKER_BONUS: i32 = 10;
 
 /// ForkEvaluator detects forks where a single piece attacks two or more enemy pieces.
 /// Forks are powerful tactical motifs that can win material since the opponent
@@ -32,7 +32,7 @@ impl ForkEvaluator {
     }
 
     /// Evaluate fork potential for one side
-    fn evaluate_side(board: &Board, color: Color) -> i32 {
+    fn evaluate_side(board: &Board, color: Color, params: &EvalParams) -> i32 {
         let mut score = 0;
         let enemy = color.opponent();
         let enemy_pieces = board.occ[enemy as usize];
@@ -63,7 +63,8 @@ impl ForkEvaluator {
 
                 // Fork requires attacking 2 or more pieces
                 if attack_count >= 2 {
-                    score += Self::score_fork(board, attacked_enemies, attacker_value, enemy);
+                    score +=
+                        Self::score_fork(board, attacked_enemies, attacker_value, enemy, params);
                 }
 
                 pieces_bb &= pieces_bb - 1;
@@ -75,8 +76,14 @@ impl ForkEvaluator {
 
     /// Calculate the score for a fork based on the attacked pieces
     #[inline]
-    fn score_fork(board: &Board, attacked_enemies: u64, attacker_value: i32, enemy: Color) -> i32 {
-        let mut fork_score = FORK_BASE_BONUS;
+    fn score_fork(
+        board: &Board,
+        attacked_enemies: u64,
+        attacker_value: i32,
+        enemy: Color,
+        params: &EvalParams,
+    ) -> i32 {
+        let mut fork_score = params.fork_base_bonus;
         let mut highest_value = 0;
         let mut second_highest_value = 0;
 
@@ -106,11 +113,11 @@ impl ForkEvaluator {
         // Scale bonus based on the two most valuable attacked pieces
         // The opponent can only save one, so we score based on what can be won
         let winnable_value = second_highest_value;
-        fork_score += (winnable_value * FORK_VALUE_SCALE) / 900;
+        fork_score += (winnable_value * params.fork_value_scale) / 900;
 
         // Extra bonus when a lower-value piece forks higher-value pieces
         if attacker_value < highest_value && attacker_value < second_highest_value {
-            fork_score += FORK_ATTACKER_BONUS;
+            fork_score += params.fork_attacker_bonus;
         }
 
         // Additional bonus for attacking more pieces (triple forks, etc.)
@@ -138,8 +145,12 @@ impl ForkEvaluator {
 
 impl BoardEvaluator for ForkEvaluator {
     fn evaluate(&self, board: &Board) -> i32 {
-        let white_score = Self::evaluate_side(board, Color::White);
-        let black_score = Self::evaluate_side(board, Color::Black);
+        self.evaluate_with_params(board, &EvalParams::DEFAULT)
+    }
+
+    fn evaluate_with_params(&self, board: &Board, params: &EvalParams) -> i32 {
+        let white_score = Self::evaluate_side(board, Color::White, params);
+        let black_score = Self::evaluate_side(board, Color::Black, params);
         white_score - black_score
     }
 }
@@ -1,14 +1,25 @@
 use crate::{
     board::{Board, Color, Piece},
-    eval::evaluator::BoardEvaluator,
+    eval::{EvalParams, evaluator::BoardEvaluator},
 };
 
+pub(crate) const CASTLED_BONUS: i32 = 10;
+pub(crate) const PAWN_SHIELD_WEIGHT: i32 = 4;
+pub(crate) const OPEN_FILE_PENALTY: i32 = 5;
+pub(crate) const ENEMY_PROXIMITY_PENALTY: i32 = 2;
+/// King zone attacker weights for pawn, knight, bishop, rook and queen
+pub(crate) const KING_ZONE_ATTACKER_WEIGHTS: [i32; 5] = [10, 30, 30, 50, 90];
+
 pub struct KingSafetyEvaluator;
 
 impl BoardEvaluator for KingSafetyEvaluator {
     fn evaluate(&self, board: &Board) -> i32 {
-        let white_king_safety: i32 = Self::king_safety(board, Color::White);
-        let black_king_safety: i32 = Self::king_safety(board, Color::Black);
+        self.evaluate_with_params(board, &EvalParams::DEFAULT)
+    }
+
+    fn evaluate_with_params(&self, board: &Board, params: &EvalParams) -> i32 {
+        let white_king_safety: i32 = Self::king_safety(board, Color::White, params);
+        let black_king_safety: i32 = Self::king_safety(board, Color::Black, params);
 
         white_king_safety - black_king_safety
     }
@@ -16,27 +27,27 @@ impl BoardEvaluator for KingSafetyEvaluator {
 
 impl KingSafetyEvaluator {
     #[inline]
-    fn king_safety(board: &Board, color: Color) -> i32 {
+    fn king_safety(board: &Board, color: Color, params: &EvalParams) -> i32 {
         let king_pos: u8 = board.king_square(color);
 
         let mut score: i32 = 0;
 
         // 1. Castling bonus
         if board.has_castled(color) {
-            score += 10;
+            score += params.castled_bonus;
         }
 
         // 2. Pawn shield
-        score += Self::pawn_shield(board, color, king_pos) * 4;
+        score += Self::pawn_shield(board, color, king_pos) * params.pawn_shield_weight;
 
         // 3. Open files next to king
-        score -= Self::open_file_penalty(board, king_pos) * 5;
+        score -= Self::open_file_penalty(board, king_pos) * params.king_open_file_penalty;
 
         // 4. Enemy proximity
-        score -= Self::enemy_piece_pressure(board, color, king_pos) * 2;
+        score -= Self::enemy_piece_pressure(board, color, king_pos) * params.king_proximity_penalty;
 
         // 5. Enemy attack pressure
-        score -= Self::attackers_to_king_zone(board, color, king_pos);
+        score -= Self::attackers_to_king_zone(board, color, king_pos, params);
 
         score
     }
@@ -135,7 +146,12 @@ impl KingSafetyEvaluator {
     }
 
     #[inline]
-    fn attackers_to_king_zone(board: &Board, color: Color, king_sq: u8) -> i32 {
+    fn attackers_to_king_zone(
+        board: &Board,
+        color: Color,
+        king_sq: u8,
+        params: &EvalParams,
+    ) -> i32 {
         let enemy: Color = color.opponent();
         let king_file = (king_sq % 8) as i32;
         let king_rank = (king_sq / 8) as i32;
@@ -163,12 +179,8 @@ impl KingSafetyEvaluator {
             let attacker_sq = all_attackers.trailing_zeros() as u8;
             if let Some((_, p)) = board.piece_on(attacker_sq) {
                 score += match p {
-                    Piece::Pawn => 10,
-                    Piece::Knight => 30,
-                    Piece::Bishop => 30,
-                    Piece::Rook => 50,
-                    Piece::Queen => 90,
-                    _ => 0,
+                    Piece::King => 0,
+                    _ => params.king_zone_attacker_weights[p as usize],
                 };
             }
             all_attackers &= all_attackers - 1;
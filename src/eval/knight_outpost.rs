@@ -1,22 +1,26 @@
 use crate::{
     board::{Board, Color, Piece},
-    eval::evaluator::BoardEvaluator,
+    eval::{EvalParams, evaluator::BoardEvaluator},
 };
 
-const OUTPOST_BONUS: i32 = 20; // base outpost value
-const SUPPORTED_BONUS: i32 = 10; // extra if supported by pawn
+pub(crate) const OUTPOST_BONUS: i32 = 20; // base outpost value
+pub(crate) const SUPPORTED_BONUS: i32 = 10; // extra if supported by pawn
 
 pub struct KnightOutpostEvaluator;
 
 impl BoardEvaluator for KnightOutpostEvaluator {
     fn evaluate(&self, board: &Board) -> i32 {
+        self.evaluate_with_params(board, &EvalParams::DEFAULT)
+    }
+
+    fn evaluate_with_params(&self, board: &Board, params: &EvalParams) -> i32 {
         let mut score = 0;
 
         // Iterate through white knights
         let mut white_knights = board.pieces[Color::White as usize][Piece::Knight as usize];
         while white_knights != 0 {
             let sq = white_knights.trailing_zeros() as usize;
-            score += Self::evaluate_knight(board, sq, Color::White);
+            score += Self::evaluate_knight(board, sq, Color::White, params);
             white_knights &= white_knights - 1;
         }
 
@@ -24,7 +28,7 @@ impl BoardEvaluator for KnightOutpostEvaluator {
         let mut black_knights = board.pieces[Color::Black as usize][Piece::Knight as usize];
         while black_knights != 0 {
             let sq = black_knights.trailing_zeros() as usize;
-            score += Self::evaluate_knight(board, sq, Color::Black);
+            score += Self::evaluate_knight(board, sq, Color::Black, params);
             black_knights &= black_knights - 1;
         }
 
@@ -33,16 +37,16 @@ impl BoardEvaluator for KnightOutpostEvaluator {
 }
 
 impl KnightOutpostEvaluator {
-    fn evaluate_knight(board: &Board, sq: usize, color: Color) -> i32 {
+    fn evaluate_knight(board: &Board, sq: usize, color: Color, params: &EvalParams) -> i32 {
         if !Self::is_outpost(board, sq, color) {
             return 0;
         }
 
-        let mut score = OUTPOST_BONUS;
+        let mut score = params.knight_outpost_bonus;
 
         // Add support bonus if friendly pawn defends the square
         if Self::is_supported_by_pawn(board, sq, color) {
-            score += SUPPORTED_BONUS;
+            score += params.knight_outpost_supported_bonus;
         }
 
         // White gets positive, Black gets negative
@@ -1,6 +1,6 @@
 use crate::{
     board::{Board, Color, Piece},
-    eval::evaluator::BoardEvaluator,
+    eval::{EvalParams, evaluator::BoardEvaluator},
 };
 
 /// Direction indices for ray masks
@@ -127,20 +127,20 @@ const ROOK_DIRS: [usize; 4] = [NORTH, SOUTH, EAST, WEST];
 const BISHOP_DIRS: [usize; 4] = [NORTH_EAST, NORTH_WEST, SOUTH_EAST, SOUTH_WEST];
 
 /// Scoring constants for line-based tactical patterns
-const ABSOLUTE_PIN_BASE: i32 = 40;
-const ABSOLUTE_PIN_QUEEN: i32 = 100;
-const ABSOLUTE_PIN_ROOK: i32 = 70;
-const ABSOLUTE_PIN_BISHOP: i32 = 60;
-const ABSOLUTE_PIN_KNIGHT: i32 = 60;
-const ABSOLUTE_PIN_PAWN: i32 = 40;
+pub(crate) const ABSOLUTE_PIN_BASE: i32 = 40;
+pub(crate) const ABSOLUTE_PIN_QUEEN: i32 = 100;
+pub(crate) const ABSOLUTE_PIN_ROOK: i32 = 70;
+pub(crate) const ABSOLUTE_PIN_BISHOP: i32 = 60;
+pub(crate) const ABSOLUTE_PIN_KNIGHT: i32 = 60;
+pub(crate) const ABSOLUTE_PIN_PAWN: i32 = 40;
 
-const RELATIVE_PIN_BASE: i32 = 20;
-const RELATIVE_PIN_MAX: i32 = 40;
+pub(crate) const RELATIVE_PIN_BASE: i32 = 20;
+pub(crate) const RELATIVE_PIN_MAX: i32 = 40;
 
-const XRAY_BONUS: i32 = 15;
-const DISCOVERED_ATTACK_BASE: i32 = 20;
-const DISCOVERED_ATTACK_MAX: i32 = 50;
-const SKEWER_BONUS: i32 = 15;
+pub(crate) const XRAY_BONUS: i32 = 15;
+pub(crate) const DISCOVERED_ATTACK_BASE: i32 = 20;
+pub(crate) const DISCOVERED_ATTACK_MAX: i32 = 50;
+pub(crate) const SKEWER_BONUS: i32 = 15;
 
 /// Context for ray evaluation to reduce function argument count
 struct RayEvalContext {
@@ -173,7 +173,7 @@ impl LinePressureEvaluator {
     }
 
     /// Evaluate line pressure for one side's sliding pieces
-    fn evaluate_side(board: &Board, color: Color) -> i32 {
+    fn evaluate_side(board: &Board, color: Color, params: &EvalParams) -> i32 {
         let mut score = 0;
         let enemy = color.opponent();
         let enemy_king_sq = board.king_sq[enemy as usize];
@@ -189,7 +189,7 @@ impl LinePressureEvaluator {
         let mut rooks = board.pieces[color as usize][Piece::Rook as usize];
         while rooks != 0 {
             let sq = rooks.trailing_zeros() as usize;
-            score += Self::evaluate_piece_rays(board, sq, &ROOK_DIRS, &ctx);
+            score += Self::evaluate_piece_rays(board, sq, &ROOK_DIRS, &ctx, params);
             rooks &= rooks - 1;
         }
 
@@ -197,7 +197,7 @@ impl LinePressureEvaluator {
         let mut bishops = board.pieces[color as usize][Piece::Bishop as usize];
         while bishops != 0 {
             let sq = bishops.trailing_zeros() as usize;
-            score += Self::evaluate_piece_rays(board, sq, &BISHOP_DIRS, &ctx);
+            score += Self::evaluate_piece_rays(board, sq, &BISHOP_DIRS, &ctx, params);
             bishops &= bishops - 1;
         }
 
@@ -205,8 +205,8 @@ impl LinePressureEvaluator {
         let mut queens = board.pieces[color as usize][Piece::Queen as usize];
         while queens != 0 {
             let sq = queens.trailing_zeros() as usize;
-            score += Self::evaluate_piece_rays(board, sq, &ROOK_DIRS, &ctx);
-            score += Self::evaluate_piece_rays(board, sq, &BISHOP_DIRS, &ctx);
+            score += Self::evaluate_piece_rays(board, sq, &ROOK_DIRS, &ctx, params);
+            score += Self::evaluate_piece_rays(board, sq, &BISHOP_DIRS, &ctx, params);
             queens &= queens - 1;
         }
 
@@ -220,6 +220,7 @@ impl LinePressureEvaluator {
         sq: usize,
         directions: &[usize],
         ctx: &RayEvalContext,
+        params: &EvalParams,
     ) -> i32 {
         let mut score = 0;
 
@@ -261,26 +262,27 @@ impl LinePressureEvaluator {
                 if first_is_enemy && second_is_enemy_king {
                     // ABSOLUTE PIN: enemy piece pinned to enemy king
                     if let Some((_, first_piece_type)) = first_piece {
-                        score += Self::absolute_pin_score(first_piece_type);
+                        score += params.absolute_pin_bonus[first_piece_type as usize];
                     }
                 } else if first_is_enemy && second_is_enemy_queen {
                     // RELATIVE PIN: enemy piece pinned to enemy queen
                     if let Some((_, first_piece_type)) = first_piece {
                         let pinned_value = Self::piece_value(first_piece_type);
                         // Score based on value difference
-                        let bonus = RELATIVE_PIN_BASE
-                            + ((900 - pinned_value).max(0) * RELATIVE_PIN_MAX / 900);
-                        score += bonus.min(RELATIVE_PIN_MAX);
+                        let bonus = params.relative_pin_base
+                            + ((900 - pinned_value).max(0) * params.relative_pin_max / 900);
+                        score += bonus.min(params.relative_pin_max);
                     }
                 } else if first_is_own && second_is_enemy {
                     // DISCOVERED ATTACK POTENTIAL: own piece blocks attack on enemy piece
                     if let Some((_, second_piece_type)) = board.piece_on(second_sq as u8) {
                         let target_value = Self::piece_value(second_piece_type);
                         // Higher score for more valuable targets
-                        let bonus = DISCOVERED_ATTACK_BASE
-                            + (target_value * (DISCOVERED_ATTACK_MAX - DISCOVERED_ATTACK_BASE)
+                        let bonus = params.discovered_attack_base
+                            + (target_value
+                                * (params.discovered_attack_max - params.discovered_attack_base)
                                 / 900);
-                        score += bonus.min(DISCOVERED_ATTACK_MAX);
+                        score += bonus.min(params.discovered_attack_max);
                     }
                 } else if first_is_enemy
                     && second_is_enemy
@@ -288,7 +290,7 @@ impl LinePressureEvaluator {
                     && matches!(second_piece_type, Piece::King | Piece::Queen | Piece::Rook)
                 {
                     // X-RAY: attack through enemy piece to another enemy piece
-                    score += XRAY_BONUS;
+                    score += params.xray_bonus;
                 }
 
                 // SKEWER: first piece is high-value, second is lower value
@@ -299,7 +301,7 @@ impl LinePressureEvaluator {
                     && second_is_enemy
                     && Self::piece_value(first_type) > Self::piece_value(second_type)
                 {
-                    score += SKEWER_BONUS;
+                    score += params.skewer_bonus;
                 }
             }
         }
@@ -342,25 +344,16 @@ impl LinePressureEvaluator {
             (Some(first), second)
         }
     }
-
-    /// Score for absolute pin based on pinned piece type
-    #[inline]
-    fn absolute_pin_score(pinned_piece: Piece) -> i32 {
-        match pinned_piece {
-            Piece::Queen => ABSOLUTE_PIN_QUEEN,
-            Piece::Rook => ABSOLUTE_PIN_ROOK,
-            Piece::Bishop => ABSOLUTE_PIN_BISHOP,
-            Piece::Knight => ABSOLUTE_PIN_KNIGHT,
-            Piece::Pawn => ABSOLUTE_PIN_PAWN,
-            Piece::King => ABSOLUTE_PIN_BASE, // Shouldn't happen, but handle gracefully
-        }
-    }
 }
 
 impl BoardEvaluator for LinePressureEvaluator {
     fn evaluate(&self, board: &Board) -> i32 {
-        let white_score = Self::evaluate_side(board, Color::White);
-        let black_score = Self::evaluate_side(board, Color::Black);
+        self.evaluate_with_params(board, &EvalParams::DEFAULT)
+    }
+
+    fn evaluate_with_params(&self, board: &Board, params: &EvalParams) -> i32 {
+        let white_score = Self::evaluate_side(board, Color::White, params);
+        let black_score = Self::evaluate_side(board, Color::Black, params);
         white_score - black_score
     }
 }
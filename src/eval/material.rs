@@ -1,12 +1,19 @@
 use crate::{
     board::{Board, Color, Piece},
-    eval::evaluator::BoardEvaluator,
+    eval::{EvalParams, evaluator::BoardEvaluator},
 };
 
+/// Default material values for pawn, knight, bishop, rook, queen and king.
+pub(crate) const PIECE_VALUES: [i32; 6] = [100, 320, 330, 500, 900, 0];
+
 pub struct MaterialEvaluator;
 
 impl BoardEvaluator for MaterialEvaluator {
     fn evaluate(&self, board: &Board) -> i32 {
+        self.evaluate_with_params(board, &EvalParams::DEFAULT)
+    }
+
+    fn evaluate_with_params(&self, board: &Board, params: &EvalParams) -> i32 {
         let mut white_material: i32 = 0;
         let mut black_material: i32 = 0;
 
@@ -20,7 +27,7 @@ impl BoardEvaluator for MaterialEvaluator {
                 4 => Piece::Queen,
                 _ => Piece::King,
             };
-            let value = Self::piece_value(piece);
+            let value = params.piece_value(piece);
 
             // Count white pieces
             let white_count = board.pieces[Color::White as usize][piece_idx].count_ones() as i32;
@@ -36,15 +43,18 @@ impl BoardEvaluator for MaterialEvaluator {
 }
 
 impl MaterialEvaluator {
+    /// Material balance from the board's incrementally maintained accumulator.
+    /// Only valid for the default parameters.
+    pub fn evaluate_incremental(board: &Board) -> i32 {
+        let acc = &board.accumulator;
+        let score = acc.material[Color::White as usize] - acc.material[Color::Black as usize];
+        debug_assert_eq!(score, MaterialEvaluator.evaluate(board));
+        score
+    }
+
+    #[cfg(test)]
     fn piece_value(piece: Piece) -> i32 {
-        match piece {
-            Piece::Pawn => 100,
-            Piece::Knight => 320,
-            Piece::Bishop => 330,
-            Piece::Rook => 500,
-            Piece::Queen => 900,
-            Piece::King => 0,
-        }
+        PIECE_VALUES[piece as usize]
     }
 }
 
@@ -1,18 +1,22 @@
 use crate::{
     board::{Board, Color, Piece},
-    eval::evaluator::BoardEvaluator,
+    eval::{EvalParams, evaluator::BoardEvaluator},
 };
 
-const MOBILITY_WEIGHT: i32 = 6;
+pub(crate) const MOBILITY_WEIGHT: i32 = 6;
 
 pub struct MobilityEvaluator;
 
 impl BoardEvaluator for MobilityEvaluator {
     fn evaluate(&self, board: &Board) -> i32 {
+        self.evaluate_with_params(board, &EvalParams::DEFAULT)
+    }
+
+    fn evaluate_with_params(&self, board: &Board, params: &EvalParams) -> i32 {
         let white_mobility: i32 = Self::count_mobility(board, Color::White);
         let black_mobility: i32 = Self::count_mobility(board, Color::Black);
 
-        MOBILITY_WEIGHT * (white_mobility - black_mobility)
+        params.mobility_weight * (white_mobility - black_mobility)
     }
 }
 
@@ -1,5 +1,7 @@
 pub mod bishop_pair;
 pub mod central_control;
+pub mod config;
+pub mod endgame;
 pub mod evaluation_scores;
 pub mod evaluator;
 pub mod fork;
@@ -8,11 +10,15 @@ pub mod knight_outpost;
 pub mod line_pressure;
 pub mod material;
 pub mod mobility;
+pub mod nnue;
+pub mod params;
 pub mod pawn_structure;
 pub mod position;
 pub mod rook_file_evaluator;
 pub mod tempo;
 pub mod threat;
 
+pub use config::{EvalConfig, EvaluatorConfig, EvaluatorKind};
 pub use evaluation_scores::EvaluationScores;
 pub use evaluator::{BoardEvaluator, Evaluator};
+pub use params::{EvalParams, ParamSpec};
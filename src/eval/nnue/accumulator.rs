@@ -0,0 +1,223 @@
+use super::NnueNetwork;
+use crate::board::{Board, ChessMove, Color, MoveUndo, Piece};
+
+const PIECES: [Piece; 6] = [
+    Piece::Pawn,
+    Piece::Knight,
+    Piece::Bishop,
+    Piece::Rook,
+    Piece::Queen,
+    Piece::King,
+];
+
+/// Hidden layer values for both perspectives.
+///
+/// Kept in sync with a board by calling `make_move`/`unmake_move` instead of the
+/// `Board` methods, or by `update` from any board it was computed for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NnueAccumulator {
+    values: [Vec<i16>; 2], // [perspective]
+}
+
+impl NnueAccumulator {
+    /// Computes the accumulator for `board` from scratch.
+    pub fn new(network: &NnueNetwork, board: &Board) -> Self {
+        let mut accumulator = Self {
+            values: [Vec::new(), Vec::new()],
+        };
+        accumulator.refresh(network, board);
+        accumulator
+    }
+
+    pub fn refresh(&mut self, network: &NnueNetwork, board: &Board) {
+        self.refresh_perspective(network, board, Color::White);
+        self.refresh_perspective(network, board, Color::Black);
+    }
+
+    /// Hidden layer values as seen from `perspective`.
+    pub fn values(&self, perspective: Color) -> &[i16] {
+        &self.values[perspective as usize]
+    }
+
+    /// Plays `mv` on `board` and updates the accumulator to match.
+    pub fn make_move(
+        &mut self,
+        network: &NnueNetwork,
+        board: &mut Board,
+        mv: ChessMove,
+    ) -> MoveUndo {
+        let before = *board;
+        let undo = board.make_move(mv);
+        self.update(network, &before, board);
+        undo
+    }
+
+    /// Takes back the move recorded in `undo` and updates the accumulator to match.
+    pub fn unmake_move(&mut self, network: &NnueNetwork, board: &mut Board, undo: MoveUndo) {
+        let before = *board;
+        board.unmake_move(undo);
+        self.update(network, &before, board);
+    }
+
+    /// Brings the accumulator, computed for `before`, up to date with `after`.
+    ///
+    /// Only the features that differ are applied, except when a king has moved:
+    /// every feature of that side's perspective depends on its king square, so it's rebuilt.
+    pub fn update(&mut self, network: &NnueNetwork, before: &Board, after: &Board) {
+        for perspective in [Color::White, Color::Black] {
+            let king = Piece::King as usize;
+            if before.pieces[perspective as usize][king] != after.pieces[perspective as usize][king]
+            {
+                self.refresh_perspective(network, after, perspective);
+                continue;
+            }
+
+            let king_sq = king_square(after, perspective);
+            let values = &mut self.values[perspective as usize];
+            for color in [Color::White, Color::Black] {
+                for piece in PIECES {
+                    let old = before.pieces[color as usize][piece as usize];
+                    let new = after.pieces[color as usize][piece as usize];
+
+                    let mut removed = old & !new;
+                    while removed != 0 {
+                        let sq = removed.trailing_zeros() as usize;
+                        removed &= removed - 1;
+                        let index = feature_index(perspective, king_sq, color, piece, sq);
+                        sub_feature(values, network.feature(index));
+                    }
+
+                    let mut added = new & !old;
+                    while added != 0 {
+                        let sq = added.trailing_zeros() as usize;
+                        added &= added - 1;
+                        let index = feature_index(perspective, king_sq, color, piece, sq);
+                        add_feature(values, network.feature(index));
+                    }
+                }
+            }
+        }
+    }
+
+    fn refresh_perspective(&mut self, network: &NnueNetwork, board: &Board, perspective: Color) {
+        let king_sq = king_square(board, perspective);
+        let values = &mut self.values[perspective as usize];
+        values.clear();
+        values.extend_from_slice(&network.feature_biases);
+
+        for color in [Color::White, Color::Black] {
+            for piece in PIECES {
+                let mut bb = board.pieces[color as usize][piece as usize];
+                while bb != 0 {
+                    let sq = bb.trailing_zeros() as usize;
+                    bb &= bb - 1;
+                    let index = feature_index(perspective, king_sq, color, piece, sq);
+                    add_feature(values, network.feature(index));
+                }
+            }
+        }
+    }
+}
+
+/// HalfKA feature index of `piece` of `color` on `sq`, seen from `perspective`.
+/// Black's view is mirrored vertically so both sides share the same weights.
+#[inline]
+pub fn feature_index(
+    perspective: Color,
+    king_sq: usize,
+    color: Color,
+    piece: Piece,
+    sq: usize,
+) -> usize {
+    let orient = |square: usize| match perspective {
+        Color::White => square,
+        Color::Black => square ^ 56,
+    };
+    let relative_piece = if color == perspective { 0 } else { 6 } + piece as usize;
+
+    (orient(king_sq) * 12 + relative_piece) * 64 + orient(sq)
+}
+
+#[inline]
+fn king_square(board: &Board, color: Color) -> usize {
+    let king_bb = board.pieces[color as usize][Piece::King as usize];
+    if king_bb == 0 {
+        0
+    } else {
+        king_bb.trailing_zeros() as usize
+    }
+}
+
+#[inline]
+fn add_feature(values: &mut [i16], weights: &[i16]) {
+    for (value, &weight) in values.iter_mut().zip(weights) {
+        *value = value.wrapping_add(weight);
+    }
+}
+
+#[inline]
+fn sub_feature(values: &mut [i16], weights: &[i16]) {
+    for (value, &weight) in values.iter_mut().zip(weights) {
+        *value = value.wrapping_sub(weight);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::movegen::MoveGenerator;
+
+    #[test]
+    fn test_incremental_updates_match_refresh() {
+        let network = NnueNetwork::random(16, 7);
+        // Captures, castling, en passant and promotions
+        let fens = [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        ];
+
+        for fen in fens {
+            let board = Board::from_fen(fen);
+            let accumulator = NnueAccumulator::new(&network, &board);
+            let mut moves = Vec::new();
+            MoveGenerator::generate_legal_moves(&board, &mut moves);
+
+            for mv in moves {
+                let mut next_board = board;
+                let mut next = accumulator.clone();
+                let undo = next.make_move(&network, &mut next_board, mv);
+
+                assert_eq!(
+                    next,
+                    NnueAccumulator::new(&network, &next_board),
+                    "accumulator out of sync after {} in {}",
+                    mv.to_uci(),
+                    fen
+                );
+
+                next.unmake_move(&network, &mut next_board, undo);
+                assert_eq!(next, accumulator, "not restored after {}", mv.to_uci());
+            }
+        }
+    }
+
+    #[test]
+    fn test_perspectives_are_mirrored() {
+        let network = NnueNetwork::random(16, 3);
+        let white = Board::from_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1");
+        let black = Board::from_fen("4k3/4p3/8/8/8/8/8/4K3 b - - 0 1");
+
+        let white_acc = NnueAccumulator::new(&network, &white);
+        let black_acc = NnueAccumulator::new(&network, &black);
+
+        assert_eq!(
+            white_acc.values(Color::White),
+            black_acc.values(Color::Black)
+        );
+        assert_eq!(
+            white_acc.values(Color::Black),
+            black_acc.values(Color::White)
+        );
+    }
+}
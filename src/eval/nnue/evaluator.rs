@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use super::{NNUE_NETWORK, NnueAccumulator, NnueNetwork};
+use crate::{
+    board::{Board, Color},
+    eval::evaluator::BoardEvaluator,
+};
+
+/// Evaluates positions with an NNUE network.
+///
+/// `evaluate` builds the accumulator from scratch. The search instead keeps one in
+/// step with its moves (see `Evaluator::update_accumulator`) and calls
+/// `evaluate_accumulator`, as can callers that walk a game.
+pub struct NnueEvaluator {
+    network: Arc<NnueNetwork>,
+}
+
+impl NnueEvaluator {
+    pub fn new(network: Arc<NnueNetwork>) -> Self {
+        Self { network }
+    }
+
+    /// Uses the network loaded from `nnue.bin`, or fails if the file is missing or invalid.
+    pub fn from_default_network() -> Result<Self, String> {
+        NNUE_NETWORK.clone().map(Self::new)
+    }
+
+    pub fn network(&self) -> &NnueNetwork {
+        &self.network
+    }
+
+    /// Scores a board whose accumulator is already up to date, from White's perspective.
+    pub fn evaluate_accumulator(&self, board: &Board, accumulator: &NnueAccumulator) -> i32 {
+        let score = self.network.evaluate(accumulator, board.side_to_move);
+        match board.side_to_move {
+            Color::White => score,
+            Color::Black => -score,
+        }
+    }
+}
+
+impl BoardEvaluator for NnueEvaluator {
+    fn evaluate(&self, board: &Board) -> i32 {
+        let accumulator = NnueAccumulator::new(&self.network, board);
+        self.evaluate_accumulator(board, &accumulator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mirrored_positions_score_opposite() {
+        let evaluator = NnueEvaluator::new(Arc::new(NnueNetwork::random(16, 11)));
+        let white = Board::from_fen("4k3/8/8/8/8/2N5/4P3/4K3 w - - 0 1");
+        let black = Board::from_fen("4k3/4p3/2n5/8/8/8/8/4K3 b - - 0 1");
+
+        assert_eq!(evaluator.evaluate(&white), -evaluator.evaluate(&black));
+    }
+}
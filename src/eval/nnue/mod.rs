@@ -0,0 +1,23 @@
+//! NNUE-style ("efficiently updatable neural network") evaluation.
+//!
+//! The network is a small HalfKA architecture: each perspective (White, Black)
+//! has one input feature per (own king square, piece, piece square) triple,
+//! feeding a single hidden layer whose values are kept in an `NnueAccumulator`.
+//! Because a move only toggles a handful of features, the accumulator can be
+//! updated incrementally instead of being recomputed from scratch.
+//!
+//! ```text
+//! 2 x [64 king squares x 12 pieces x 64 squares] -> 2 x HIDDEN (i16)
+//!     -> clipped ReLU -> 1 output (i8 weights, i32 bias)
+//! ```
+//!
+//! Weights are stored in a little-endian binary file (see `NnueNetwork` for the layout)
+//! and loaded from `nnue.bin` in the working directory.
+
+pub mod accumulator;
+pub mod evaluator;
+pub mod network;
+
+pub use accumulator::NnueAccumulator;
+pub use evaluator::NnueEvaluator;
+pub use network::{NNUE_BIN, NNUE_NETWORK, NnueNetwork};
@@ -0,0 +1,266 @@
+use once_cell::sync::Lazy;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::sync::Arc;
+
+use super::NnueAccumulator;
+use crate::board::Color;
+
+// =========
+// CONSTANTS
+// =========
+
+pub const NNUE_BIN: &str = "nnue.bin";
+
+/// File header magic ("RCNN")
+const MAGIC: [u8; 4] = *b"RCNN";
+const VERSION: u32 = 1;
+
+/// Input features per perspective: own king square x 12 pieces x 64 squares
+pub const NUM_FEATURES: usize = 64 * 12 * 64;
+
+/// Upper bound on the hidden layer size accepted from a file
+const MAX_HIDDEN: usize = 4096;
+
+/// Clipped ReLU ceiling, i.e. the quantization scale of the hidden layer
+pub const QA: i32 = 127;
+/// Quantization scale of the output weights
+pub const QB: i32 = 64;
+/// Converts the network output to centipawns
+pub const OUTPUT_SCALE: i32 = 400;
+
+// The default network, loaded from `NNUE_BIN` on first use. A failed load is kept
+// as an error message so every evaluator built from it reports the same problem.
+pub static NNUE_NETWORK: Lazy<Result<Arc<NnueNetwork>, String>> = Lazy::new(|| {
+    NnueNetwork::load_from_bin(NNUE_BIN)
+        .map(Arc::new)
+        .map_err(|e| format!("Failed to load NNUE network '{}': {}", NNUE_BIN, e))
+});
+
+// =======
+// NETWORK
+// =======
+
+/// Quantized NNUE weights.
+///
+/// Binary layout (all little-endian):
+///
+/// | field             | type  | count                |
+/// |-------------------|-------|----------------------|
+/// | magic `"RCNN"`    | u8    | 4                    |
+/// | version (1)       | u32   | 1                    |
+/// | hidden size `H`   | u32   | 1                    |
+/// | feature biases    | i16   | H                    |
+/// | feature weights   | i16   | `NUM_FEATURES` x H   |
+/// | output weights    | i8    | 2 x H (us, then them)|
+/// | output bias       | i32   | 1                    |
+///
+/// Feature weights are stored feature-major, so the H weights of one feature are contiguous.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NnueNetwork {
+    pub hidden: usize,
+    pub feature_biases: Vec<i16>,
+    pub feature_weights: Vec<i16>,
+    pub output_weights: Vec<i8>,
+    pub output_bias: i32,
+}
+
+impl NnueNetwork {
+    /// Creates a randomly initialized network, mainly useful as a test fixture.
+    pub fn random(hidden: usize, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        Self {
+            hidden,
+            feature_biases: (0..hidden).map(|_| rng.random_range(-64..=64)).collect(),
+            feature_weights: (0..NUM_FEATURES * hidden)
+                .map(|_| rng.random_range(-32..=32))
+                .collect(),
+            output_weights: (0..2 * hidden)
+                .map(|_| rng.random_range(-64..=64))
+                .collect(),
+            output_bias: rng.random_range(-1000..=1000),
+        }
+    }
+
+    pub fn load_from_bin(path: &str) -> std::io::Result<Self> {
+        let file: File = File::open(path)?;
+        Self::read_from(&mut BufReader::new(file))
+    }
+
+    pub fn save_to_bin(&self, path: &str) -> std::io::Result<()> {
+        let file: File = File::create(path)?;
+        let mut writer = BufWriter::new(file);
+        self.write_to(&mut writer)?;
+        writer.flush()
+    }
+
+    pub fn read_from(reader: &mut impl Read) -> std::io::Result<Self> {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if magic != MAGIC {
+            return Err(invalid_data("not an NNUE network file"));
+        }
+
+        let version = read_u32(reader)?;
+        if version != VERSION {
+            return Err(invalid_data(&format!(
+                "unsupported NNUE version {}",
+                version
+            )));
+        }
+
+        let hidden = read_u32(reader)? as usize;
+        if hidden == 0 || hidden > MAX_HIDDEN {
+            return Err(invalid_data(&format!("invalid hidden size {}", hidden)));
+        }
+
+        let feature_biases = read_i16s(reader, hidden)?;
+        let feature_weights = read_i16s(reader, NUM_FEATURES * hidden)?;
+
+        let mut output_bytes = vec![0u8; 2 * hidden];
+        reader.read_exact(&mut output_bytes)?;
+        let output_weights = output_bytes.into_iter().map(|b| b as i8).collect();
+
+        let mut buf = [0u8; 4];
+        reader.read_exact(&mut buf)?;
+        let output_bias = i32::from_le_bytes(buf);
+
+        Ok(Self {
+            hidden,
+            feature_biases,
+            feature_weights,
+            output_weights,
+            output_bias,
+        })
+    }
+
+    pub fn write_to(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&(self.hidden as u32).to_le_bytes())?;
+        for value in self.feature_biases.iter().chain(&self.feature_weights) {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+        for &weight in &self.output_weights {
+            writer.write_all(&[weight as u8])?;
+        }
+        writer.write_all(&self.output_bias.to_le_bytes())
+    }
+
+    /// Hidden layer weights of a single input feature.
+    #[inline]
+    pub fn feature(&self, index: usize) -> &[i16] {
+        &self.feature_weights[index * self.hidden..(index + 1) * self.hidden]
+    }
+
+    /// Runs the output layer. Returns centipawns from `side_to_move`'s perspective.
+    pub fn evaluate(&self, accumulator: &NnueAccumulator, side_to_move: Color) -> i32 {
+        let us = accumulator.values(side_to_move);
+        let them = accumulator.values(side_to_move.opponent());
+        let (us_weights, them_weights) = self.output_weights.split_at(self.hidden);
+
+        // i64 because a large output bias times OUTPUT_SCALE doesn't fit in an i32
+        let mut sum: i64 = 0;
+        for (values, weights) in [(us, us_weights), (them, them_weights)] {
+            for (&value, &weight) in values.iter().zip(weights) {
+                sum += ((value as i32).clamp(0, QA) * weight as i32) as i64;
+            }
+        }
+
+        let score = (sum + self.output_bias as i64) * OUTPUT_SCALE as i64 / (QA * QB) as i64;
+        score.clamp(i32::MIN as i64, i32::MAX as i64) as i32
+    }
+}
+
+fn invalid_data(message: &str) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message.to_string())
+}
+
+fn read_u32(reader: &mut impl Read) -> std::io::Result<u32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+/// Reads `count` values, growing the buffer only as data arrives so a header claiming
+/// a huge network can't allocate more than the file actually holds.
+fn read_i16s(reader: &mut impl Read, count: usize) -> std::io::Result<Vec<i16>> {
+    let mut bytes = Vec::new();
+    reader.take(count as u64 * 2).read_to_end(&mut bytes)?;
+    if bytes.len() != count * 2 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::UnexpectedEof,
+            "NNUE network file is truncated",
+        ));
+    }
+    Ok(bytes
+        .chunks_exact(2)
+        .map(|b| i16::from_le_bytes([b[0], b[1]]))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_round_trip() {
+        let network = NnueNetwork::random(8, 1);
+
+        let mut bytes = Vec::new();
+        network.write_to(&mut bytes).unwrap();
+        let loaded = NnueNetwork::read_from(&mut bytes.as_slice()).unwrap();
+
+        assert_eq!(loaded, network);
+    }
+
+    #[test]
+    fn test_rejects_bad_header() {
+        let mut bytes = Vec::new();
+        NnueNetwork::random(4, 1).write_to(&mut bytes).unwrap();
+
+        bytes[0] = b'X';
+        assert!(NnueNetwork::read_from(&mut bytes.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_rejects_truncated_file() {
+        let mut bytes = Vec::new();
+        NnueNetwork::random(4, 1).write_to(&mut bytes).unwrap();
+
+        bytes.truncate(bytes.len() - 1);
+        assert!(NnueNetwork::read_from(&mut bytes.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_rejects_header_larger_than_file() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        bytes.extend_from_slice(&(MAX_HIDDEN as u32).to_le_bytes());
+        bytes.extend_from_slice(&[0; 64]);
+
+        let error = NnueNetwork::read_from(&mut bytes.as_slice()).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_large_output_bias_does_not_overflow() {
+        let board = crate::board::Board::startpos();
+        for bias in [i32::MAX, i32::MIN] {
+            let mut network = NnueNetwork::random(4, 1);
+            network.output_weights.fill(0);
+            network.output_bias = bias;
+            let accumulator = NnueAccumulator::new(&network, &board);
+
+            let expected = bias as i64 * OUTPUT_SCALE as i64 / (QA * QB) as i64;
+            assert_eq!(
+                network.evaluate(&accumulator, Color::White) as i64,
+                expected
+            );
+        }
+    }
+}
@@ -0,0 +1,524 @@
+use crate::board::Piece;
+use crate::eval::{
+    EvaluatorKind, bishop_pair, central_control, fork, king_safety, knight_outpost, line_pressure,
+    material, mobility, pawn_structure, position, rook_file_evaluator, tempo,
+};
+use serde_json::{Map, Value};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+
+/// Tunable constants used by the hand-crafted evaluators.
+///
+/// `EvalParams::DEFAULT` holds the hand-picked values the evaluators have always used.
+/// The `tune` binary adjusts these from labeled positions and writes them to a JSON
+/// parameter file, which `Evaluator::with_params` can load back in.
+///
+/// Every parameter is also reachable through a flat vector (see `to_vector`, `set`,
+/// `specs`) so tuners can treat the whole evaluation as one parameter vector.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EvalParams {
+    /// Material values for pawn, knight, bishop, rook and queen.
+    pub piece_values: [i32; 5],
+    /// Piece-square tables from White's perspective: pawn, knight, bishop, rook,
+    /// queen, king (middlegame) and king (endgame).
+    pub piece_square_tables: [[i32; 64]; 7],
+    /// Passed pawn bonus indexed by how many ranks the pawn has advanced.
+    pub passed_pawn_bonus: [i32; 7],
+    pub isolated_pawn_penalty: i32,
+    pub doubled_pawn_penalty: i32,
+    pub mobility_weight: i32,
+    pub castled_bonus: i32,
+    pub pawn_shield_weight: i32,
+    pub king_open_file_penalty: i32,
+    pub king_proximity_penalty: i32,
+    /// King-zone attacker weights for pawn, knight, bishop, rook and queen.
+    pub king_zone_attacker_weights: [i32; 5],
+    pub tempo_bonus: i32,
+    pub bishop_pair_bonus: i32,
+    pub knight_outpost_bonus: i32,
+    pub knight_outpost_supported_bonus: i32,
+    pub rook_open_file_bonus: i32,
+    pub rook_semi_open_file_bonus: i32,
+    pub center_control_bonus: i32,
+    /// Absolute pin bonus for a pinned pawn, knight, bishop, rook, queen and king.
+    pub absolute_pin_bonus: [i32; 6],
+    pub relative_pin_base: i32,
+    pub relative_pin_max: i32,
+    pub xray_bonus: i32,
+    pub discovered_attack_base: i32,
+    pub discovered_attack_max: i32,
+    pub skewer_bonus: i32,
+    pub fork_base_bonus: i32,
+    pub fork_value_scale: i32,
+    pub fork_attacker_bonus: i32,
+}
+
+/// Describes one entry of the flat parameter vector.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParamSpec {
+    /// Stable name used in parameter files, e.g. `material.knight` or `pst.knight.e4`.
+    pub name: String,
+    /// The evaluator whose score depends on this parameter.
+    pub owner: EvaluatorKind,
+}
+
+const PIECE_NAMES: [&str; 6] = ["pawn", "knight", "bishop", "rook", "queen", "king"];
+const PST_GROUPS: [&str; 7] = [
+    "pst.pawn",
+    "pst.knight",
+    "pst.bishop",
+    "pst.rook",
+    "pst.queen",
+    "pst.king_middlegame",
+    "pst.king_endgame",
+];
+
+/// A single slot of the flat parameter vector.
+struct Slot<'a> {
+    group: &'static str,
+    label: Label,
+    owner: EvaluatorKind,
+    value: &'a mut i32,
+}
+
+enum Label {
+    None,
+    Name(&'static str),
+    Square(usize),
+    Index(usize),
+}
+
+impl Slot<'_> {
+    fn name(&self) -> String {
+        match self.label {
+            Label::None => self.group.to_string(),
+            Label::Name(name) => format!("{}.{}", self.group, name),
+            Label::Square(sq) => {
+                let file = (b'a' + (sq % 8) as u8) as char;
+                let rank = (b'1' + (sq / 8) as u8) as char;
+                format!("{}.{}{}", self.group, file, rank)
+            }
+            Label::Index(i) => format!("{}[{}]", self.group, i),
+        }
+    }
+}
+
+impl Default for EvalParams {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl EvalParams {
+    /// The hand-picked values used by the evaluators out of the box.
+    pub const DEFAULT: EvalParams = EvalParams {
+        piece_values: [
+            material::PIECE_VALUES[0],
+            material::PIECE_VALUES[1],
+            material::PIECE_VALUES[2],
+            material::PIECE_VALUES[3],
+            material::PIECE_VALUES[4],
+        ],
+        piece_square_tables: [
+            position::PAWN_TABLE,
+            position::KNIGHT_TABLE,
+            position::BISHOP_TABLE,
+            position::ROOK_TABLE,
+            position::QUEEN_TABLE,
+            position::KING_MIDDLEGAME_TABLE,
+            position::KING_ENDGAME_TABLE,
+        ],
+        passed_pawn_bonus: pawn_structure::PASSED_PAWN_BONUS,
+        isolated_pawn_penalty: pawn_structure::ISOLATED_PAWN_PENALTY,
+        doubled_pawn_penalty: pawn_structure::DOUBLED_PAWN_PENALTY,
+        mobility_weight: mobility::MOBILITY_WEIGHT,
+        castled_bonus: king_safety::CASTLED_BONUS,
+        pawn_shield_weight: king_safety::PAWN_SHIELD_WEIGHT,
+        king_open_file_penalty: king_safety::OPEN_FILE_PENALTY,
+        king_proximity_penalty: king_safety::ENEMY_PROXIMITY_PENALTY,
+        king_zone_attacker_weights: king_safety::KING_ZONE_ATTACKER_WEIGHTS,
+        tempo_bonus: tempo::TEMPO_BONUS,
+        bishop_pair_bonus: bishop_pair::BISHOP_PAIR_BONUS,
+        knight_outpost_bonus: knight_outpost::OUTPOST_BONUS,
+        knight_outpost_supported_bonus: knight_outpost::SUPPORTED_BONUS,
+        rook_open_file_bonus: rook_file_evaluator::OPEN_FILE_BONUS,
+        rook_semi_open_file_bonus: rook_file_evaluator::SEMI_OPEN_FILE_BONUS,
+        center_control_bonus: central_control::CENTER_BONUS,
+        absolute_pin_bonus: [
+            line_pressure::ABSOLUTE_PIN_PAWN,
+            line_pressure::ABSOLUTE_PIN_KNIGHT,
+            line_pressure::ABSOLUTE_PIN_BISHOP,
+            line_pressure::ABSOLUTE_PIN_ROOK,
+            line_pressure::ABSOLUTE_PIN_QUEEN,
+            line_pressure::ABSOLUTE_PIN_BASE,
+        ],
+        relative_pin_base: line_pressure::RELATIVE_PIN_BASE,
+        relative_pin_max: line_pressure::RELATIVE_PIN_MAX,
+        xray_bonus: line_pressure::XRAY_BONUS,
+        discovered_attack_base: line_pressure::DISCOVERED_ATTACK_BASE,
+        discovered_attack_max: line_pressure::DISCOVERED_ATTACK_MAX,
+        skewer_bonus: line_pressure::SKEWER_BONUS,
+        fork_base_bonus: fork::FORK_BASE_BONUS,
+        fork_value_scale: fork::FORK_VALUE_SCALE,
+        fork_attacker_bonus: fork::FORK_ATTACKER_BONUS,
+    };
+
+    /// Material value of a piece (the king is worth 0).
+    #[inline]
+    pub fn piece_value(&self, piece: Piece) -> i32 {
+        match piece {
+            Piece::King => 0,
+            _ => self.piece_values[piece as usize],
+        }
+    }
+
+    /// Lists every parameter in flat-vector order, in a single pass over the struct.
+    fn slots(&mut self) -> Vec<Slot<'_>> {
+        use EvaluatorKind as K;
+
+        let mut slots = Vec::new();
+        let mut push = |group, label, owner, value| {
+            slots.push(Slot {
+                group,
+                label,
+                owner,
+                value,
+            })
+        };
+
+        for (i, value) in self.piece_values.iter_mut().enumerate() {
+            push("material", Label::Name(PIECE_NAMES[i]), K::Material, value);
+        }
+        for (table, group) in self.piece_square_tables.iter_mut().zip(PST_GROUPS) {
+            for (sq, value) in table.iter_mut().enumerate() {
+                push(group, Label::Square(sq), K::Position, value);
+            }
+        }
+        for (i, value) in self.passed_pawn_bonus.iter_mut().enumerate() {
+            push("pawn.passed", Label::Index(i), K::PawnStructure, value);
+        }
+        push(
+            "pawn.isolated",
+            Label::None,
+            K::PawnStructure,
+            &mut self.isolated_pawn_penalty,
+        );
+        push(
+            "pawn.doubled",
+            Label::None,
+            K::PawnStructure,
+            &mut self.doubled_pawn_penalty,
+        );
+        push(
+            "mobility.weight",
+            Label::None,
+            K::Mobility,
+            &mut self.mobility_weight,
+        );
+        push(
+            "king_safety.castled",
+            Label::None,
+            K::KingSafety,
+            &mut self.castled_bonus,
+        );
+        push(
+            "king_safety.pawn_shield",
+            Label::None,
+            K::KingSafety,
+            &mut self.pawn_shield_weight,
+        );
+        push(
+            "king_safety.open_file",
+            Label::None,
+            K::KingSafety,
+            &mut self.king_open_file_penalty,
+        );
+        push(
+            "king_safety.proximity",
+            Label::None,
+            K::KingSafety,
+            &mut self.king_proximity_penalty,
+        );
+        for (i, value) in self.king_zone_attacker_weights.iter_mut().enumerate() {
+            push(
+                "king_safety.attacker",
+                Label::Name(PIECE_NAMES[i]),
+                K::KingSafety,
+                value,
+            );
+        }
+        push("tempo", Label::None, K::Tempo, &mut self.tempo_bonus);
+        push(
+            "bishop_pair",
+            Label::None,
+            K::BishopPair,
+            &mut self.bishop_pair_bonus,
+        );
+        push(
+            "knight_outpost",
+            Label::None,
+            K::KnightOutpost,
+            &mut self.knight_outpost_bonus,
+        );
+        push(
+            "knight_outpost.supported",
+            Label::None,
+            K::KnightOutpost,
+            &mut self.knight_outpost_supported_bonus,
+        );
+        push(
+            "rook_file.open",
+            Label::None,
+            K::RookFile,
+            &mut self.rook_open_file_bonus,
+        );
+        push(
+            "rook_file.semi_open",
+            Label::None,
+            K::RookFile,
+            &mut self.rook_semi_open_file_bonus,
+        );
+        push(
+            "central_control",
+            Label::None,
+            K::CentralControl,
+            &mut self.center_control_bonus,
+        );
+        for (i, value) in self.absolute_pin_bonus.iter_mut().enumerate() {
+            push(
+                "line_pressure.absolute_pin",
+                Label::Name(PIECE_NAMES[i]),
+                K::LinePressure,
+                value,
+            );
+        }
+        push(
+            "line_pressure.relative_pin_base",
+            Label::None,
+            K::LinePressure,
+            &mut self.relative_pin_base,
+        );
+        push(
+            "line_pressure.relative_pin_max",
+            Label::None,
+            K::LinePressure,
+            &mut self.relative_pin_max,
+        );
+        push(
+            "line_pressure.xray",
+            Label::None,
+            K::LinePressure,
+            &mut self.xray_bonus,
+        );
+        push(
+            "line_pressure.discovered_attack_base",
+            Label::None,
+            K::LinePressure,
+            &mut self.discovered_attack_base,
+        );
+        push(
+            "line_pressure.discovered_attack_max",
+            Label::None,
+            K::LinePressure,
+            &mut self.discovered_attack_max,
+        );
+        push(
+            "line_pressure.skewer",
+            Label::None,
+            K::LinePressure,
+            &mut self.skewer_bonus,
+        );
+        push("fork.base", Label::None, K::Fork, &mut self.fork_base_bonus);
+        push(
+            "fork.value_scale",
+            Label::None,
+            K::Fork,
+            &mut self.fork_value_scale,
+        );
+        push(
+            "fork.attacker",
+            Label::None,
+            K::Fork,
+            &mut self.fork_attacker_bonus,
+        );
+
+        slots
+    }
+
+    /// Names and owning evaluators of every parameter, in flat-vector order.
+    pub fn specs() -> Vec<ParamSpec> {
+        let mut params = Self::DEFAULT;
+        params
+            .slots()
+            .iter()
+            .map(|slot| ParamSpec {
+                name: slot.name(),
+                owner: slot.owner,
+            })
+            .collect()
+    }
+
+    /// Number of entries in the flat parameter vector.
+    pub fn len() -> usize {
+        Self::DEFAULT.clone().slots().len()
+    }
+
+    /// Returns all parameters as a flat vector.
+    pub fn to_vector(&self) -> Vec<i32> {
+        let mut params = self.clone();
+        params.slots().iter().map(|slot| *slot.value).collect()
+    }
+
+    /// Builds parameters from a flat vector produced by `to_vector`.
+    pub fn from_vector(values: &[i32]) -> Result<Self, String> {
+        let mut params = Self::DEFAULT;
+        let mut slots = params.slots();
+        if values.len() != slots.len() {
+            return Err(format!(
+                "Expected {} parameters, got {}",
+                slots.len(),
+                values.len()
+            ));
+        }
+        for (slot, &value) in slots.iter_mut().zip(values) {
+            *slot.value = value;
+        }
+        drop(slots);
+        Ok(params)
+    }
+
+    /// Returns the parameter at `index` in the flat vector.
+    pub fn get(&self, index: usize) -> i32 {
+        let mut params = self.clone();
+        *params.slots()[index].value
+    }
+
+    /// Sets the parameter at `index` in the flat vector.
+    pub fn set(&mut self, index: usize, value: i32) {
+        *self.slots()[index].value = value;
+    }
+
+    /// Writes the parameters as a JSON object of `name: value` pairs.
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        let file = File::create(path)?;
+        let writer = BufWriter::new(file);
+        serde_json::to_writer_pretty(writer, &self.to_json()).map_err(std::io::Error::other)
+    }
+
+    /// Loads a parameter file written by `save` (or by the `tune` binary).
+    pub fn load(path: &str) -> std::io::Result<Self> {
+        let file = File::open(path)?;
+        let reader = BufReader::new(file);
+        let value: Value = serde_json::from_reader(reader)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        Self::from_json(&value).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn to_json(&self) -> Value {
+        let mut params = self.clone();
+        let map: Map<String, Value> = params
+            .slots()
+            .iter()
+            .map(|slot| (slot.name(), Value::from(*slot.value)))
+            .collect();
+        Value::Object(map)
+    }
+
+    /// Parses a JSON object of `name: value` pairs. Parameters missing from
+    /// the object keep their default value; unknown names are rejected.
+    pub fn from_json(value: &Value) -> Result<Self, String> {
+        let object = value
+            .as_object()
+            .ok_or_else(|| "Parameter file must contain a JSON object".to_string())?;
+
+        let mut params = Self::DEFAULT;
+        let mut slots = params.slots();
+        let mut matched = 0;
+
+        for slot in slots.iter_mut() {
+            if let Some(v) = object.get(&slot.name()) {
+                *slot.value = v
+                    .as_i64()
+                    .and_then(|v| i32::try_from(v).ok())
+                    .ok_or_else(|| format!("Parameter '{}' must be an integer", slot.name()))?;
+                matched += 1;
+            }
+        }
+
+        if matched != object.len() {
+            let known: Vec<String> = slots.iter().map(|slot| slot.name()).collect();
+            let unknown = object
+                .keys()
+                .find(|key| !known.contains(key))
+                .cloned()
+                .unwrap_or_default();
+            return Err(format!("Unknown parameter '{}'", unknown));
+        }
+
+        drop(slots);
+        Ok(params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_specs_match_vector_length() {
+        let specs = EvalParams::specs();
+        assert_eq!(specs.len(), EvalParams::len());
+        assert_eq!(specs.len(), EvalParams::DEFAULT.to_vector().len());
+    }
+
+    #[test]
+    fn test_spec_names_are_unique() {
+        let mut names: Vec<String> = EvalParams::specs().into_iter().map(|s| s.name).collect();
+        let count = names.len();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), count);
+    }
+
+    #[test]
+    fn test_vector_round_trip() {
+        let mut values = EvalParams::DEFAULT.to_vector();
+        values[0] = 95;
+        values[100] += 7;
+
+        let params = EvalParams::from_vector(&values).unwrap();
+        assert_eq!(params.piece_values[0], 95);
+        assert_eq!(params.to_vector(), values);
+    }
+
+    #[test]
+    fn test_set_and_get() {
+        let mut params = EvalParams::default();
+        let index = EvalParams::specs()
+            .iter()
+            .position(|s| s.name == "pst.knight.e4")
+            .unwrap();
+
+        params.set(index, 42);
+        assert_eq!(params.get(index), 42);
+        assert_eq!(params.piece_square_tables[1][28], 42);
+    }
+
+    #[test]
+    fn test_json_round_trip_and_partial_files() {
+        let params = EvalParams {
+            bishop_pair_bonus: 45,
+            ..EvalParams::default()
+        };
+        let parsed = EvalParams::from_json(&params.to_json()).unwrap();
+        assert_eq!(parsed, params);
+
+        let partial = serde_json::json!({ "material.queen": 950 });
+        let parsed = EvalParams::from_json(&partial).unwrap();
+        assert_eq!(parsed.piece_values[4], 950);
+        assert_eq!(parsed.piece_values[3], 500);
+
+        let unknown = serde_json::json!({ "material.dragon": 1 });
+        assert!(EvalParams::from_json(&unknown).is_err());
+    }
+}
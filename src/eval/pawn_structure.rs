@@ -1,12 +1,21 @@
 use crate::{
     board::{Board, Color, Piece},
-    eval::evaluator::BoardEvaluator,
+    eval::{EvalParams, evaluator::BoardEvaluator},
 };
 
+/// Passed pawn bonus by ranks advanced: increases dramatically as the pawn advances
+pub(crate) const PASSED_PAWN_BONUS: [i32; 7] = [0, 10, 20, 40, 60, 90, 160];
+pub(crate) const ISOLATED_PAWN_PENALTY: i32 = 20;
+pub(crate) const DOUBLED_PAWN_PENALTY: i32 = 10;
+
 pub struct PawnStructureEvaluator;
 
 impl BoardEvaluator for PawnStructureEvaluator {
     fn evaluate(&self, board: &Board) -> i32 {
+        self.evaluate_with_params(board, &EvalParams::DEFAULT)
+    }
+
+    fn evaluate_with_params(&self, board: &Board, params: &EvalParams) -> i32 {
         let mut score = 0;
 
         // Build pawn maps for fast lookups
@@ -14,12 +23,14 @@ impl BoardEvaluator for PawnStructureEvaluator {
 
         // Evaluate White pawns
         for position in &white_pawns {
-            score += Self::evaluate_pawn(*position, Color::White, &white_pawns, &black_pawns);
+            score +=
+                Self::evaluate_pawn(*position, Color::White, &white_pawns, &black_pawns, params);
         }
 
         // Evaluate Black pawns
         for position in &black_pawns {
-            score -= Self::evaluate_pawn(*position, Color::Black, &black_pawns, &white_pawns);
+            score -=
+                Self::evaluate_pawn(*position, Color::Black, &black_pawns, &white_pawns, params);
         }
 
         score
@@ -32,6 +43,7 @@ impl PawnStructureEvaluator {
         color: Color,
         friendly_pawns: &[usize],
         enemy_pawns: &[usize],
+        params: &EvalParams,
     ) -> i32 {
         let mut score: i32 = 0;
 
@@ -40,17 +52,17 @@ impl PawnStructureEvaluator {
 
         // Check for passed pawn
         if Self::is_passed_pawn(position, color, enemy_pawns) {
-            score += Self::passed_pawn_bonus(rank, color);
+            score += Self::passed_pawn_bonus(rank, color, params);
         }
 
         // Check for isolated pawn
         if Self::is_isolated_pawn(file, friendly_pawns) {
-            score -= 20;
+            score -= params.isolated_pawn_penalty;
         }
 
         // Check for doubled pawn
         if Self::is_doubled_pawn(position, color, friendly_pawns) {
-            score -= 10;
+            score -= params.doubled_pawn_penalty;
         }
 
         score
@@ -115,7 +127,7 @@ impl PawnStructureEvaluator {
         true
     }
 
-    fn passed_pawn_bonus(rank: usize, color: Color) -> i32 {
+    fn passed_pawn_bonus(rank: usize, color: Color, params: &EvalParams) -> i32 {
         // Bonus increases dramatically as pawn advances
         // Rank is 0-7, with 0=rank1, 7=rank8
         let advancement = match color {
@@ -123,17 +135,12 @@ impl PawnStructureEvaluator {
             Color::Black => 7 - rank, // Rank 7-0, want low rank
         };
 
-        // Exponential bonus: 2nd rank = 10, 7th rank = 160
-        match advancement {
-            0 => 0,  // Starting rank (shouldn't happen)
-            1 => 10, // Advanced one square
-            2 => 20,
-            3 => 40,
-            4 => 60,
-            5 => 90,
-            6 => 160, // Nearly promoting!
-            _ => 0,
-        }
+        // Exponential bonus by default: 2nd rank = 10, 7th rank = 160
+        params
+            .passed_pawn_bonus
+            .get(advancement)
+            .copied()
+            .unwrap_or(0)
     }
 
     fn is_isolated_pawn(file: usize, friendly_pawns: &[usize]) -> bool {
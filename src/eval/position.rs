@@ -1,6 +1,6 @@
 use crate::{
     board::{Board, Color, Piece},
-    eval::evaluator::BoardEvaluator,
+    eval::{EvalParams, evaluator::BoardEvaluator},
 };
 
 pub struct PositionEvaluator;
@@ -8,6 +8,10 @@ pub struct PositionEvaluator;
 impl BoardEvaluator for PositionEvaluator {
     // Evaluate the positional score of a chess board state
     fn evaluate(&self, board: &Board) -> i32 {
+        self.evaluate_with_params(board, &EvalParams::DEFAULT)
+    }
+
+    fn evaluate_with_params(&self, board: &Board, params: &EvalParams) -> i32 {
         let mut white_position: i32 = 0;
         let mut black_position: i32 = 0;
 
@@ -30,7 +34,7 @@ impl BoardEvaluator for PositionEvaluator {
                     let square = bitboard.trailing_zeros() as usize;
                     bitboard &= bitboard - 1; // Clear the least significant bit
 
-                    let bonus = Self::piece_value(piece, square, color, game_phase);
+                    let bonus = Self::piece_value(piece, square, color, game_phase, params);
                     match color {
                         Color::White => white_position += bonus,
                         Color::Black => black_position += bonus,
@@ -44,32 +48,33 @@ impl BoardEvaluator for PositionEvaluator {
 }
 
 impl PositionEvaluator {
-    fn piece_value(piece: Piece, position: usize, color: Color, game_phase: i32) -> i32 {
+    fn piece_value(
+        piece: Piece,
+        position: usize,
+        color: Color,
+        game_phase: i32,
+        params: &EvalParams,
+    ) -> i32 {
         // For Black pieces, flip the board vertically to normalize Piece-Square tables
         let normalized_position: usize = match color {
             Color::White => position,
             Color::Black => position ^ 56, // Flip rank (XOR with 56)
         };
 
+        let tables = &params.piece_square_tables;
         match piece {
-            Piece::Pawn => PAWN_TABLE[normalized_position],
-            Piece::Knight => KNIGHT_TABLE[normalized_position],
-            Piece::Bishop => BISHOP_TABLE[normalized_position],
-            Piece::Rook => ROOK_TABLE[normalized_position],
-            Piece::Queen => QUEEN_TABLE[normalized_position],
             Piece::King => {
-                let mg = KING_MIDDLEGAME_TABLE[normalized_position];
-                let eg = KING_ENDGAME_TABLE[normalized_position];
+                let mg = tables[Piece::King as usize][normalized_position];
+                let eg = tables[Piece::King as usize + 1][normalized_position];
                 // Blend middle game and endgame king piece-square table positioning
                 // proportionally to the phase of the game (early, mid, late)
                 (mg * game_phase + eg * (256 - game_phase)) / 256
             }
+            _ => tables[piece as usize][normalized_position],
         }
     }
 
     fn game_phase(board: &Board) -> i32 {
-        const MAX_PHASE: i32 = 24; // Sum of all piece phase values
-
         let mut phase: i32 = 0;
 
         // Count pieces for both colors
@@ -80,32 +85,63 @@ impl PositionEvaluator {
             phase += board.count_pieces(color, Piece::Queen) as i32 * 4;
         }
 
+        Self::scale_phase(phase)
+    }
+
+    /// Scales a raw phase count (24 = all pieces on board) to the 0..=256 range.
+    fn scale_phase(phase: i32) -> i32 {
+        const MAX_PHASE: i32 = 24; // Sum of all piece phase values
+
         (phase * 256 + MAX_PHASE / 2) / MAX_PHASE
     }
+
+    /// Positional score from the board's incrementally maintained accumulator.
+    /// Only valid for the default parameters.
+    pub fn evaluate_incremental(board: &Board) -> i32 {
+        let acc = &board.accumulator;
+        let game_phase = Self::scale_phase(acc.phase);
+        let params = &EvalParams::DEFAULT;
+
+        // Kings are blended by phase, so they're read here rather than accumulated
+        let king_value = |color: Color| {
+            let king_bb = board.pieces[color as usize][Piece::King as usize];
+            if king_bb == 0 {
+                return 0;
+            }
+            let square = king_bb.trailing_zeros() as usize;
+            Self::piece_value(Piece::King, square, color, game_phase, params)
+        };
+
+        let score = acc.pst[Color::White as usize] + king_value(Color::White)
+            - acc.pst[Color::Black as usize]
+            - king_value(Color::Black);
+        debug_assert_eq!(score, PositionEvaluator.evaluate(board));
+        score
+    }
 }
 
 // Piece-Square Tables (from white's perspective)
 // Values are in centipawns (1/100 of a pawn)
 
-const PAWN_TABLE: [i32; 64] = [
+pub(crate) const PAWN_TABLE: [i32; 64] = [
     0, 0, 0, 0, 0, 0, 0, 0, 50, 50, 50, 50, 50, 50, 50, 50, 10, 10, 20, 30, 30, 20, 10, 10, 5, 5,
     10, 27, 27, 10, 5, 5, 0, 0, 0, 25, 25, 0, 0, 0, 5, -5, -10, 10, 10, -10, -5, 5, 50, 50, 50, 50,
     50, 50, 50, 50, 200, 200, 200, 200, 200, 200, 200, 200,
 ];
 
-const KNIGHT_TABLE: [i32; 64] = [
+pub(crate) const KNIGHT_TABLE: [i32; 64] = [
     -50, -40, -30, -30, -30, -30, -40, -50, -40, -20, 0, 0, 0, 0, -20, -40, -30, 0, 10, 15, 15, 10,
     0, -30, -30, 5, 15, 20, 20, 15, 5, -30, -30, 0, 15, 20, 20, 15, 0, -30, -30, 5, 10, 15, 15, 10,
     5, -30, -40, -20, 0, 5, 5, 0, -20, -40, -50, -40, -30, -30, -30, -30, -40, -50,
 ];
 
-const BISHOP_TABLE: [i32; 64] = [
+pub(crate) const BISHOP_TABLE: [i32; 64] = [
     -20, -10, -10, -10, -10, -10, -10, -20, -10, 0, 0, 0, 0, 0, 0, -10, -10, 0, 5, 10, 10, 5, 0,
     -10, -10, 5, 5, 10, 10, 5, 5, -10, -10, 0, 10, 10, 10, 10, 0, -10, -10, 10, 10, 10, 10, 10, 10,
     -10, -10, 5, 0, 0, 0, 0, 5, -10, -20, -10, -10, -10, -10, -10, -10, -20,
 ];
 
-const ROOK_TABLE: [i32; 64] = [
+pub(crate) const ROOK_TABLE: [i32; 64] = [
     0, 0, 0, 5, 5, 0, 0, 0, // Back rank
     -5, 0, 0, 0, 0, 0, 0, -5, // Rank 2
     -5, 0, 0, 0, 0, 0, 0, -5, // Rank 3
@@ -116,20 +152,20 @@ const ROOK_TABLE: [i32; 64] = [
     0, 0, 0, 0, 0, 0, 0, 0, // Rank 8
 ];
 
-const QUEEN_TABLE: [i32; 64] = [
+pub(crate) const QUEEN_TABLE: [i32; 64] = [
     -20, -10, -10, -5, -5, -10, -10, -20, -10, 0, 0, 0, 0, 0, 0, -10, -10, 0, 5, 5, 5, 5, 0, -10,
     -5, 0, 5, 5, 5, 5, 0, -5, 0, 0, 5, 5, 5, 5, 0, -5, -10, 5, 5, 5, 5, 5, 0, -10, -10, 0, 5, 0, 0,
     0, 0, -10, -20, -10, -10, -5, -5, -10, -10, -20,
 ];
 
-const KING_MIDDLEGAME_TABLE: [i32; 64] = [
+pub(crate) const KING_MIDDLEGAME_TABLE: [i32; 64] = [
     -30, -40, -40, -50, -50, -40, -40, -30, -30, -40, -40, -50, -50, -40, -40, -30, -30, -40, -40,
     -50, -50, -40, -40, -30, -30, -40, -40, -50, -50, -40, -40, -30, -20, -30, -30, -40, -40, -30,
     -30, -20, -10, -20, -20, -20, -20, -20, -20, -10, 20, 20, 0, 0, 0, 0, 20, 20, 20, 30, 10, 0, 0,
     10, 30, 20,
 ];
 
-const KING_ENDGAME_TABLE: [i32; 64] = [
+pub(crate) const KING_ENDGAME_TABLE: [i32; 64] = [
     -50, -40, -30, -20, -20, -30, -40, -50, -30, -20, -10, 0, 0, -10, -20, -30, -30, -10, 20, 30,
     30, 20, -10, -30, -30, -10, 30, 40, 40, 30, -10, -30, -30, -10, 30, 40, 40, 30, -10, -30, -30,
     -10, 20, 30, 30, 20, -10, -30, -30, -30, 0, 0, 0, 0, -30, -30, -50, -30, -30, -30, -30, -30,
@@ -1,10 +1,10 @@
 use crate::{
     board::{Board, Color, Piece},
-    eval::evaluator::BoardEvaluator,
+    eval::{EvalParams, evaluator::BoardEvaluator},
 };
 
-const OPEN_FILE_BONUS: i32 = 20;
-const SEMI_OPEN_FILE_BONUS: i32 = 10;
+pub(crate) const OPEN_FILE_BONUS: i32 = 20;
+pub(crate) const SEMI_OPEN_FILE_BONUS: i32 = 10;
 
 /// Evaluates rooks on open or semi-open files.
 /// - **Open file**: no pawns of either color on the file → +20
@@ -15,13 +15,17 @@ pub struct RookFileEvaluator;
 
 impl BoardEvaluator for RookFileEvaluator {
     fn evaluate(&self, board: &Board) -> i32 {
+        self.evaluate_with_params(board, &EvalParams::DEFAULT)
+    }
+
+    fn evaluate_with_params(&self, board: &Board, params: &EvalParams) -> i32 {
         let mut score = 0;
 
         // Iterate through white rooks
         let mut white_rooks = board.pieces[Color::White as usize][Piece::Rook as usize];
         while white_rooks != 0 {
             let sq = white_rooks.trailing_zeros() as usize;
-            score += Self::rook_file_score(board, sq, Color::White);
+            score += Self::rook_file_score(board, sq, Color::White, params);
             white_rooks &= white_rooks - 1;
         }
 
@@ -29,7 +33,7 @@ impl BoardEvaluator for RookFileEvaluator {
         let mut black_rooks = board.pieces[Color::Black as usize][Piece::Rook as usize];
         while black_rooks != 0 {
             let sq = black_rooks.trailing_zeros() as usize;
-            score += Self::rook_file_score(board, sq, Color::Black);
+            score += Self::rook_file_score(board, sq, Color::Black, params);
             black_rooks &= black_rooks - 1;
         }
 
@@ -38,7 +42,7 @@ impl BoardEvaluator for RookFileEvaluator {
 }
 
 impl RookFileEvaluator {
-    fn rook_file_score(board: &Board, sq: usize, color: Color) -> i32 {
+    fn rook_file_score(board: &Board, sq: usize, color: Color, params: &EvalParams) -> i32 {
         let file = sq % 8;
         let mut has_friendly_pawn = false;
         let mut has_enemy_pawn = false;
@@ -57,9 +61,9 @@ impl RookFileEvaluator {
         }
 
         let bonus = if !has_friendly_pawn && !has_enemy_pawn {
-            OPEN_FILE_BONUS
+            params.rook_open_file_bonus
         } else if !has_friendly_pawn {
-            SEMI_OPEN_FILE_BONUS
+            params.rook_semi_open_file_bonus
         } else {
             0
         };
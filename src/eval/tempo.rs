@@ -1,15 +1,21 @@
 use crate::{
     board::{Board, Color},
-    eval::evaluator::BoardEvaluator,
+    eval::{EvalParams, evaluator::BoardEvaluator},
 };
 
+pub(crate) const TEMPO_BONUS: i32 = 10;
+
 pub struct TempoEvaluator;
 
 impl BoardEvaluator for TempoEvaluator {
     fn evaluate(&self, board: &Board) -> i32 {
+        self.evaluate_with_params(board, &EvalParams::DEFAULT)
+    }
+
+    fn evaluate_with_params(&self, board: &Board, params: &EvalParams) -> i32 {
         match board.side_to_move {
-            Color::White => 10,
-            Color::Black => -10,
+            Color::White => params.tempo_bonus,
+            Color::Black => -params.tempo_bonus,
         }
     }
 }
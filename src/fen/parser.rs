@@ -205,36 +205,7 @@ impl ParsedFEN {
 
         // Castling rights
         fen.push(' ');
-        let mut castling = String::new();
-        if self
-            .castling_rights
-            .has(Color::White, CastlingSide::KingSide)
-        {
-            castling.push('K');
-        }
-        if self
-            .castling_rights
-            .has(Color::White, CastlingSide::QueenSide)
-        {
-            castling.push('Q');
-        }
-        if self
-            .castling_rights
-            .has(Color::Black, CastlingSide::KingSide)
-        {
-            castling.push('k');
-        }
-        if self
-            .castling_rights
-            .has(Color::Black, CastlingSide::QueenSide)
-        {
-            castling.push('q');
-        }
-        if castling.is_empty() {
-            fen.push('-');
-        } else {
-            fen.push_str(&castling);
-        }
+        fen.push_str(&self.castling_rights.to_fen_field());
 
         // En passant
         fen.push(' ');
@@ -264,7 +235,8 @@ impl FENParser {
     /// * `fen` - A FEN string with 6 space-separated parts:
     ///   1. Piece placement (e.g., "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR")
     ///   2. Active color ('w' or 'b')
-    ///   3. Castling availability (e.g., "KQkq" or "-")
+    ///   3. Castling availability (e.g., "KQkq" or "-"); Chess960 rook files may be
+    ///      given Shredder-FEN style ("HAha") or X-FEN style ("KQkq", outermost rook)
     ///   4. En passant target square (e.g., "e3" or "-")
     ///   5. Halfmove clock (e.g., "0")
     ///   6. Fullmove number (e.g., "1")
@@ -289,7 +261,7 @@ impl FENParser {
 
         let board = Self::parse_piece_placement(parts[0])?;
         let active_color = Self::parse_active_color(parts[1])?;
-        let castling_rights = Self::parse_castling(parts[2])?;
+        let castling_rights = Self::parse_castling(parts[2], &board)?;
         let en_passant_square = Self::parse_en_passant(parts[3])?;
         let halfmove_clock = Self::parse_halfmove_clock(parts[4])?;
         let fullmove_number = Self::parse_fullmove_number(parts[5])?;
@@ -405,7 +377,10 @@ impl FENParser {
         }
     }
 
-    fn parse_castling(castling: &str) -> Result<CastlingRights, FENParseError> {
+    fn parse_castling(
+        castling: &str,
+        board: &[[Option<ColoredPiece>; 8]; 8],
+    ) -> Result<CastlingRights, FENParseError> {
         let mut rights = CastlingRights::empty();
 
         if castling == "-" {
@@ -413,11 +388,45 @@ impl FENParser {
         }
 
         for ch in castling.chars() {
-            match ch {
-                'K' => rights.add(Color::White, CastlingSide::KingSide),
-                'Q' => rights.add(Color::White, CastlingSide::QueenSide),
-                'k' => rights.add(Color::Black, CastlingSide::KingSide),
-                'q' => rights.add(Color::Black, CastlingSide::QueenSide),
+            let color = if ch.is_ascii_uppercase() {
+                Color::White
+            } else {
+                Color::Black
+            };
+            let back_rank = &board[match color {
+                Color::White => 0,
+                Color::Black => 7,
+            }];
+            let is_piece =
+                |file: usize, piece: Piece| back_rank[file] == Some(ColoredPiece { piece, color });
+            // Without a king on the back rank, assume the standard e-file
+            let king_file = (0..8).find(|&f| is_piece(f, Piece::King)).unwrap_or(4);
+
+            match ch.to_ascii_uppercase() {
+                // X-FEN: the outermost rook on that side of the king
+                'K' => {
+                    let file = (king_file + 1..8)
+                        .rev()
+                        .find(|&f| is_piece(f, Piece::Rook))
+                        .unwrap_or(7);
+                    rights.add_with_rook_file(color, CastlingSide::KingSide, file as u8);
+                }
+                'Q' => {
+                    let file = (0..king_file)
+                        .find(|&f| is_piece(f, Piece::Rook))
+                        .unwrap_or(0);
+                    rights.add_with_rook_file(color, CastlingSide::QueenSide, file as u8);
+                }
+                // Shredder-FEN: the rook's file
+                'A'..='H' => {
+                    let file = ch.to_ascii_uppercase() as u8 - b'A';
+                    let side = if file as usize > king_file {
+                        CastlingSide::KingSide
+                    } else {
+                        CastlingSide::QueenSide
+                    };
+                    rights.add_with_rook_file(color, side, file);
+                }
                 _ => return Err(FENParseError::InvalidCastlingChar(ch)),
             }
         }
@@ -546,6 +555,23 @@ mod tests {
         assert_eq!(parsed.fullmove_number, 10);
     }
 
+    #[test]
+    fn test_parse_chess960_castling() {
+        // Shredder-FEN names the rook files directly
+        let shredder =
+            FENParser::parse("bqnbrkrn/pppppppp/8/8/8/8/PPPPPPPP/BQNBRKRN w GEge - 0 1").unwrap();
+        let rights = shredder.castling_rights;
+        assert_eq!(rights.rook_square(Color::White, CastlingSide::KingSide), 6);
+        assert_eq!(rights.rook_square(Color::White, CastlingSide::QueenSide), 4);
+        assert_eq!(rights.rook_square(Color::Black, CastlingSide::KingSide), 62);
+        assert_eq!(shredder.to_fen_string().split(' ').nth(2), Some("GEge"));
+
+        // X-FEN letters pick the outermost rook on each side of the king
+        let xfen =
+            FENParser::parse("bqnbrkrn/pppppppp/8/8/8/8/PPPPPPPP/BQNBRKRN w KQkq - 0 1").unwrap();
+        assert_eq!(xfen.castling_rights, rights);
+    }
+
     #[test]
     fn test_to_fen_string_roundtrip() {
         let original = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
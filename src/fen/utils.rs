@@ -1,4 +1,4 @@
-use crate::board::{Board, Color, Piece, castling::CastlingSide};
+use crate::board::{Board, Color, Piece};
 
 /// Convert a Board to a FEN string
 ///
@@ -50,24 +50,7 @@ pub fn board_fen(board: &Board) -> String {
 
     // Part 3: Castling availability
     fen.push(' ');
-    let mut castling = String::new();
-    if board.castling.has(Color::White, CastlingSide::KingSide) {
-        castling.push('K');
-    }
-    if board.castling.has(Color::White, CastlingSide::QueenSide) {
-        castling.push('Q');
-    }
-    if board.castling.has(Color::Black, CastlingSide::KingSide) {
-        castling.push('k');
-    }
-    if board.castling.has(Color::Black, CastlingSide::QueenSide) {
-        castling.push('q');
-    }
-    if castling.is_empty() {
-        fen.push('-');
-    } else {
-        fen.push_str(&castling);
-    }
+    fen.push_str(&board.castling.to_fen_field());
 
     // Part 4: En passant target square
     fen.push(' ');
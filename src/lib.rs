@@ -6,6 +6,8 @@ pub mod fen;
 pub mod metrics;
 pub mod movegen;
 pub mod opening;
+pub mod pgn;
 pub mod search;
+pub mod tablebase;
 pub mod terminal;
 pub mod transpositions;
@@ -1,5 +1,7 @@
 use crate::board::Color;
-use crate::metrics::output::{AiMoveMetrics, GameMetadata, GameRecording, GameResult, MoveRecord};
+use crate::metrics::output::{
+    AiMoveMetrics, ClockRecord, GameMetadata, GameRecording, GameResult, MoveRecord,
+};
 use chrono::Local;
 use std::fs;
 use std::io::Write;
@@ -12,12 +14,20 @@ pub struct GameRecorder {
 }
 
 impl GameRecorder {
-    pub fn new(player_color: Color, ai_depth: u8) -> Self {
+    /// Starts a recording between the named players. `player_color` is the human's
+    /// color when a single human plays against an engine.
+    pub fn new(white_player: &str, black_player: &str, player_color: Option<Color>) -> Self {
         let now = Local::now();
         let date = now.format("%Y-%m-%d").to_string();
         let start_time = now.format("%H:%M:%S").to_string();
 
-        let metadata = GameMetadata::new(player_color, ai_depth, start_time, date);
+        let metadata = GameMetadata::new(
+            white_player.to_string(),
+            black_player.to_string(),
+            player_color,
+            start_time,
+            date,
+        );
         let recording = GameRecording::new(metadata);
 
         Self {
@@ -27,6 +37,14 @@ impl GameRecorder {
         }
     }
 
+    pub fn set_start_fen(&mut self, fen: String) {
+        self.recording.metadata.start_fen = Some(fen);
+    }
+
+    pub fn set_time_control(&mut self, time_control: String) {
+        self.recording.metadata.time_control = Some(time_control);
+    }
+
     pub fn record_player_move(&mut self, move_number: u16, color: Color, move_notation: String) {
         let move_record = MoveRecord::new_player_move(move_number, color, move_notation);
         self.recording.add_move(move_record);
@@ -43,6 +61,22 @@ impl GameRecorder {
         self.recording.add_move(move_record);
     }
 
+    /// Removes the last recorded move when it is taken back, returning it so it can
+    /// be restored if the move is replayed.
+    pub fn take_back_move(&mut self) -> Option<MoveRecord> {
+        self.recording.pop_move()
+    }
+
+    /// Records a move again after it was taken back.
+    pub fn restore_move(&mut self, move_record: MoveRecord) {
+        self.recording.add_move(move_record);
+    }
+
+    /// Records both clocks after the last recorded move.
+    pub fn record_clock(&mut self, clock: ClockRecord) {
+        self.recording.set_last_clock(clock);
+    }
+
     pub fn finalize_and_save(&mut self, result: GameResult) -> Result<String, String> {
         let now = Local::now();
         let end_time = now.format("%H:%M:%S").to_string();
@@ -2,4 +2,4 @@ pub mod game_recorder;
 pub mod output;
 
 pub use game_recorder::GameRecorder;
-pub use output::{AiMoveMetrics, GameMetadata, GameRecording, GameResult, MoveRecord};
+pub use output::{AiMoveMetrics, ClockRecord, GameMetadata, GameRecording, GameResult, MoveRecord};
@@ -15,17 +15,30 @@ pub struct GameMetadata {
     pub start_time: String,
     pub end_time: Option<String>,
     pub duration_seconds: Option<u64>,
-    pub player_color: String,
-    pub ai_depth: u8,
+    #[serde(default)]
+    pub white_player: String,
+    #[serde(default)]
+    pub black_player: String,
+    /// The human's color when exactly one side is played by a human
+    pub player_color: Option<String>,
+    /// The position the game started from
+    #[serde(default)]
+    pub start_fen: Option<String>,
+    /// Time control such as "15+10", when the game was played with clocks
+    #[serde(default)]
+    pub time_control: Option<String>,
     pub result: GameResult,
     pub final_position_fen: Option<String>,
 }
 
-/// Game result enum
+/// Game result enum. `PlayerWin` and `AIWin` are used when a single human plays
+/// against an engine, `WhiteWin` and `BlackWin` otherwise.
 #[derive(Debug, Serialize, Deserialize)]
 pub enum GameResult {
     PlayerWin,
     AIWin,
+    WhiteWin,
+    BlackWin,
     Draw,
     InProgress,
 }
@@ -37,6 +50,17 @@ pub struct MoveRecord {
     pub color: String,
     pub move_notation: String,
     pub ai_metrics: Option<AiMoveMetrics>,
+    /// Both clocks after the move, when the game was played with clocks
+    #[serde(default)]
+    pub clock: Option<ClockRecord>,
+}
+
+/// Clock readings right after a move
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ClockRecord {
+    pub time_spent_ms: u64,
+    pub white_remaining_ms: u64,
+    pub black_remaining_ms: u64,
 }
 
 /// AI search metrics for a single move
@@ -67,6 +91,18 @@ impl GameRecording {
         self.moves.push(move_record);
     }
 
+    /// Removes and returns the last recorded move.
+    pub fn pop_move(&mut self) -> Option<MoveRecord> {
+        self.moves.pop()
+    }
+
+    /// Attaches clock readings to the last recorded move.
+    pub fn set_last_clock(&mut self, clock: ClockRecord) {
+        if let Some(last) = self.moves.last_mut() {
+            last.clock = Some(clock);
+        }
+    }
+
     pub fn finalize(&mut self, end_time: String, duration_seconds: u64, result: GameResult) {
         self.metadata.end_time = Some(end_time);
         self.metadata.duration_seconds = Some(duration_seconds);
@@ -75,19 +111,28 @@ impl GameRecording {
 }
 
 impl GameMetadata {
-    pub fn new(player_color: Color, ai_depth: u8, start_time: String, date: String) -> Self {
-        let player_color_str = match player_color {
+    pub fn new(
+        white_player: String,
+        black_player: String,
+        player_color: Option<Color>,
+        start_time: String,
+        date: String,
+    ) -> Self {
+        let player_color_str = player_color.map(|color| match color {
             Color::White => "White".to_string(),
             Color::Black => "Black".to_string(),
-        };
+        });
 
         Self {
             date,
             start_time,
             end_time: None,
             duration_seconds: None,
+            white_player,
+            black_player,
             player_color: player_color_str,
-            ai_depth,
+            start_fen: None,
+            time_control: None,
             result: GameResult::InProgress,
             final_position_fen: None,
         }
@@ -106,6 +151,7 @@ impl MoveRecord {
             color: color_str,
             move_notation,
             ai_metrics: None,
+            clock: None,
         }
     }
 
@@ -125,6 +171,7 @@ impl MoveRecord {
             color: color_str,
             move_notation,
             ai_metrics: Some(metrics),
+            clock: None,
         }
     }
 }
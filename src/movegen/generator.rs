@@ -219,80 +219,52 @@ impl MoveGenerator {
     fn generate_castling_moves(board: &Board, color: Color, moves: &mut Vec<ChessMove>) {
         use crate::board::castling::CastlingSide;
 
-        match color {
-            Color::White => {
-                // Kingside castling
-                if board.castling.has(Color::White, CastlingSide::KingSide) {
-                    let king_sq = 4; // e1
-
-                    // Check if squares between king and rook are empty
-                    if board.piece_on(5).is_none() && board.piece_on(6).is_none() {
-                        // Check if king is not in check
-                        if !board.in_check(Color::White) {
-                            // Check if king doesn't move through check (f1)
-                            if !board.is_square_attacked(5, Color::Black) {
-                                // Check if king doesn't land in check (g1) - this will be verified later
-                                moves.push(ChessMove::new_castle(king_sq, 6));
-                            }
-                        }
-                    }
-                }
+        let king_sq = board.king_sq[color as usize] as usize;
+        let back_rank = match color {
+            Color::White => 0,
+            Color::Black => 56,
+        };
+        let mut checked = None;
 
-                // Queenside castling
-                if board.castling.has(Color::White, CastlingSide::QueenSide) {
-                    let king_sq = 4; // e1
-
-                    // Check if squares between king and rook are empty
-                    if board.piece_on(3).is_none()
-                        && board.piece_on(2).is_none()
-                        && board.piece_on(1).is_none()
-                    {
-                        // Check if king is not in check
-                        if !board.in_check(Color::White) {
-                            // Check if king doesn't move through check (d1)
-                            if !board.is_square_attacked(3, Color::Black) {
-                                moves.push(ChessMove::new_castle(king_sq, 2));
-                            }
-                        }
-                    }
-                }
+        for side in [CastlingSide::KingSide, CastlingSide::QueenSide] {
+            if !board.castling.has(color, side) || king_sq & !7 != back_rank {
+                continue;
+            }
+            // The rook may sit on any file in Chess960; it must still be there
+            let rook_sq = board.castling.rook_square(color, side) as usize;
+            if board.pieces_of(color, Piece::Rook) & (1u64 << rook_sq) == 0 {
+                continue;
+            }
+            let king_to = back_rank + side.king_destination_file() as usize;
+            let rook_to = back_rank + side.rook_destination_file() as usize;
+
+            // Every square the king or rook crosses must be empty, apart from the two of them
+            let path = (rank_span(king_sq, king_to) | rank_span(rook_sq, rook_to))
+                & !(1u64 << king_sq)
+                & !(1u64 << rook_sq);
+            if board.occ_all & path != 0 {
+                continue;
             }
-            Color::Black => {
-                // Kingside castling
-                if board.castling.has(Color::Black, CastlingSide::KingSide) {
-                    let king_sq = 60; // e8
-
-                    // Check if squares between king and rook are empty
-                    if board.piece_on(61).is_none() && board.piece_on(62).is_none() {
-                        // Check if king is not in check
-                        if !board.in_check(Color::Black) {
-                            // Check if king doesn't move through check (f8)
-                            if !board.is_square_attacked(61, Color::White) {
-                                moves.push(ChessMove::new_castle(king_sq, 62));
-                            }
-                        }
-                    }
-                }
 
-                // Queenside castling
-                if board.castling.has(Color::Black, CastlingSide::QueenSide) {
-                    let king_sq = 60; // e8
-
-                    // Check if squares between king and rook are empty
-                    if board.piece_on(59).is_none()
-                        && board.piece_on(58).is_none()
-                        && board.piece_on(57).is_none()
-                    {
-                        // Check if king is not in check
-                        if !board.in_check(Color::Black) {
-                            // Check if king doesn't move through check (d8)
-                            if !board.is_square_attacked(59, Color::White) {
-                                moves.push(ChessMove::new_castle(king_sq, 58));
-                            }
-                        }
-                    }
+            // Check if king is not in check
+            if *checked.get_or_insert_with(|| board.in_check(color)) {
+                return;
+            }
+
+            // Check if king doesn't move through check; landing in check is verified later
+            let mut passed = rank_span(king_sq, king_to) & !(1u64 << king_sq) & !(1u64 << king_to);
+            let mut safe = true;
+            while passed != 0 {
+                let sq = passed.trailing_zeros() as u8;
+                passed &= passed - 1;
+                if board.is_square_attacked(sq, color.opponent()) {
+                    safe = false;
+                    break;
                 }
             }
+            if safe {
+                moves.push(ChessMove::new_castle(king_sq, rook_sq));
+            }
         }
     }
 
@@ -309,6 +281,12 @@ impl MoveGenerator {
     }
 }
 
+/// Squares from `a` to `b` inclusive, both on the same rank.
+fn rank_span(a: usize, b: usize) -> u64 {
+    let (low, high) = (a.min(b), a.max(b));
+    (u64::MAX >> (63 - high)) & (u64::MAX << low)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,116 @@
+use crate::board::{Board, ChessMove};
+use crate::opening::{BookResult, BookSelection, OpeningBook, PolyglotBook};
+use rand::Rng;
+
+/// An opening book in either of the supported formats.
+pub enum Book {
+    /// This engine's bincode format, keyed by its own Zobrist hashes
+    Native(OpeningBook),
+    /// A standard Polyglot `.bin` book
+    Polyglot(PolyglotBook),
+}
+
+impl Book {
+    /// Loads a book, detecting whether the file is a native or Polyglot book.
+    pub fn load(path: &str) -> std::io::Result<Self> {
+        let data = std::fs::read(path)?;
+        Self::from_bytes(&data)
+    }
+
+    pub fn from_bytes(data: &[u8]) -> std::io::Result<Self> {
+        // A Polyglot file's first key read as a bincode map length is far too large,
+        // so native books are tried first
+        if let Ok(book) = bincode::deserialize::<OpeningBook>(data) {
+            return Ok(Book::Native(book));
+        }
+
+        PolyglotBook::from_bytes(data)
+            .map(Book::Polyglot)
+            .map_err(|_| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "Not a native or Polyglot opening book",
+                )
+            })
+    }
+
+    pub fn probe(&self, board: &Board) -> Option<ChessMove> {
+        match self {
+            Book::Native(book) => book.probe(board.hash),
+            Book::Polyglot(book) => book.probe(board),
+        }
+    }
+
+    /// Chooses a book move using the selection policy.
+    pub fn select<R: Rng>(
+        &self,
+        board: &Board,
+        selection: &BookSelection,
+        rng: &mut R,
+    ) -> Option<ChessMove> {
+        match self {
+            Book::Native(book) => book.select(board.hash, selection, rng),
+            Book::Polyglot(book) => book.select(board, selection, rng),
+        }
+    }
+
+    /// Records a game result for each `(hash, move)` pair the book played.
+    /// Only native books keep learning data; this is a no-op for Polyglot books.
+    pub fn record_result(&mut self, book_moves: &[(u64, ChessMove)], result: BookResult) {
+        if let Book::Native(book) = self {
+            for &(hash, chess_move) in book_moves {
+                book.record_result(hash, chess_move, result);
+            }
+        }
+    }
+
+    /// Saves a native book (including learned results). Polyglot books are read-only.
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        match self {
+            Book::Native(book) => book.save(path),
+            Book::Polyglot(_) => Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "Saving Polyglot books is not supported",
+            )),
+        }
+    }
+}
+
+impl From<OpeningBook> for Book {
+    fn from(book: OpeningBook) -> Self {
+        Book::Native(book)
+    }
+}
+
+impl From<PolyglotBook> for Book {
+    fn from(book: PolyglotBook) -> Self {
+        Book::Polyglot(book)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opening::create_london_system_opening_book;
+
+    #[test]
+    fn test_detects_book_format() {
+        let native = create_london_system_opening_book();
+        let polyglot = PolyglotBook::from_opening_book(&native);
+
+        let native_bytes = bincode::serialize(&native).unwrap();
+        assert!(matches!(
+            Book::from_bytes(&native_bytes).unwrap(),
+            Book::Native(_)
+        ));
+
+        let book = Book::from_bytes(&polyglot.to_bytes()).unwrap();
+        assert!(matches!(book, Book::Polyglot(_)));
+        assert_eq!(
+            book.probe(&Board::startpos()),
+            native.probe(Board::startpos().hash)
+        );
+
+        assert!(Book::from_bytes(&[1, 2, 3]).is_err());
+    }
+}
@@ -0,0 +1,17 @@
+use crate::board::Color;
+use crate::opening::{OpeningBook, create_opening_book_from_repertoire};
+
+// ==============================
+// King's Indian Defense (Black)
+// ==============================
+
+const KINGS_INDIAN_REPERTOIRE: &str = include_str!("repertoires/kings_indian.txt");
+
+/// Build an opening book featuring the King's Indian Defense setup for Black.
+///
+/// Answers 1. d4, 1. c4 and 1. Nf3 with ...Nf6, ...g6, ...Bg7, ...d6 and ...O-O,
+/// and 1. e4 with the Pirc, which reaches the same setup.
+pub fn create_kings_indian_defense_opening_book() -> OpeningBook {
+    create_opening_book_from_repertoire(KINGS_INDIAN_REPERTOIRE, Color::Black)
+        .expect("built-in King's Indian repertoire is valid")
+}
@@ -1,11 +1,23 @@
+mod book;
 mod colle_system;
+mod kings_indian_defense;
 mod london_system;
 mod opening_book;
+mod polyglot;
+mod repertoire;
+mod selection;
+mod sicilian_defense;
 mod utils;
 
+pub use book::Book;
 pub use colle_system::create_colle_system_opening_book;
+pub use kings_indian_defense::create_kings_indian_defense_opening_book;
 pub use london_system::create_london_system_opening_book;
-pub use opening_book::{OpeningBook, create_basic_book};
+pub use opening_book::{BookResult, MoveStats, OpeningBook, create_basic_book};
+pub use polyglot::{PolyglotBook, PolyglotEntry, decode_move, encode_move};
+pub use repertoire::create_opening_book_from_repertoire;
+pub use selection::{BookSelection, SelectionPolicy};
+pub use sicilian_defense::create_sicilian_defense_opening_book;
 pub use utils::{
     A1, A2, A3, A4, A5, A6, A7, A8, B1, B2, B3, B4, B5, B6, B7, B8, C1, C2, C3, C4, C5, C6, C7, C8,
     D1, D2, D3, D4, D5, D6, D7, D8, E1, E2, E3, E4, E5, E6, E7, E8, F1, F2, F3, F4, F5, F6, F7, F8,
@@ -1,10 +1,52 @@
 use crate::board::{Board, ChessMove};
+use crate::opening::BookSelection;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Serialize, Deserialize)]
+/// Result of a game from the point of view of the side that played a book move.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BookResult {
+    Win,
+    Draw,
+    Loss,
+}
+
+/// Win/draw/loss record of a book move in games the engine has played.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MoveStats {
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+}
+
+impl MoveStats {
+    pub fn games(&self) -> u32 {
+        self.wins + self.draws + self.losses
+    }
+
+    pub fn record(&mut self, result: BookResult) {
+        match result {
+            BookResult::Win => self.wins += 1,
+            BookResult::Draw => self.draws += 1,
+            BookResult::Loss => self.losses += 1,
+        }
+    }
+
+    /// Multiplier applied to a move's weight: 1.0 with no games, towards 2.0 for a move
+    /// that keeps winning and towards 0.0 for one that keeps losing.
+    pub fn weight_factor(&self) -> f64 {
+        // Score with one virtual draw so a single game doesn't swing the weight to an extreme
+        let score = self.wins as f64 + 0.5 * self.draws as f64 + 0.5;
+        2.0 * score / (self.games() as f64 + 1.0)
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct OpeningBook {
     positions: HashMap<u64, Vec<(ChessMove, u32)>>,
+    /// Game results per position and book move, from PGN imports and the engine's own games
+    stats: HashMap<(u64, ChessMove), MoveStats>,
 }
 
 impl Default for OpeningBook {
@@ -17,6 +59,7 @@ impl OpeningBook {
     pub fn new() -> Self {
         Self {
             positions: HashMap::new(),
+            stats: HashMap::new(),
         }
     }
 
@@ -33,8 +76,33 @@ impl OpeningBook {
     pub fn finalize(&mut self) {
         // Sort moves by frequency (most common first)
         for moves in self.positions.values_mut() {
-            moves.sort_by(|a, b| b.1.cmp(&a.1));
+            moves.sort_by_key(|m| std::cmp::Reverse(m.1));
+        }
+    }
+
+    /// Removes moves seen fewer than `min_count` times, along with their results.
+    pub fn prune(&mut self, min_count: u32) {
+        for moves in self.positions.values_mut() {
+            moves.retain(|(_, count)| *count >= min_count);
         }
+        self.positions.retain(|_, moves| !moves.is_empty());
+
+        let positions = &self.positions;
+        self.stats.retain(|(hash, chess_move), _| {
+            positions
+                .get(hash)
+                .is_some_and(|moves| moves.iter().any(|(m, _)| m == chess_move))
+        });
+    }
+
+    /// Number of positions in the book.
+    pub fn num_positions(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// All book moves for a position with their counts, most common first.
+    pub fn moves(&self, hash: u64) -> Option<&[(ChessMove, u32)]> {
+        self.positions.get(&hash).map(|moves| moves.as_slice())
     }
 
     pub fn probe(&self, hash: u64) -> Option<ChessMove> {
@@ -44,6 +112,38 @@ impl OpeningBook {
             .map(|(m, _)| *m)
     }
 
+    /// Chooses a book move using the selection policy, with weights adjusted by learned results.
+    pub fn select<R: Rng>(
+        &self,
+        hash: u64,
+        selection: &BookSelection,
+        rng: &mut R,
+    ) -> Option<ChessMove> {
+        let candidates: Vec<(ChessMove, f64)> = self
+            .moves(hash)?
+            .iter()
+            .filter(|(_, count)| *count >= selection.min_weight)
+            .map(|&(m, count)| (m, count as f64 * self.stats(hash, m).weight_factor()))
+            .collect();
+        selection.choose(&candidates, rng)
+    }
+
+    /// Learned results for a book move (all zero if it has never been played).
+    pub fn stats(&self, hash: u64, chess_move: ChessMove) -> MoveStats {
+        self.stats
+            .get(&(hash, chess_move))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Records the result of a game in which `chess_move` was played from this position.
+    pub fn record_result(&mut self, hash: u64, chess_move: ChessMove, result: BookResult) {
+        self.stats
+            .entry((hash, chess_move))
+            .or_default()
+            .record(result);
+    }
+
     pub fn save(&self, path: &str) -> std::io::Result<()> {
         let encoded = bincode::serialize(self).unwrap();
         std::fs::write(path, encoded)
@@ -214,7 +314,7 @@ pub fn create_basic_book() -> OpeningBook {
     // If Black castles instead
     let mut main_line_oo = main_line;
     // Black castles kingside (e8-g8)
-    let castle_move = ChessMove::new_castle(60, 62);
+    let castle_move = ChessMove::new_castle(60, 63);
     main_line_oo.make_move(castle_move);
     book.add_move(main_line_oo.hash, mv(G1, F3)); // 6. Nf3 if not already played
 
@@ -223,3 +323,97 @@ pub fn create_basic_book() -> OpeningBook {
 
     book
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    fn two_move_book() -> (OpeningBook, u64) {
+        let hash = Board::startpos().hash;
+        let mut book = OpeningBook::new();
+        book.add_move(hash, ChessMove::new(11, 27)); // d4
+        book.add_move(hash, ChessMove::new(11, 27));
+        book.add_move(hash, ChessMove::new(12, 28)); // e4
+        book.finalize();
+        (book, hash)
+    }
+
+    #[test]
+    fn test_min_weight_filters_rare_moves() {
+        let (book, hash) = two_move_book();
+        let selection = BookSelection::weighted_random().with_min_weight(2);
+        let mut rng = StdRng::seed_from_u64(3);
+
+        for _ in 0..50 {
+            assert_eq!(
+                book.select(hash, &selection, &mut rng),
+                Some(ChessMove::new(11, 27))
+            );
+        }
+        let too_strict = BookSelection::best_only().with_min_weight(3);
+        assert_eq!(book.select(hash, &too_strict, &mut rng), None);
+    }
+
+    #[test]
+    fn test_learning_shifts_best_move() {
+        let (mut book, hash) = two_move_book();
+        let mut rng = StdRng::seed_from_u64(3);
+        let d4 = ChessMove::new(11, 27);
+        let e4 = ChessMove::new(12, 28);
+
+        assert_eq!(
+            book.select(hash, &BookSelection::best_only(), &mut rng),
+            Some(d4)
+        );
+
+        for _ in 0..3 {
+            book.record_result(hash, d4, BookResult::Loss);
+            book.record_result(hash, e4, BookResult::Win);
+        }
+        assert_eq!(
+            book.stats(hash, e4),
+            MoveStats {
+                wins: 3,
+                draws: 0,
+                losses: 0
+            }
+        );
+        assert_eq!(
+            book.select(hash, &BookSelection::best_only(), &mut rng),
+            Some(e4)
+        );
+    }
+
+    #[test]
+    fn test_prune_drops_rare_moves_and_their_stats() {
+        let (mut book, hash) = two_move_book();
+        book.record_result(hash, ChessMove::new(12, 28), BookResult::Win);
+        book.prune(2);
+
+        assert_eq!(book.moves(hash), Some(&[(ChessMove::new(11, 27), 2)][..]));
+        assert_eq!(
+            book.stats(hash, ChessMove::new(12, 28)),
+            MoveStats::default()
+        );
+
+        book.prune(3);
+        assert_eq!(book.num_positions(), 0);
+    }
+
+    #[test]
+    fn test_stats_survive_save_and_load() {
+        let (mut book, hash) = two_move_book();
+        book.record_result(hash, ChessMove::new(12, 28), BookResult::Draw);
+
+        let path = std::env::temp_dir().join("rusty_chess_book_learning_test.bin");
+        let path = path.to_str().unwrap();
+        book.save(path).unwrap();
+        let loaded = OpeningBook::load(path).unwrap();
+        std::fs::remove_file(path).ok();
+
+        assert_eq!(loaded.stats(hash, ChessMove::new(12, 28)).draws, 1);
+        assert_eq!(loaded.moves(hash), book.moves(hash));
+    }
+}
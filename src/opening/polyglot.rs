@@ -0,0 +1,319 @@
+use crate::board::{Board, ChessMove, Piece};
+use crate::movegen::MoveGenerator;
+use crate::opening::{BookSelection, OpeningBook};
+use crate::search::compute_polyglot_hash;
+use rand::Rng;
+use std::collections::HashSet;
+
+/// Size of one book entry on disk
+const ENTRY_SIZE: usize = 16;
+
+/// A single Polyglot book entry: one move from one position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PolyglotEntry {
+    /// Polyglot hash of the position (see `compute_polyglot_hash`)
+    pub key: u64,
+    /// Move in Polyglot encoding
+    pub raw_move: u16,
+    /// Relative move weight, higher is better
+    pub weight: u16,
+    /// Learning data, unused by most books
+    pub learn: u32,
+}
+
+impl PolyglotEntry {
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            key: u64::from_be_bytes(bytes[0..8].try_into().unwrap()),
+            raw_move: u16::from_be_bytes([bytes[8], bytes[9]]),
+            weight: u16::from_be_bytes([bytes[10], bytes[11]]),
+            learn: u32::from_be_bytes(bytes[12..16].try_into().unwrap()),
+        }
+    }
+
+    fn to_bytes(self) -> [u8; ENTRY_SIZE] {
+        let mut bytes = [0u8; ENTRY_SIZE];
+        bytes[0..8].copy_from_slice(&self.key.to_be_bytes());
+        bytes[8..10].copy_from_slice(&self.raw_move.to_be_bytes());
+        bytes[10..12].copy_from_slice(&self.weight.to_be_bytes());
+        bytes[12..16].copy_from_slice(&self.learn.to_be_bytes());
+        bytes
+    }
+}
+
+/// An opening book in the Polyglot `.bin` format.
+///
+/// The file is a sequence of 16-byte big-endian entries sorted by key:
+///
+/// | bytes | field  | meaning                                         |
+/// |-------|--------|-------------------------------------------------|
+/// | 0-7   | key    | Polyglot hash of the position                   |
+/// | 8-9   | move   | to file/rank, from file/rank, promotion (3 bits each) |
+/// | 10-11 | weight | relative move weight                            |
+/// | 12-15 | learn  | learning data                                   |
+///
+/// Castling is stored as the king capturing its own rook (e1h1 for White short castling).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PolyglotBook {
+    entries: Vec<PolyglotEntry>,
+}
+
+impl PolyglotBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a book from entries in any order.
+    pub fn from_entries(mut entries: Vec<PolyglotEntry>) -> Self {
+        entries.sort_by_key(|e| (e.key, std::cmp::Reverse(e.weight), e.raw_move));
+        Self { entries }
+    }
+
+    /// Exports the lines of a native book, walking it from the starting position.
+    /// Move counts become weights (saturating at `u16::MAX`).
+    pub fn from_opening_book(book: &OpeningBook) -> Self {
+        let mut entries = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack = vec![Board::startpos()];
+
+        while let Some(board) = stack.pop() {
+            if !visited.insert(board.hash) {
+                continue;
+            }
+            let Some(moves) = book.moves(board.hash) else {
+                continue;
+            };
+
+            let key = compute_polyglot_hash(&board);
+            for &(chess_move, count) in moves {
+                entries.push(PolyglotEntry {
+                    key,
+                    raw_move: encode_move(chess_move),
+                    weight: count.min(u16::MAX as u32) as u16,
+                    learn: 0,
+                });
+
+                let mut next = board;
+                next.make_move(chess_move);
+                stack.push(next);
+            }
+        }
+
+        Self::from_entries(entries)
+    }
+
+    pub fn entries(&self) -> &[PolyglotEntry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Adds a move, accumulating its weight if it is already in the book.
+    pub fn add_move(&mut self, board: &Board, chess_move: ChessMove, weight: u16) {
+        let key = compute_polyglot_hash(board);
+        let raw_move = encode_move(chess_move);
+
+        let start = self.entries.partition_point(|e| e.key < key);
+        let end = start + self.entries[start..].partition_point(|e| e.key == key);
+        match self.entries[start..end]
+            .iter_mut()
+            .find(|e| e.raw_move == raw_move)
+        {
+            Some(entry) => entry.weight = entry.weight.saturating_add(weight),
+            None => self.entries.insert(
+                end,
+                PolyglotEntry {
+                    key,
+                    raw_move,
+                    weight,
+                    learn: 0,
+                },
+            ),
+        }
+    }
+
+    /// All entries for a position, found by binary search.
+    pub fn lookup(&self, key: u64) -> &[PolyglotEntry] {
+        let start = self.entries.partition_point(|e| e.key < key);
+        let end = start + self.entries[start..].partition_point(|e| e.key == key);
+        &self.entries[start..end]
+    }
+
+    /// Legal book moves for a position with their weights, highest weight first.
+    pub fn moves(&self, board: &Board) -> Vec<(ChessMove, u16)> {
+        let mut moves: Vec<(ChessMove, u16)> = self
+            .lookup(compute_polyglot_hash(board))
+            .iter()
+            .filter_map(|e| decode_move(board, e.raw_move).map(|m| (m, e.weight)))
+            .collect();
+        moves.sort_by_key(|m| std::cmp::Reverse(m.1));
+        moves
+    }
+
+    /// Returns the highest-weighted book move for the position.
+    pub fn probe(&self, board: &Board) -> Option<ChessMove> {
+        self.moves(board).first().map(|(m, _)| *m)
+    }
+
+    /// Chooses a book move using the selection policy.
+    pub fn select<R: Rng>(
+        &self,
+        board: &Board,
+        selection: &BookSelection,
+        rng: &mut R,
+    ) -> Option<ChessMove> {
+        let candidates: Vec<(ChessMove, f64)> = self
+            .moves(board)
+            .into_iter()
+            .filter(|(_, weight)| *weight as u32 >= selection.min_weight)
+            .map(|(m, weight)| (m, weight as f64))
+            .collect();
+        selection.choose(&candidates, rng)
+    }
+
+    pub fn from_bytes(data: &[u8]) -> std::io::Result<Self> {
+        if !data.len().is_multiple_of(ENTRY_SIZE) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Polyglot book size is not a multiple of 16 bytes",
+            ));
+        }
+
+        let entries: Vec<PolyglotEntry> = data
+            .chunks_exact(ENTRY_SIZE)
+            .map(PolyglotEntry::from_bytes)
+            .collect();
+        if entries.windows(2).any(|w| w[0].key > w[1].key) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Polyglot book entries are not sorted by key",
+            ));
+        }
+
+        Ok(Self { entries })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.entries.iter().flat_map(|e| e.to_bytes()).collect()
+    }
+
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        std::fs::write(path, self.to_bytes())
+    }
+
+    pub fn load(path: &str) -> std::io::Result<Self> {
+        let data = std::fs::read(path)?;
+        Self::from_bytes(&data)
+    }
+}
+
+/// Encodes a move in Polyglot format. Castling is king-takes-rook, as in `ChessMove`.
+pub fn encode_move(chess_move: ChessMove) -> u16 {
+    let from = chess_move.from();
+    let to = chess_move.to();
+
+    let promotion = match chess_move.promotion_piece() {
+        Some(Piece::Knight) => 1,
+        Some(Piece::Bishop) => 2,
+        Some(Piece::Rook) => 3,
+        Some(Piece::Queen) => 4,
+        _ => 0,
+    };
+
+    ((promotion as u16) << 12) | ((from as u16) << 6) | to as u16
+}
+
+/// Decodes a Polyglot move into the matching legal move, or None if it isn't legal here.
+pub fn decode_move(board: &Board, raw_move: u16) -> Option<ChessMove> {
+    let to = (raw_move & 0x3F) as usize;
+    let from = ((raw_move >> 6) & 0x3F) as usize;
+    let promotion = match (raw_move >> 12) & 0x7 {
+        1 => Some(Piece::Knight),
+        2 => Some(Piece::Bishop),
+        3 => Some(Piece::Rook),
+        4 => Some(Piece::Queen),
+        _ => None,
+    };
+
+    let mut moves = Vec::with_capacity(64);
+    MoveGenerator::generate_legal_moves(board, &mut moves);
+
+    moves
+        .into_iter()
+        .find(|m| m.from() == from && m.to() == to && m.promotion_piece() == promotion)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opening::create_london_system_opening_book;
+
+    #[test]
+    fn test_castling_uses_king_takes_rook() {
+        let board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+        let short = ChessMove::new_castle(4, 7);
+        let long = ChessMove::new_castle(4, 0);
+
+        // e1h1 and e1a1
+        assert_eq!(encode_move(short), (4 << 6) | 7);
+        assert_eq!(encode_move(long), 4 << 6);
+        assert_eq!(decode_move(&board, (4 << 6) | 7), Some(short));
+        assert_eq!(decode_move(&board, 4 << 6), Some(long));
+    }
+
+    #[test]
+    fn test_promotion_round_trip() {
+        let board = Board::from_fen("8/1P2k3/8/8/8/8/8/4K3 w - - 0 1");
+        let promotion = ChessMove::new_promotion(49, 57, Piece::Knight);
+
+        let raw = encode_move(promotion);
+        assert_eq!(raw >> 12, 1);
+        assert_eq!(decode_move(&board, raw), Some(promotion));
+    }
+
+    #[test]
+    fn test_binary_round_trip_and_probe() {
+        let mut book = PolyglotBook::new();
+        let board = Board::startpos();
+        book.add_move(&board, ChessMove::new(12, 28), 10); // e4
+        book.add_move(&board, ChessMove::new(11, 27), 20); // d4
+        book.add_move(&board, ChessMove::new(12, 28), 15); // e4 again
+
+        let loaded = PolyglotBook::from_bytes(&book.to_bytes()).unwrap();
+        assert_eq!(loaded, book);
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded.probe(&board), Some(ChessMove::new(12, 28)));
+        assert_eq!(loaded.lookup(0x463b96181691fc9c).len(), 2);
+    }
+
+    #[test]
+    fn test_rejects_unsorted_or_truncated_data() {
+        let high = PolyglotEntry {
+            key: 2,
+            raw_move: 0,
+            weight: 1,
+            learn: 0,
+        };
+        let low = PolyglotEntry { key: 1, ..high };
+        let bytes: Vec<u8> = [high, low].iter().flat_map(|e| e.to_bytes()).collect();
+
+        assert!(PolyglotBook::from_bytes(&bytes).is_err());
+        assert!(PolyglotBook::from_bytes(&bytes[..20]).is_err());
+    }
+
+    #[test]
+    fn test_export_native_book() {
+        let native = create_london_system_opening_book();
+        let exported = PolyglotBook::from_opening_book(&native);
+        let board = Board::startpos();
+
+        assert!(!exported.is_empty());
+        assert_eq!(exported.probe(&board), native.probe(board.hash));
+    }
+}
@@ -0,0 +1,193 @@
+use crate::board::{Board, Color};
+use crate::opening::OpeningBook;
+use crate::pgn::parse_san;
+
+/// Builds an opening book from a repertoire written as SAN move trees.
+///
+/// The format is PGN-style movetext. Lines are separated by blank lines and each
+/// starts from the standard position. A parenthesized variation is an alternative
+/// to the move just before it, as in PGN:
+///
+/// ```text
+/// # Najdorf
+/// 1. e4 c5 2. Nf3 d6 3. d4 cxd4 4. Nxd4 Nf6 5. Nc3 a6
+///    6. Be2 (6. Bg5 e6) (6. Be3 e5) e5
+///
+/// # Alapin
+/// 1. e4 c5 2. c3 Nf6 3. e5 Nd5
+/// ```
+///
+/// `#` starts a comment that runs to the end of the line, and `{...}` comments and move
+/// numbers are ignored. Only moves made by `side` are added to the book; the other side's
+/// moves just select the positions the repertoire answers.
+pub fn create_opening_book_from_repertoire(text: &str, side: Color) -> Result<OpeningBook, String> {
+    let mut book = OpeningBook::new();
+
+    for (index, line) in split_lines(text).iter().enumerate() {
+        add_line(&mut book, line, side)
+            .map_err(|e| format!("Repertoire line {}: {}", index + 1, e))?;
+    }
+
+    book.finalize();
+    Ok(book)
+}
+
+/// Splits a repertoire into its blank-line separated lines, with comments removed.
+fn split_lines(text: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut in_comment = false;
+
+    for row in text.lines() {
+        // Only a truly blank row ends a line; comment-only rows are skipped
+        if row.trim().is_empty() && !in_comment {
+            if !current.trim().is_empty() {
+                lines.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        let row = match row.split_once('#') {
+            Some((before, _)) if !in_comment => before,
+            _ => row,
+        };
+
+        for c in row.chars() {
+            match c {
+                '{' => in_comment = true,
+                '}' => in_comment = false,
+                // Pad parentheses so they become their own tokens
+                '(' | ')' if !in_comment => {
+                    current.push(' ');
+                    current.push(c);
+                    current.push(' ');
+                }
+                _ if !in_comment => current.push(c),
+                _ => {}
+            }
+        }
+        current.push(' ');
+    }
+
+    if !current.trim().is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Replays one line, including its variations, adding `side`'s moves to the book.
+fn add_line(book: &mut OpeningBook, line: &str, side: Color) -> Result<(), String> {
+    // Position before the most recent move, and the current position
+    let mut before: Option<Board> = None;
+    let mut board = Board::startpos();
+    let mut stack: Vec<(Option<Board>, Board)> = Vec::new();
+
+    for token in line.split_whitespace() {
+        match token {
+            "(" => {
+                let Some(previous) = before else {
+                    return Err("Variation has no move to replace".to_string());
+                };
+                stack.push((before, board));
+                // The variation replaces the last move, so it has no move of its own yet
+                before = None;
+                board = previous;
+            }
+            ")" => {
+                (before, board) = stack.pop().ok_or_else(|| "Unmatched ')'".to_string())?;
+            }
+            "1-0" | "0-1" | "1/2-1/2" | "*" => {}
+            _ => {
+                let san = token.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.');
+                if san.is_empty() {
+                    continue;
+                }
+
+                let chess_move = parse_san(&board, san)?;
+                if board.side_to_move == side {
+                    book.add_move(board.hash, chess_move);
+                }
+                before = Some(board);
+                board.make_move(chess_move);
+            }
+        }
+    }
+
+    if stack.is_empty() {
+        Ok(())
+    } else {
+        Err("Unclosed '('".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::ChessMove;
+    use crate::opening::{
+        create_kings_indian_defense_opening_book, create_sicilian_defense_opening_book,
+    };
+
+    #[test]
+    fn test_variations_branch_from_the_replaced_move() {
+        let text = "1. e4 c5 2. Nf3 (2. c3 Nf6) (2. Nc3 Nc6) d6 3. d4 cxd4";
+        let black = create_opening_book_from_repertoire(text, Color::Black).unwrap();
+        let white = create_opening_book_from_repertoire(text, Color::White).unwrap();
+
+        let mut board = Board::startpos();
+        board.make_move(ChessMove::new(12, 28)); // e4
+        assert_eq!(black.probe(board.hash), Some(ChessMove::new(50, 34))); // c5
+        assert_eq!(white.probe(board.hash), None);
+
+        board.make_move(ChessMove::new(50, 34));
+        let mut alapin = board;
+        alapin.make_move(ChessMove::new(10, 18)); // c3
+        assert_eq!(black.probe(alapin.hash), Some(ChessMove::new(62, 45))); // Nf6
+
+        let mut open = board;
+        open.make_move(ChessMove::new(6, 21)); // Nf3
+        assert_eq!(black.probe(open.hash), Some(ChessMove::new(51, 43))); // d6
+    }
+
+    #[test]
+    fn test_comments_and_blank_line_separated_lines() {
+        let text = "# Two lines\n1. d4 Nf6 {main move}\n\n1. c4\n# English\ne5\n";
+        let book = create_opening_book_from_repertoire(text, Color::Black).unwrap();
+
+        let mut english = Board::startpos();
+        english.make_move(ChessMove::new(10, 26)); // c4
+        assert_eq!(book.probe(english.hash), Some(ChessMove::new(52, 36))); // e5
+        assert_eq!(book.num_positions(), 2);
+    }
+
+    #[test]
+    fn test_builtin_black_repertoires_are_valid() {
+        let mut after_e4 = Board::startpos();
+        after_e4.make_move(ChessMove::new(12, 28));
+        let mut after_d4 = Board::startpos();
+        after_d4.make_move(ChessMove::new(11, 27));
+
+        let sicilian = create_sicilian_defense_opening_book();
+        assert_eq!(sicilian.probe(after_e4.hash), Some(ChessMove::new(50, 34))); // c5
+
+        let kings_indian = create_kings_indian_defense_opening_book();
+        assert_eq!(
+            kings_indian.probe(after_d4.hash),
+            Some(ChessMove::new(62, 45))
+        ); // Nf6
+        assert_eq!(
+            kings_indian.probe(after_e4.hash),
+            Some(ChessMove::new(51, 43))
+        ); // d6
+    }
+
+    #[test]
+    fn test_errors_name_the_line() {
+        let err = create_opening_book_from_repertoire("1. e4 e5\n\n1. d4 Ke7", Color::Black)
+            .err()
+            .unwrap();
+        assert!(err.starts_with("Repertoire line 2"), "{}", err);
+
+        assert!(create_opening_book_from_repertoire("1. e4 (e5", Color::Black).is_err());
+        assert!(create_opening_book_from_repertoire("( 1. e4 )", Color::Black).is_err());
+    }
+}
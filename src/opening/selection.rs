@@ -0,0 +1,127 @@
+use crate::board::ChessMove;
+use rand::Rng;
+
+/// How a move is chosen among the book moves for a position.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SelectionPolicy {
+    /// Always play the highest-weighted move
+    #[default]
+    BestOnly,
+    /// Pick a move at random with probability proportional to its weight
+    WeightedRandom,
+}
+
+/// Book move selection settings.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BookSelection {
+    pub policy: SelectionPolicy,
+    /// Moves with a raw weight below this are never played
+    pub min_weight: u32,
+}
+
+impl BookSelection {
+    pub fn best_only() -> Self {
+        Self::default()
+    }
+
+    pub fn weighted_random() -> Self {
+        Self {
+            policy: SelectionPolicy::WeightedRandom,
+            min_weight: 0,
+        }
+    }
+
+    pub fn with_min_weight(mut self, min_weight: u32) -> Self {
+        self.min_weight = min_weight;
+        self
+    }
+
+    /// Picks a move from weighted candidates. Candidates with a non-positive weight are skipped.
+    pub fn choose<R: Rng>(
+        &self,
+        candidates: &[(ChessMove, f64)],
+        rng: &mut R,
+    ) -> Option<ChessMove> {
+        let playable = candidates.iter().filter(|(_, weight)| *weight > 0.0);
+
+        match self.policy {
+            SelectionPolicy::BestOnly => playable
+                .fold(
+                    None,
+                    |best: Option<&(ChessMove, f64)>, candidate| match best {
+                        Some(b) if b.1 >= candidate.1 => Some(b),
+                        _ => Some(candidate),
+                    },
+                )
+                .map(|(m, _)| *m),
+            SelectionPolicy::WeightedRandom => {
+                let total: f64 = playable.clone().map(|(_, weight)| weight).sum();
+                if total <= 0.0 {
+                    return None;
+                }
+
+                let mut pick = rng.random_range(0.0..total);
+                let mut last = None;
+                for &(chess_move, weight) in playable {
+                    if pick < weight {
+                        return Some(chess_move);
+                    }
+                    pick -= weight;
+                    last = Some(chess_move);
+                }
+                // Floating point rounding can leave a sliver past the last move
+                last
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_best_only_picks_heaviest_move() {
+        let candidates = [
+            (ChessMove::new(12, 28), 3.0),
+            (ChessMove::new(11, 27), 7.0),
+            (ChessMove::new(6, 21), 0.0),
+        ];
+        let mut rng = StdRng::seed_from_u64(1);
+
+        assert_eq!(
+            BookSelection::best_only().choose(&candidates, &mut rng),
+            Some(ChessMove::new(11, 27))
+        );
+        assert_eq!(BookSelection::best_only().choose(&[], &mut rng), None);
+    }
+
+    #[test]
+    fn test_weighted_random_follows_weights_and_seed() {
+        let candidates = [
+            (ChessMove::new(12, 28), 1.0),
+            (ChessMove::new(11, 27), 3.0),
+            (ChessMove::new(6, 21), 0.0),
+        ];
+        let selection = BookSelection::weighted_random();
+
+        let picks = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..400)
+                .map(|_| selection.choose(&candidates, &mut rng).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        let first = picks(7);
+        assert_eq!(first, picks(7));
+        assert!(!first.contains(&ChessMove::new(6, 21)));
+
+        let d4 = first
+            .iter()
+            .filter(|m| **m == ChessMove::new(11, 27))
+            .count();
+        assert!((240..360).contains(&d4), "d4 picked {} of 400 times", d4);
+    }
+}
@@ -0,0 +1,17 @@
+use crate::board::Color;
+use crate::opening::{OpeningBook, create_opening_book_from_repertoire};
+
+// ==============================
+// Sicilian Defense (Black)
+// ==============================
+
+const SICILIAN_REPERTOIRE: &str = include_str!("repertoires/sicilian.txt");
+
+/// Build an opening book featuring the Sicilian Defense (1. e4 c5) for Black.
+///
+/// Covers the Najdorf against the Open Sicilian plus the common anti-Sicilians:
+/// the Moscow Variation, Closed Sicilian, Grand Prix, Alapin and Smith-Morra.
+pub fn create_sicilian_defense_opening_book() -> OpeningBook {
+    create_opening_book_from_repertoire(SICILIAN_REPERTOIRE, Color::Black)
+        .expect("built-in Sicilian repertoire is valid")
+}
@@ -0,0 +1,7 @@
+mod parser;
+mod san;
+mod writer;
+
+pub use parser::{PgnGame, parse_pgn};
+pub use san::{parse_san, san_candidates, to_san};
+pub use writer::MoveAnnotation;
@@ -0,0 +1,219 @@
+use crate::board::{Board, BoardFromFenError, ChessMove, Color};
+use crate::pgn::parse_san;
+
+/// A game read from a PGN file: its tag pairs and main-line moves in SAN.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PgnGame {
+    pub tags: Vec<(String, String)>,
+    pub moves: Vec<String>,
+}
+
+impl PgnGame {
+    /// Returns the value of a tag such as `White` or `Result`.
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The `Result` tag as a score for White (1.0, 0.5 or 0.0), or None if unknown.
+    pub fn white_score(&self) -> Option<f64> {
+        match self.tag("Result")? {
+            "1-0" => Some(1.0),
+            "0-1" => Some(0.0),
+            "1/2-1/2" => Some(0.5),
+            _ => None,
+        }
+    }
+
+    /// A player's rating from the `WhiteElo`/`BlackElo` tag, if present.
+    pub fn elo(&self, color: Color) -> Option<u32> {
+        let tag = match color {
+            Color::White => "WhiteElo",
+            Color::Black => "BlackElo",
+        };
+        self.tag(tag)?.parse().ok()
+    }
+
+    /// The starting position: the `FEN` tag if present, otherwise the standard position.
+    /// Fails if the `FEN` tag is malformed or describes an illegal position.
+    pub fn start_board(&self) -> Result<Board, BoardFromFenError> {
+        match self.tag("FEN") {
+            Some(fen) => Board::try_from_fen(fen),
+            None => Ok(Board::startpos()),
+        }
+    }
+
+    /// Replays the main line, returning each move together with the position it was played from.
+    /// Stops after `max_plies` moves if given.
+    pub fn replay(&self, max_plies: Option<usize>) -> Result<Vec<(Board, ChessMove)>, String> {
+        let mut board = self
+            .start_board()
+            .map_err(|e| format!("Invalid FEN tag: {}", e))?;
+        let limit = max_plies.unwrap_or(usize::MAX);
+
+        self.moves
+            .iter()
+            .take(limit)
+            .map(|san| {
+                let chess_move = parse_san(&board, san)?;
+                let position = board;
+                board.make_move(chess_move);
+                Ok((position, chess_move))
+            })
+            .collect()
+    }
+}
+
+/// Parses every game in a PGN document.
+///
+/// Comments, recursive variations, NAGs and move numbers are skipped, so only
+/// the main line is kept. Moves are not validated here; see `PgnGame::replay`.
+pub fn parse_pgn(text: &str) -> Vec<PgnGame> {
+    let mut games = Vec::new();
+    let mut game = PgnGame::default();
+    let mut in_movetext = false;
+
+    // Brace comments and variations may span lines
+    let mut comment = false;
+    let mut variation_depth = 0usize;
+
+    for line in text.lines() {
+        let line = line.trim();
+
+        if !comment && variation_depth == 0 && line.starts_with('[') {
+            if in_movetext {
+                games.push(std::mem::take(&mut game));
+                in_movetext = false;
+            }
+            if let Some(tag) = parse_tag(line) {
+                game.tags.push(tag);
+            }
+            continue;
+        }
+        // "%" escapes a whole line
+        if line.starts_with('%') {
+            continue;
+        }
+
+        let mut token = String::new();
+        for c in line.chars() {
+            if comment {
+                comment = c != '}';
+                continue;
+            }
+            match c {
+                '{' => comment = true,
+                ';' if variation_depth == 0 => break,
+                '(' => variation_depth += 1,
+                ')' => variation_depth = variation_depth.saturating_sub(1),
+                _ if variation_depth > 0 => {}
+                c if c.is_whitespace() => {
+                    push_token(&mut game, &mut token, &mut in_movetext);
+                }
+                c => token.push(c),
+            }
+            if matches!(c, '{' | '(' | ')') {
+                push_token(&mut game, &mut token, &mut in_movetext);
+            }
+        }
+        push_token(&mut game, &mut token, &mut in_movetext);
+    }
+
+    if in_movetext || !game.tags.is_empty() {
+        games.push(game);
+    }
+    games
+}
+
+fn parse_tag(line: &str) -> Option<(String, String)> {
+    let inner = line.strip_prefix('[')?.strip_suffix(']')?.trim();
+    let (name, value) = inner.split_once(char::is_whitespace)?;
+    let value = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+    Some((name.to_string(), value.replace("\\\"", "\"")))
+}
+
+fn push_token(game: &mut PgnGame, token: &mut String, in_movetext: &mut bool) {
+    if token.is_empty() {
+        return;
+    }
+    let text = std::mem::take(token);
+    *in_movetext = true;
+
+    // Skip NAGs ($1), results and move numbers ("12." or "12...")
+    if text.starts_with('$') || matches!(text.as_str(), "1-0" | "0-1" | "1/2-1/2" | "*") {
+        return;
+    }
+    let san = text.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.');
+    if !san.is_empty() {
+        game.moves.push(san.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+[Event "Casual"]
+[White "Alice"]
+[Black "Bob"]
+[Result "1-0"]
+[WhiteElo "2100"]
+
+1. e4 e5 2. Nf3 {the main move} Nc6 (2... d6 3. d4) 3. Bb5 $1 a6
+4. Ba4; Ruy Lopez
+Nf6 1-0
+
+[Event "Second"]
+[Result "1/2-1/2"]
+
+1.d4 d5 2.c4 1/2-1/2
+"#;
+
+    #[test]
+    fn test_parse_tags_and_main_line() {
+        let games = parse_pgn(SAMPLE);
+        assert_eq!(games.len(), 2);
+
+        let first = &games[0];
+        assert_eq!(first.tag("White"), Some("Alice"));
+        assert_eq!(first.white_score(), Some(1.0));
+        assert_eq!(first.elo(Color::White), Some(2100));
+        assert_eq!(first.elo(Color::Black), None);
+        assert_eq!(
+            first.moves,
+            vec!["e4", "e5", "Nf3", "Nc6", "Bb5", "a6", "Ba4", "Nf6"]
+        );
+
+        assert_eq!(games[1].moves, vec!["d4", "d5", "c4"]);
+        assert_eq!(games[1].white_score(), Some(0.5));
+    }
+
+    #[test]
+    fn test_replay_stops_at_ply_limit() {
+        let games = parse_pgn(SAMPLE);
+        let plies = games[0].replay(Some(3)).unwrap();
+
+        assert_eq!(plies.len(), 3);
+        assert_eq!(plies[0].0.hash, Board::startpos().hash);
+        assert_eq!(plies[2].1, ChessMove::new(6, 21));
+
+        let bad = PgnGame {
+            tags: Vec::new(),
+            moves: vec!["e4".to_string(), "Ke3".to_string()],
+        };
+        assert!(bad.replay(None).is_err());
+    }
+
+    #[test]
+    fn test_bad_fen_tag_is_an_error() {
+        let game = PgnGame {
+            tags: vec![("FEN".to_string(), "not a position".to_string())],
+            moves: vec!["e4".to_string()],
+        };
+        assert!(game.start_board().is_err());
+        assert!(game.replay(None).is_err());
+    }
+}
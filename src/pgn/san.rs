@@ -0,0 +1,291 @@
+use crate::board::{Board, ChessMove, Piece};
+use crate::movegen::MoveGenerator;
+
+/// Parses a move in Standard Algebraic Notation (e.g. `Nf3`, `exd5`, `O-O`, `e8=Q+`)
+/// into the matching legal move for the position.
+///
+/// Check/mate markers and annotation suffixes (`+`, `#`, `!`, `?`) are ignored.
+pub fn parse_san(board: &Board, san: &str) -> Result<ChessMove, String> {
+    match san_candidates(board, san)?[..] {
+        [chess_move] => Ok(chess_move),
+        [] => Err(format!("Illegal SAN move: '{}'", san)),
+        ref candidates => {
+            let names: Vec<String> = candidates.iter().map(|&m| to_san(board, m)).collect();
+            Err(format!(
+                "Ambiguous SAN move: '{}' could be {}",
+                san,
+                names.join(" or ")
+            ))
+        }
+    }
+}
+
+/// Every legal move that a SAN move could mean: none when it is illegal, several when
+/// it is ambiguous. A pawn move to the last rank without a promotion piece matches
+/// each promotion. Errors only when the text isn't SAN at all.
+pub fn san_candidates(board: &Board, san: &str) -> Result<Vec<ChessMove>, String> {
+    let text = san.trim_end_matches(['+', '#', '!', '?']);
+    if text.is_empty() {
+        return Err(format!("Invalid SAN move: '{}'", san));
+    }
+
+    let mut legal_moves = Vec::with_capacity(128);
+    MoveGenerator::generate_legal_moves(board, &mut legal_moves);
+
+    if let Some(kingside) = castling_side(text) {
+        return Ok(legal_moves
+            .into_iter()
+            .filter(|m| m.is_castle() && (m.to() > m.from()) == kingside)
+            .collect());
+    }
+
+    let (piece, rest) = match text.chars().next() {
+        Some('N') => (Piece::Knight, &text[1..]),
+        Some('B') => (Piece::Bishop, &text[1..]),
+        Some('R') => (Piece::Rook, &text[1..]),
+        Some('Q') => (Piece::Queen, &text[1..]),
+        Some('K') => (Piece::King, &text[1..]),
+        _ => (Piece::Pawn, text),
+    };
+
+    // Promotion suffix: "=Q", or a bare trailing piece letter as some exporters write it
+    let (rest, promotion) = match rest.rsplit_once('=') {
+        Some((head, promo)) => (head, Some(promotion_piece(promo, san)?)),
+        None if piece == Piece::Pawn && rest.ends_with(['N', 'B', 'R', 'Q']) => {
+            let (head, promo) = rest.split_at(rest.len() - 1);
+            (head, Some(promotion_piece(promo, san)?))
+        }
+        None => (rest, None),
+    };
+
+    let rest = rest.replace('x', "");
+    if rest.len() < 2 || !rest.is_ascii() {
+        return Err(format!("Invalid SAN move: '{}'", san));
+    }
+    let (disambiguation, target) = rest.split_at(rest.len() - 2);
+    let to = square_index(target).ok_or_else(|| format!("Invalid SAN move: '{}'", san))?;
+
+    let mut from_file = None;
+    let mut from_rank = None;
+    for c in disambiguation.chars() {
+        match c {
+            'a'..='h' => from_file = Some(c as usize - 'a' as usize),
+            '1'..='8' => from_rank = Some(c as usize - '1' as usize),
+            _ => return Err(format!("Invalid SAN move: '{}'", san)),
+        }
+    }
+
+    Ok(legal_moves
+        .into_iter()
+        .filter(|m| {
+            !m.is_castle()
+                && m.to() == to
+                && (m.promotion_piece() == promotion || promotion.is_none())
+                && board.piece_on(m.from() as u8).map(|(_, p)| p) == Some(piece)
+                && from_file.is_none_or(|file| m.from() % 8 == file)
+                && from_rank.is_none_or(|rank| m.from() / 8 == rank)
+        })
+        .collect())
+}
+
+/// Returns Some(true) for kingside and Some(false) for queenside castling notation.
+fn castling_side(text: &str) -> Option<bool> {
+    match text {
+        "O-O" | "0-0" => Some(true),
+        "O-O-O" | "0-0-0" => Some(false),
+        _ => None,
+    }
+}
+
+/// Formats a legal move in Standard Algebraic Notation, with a `+` or `#` suffix
+/// when it gives check or mate.
+pub fn to_san(board: &Board, chess_move: ChessMove) -> String {
+    let mut legal_moves = Vec::with_capacity(128);
+    MoveGenerator::generate_legal_moves(board, &mut legal_moves);
+
+    let mut san = match (
+        chess_move.to_castling_notation(),
+        board.piece_on(chess_move.from() as u8),
+    ) {
+        (Some(castling), _) => castling.to_string(),
+        (None, Some((_, piece))) => {
+            let from = chess_move.from();
+            let to = chess_move.to();
+            let capture = board.is_capture(chess_move);
+            let mut text = String::new();
+
+            if piece == Piece::Pawn {
+                if capture {
+                    text.push(file_char(from));
+                }
+            } else {
+                text.push(piece_letter(piece));
+                // Name the file, the rank, or both when another piece of the same kind could go there
+                let rivals: Vec<usize> = legal_moves
+                    .iter()
+                    .filter(|m| {
+                        !m.is_castle()
+                            && m.to() == to
+                            && m.from() != from
+                            && board.piece_on(m.from() as u8).map(|(_, p)| p) == Some(piece)
+                    })
+                    .map(|m| m.from())
+                    .collect();
+                if !rivals.is_empty() {
+                    if rivals.iter().all(|&r| r % 8 != from % 8) {
+                        text.push(file_char(from));
+                    } else if rivals.iter().all(|&r| r / 8 != from / 8) {
+                        text.push(rank_char(from));
+                    } else {
+                        text.push(file_char(from));
+                        text.push(rank_char(from));
+                    }
+                }
+            }
+
+            if capture {
+                text.push('x');
+            }
+            text.push(file_char(to));
+            text.push(rank_char(to));
+            if let Some(promotion) = chess_move.promotion_piece() {
+                text.push('=');
+                text.push(piece_letter(promotion));
+            }
+            text
+        }
+        (None, None) => chess_move.to_uci(),
+    };
+
+    let mut after = *board;
+    after.make_move(chess_move);
+    if after.in_check(after.side_to_move) {
+        MoveGenerator::generate_legal_moves(&after, &mut legal_moves);
+        san.push(if legal_moves.is_empty() { '#' } else { '+' });
+    }
+    san
+}
+
+fn piece_letter(piece: Piece) -> char {
+    match piece {
+        Piece::Knight => 'N',
+        Piece::Bishop => 'B',
+        Piece::Rook => 'R',
+        Piece::Queen => 'Q',
+        Piece::King => 'K',
+        Piece::Pawn => 'P',
+    }
+}
+
+fn file_char(sq: usize) -> char {
+    (b'a' + (sq % 8) as u8) as char
+}
+
+fn rank_char(sq: usize) -> char {
+    (b'1' + (sq / 8) as u8) as char
+}
+
+fn promotion_piece(letter: &str, san: &str) -> Result<Piece, String> {
+    match letter {
+        "N" => Ok(Piece::Knight),
+        "B" => Ok(Piece::Bishop),
+        "R" => Ok(Piece::Rook),
+        "Q" => Ok(Piece::Queen),
+        _ => Err(format!("Invalid promotion in SAN move: '{}'", san)),
+    }
+}
+
+fn square_index(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let file = bytes[0].checked_sub(b'a').filter(|f| *f < 8)?;
+    let rank = bytes[1].checked_sub(b'1').filter(|r| *r < 8)?;
+    Some(rank as usize * 8 + file as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_basic_moves() {
+        let board = Board::startpos();
+        assert_eq!(parse_san(&board, "e4"), Ok(ChessMove::new(12, 28)));
+        assert_eq!(parse_san(&board, "Nf3"), Ok(ChessMove::new(6, 21)));
+        assert!(parse_san(&board, "e5").is_err());
+        assert!(parse_san(&board, "Qh5").is_err());
+    }
+
+    #[test]
+    fn test_parse_castling_promotion_and_disambiguation() {
+        let board = Board::from_fen("r3k2r/1P6/8/8/8/8/8/R3K2R w KQkq - 0 1");
+        assert_eq!(parse_san(&board, "O-O"), Ok(ChessMove::new_castle(4, 7)));
+        assert_eq!(parse_san(&board, "0-0-0+"), Ok(ChessMove::new_castle(4, 0)));
+        assert_eq!(
+            parse_san(&board, "bxa8=Q+"),
+            Ok(ChessMove::new_promotion(49, 56, Piece::Queen))
+        );
+        assert_eq!(
+            parse_san(&board, "b8N"),
+            Ok(ChessMove::new_promotion(49, 57, Piece::Knight))
+        );
+
+        // A promotion needs its piece
+        assert_eq!(san_candidates(&board, "b8").map(|m| m.len()), Ok(4));
+        assert!(parse_san(&board, "b8").is_err());
+
+        // Both rooks can reach d1 and a3
+        let board = Board::from_fen("4k3/8/8/8/R7/8/8/R4RK1 w - - 0 1");
+        assert_eq!(
+            parse_san(&board, "Rd1"),
+            Err("Ambiguous SAN move: 'Rd1' could be Rad1 or Rfd1".to_string())
+        );
+        assert_eq!(parse_san(&board, "Rad1"), Ok(ChessMove::new(0, 3)));
+        assert_eq!(parse_san(&board, "Rfd1"), Ok(ChessMove::new(5, 3)));
+        assert!(parse_san(&board, "Ra3").is_err());
+        assert_eq!(parse_san(&board, "R4a3"), Ok(ChessMove::new(24, 16)));
+    }
+
+    #[test]
+    fn test_to_san() {
+        let board = Board::startpos();
+        assert_eq!(to_san(&board, ChessMove::new(12, 28)), "e4");
+        assert_eq!(to_san(&board, ChessMove::new(6, 21)), "Nf3");
+
+        let board = Board::from_fen("r3k2r/1P6/8/8/8/8/8/R3K2R w KQkq - 0 1");
+        assert_eq!(to_san(&board, ChessMove::new_castle(4, 7)), "O-O");
+        assert_eq!(
+            to_san(&board, ChessMove::new_promotion(49, 56, Piece::Queen)),
+            "bxa8=Q+"
+        );
+
+        let board = Board::from_fen("4k3/8/8/8/R7/8/8/R4RK1 w - - 0 1");
+        assert_eq!(to_san(&board, ChessMove::new(0, 3)), "Rad1");
+        assert_eq!(to_san(&board, ChessMove::new(24, 16)), "R4a3");
+
+        // Fool's mate
+        let board =
+            Board::from_fen("rnbqkbnr/pppp1ppp/8/4p3/6P1/5P2/PPPPP2P/RNBQKBNR b KQkq g3 0 2");
+        assert_eq!(to_san(&board, ChessMove::new(59, 31)), "Qh4#");
+    }
+
+    #[test]
+    fn test_to_san_round_trips_through_parse_san() {
+        let board =
+            Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1");
+        let mut moves = Vec::new();
+        MoveGenerator::generate_legal_moves(&board, &mut moves);
+        for mv in moves {
+            assert_eq!(parse_san(&board, &to_san(&board, mv)), Ok(mv));
+        }
+    }
+
+    #[test]
+    fn test_parse_en_passant() {
+        let board =
+            Board::from_fen("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3");
+        assert_eq!(
+            parse_san(&board, "exf6"),
+            Ok(ChessMove::new_en_passant(36, 45))
+        );
+    }
+}
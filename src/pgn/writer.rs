@@ -0,0 +1,187 @@
+use crate::board::Color;
+use crate::fen::FENParser;
+use crate::pgn::PgnGame;
+
+/// Movetext lines are wrapped below this width, as the PGN standard recommends
+const MAX_LINE_LENGTH: usize = 80;
+
+/// Notes on one move of an annotated PGN.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MoveAnnotation {
+    /// Numeric annotation glyph, e.g. 2 for "?" or 4 for "??"
+    pub nag: Option<u8>,
+    pub comment: Option<String>,
+    /// Moves in SAN that could have been played instead of this one
+    pub variation: Vec<String>,
+}
+
+impl PgnGame {
+    /// Formats the game as PGN: tag pairs, a blank line, then the movetext
+    /// ending with the `Result` tag (or `*` when there is none).
+    ///
+    /// Move numbers follow the `FEN` tag's side to move and fullmove number when present.
+    pub fn to_pgn(&self) -> String {
+        self.to_annotated_pgn(&[])
+    }
+
+    /// Formats the game like `to_pgn`, following the i-th move with the i-th annotation
+    /// (if any): its NAG, its comment in braces and its variation in parentheses.
+    pub fn to_annotated_pgn(&self, annotations: &[MoveAnnotation]) -> String {
+        let mut pgn = String::new();
+        for (name, value) in &self.tags {
+            pgn.push_str(&format!("[{} \"{}\"]\n", name, value.replace('"', "\\\"")));
+        }
+        pgn.push('\n');
+
+        let (mut color, mut number) =
+            match self.tag("FEN").and_then(|fen| FENParser::parse(fen).ok()) {
+                Some(parsed) => (parsed.active_color, parsed.fullmove_number),
+                None => (Color::White, 1),
+            };
+
+        let mut tokens = Vec::with_capacity(self.moves.len() * 3 / 2 + 1);
+        // Black's move needs its number at the start and after comments or variations
+        let mut numbered = true;
+        for (i, san) in self.moves.iter().enumerate() {
+            tokens.push(numbered_move(color, number, numbered, san));
+            numbered = false;
+
+            if let Some(annotation) = annotations.get(i) {
+                if let Some(nag) = annotation.nag {
+                    tokens.push(format!("${}", nag));
+                }
+                if let Some(comment) = &annotation.comment {
+                    // Braces can't be escaped inside a comment
+                    let comment = comment.replace(['{', '}'], "");
+                    let start = tokens.len();
+                    tokens.extend(comment.split_whitespace().map(str::to_string));
+                    wrap_tokens(&mut tokens[start..], "{", "}");
+                    numbered = true;
+                }
+                if !annotation.variation.is_empty() {
+                    let start = tokens.len();
+                    let (mut variation_color, mut variation_number) = (color, number);
+                    for (j, san) in annotation.variation.iter().enumerate() {
+                        tokens.push(numbered_move(
+                            variation_color,
+                            variation_number,
+                            j == 0,
+                            san,
+                        ));
+                        if variation_color == Color::Black {
+                            variation_number += 1;
+                        }
+                        variation_color = variation_color.opponent();
+                    }
+                    wrap_tokens(&mut tokens[start..], "(", ")");
+                    numbered = true;
+                }
+            }
+
+            if color == Color::Black {
+                number += 1;
+            }
+            color = color.opponent();
+        }
+        tokens.push(self.tag("Result").unwrap_or("*").to_string());
+
+        let mut line_length = 0;
+        for token in tokens {
+            if line_length > 0 && line_length + 1 + token.len() >= MAX_LINE_LENGTH {
+                pgn.push('\n');
+                line_length = 0;
+            } else if line_length > 0 {
+                pgn.push(' ');
+                line_length += 1;
+            }
+            line_length += token.len();
+            pgn.push_str(&token);
+        }
+        pgn.push_str("\n\n");
+        pgn
+    }
+}
+
+/// A move with its number: "12. e4" for White, "12... e5" for Black when `numbered`
+/// (first in the movetext, or after a comment or variation), otherwise just "e5".
+fn numbered_move(color: Color, number: u16, numbered: bool, san: &str) -> String {
+    match color {
+        Color::White => format!("{}. {}", number, san),
+        Color::Black if numbered => format!("{}... {}", number, san),
+        Color::Black => san.to_string(),
+    }
+}
+
+/// Puts `open` before the first token and `close` after the last.
+fn wrap_tokens(tokens: &mut [String], open: &str, close: &str) {
+    if let Some(first) = tokens.first_mut() {
+        first.insert_str(0, open);
+    }
+    if let Some(last) = tokens.last_mut() {
+        last.push_str(close);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pgn::{MoveAnnotation, PgnGame, parse_pgn};
+
+    fn game(tags: &[(&str, &str)], moves: &[&str]) -> PgnGame {
+        PgnGame {
+            tags: tags
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            moves: moves.iter().map(|m| m.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_to_pgn_round_trips() {
+        let original = game(
+            &[("White", "A"), ("Black", "B"), ("Result", "1-0")],
+            &["e4", "e5", "Qh5", "Nc6", "Bc4", "Nf6", "Qxf7#"],
+        );
+        let text = original.to_pgn();
+        assert!(text.contains("1. e4 e5 2. Qh5 Nc6 3. Bc4 Nf6 4. Qxf7# 1-0"));
+        assert_eq!(parse_pgn(&text), vec![original]);
+    }
+
+    #[test]
+    fn test_to_pgn_numbers_moves_from_the_fen() {
+        let from_black = game(
+            &[("FEN", "4k3/8/8/8/8/8/4P3/4K3 b - - 0 12"), ("Result", "*")],
+            &["Kd7", "e4"],
+        );
+        assert!(from_black.to_pgn().ends_with("12... Kd7 13. e4 *\n\n"));
+    }
+
+    #[test]
+    fn test_to_pgn_wraps_long_movetext() {
+        let long = game(&[], &["Nf3", "Nf6", "Ng1", "Ng8"].repeat(20));
+        let text = long.to_pgn();
+        assert!(text.lines().all(|line| line.len() < 80));
+        assert_eq!(parse_pgn(&text)[0].moves.len(), 80);
+    }
+
+    #[test]
+    fn test_annotations_keep_the_main_line() {
+        let original = game(&[("Result", "0-1")], &["f3", "e5", "g4", "Qh4#"]);
+        let annotations = [
+            MoveAnnotation::default(),
+            MoveAnnotation::default(),
+            MoveAnnotation {
+                nag: Some(4),
+                comment: Some("Blunder {mate follows}".to_string()),
+                variation: vec!["Kf2".to_string(), "Qh4+".to_string()],
+            },
+        ];
+        let text = original.to_annotated_pgn(&annotations);
+        assert!(
+            text.contains("1. f3 e5 2. g4 $4 {Blunder mate follows} (2. Kf2 Qh4+) 2... Qh4# 0-1"),
+            "{}",
+            text
+        );
+        assert_eq!(parse_pgn(&text), vec![original]);
+    }
+}
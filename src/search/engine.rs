@@ -1,7 +1,13 @@
 use crate::board::{Board, ChessMove};
-use crate::opening::OpeningBook;
-use crate::search::{Minimax, SearchHistory, SearchMetrics, SearchParams};
+use crate::eval::{EvalConfig, EvalParams, Evaluator};
+use crate::movegen::MoveGenerator;
+use crate::opening::{Book, BookResult, BookSelection, OpeningBook, PolyglotBook};
+use crate::search::{Minimax, SearchHistory, SearchMetrics, SearchParams, Skill};
+use crate::tablebase::{Tablebase, rank_root_moves};
 use crate::transpositions::TranspositionTable;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::sync::Arc;
 
 /// Chess move search engine using minimax with alpha-beta pruning.
 ///
@@ -11,8 +17,16 @@ pub struct ChessEngine {
     minimax: Minimax,
     tt: TranspositionTable,
     last_search_metrics: Option<SearchMetrics>,
-    opening_book: Option<OpeningBook>,
+    opening_book: Option<Book>,
     use_opening_book: bool,
+    book_selection: BookSelection,
+    book_rng: StdRng,
+    last_move_from_book: bool,
+    tablebase: Option<Arc<dyn Tablebase>>,
+    verbose: bool,
+    skill: Skill,
+    skill_seed: u64,
+    skill_rng: StdRng,
 }
 
 impl Default for ChessEngine {
@@ -23,24 +37,40 @@ impl Default for ChessEngine {
 
 impl ChessEngine {
     pub fn new() -> Self {
+        Self::with_transposition_table(TranspositionTable::default())
+    }
+
+    /// Creates a new ChessEngine with a transposition table of about `size_mb` megabytes.
+    pub fn with_hash_size_mb(size_mb: usize) -> Self {
+        Self::with_transposition_table(TranspositionTable::new_with_size_mb(size_mb))
+    }
+
+    fn with_transposition_table(tt: TranspositionTable) -> Self {
         Self {
             minimax: Minimax::new(),
-            tt: TranspositionTable::default(),
+            tt,
             last_search_metrics: None,
             opening_book: None,
             use_opening_book: false,
+            book_selection: BookSelection::default(),
+            book_rng: StdRng::seed_from_u64(0),
+            last_move_from_book: false,
+            tablebase: None,
+            verbose: true,
+            skill: Skill::full_strength(),
+            skill_seed: 0,
+            skill_rng: StdRng::seed_from_u64(0),
         }
     }
 
     /// Creates a new ChessEngine with an opening book loaded from a file.
+    /// Both native and Polyglot books are accepted; the format is detected from the contents.
     pub fn with_opening_book(book_path: &str) -> std::io::Result<Self> {
-        let book = OpeningBook::load(book_path)?;
+        let book = Book::load(book_path)?;
         Ok(Self {
-            minimax: Minimax::new(),
-            tt: TranspositionTable::default(),
-            last_search_metrics: None,
             opening_book: Some(book),
             use_opening_book: true,
+            ..Self::new()
         })
     }
 
@@ -48,34 +78,144 @@ impl ChessEngine {
     pub fn with_london_system() -> Self {
         use crate::opening::create_london_system_opening_book;
         Self {
-            minimax: Minimax::new(),
-            tt: TranspositionTable::default(),
-            last_search_metrics: None,
-            opening_book: Some(create_london_system_opening_book()),
+            opening_book: Some(create_london_system_opening_book().into()),
             use_opening_book: true,
+            ..Self::new()
         }
     }
 
+    /// Creates a new ChessEngine whose evaluator is built from an eval config.
+    pub fn with_eval_config(config: &EvalConfig) -> Result<Self, String> {
+        let mut engine = Self::new();
+        engine.set_eval_config(config)?;
+        Ok(engine)
+    }
+
+    /// Rebuilds the search evaluator from an eval config (enabled evaluators and weights).
+    /// On error the current evaluator is kept.
+    pub fn set_eval_config(&mut self, config: &EvalConfig) -> Result<(), String> {
+        self.set_evaluator(Evaluator::from_config(config)?);
+        Ok(())
+    }
+
+    /// Sets the playing strength used by `find_best_move_iterative`. Below full
+    /// strength the engine searches less, evaluates with noise and sometimes picks
+    /// one of the other best moves (see `Skill`).
+    pub fn set_skill(&mut self, skill: Skill) {
+        self.skill = skill;
+        self.apply_skill();
+    }
+
+    pub fn skill(&self) -> Skill {
+        self.skill
+    }
+
+    /// Reseeds the randomness of weakened play: evaluation noise and move choice.
+    pub fn set_skill_seed(&mut self, seed: u64) {
+        self.skill_seed = seed;
+        self.skill_rng = StdRng::seed_from_u64(seed);
+        self.apply_skill();
+    }
+
+    fn apply_skill(&mut self) {
+        self.minimax
+            .set_eval_noise(self.skill.eval_noise(), self.skill_seed);
+        self.minimax.set_quiescence(self.skill.uses_quiescence());
+        // Cached scores were produced with different noise
+        self.tt.clear();
+    }
+
+    /// Replaces the evaluator used by the search.
+    pub fn set_evaluator(&mut self, evaluator: Evaluator) {
+        self.minimax.set_evaluator(evaluator);
+        // Cached scores were produced by the old evaluator
+        self.tt.clear();
+    }
+
+    /// Replaces the tunable evaluation constants, keeping the enabled evaluators and weights.
+    pub fn set_eval_params(&mut self, params: EvalParams) {
+        self.minimax.set_eval_params(params);
+        self.tt.clear();
+    }
+
     /// Sets the opening book for this engine. If None, the opening book is disabled.
     pub fn set_opening_book(&mut self, book: Option<OpeningBook>) {
-        self.opening_book = book;
+        self.opening_book = book.map(Book::Native);
+        self.use_opening_book = self.opening_book.is_some();
+    }
+
+    /// Sets a Polyglot opening book for this engine. If None, the opening book is disabled.
+    pub fn set_polyglot_book(&mut self, book: Option<PolyglotBook>) {
+        self.opening_book = book.map(Book::Polyglot);
         self.use_opening_book = self.opening_book.is_some();
     }
 
+    /// Returns the opening book, if one is loaded.
+    pub fn opening_book(&self) -> Option<&Book> {
+        self.opening_book.as_ref()
+    }
+
+    /// Sets how book moves are chosen. Defaults to always playing the most common move.
+    pub fn set_book_selection(&mut self, selection: BookSelection) {
+        self.book_selection = selection;
+    }
+
+    /// Reseeds the random number generator used for book move selection.
+    pub fn set_book_seed(&mut self, seed: u64) {
+        self.book_rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// Returns true if the last move returned by a search came from the opening book.
+    pub fn last_move_from_book(&self) -> bool {
+        self.last_move_from_book
+    }
+
+    /// Updates the book's learning data with a finished game's result.
+    ///
+    /// `book_moves` are the `(position hash, move)` pairs the book played, and `result`
+    /// is from the engine's point of view.
+    pub fn record_book_result(&mut self, book_moves: &[(u64, ChessMove)], result: BookResult) {
+        if let Some(book) = self.opening_book.as_mut() {
+            book.record_result(book_moves, result);
+        }
+    }
+
+    /// Saves the opening book, including learning data, so it carries over to later games.
+    pub fn save_opening_book(&self, path: &str) -> std::io::Result<()> {
+        match &self.opening_book {
+            Some(book) => book.save(path),
+            None => Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "No opening book loaded",
+            )),
+        }
+    }
+
+    /// Sets the endgame tablebase. The search probes it for win/draw/loss once few enough
+    /// pieces remain, and root moves in tablebase positions are chosen by DTZ.
+    pub fn set_tablebase(&mut self, tablebase: Option<Arc<dyn Tablebase>>) {
+        self.minimax.set_tablebase(tablebase.clone());
+        self.tablebase = tablebase;
+        self.tt.clear();
+    }
+
+    /// Enables or disables printing of book moves and search statistics.
+    pub fn set_verbose(&mut self, verbose: bool) {
+        self.verbose = verbose;
+    }
+
     /// Enables or disables the opening book.
     pub fn set_use_opening_book(&mut self, enabled: bool) {
         self.use_opening_book = enabled && self.opening_book.is_some();
     }
 
     pub fn find_best_move(&mut self, board: &Board, depth: u8) -> Option<ChessMove> {
-        // Check opening book first
-        if self.use_opening_book
-            && let Some(ref book) = self.opening_book
-            && let Some(book_move) = book.probe(board.hash)
-        {
-            println!("Opening book move: {}", book_move.to_uci());
+        if let Some(book_move) = self.probe_book(board) {
             return Some(book_move);
         }
+        if let Some(tb_move) = self.probe_tablebase_root(board) {
+            return Some(tb_move);
+        }
 
         // Use minimax search
         let mut history = SearchHistory::new();
@@ -85,7 +225,9 @@ impl ChessEngine {
             self.minimax
                 .find_best_move(board, depth, &mut history, &mut self.tt, &mut metrics);
 
-        self.print_search_stats(&metrics);
+        if self.verbose {
+            self.print_search_stats(&metrics);
+        }
 
         // Store metrics for later retrieval
         self.last_search_metrics = Some(metrics);
@@ -111,14 +253,15 @@ impl ChessEngine {
         board: &Board,
         params: &SearchParams,
     ) -> Option<ChessMove> {
-        // Check opening book first
-        if self.use_opening_book
-            && let Some(ref book) = self.opening_book
-            && let Some(book_move) = book.probe(board.hash)
-        {
-            println!("Opening book move: {}", book_move.to_uci());
+        if let Some(book_move) = self.probe_book(board) {
             return Some(book_move);
         }
+        if self.skill.is_limited() {
+            return self.find_weakened_move(board, params);
+        }
+        if let Some(tb_move) = self.probe_tablebase_root(board) {
+            return Some(tb_move);
+        }
 
         // Use iterative deepening search
         let mut history = SearchHistory::new();
@@ -132,7 +275,9 @@ impl ChessEngine {
             &mut metrics,
         );
 
-        self.print_search_stats(&metrics);
+        if self.verbose {
+            self.print_search_stats(&metrics);
+        }
 
         // Store metrics for later retrieval
         self.last_search_metrics = Some(metrics);
@@ -140,6 +285,114 @@ impl ChessEngine {
         result
     }
 
+    /// Searches within the skill level's limits and lets it choose among the best root moves.
+    /// Tablebases are skipped so a weakened engine doesn't play endgames perfectly.
+    fn find_weakened_move(&mut self, board: &Board, params: &SearchParams) -> Option<ChessMove> {
+        let params = self.skill.limit_search(params);
+        let mut history = SearchHistory::new();
+        let mut metrics = SearchMetrics::new();
+
+        let root_moves = self.minimax.find_root_moves_iterative(
+            board,
+            &params,
+            &mut history,
+            &mut self.tt,
+            &mut metrics,
+        );
+
+        if self.verbose {
+            self.print_search_stats(&metrics);
+        }
+        self.last_search_metrics = Some(metrics);
+
+        self.skill.pick_move(&root_moves, &mut self.skill_rng)
+    }
+
+    /// Scores every legal move, best first, from the side to move's perspective.
+    /// Unlike `find_best_move_iterative`, the opening book is never consulted.
+    pub fn score_root_moves(
+        &mut self,
+        board: &Board,
+        params: &SearchParams,
+    ) -> Vec<(ChessMove, i32)> {
+        let mut history = SearchHistory::new();
+        let mut metrics = SearchMetrics::new();
+
+        let root_moves = self.minimax.find_root_moves_iterative(
+            board,
+            params,
+            &mut history,
+            &mut self.tt,
+            &mut metrics,
+        );
+
+        if self.verbose {
+            self.print_search_stats(&metrics);
+        }
+        self.last_search_metrics = Some(metrics);
+        root_moves
+    }
+
+    /// The line the last searches expect from `board`, following the best moves stored
+    /// in the transposition table for up to `max_length` moves.
+    pub fn principal_variation(&mut self, board: &Board, max_length: usize) -> Vec<ChessMove> {
+        let mut line = Vec::new();
+        let mut board = *board;
+        let mut seen = vec![board.hash];
+        let mut legal_moves = Vec::with_capacity(128);
+        while line.len() < max_length {
+            let Some(chess_move) = self.tt.probe(board.hash).and_then(|entry| entry.best_move)
+            else {
+                break;
+            };
+            // A hash collision can store a move that isn't legal here
+            MoveGenerator::generate_legal_moves(&board, &mut legal_moves);
+            if !legal_moves.contains(&chess_move) {
+                break;
+            }
+            board.make_move(chess_move);
+            line.push(chess_move);
+            // Stop at a repetition rather than going round in circles
+            if seen.contains(&board.hash) {
+                break;
+            }
+            seen.push(board.hash);
+        }
+        line
+    }
+
+    fn probe_book(&mut self, board: &Board) -> Option<ChessMove> {
+        self.last_move_from_book = false;
+        if !self.use_opening_book {
+            return None;
+        }
+
+        let book_move =
+            self.opening_book
+                .as_ref()?
+                .select(board, &self.book_selection, &mut self.book_rng)?;
+        if self.verbose {
+            println!("Opening book move: {}", book_move.to_uci());
+        }
+        self.last_move_from_book = true;
+        Some(book_move)
+    }
+
+    /// Picks the move that best preserves the tablebase result, if the position is covered.
+    fn probe_tablebase_root(&self, board: &Board) -> Option<ChessMove> {
+        let ranked = rank_root_moves(self.tablebase.as_deref()?, board)?;
+        let best = ranked.first()?;
+        if self.verbose {
+            println!(
+                "Tablebase move: {} ({:?}, DTZ {})",
+                best.chess_move.to_uci(),
+                best.wdl,
+                best.dtz
+            );
+        }
+        Some(best.chess_move)
+    }
+
     // Clear the transposition table (call when starting a new game)
     pub fn new_game(&mut self) {
         self.tt.clear();
@@ -165,6 +418,11 @@ impl ChessEngine {
         self.tt.num_entries()
     }
 
+    /// Replace the transposition table with an empty one of `num_entries` entries
+    pub fn set_tt_num_entries(&mut self, num_entries: usize) {
+        self.tt = TranspositionTable::new_with_entries(num_entries);
+    }
+
     /// Get transposition table hits
     pub fn get_tt_hits(&self) -> usize {
         self.tt.hits()
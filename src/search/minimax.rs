@@ -1,14 +1,20 @@
 use crate::board::{Board, ChessMove, Piece};
-use crate::eval::Evaluator;
+use crate::eval::nnue::NnueAccumulator;
+use crate::eval::{EvalParams, Evaluator};
 use crate::movegen::MoveGenerator;
 use crate::search::SearchHistory;
 use crate::search::quiescence::quiescence_search;
+use crate::tablebase::Tablebase;
 use crate::transpositions::{Bound, TranspositionTable};
+use std::sync::Arc;
 use std::time::Instant;
 
 /// Maximum search depth for PV table
 const MAX_PLY: usize = 64;
 
+/// Checkmate scores are at least this large; faster mates score higher
+pub const MATE_SCORE: i32 = 100_000;
+
 /// History heuristic table for move ordering.
 /// Tracks scores for quiet moves that cause beta cutoffs.
 /// Indexed by [from_square][to_square] where squares are 0-63.
@@ -127,6 +133,8 @@ pub struct SearchParams {
     pub max_depth: u8,
     /// Minimum time to search per move in milliseconds (ensures sufficient evaluation)
     pub min_search_time_ms: u64,
+    /// Node budget per move. When set, the time limit is ignored so results are reproducible.
+    pub max_nodes: Option<u64>,
 }
 
 impl SearchParams {
@@ -134,6 +142,17 @@ impl SearchParams {
         Self {
             max_depth,
             min_search_time_ms,
+            max_nodes: None,
+        }
+    }
+
+    /// Searches up to `max_depth` or until `max_nodes` nodes have been explored,
+    /// regardless of how long that takes.
+    pub fn fixed_nodes(max_depth: u8, max_nodes: u64) -> Self {
+        Self {
+            max_depth,
+            min_search_time_ms: 0,
+            max_nodes: Some(max_nodes),
         }
     }
 }
@@ -143,27 +162,33 @@ impl Default for SearchParams {
         Self {
             max_depth: 8,
             min_search_time_ms: 1000,
+            max_nodes: None,
         }
     }
 }
 
-/// Time constraints for search operations.
+/// Time or node limits for search operations.
 #[derive(Debug, Clone, Copy)]
-struct TimeConstraints<'a> {
+struct SearchLimits<'a> {
     start_time: &'a Instant,
     min_time_ms: u64,
+    max_nodes: Option<u64>,
 }
 
-impl<'a> TimeConstraints<'a> {
-    fn new(start_time: &'a Instant, min_time_ms: u64) -> Self {
+impl<'a> SearchLimits<'a> {
+    fn new(start_time: &'a Instant, params: &SearchParams) -> Self {
         Self {
             start_time,
-            min_time_ms,
+            min_time_ms: params.min_search_time_ms,
+            max_nodes: params.max_nodes,
         }
     }
 
-    fn is_time_exceeded(&self) -> bool {
-        self.start_time.elapsed().as_millis() >= self.min_time_ms as u128
+    fn is_exceeded(&self, metrics: &SearchMetrics) -> bool {
+        match self.max_nodes {
+            Some(max_nodes) => metrics.nodes_explored >= max_nodes,
+            None => self.start_time.elapsed().as_millis() >= self.min_time_ms as u128,
+        }
     }
 }
 
@@ -178,6 +203,12 @@ pub struct SearchMetrics {
     pub beta_cutoffs: u64,
     /// Time taken for the search
     pub search_time: std::time::Duration,
+    /// Score of the deepest fully searched iteration, from the side to move's perspective
+    pub score: i32,
+    /// Depth of the deepest fully searched iteration
+    pub completed_depth: u8,
+    /// Number of positions resolved by the endgame tablebase
+    pub tb_hits: u64,
 }
 
 impl SearchMetrics {
@@ -200,6 +231,9 @@ impl SearchMetrics {
 /// - [Wikipedia: Minimax](https://en.wikipedia.org/wiki/Minimax)
 pub struct Minimax {
     evaluator: Evaluator,
+    tablebase: Option<Arc<dyn Tablebase>>,
+    /// When false, leaves are scored statically instead of resolving captures first
+    use_quiescence: bool,
 }
 
 impl Default for Minimax {
@@ -211,11 +245,63 @@ impl Default for Minimax {
 impl Minimax {
     /// Creates a new Minimax instance with a fresh evaluator
     pub fn new() -> Self {
+        Self::with_evaluator(Evaluator::new())
+    }
+
+    /// Creates a new Minimax instance that searches with the given evaluator
+    pub fn with_evaluator(evaluator: Evaluator) -> Self {
         Self {
-            evaluator: Evaluator::new(),
+            evaluator,
+            tablebase: None,
+            use_quiescence: true,
         }
     }
 
+    /// Replaces the evaluator used at the leaves of the search, keeping any evaluation noise
+    pub fn set_evaluator(&mut self, mut evaluator: Evaluator) {
+        let (amplitude, seed) = self.evaluator.noise();
+        evaluator.set_noise(amplitude, seed);
+        self.evaluator = evaluator;
+    }
+
+    /// Perturbs every leaf evaluation by up to `amplitude` centipawns (see `Evaluator::set_noise`)
+    pub fn set_eval_noise(&mut self, amplitude: i32, seed: u64) {
+        self.evaluator.set_noise(amplitude, seed);
+    }
+
+    /// Enables or disables quiescence search at the leaves (enabled by default)
+    pub fn set_quiescence(&mut self, enabled: bool) {
+        self.use_quiescence = enabled;
+    }
+
+    /// Replaces the tunable constants of the current evaluator
+    pub fn set_eval_params(&mut self, params: EvalParams) {
+        self.evaluator.set_params(params);
+    }
+
+    /// Sets the endgame tablebase probed inside the search. If None, probing is disabled.
+    pub fn set_tablebase(&mut self, tablebase: Option<Arc<dyn Tablebase>>) {
+        self.tablebase = tablebase;
+    }
+
+    /// Exact score for a tablebase position, probed only right after a capture or pawn
+    /// move since WDL results assume a fresh fifty-move counter.
+    fn probe_tablebase(
+        &self,
+        board: &Board,
+        ply: usize,
+        metrics: &mut SearchMetrics,
+    ) -> Option<i32> {
+        let tablebase = self.tablebase.as_ref()?;
+        if board.halfmove_clock != 0 || !tablebase.covers(board) {
+            return None;
+        }
+
+        let wdl = tablebase.probe_wdl(board)?;
+        metrics.tb_hits += 1;
+        Some(wdl.to_score(ply))
+    }
+
     /// Find the best move using minimax with alpha-beta pruning
     pub fn find_best_move(
         &self,
@@ -229,6 +315,7 @@ impl Minimax {
 
         // Initialize history with the current position
         history.push(board.hash);
+        let nnue = &mut self.evaluator.new_accumulator(board);
 
         // Preallocate move buffer for reuse across recursive calls
         let mut move_buffer = Vec::with_capacity(128);
@@ -254,9 +341,11 @@ impl Minimax {
 
             // Push position before recursing
             history.push(board_copy.hash);
+            self.evaluator.update_accumulator(nnue, board, &board_copy);
 
             let score = -self.alpha_beta(
                 &board_copy,
+                nnue,
                 depth - 1,
                 i32::MIN + 1,
                 i32::MAX,
@@ -269,6 +358,7 @@ impl Minimax {
 
             // Pop position after returning
             history.pop();
+            self.evaluator.update_accumulator(nnue, &board_copy, board);
 
             if score > best_score {
                 best_score = score;
@@ -278,6 +368,8 @@ impl Minimax {
 
         history.pop(); // Clean up the initial position
         metrics.search_time = start_time.elapsed();
+        metrics.score = best_score;
+        metrics.completed_depth = depth;
         Some(best_move)
     }
 
@@ -301,11 +393,50 @@ impl Minimax {
         history: &mut SearchHistory,
         tt: &mut TranspositionTable,
         metrics: &mut SearchMetrics,
+    ) -> Option<ChessMove> {
+        self.iterative_deepening(board, params, history, tt, metrics, &mut Vec::new())
+    }
+
+    /// Searches like `find_best_move_iterative`, but returns every root move with its
+    /// score from the deepest fully searched iteration, best first.
+    ///
+    /// Root moves are searched with a full window, so the scores are exact rather than bounds.
+    pub fn find_root_moves_iterative(
+        &self,
+        board: &Board,
+        params: &SearchParams,
+        history: &mut SearchHistory,
+        tt: &mut TranspositionTable,
+        metrics: &mut SearchMetrics,
+    ) -> Vec<(ChessMove, i32)> {
+        let mut root_moves = Vec::new();
+        let best_move =
+            self.iterative_deepening(board, params, history, tt, metrics, &mut root_moves);
+        // Even the first root move was cut short: fall back to the best move found
+        if root_moves.is_empty()
+            && let Some(best_move) = best_move
+        {
+            root_moves.push((best_move, metrics.score));
+        }
+        root_moves
+    }
+
+    /// Iterative deepening behind `find_best_move_iterative`. Fills `root_moves` with the
+    /// scored root moves of the deepest completed iteration (or of the interrupted first one).
+    fn iterative_deepening(
+        &self,
+        board: &Board,
+        params: &SearchParams,
+        history: &mut SearchHistory,
+        tt: &mut TranspositionTable,
+        metrics: &mut SearchMetrics,
+        root_moves: &mut Vec<(ChessMove, i32)>,
     ) -> Option<ChessMove> {
         let start_time = Instant::now();
 
         // Initialize history with the current position
         history.push(board.hash);
+        let mut nnue = self.evaluator.new_accumulator(board);
 
         // Check if there are any legal moves
         let mut move_buffer = Vec::with_capacity(128);
@@ -324,63 +455,87 @@ impl Minimax {
 
         let mut best_move: Option<ChessMove> = None;
         let mut depth = 1;
+        let mut iteration_moves = Vec::with_capacity(move_buffer.len());
+        root_moves.clear();
 
         // Iterative deepening up to max_depth
-        let time_constraints = TimeConstraints::new(&start_time, params.min_search_time_ms);
+        let limits = SearchLimits::new(&start_time, params);
         while depth <= params.max_depth {
-            let (_score, mv) = self.search_depth(
+            let (score, mv) = self.search_depth(
                 board,
+                &mut nnue,
                 depth,
-                &time_constraints,
+                &limits,
                 history,
                 tt,
                 metrics,
                 &mut history_table,
                 &mut killer_table,
                 &pv_table,
+                &mut iteration_moves,
             );
 
+            // Stop if we've exceeded the minimum time (or node budget). The interrupted
+            // iteration's move and score are discarded for the last completed ones.
+            if limits.is_exceeded(metrics) {
+                if best_move.is_none() {
+                    best_move = self.interrupted_first_iteration(
+                        board,
+                        &mut nnue,
+                        mv.or(move_buffer.first().copied()),
+                        &iteration_moves,
+                        root_moves,
+                        metrics,
+                    );
+                }
+                break;
+            }
             if let Some(mv) = mv {
                 best_move = Some(mv);
                 // Store the best move in PV table for next iteration
                 pv_table.set(depth as usize, mv);
             }
-
-            // Stop if we've exceeded the minimum time
-            if start_time.elapsed().as_millis() >= params.min_search_time_ms as u128 {
-                break;
-            }
+            metrics.score = score;
+            metrics.completed_depth = depth;
+            Self::store_root_moves(root_moves, &iteration_moves);
 
             depth += 1;
         }
 
         // Ensure minimum search time is satisfied
         // Continue searching at deeper depths if we haven't used enough time
-        while start_time.elapsed().as_millis() < params.min_search_time_ms as u128 {
+        while params.max_nodes.is_none()
+            && start_time.elapsed().as_millis() < params.min_search_time_ms as u128
+        {
             depth += 1;
-            let (_score, mv) = self.search_depth(
+            let (score, mv) = self.search_depth(
                 board,
+                &mut nnue,
                 depth,
-                &time_constraints,
+                &limits,
                 history,
                 tt,
                 metrics,
                 &mut history_table,
                 &mut killer_table,
                 &pv_table,
+                &mut iteration_moves,
             );
 
-            if let Some(mv) = mv {
-                best_move = Some(mv);
-                // Store the best move in PV table for next iteration
-                pv_table.set(depth as usize, mv);
-            }
-
             // If search completed very quickly, avoid infinite loop
             // This can happen in endgames with few pieces
-            if mv.is_none() {
+            if mv.is_none() || limits.is_exceeded(metrics) {
                 break;
             }
+
+            best_move = mv;
+            if let Some(mv) = mv {
+                // Store the best move in PV table for next iteration
+                pv_table.set(depth as usize, mv);
+            }
+            metrics.score = score;
+            metrics.completed_depth = depth;
+            Self::store_root_moves(root_moves, &iteration_moves);
         }
 
         history.pop();
@@ -389,25 +544,76 @@ impl Minimax {
         best_move
     }
 
+    /// The move and score to fall back on when the limit cuts the depth 1 iteration
+    /// short: the best of the root moves scored before it, or, if there are none,
+    /// `first_move` scored by the position's own leaf score.
+    fn interrupted_first_iteration(
+        &self,
+        board: &Board,
+        nnue: &mut Option<NnueAccumulator>,
+        first_move: Option<ChessMove>,
+        iteration_moves: &[(ChessMove, i32)],
+        root_moves: &mut Vec<(ChessMove, i32)>,
+        metrics: &mut SearchMetrics,
+    ) -> Option<ChessMove> {
+        Self::store_root_moves(root_moves, iteration_moves);
+        let (best_move, score) = match root_moves.first() {
+            Some(&(best_move, score)) => (best_move, score),
+            None => (
+                first_move?,
+                self.leaf_score(board, nnue, i32::MIN + 1, i32::MAX),
+            ),
+        };
+        metrics.score = score;
+        Some(best_move)
+    }
+
+    /// Copies an iteration's scored root moves, best first.
+    fn store_root_moves(root_moves: &mut Vec<(ChessMove, i32)>, iteration: &[(ChessMove, i32)]) {
+        root_moves.clear();
+        root_moves.extend_from_slice(iteration);
+        root_moves.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+    }
+
+    /// Scores a leaf with quiescence search, or statically when quiescence is disabled.
+    fn leaf_score(
+        &self,
+        board: &Board,
+        nnue: &mut Option<NnueAccumulator>,
+        alpha: i32,
+        beta: i32,
+    ) -> i32 {
+        if self.use_quiescence {
+            quiescence_search(board, nnue, alpha, beta, &self.evaluator)
+        } else {
+            self.evaluator
+                .evaluate_with_accumulator(board, nnue.as_ref())
+        }
+    }
+
     /// Performs a depth-limited search with time checking.
     ///
     /// Returns (score, best_move). If time limit is exceeded, returns (0, None).
     /// Uses PV-first, killer moves, and history heuristic for enhanced move ordering.
+    /// Each searched root move is recorded with its score in `root_moves`.
     #[allow(clippy::too_many_arguments)]
     fn search_depth(
         &self,
         board: &Board,
+        nnue: &mut Option<NnueAccumulator>,
         depth: u8,
-        time_constraints: &TimeConstraints,
+        limits: &SearchLimits,
         history: &mut SearchHistory,
         tt: &mut TranspositionTable,
         metrics: &mut SearchMetrics,
         history_table: &mut HistoryTable,
         killer_table: &mut KillerTable,
         pv_table: &PVTable,
+        root_moves: &mut Vec<(ChessMove, i32)>,
     ) -> (i32, Option<ChessMove>) {
+        root_moves.clear();
         // Stop if time limit is exceeded
-        if time_constraints.is_time_exceeded() {
+        if limits.is_exceeded(metrics) {
             return (0, None);
         }
 
@@ -444,9 +650,11 @@ impl Minimax {
 
             // Push position before recursing
             history.push(board_copy.hash);
+            self.evaluator.update_accumulator(nnue, board, &board_copy);
 
             let score = -self.alpha_beta_with_time(
                 &board_copy,
+                nnue,
                 depth - 1,
                 i32::MIN + 1,
                 i32::MAX,
@@ -455,13 +663,14 @@ impl Minimax {
                 metrics,
                 depth,
                 &mut move_buffer,
-                time_constraints,
+                limits,
                 history_table,
                 killer_table,
             );
 
             // Pop position after returning
             history.pop();
+            self.evaluator.update_accumulator(nnue, &board_copy, board);
 
             if score > best_score {
                 best_score = score;
@@ -469,9 +678,11 @@ impl Minimax {
             }
 
             // Check time limit during move iteration
-            if time_constraints.is_time_exceeded() {
+            if limits.is_exceeded(metrics) {
                 break;
             }
+            // Scores of moves cut short by the limit aren't recorded
+            root_moves.push((*chess_move, score));
         }
 
         (best_score, Some(best_move))
@@ -483,6 +694,7 @@ impl Minimax {
     fn alpha_beta_with_time(
         &self,
         board: &Board,
+        nnue: &mut Option<NnueAccumulator>,
         depth: u8,
         mut alpha: i32,
         mut beta: i32,
@@ -491,12 +703,12 @@ impl Minimax {
         metrics: &mut SearchMetrics,
         original_depth: u8,
         move_buffer: &mut Vec<ChessMove>,
-        time_constraints: &TimeConstraints,
+        limits: &SearchLimits,
         history_table: &mut HistoryTable,
         killer_table: &mut KillerTable,
     ) -> i32 {
         // Check time limit at each node
-        if time_constraints.is_time_exceeded() {
+        if limits.is_exceeded(metrics) {
             return 0; // Time exceeded, return neutral score
         }
 
@@ -513,6 +725,11 @@ impl Minimax {
             return 0; // Repetition is a draw
         }
 
+        // Tablebase positions have an exact result
+        if let Some(score) = self.probe_tablebase(board, ply, metrics) {
+            return score;
+        }
+
         // Store original alpha for bound determination
         let original_alpha = alpha;
 
@@ -554,7 +771,7 @@ impl Minimax {
         if move_buffer.is_empty() {
             let score = if board.in_check(board.side_to_move) {
                 // Losing position - adjust score by depth to prefer faster checkmates
-                -100_000 - (depth as i32)
+                -MATE_SCORE - (depth as i32)
             } else {
                 // Stalemate - return draw score
                 0
@@ -566,7 +783,7 @@ impl Minimax {
 
         // Leaf node - use quiescence search to resolve tactical sequences
         if depth == 0 {
-            let score = quiescence_search(board, alpha, beta, &self.evaluator);
+            let score = self.leaf_score(board, nnue, alpha, beta);
             tt.store(board.hash, depth, score, None, Bound::Exact);
             return score;
         }
@@ -592,9 +809,11 @@ impl Minimax {
 
             // Push position before recursing
             history.push(board_copy.hash);
+            self.evaluator.update_accumulator(nnue, board, &board_copy);
 
             let score = -self.alpha_beta_with_time(
                 &board_copy,
+                nnue,
                 depth - 1,
                 -beta,
                 -alpha,
@@ -603,22 +822,21 @@ impl Minimax {
                 metrics,
                 original_depth,
                 move_buffer,
-                time_constraints,
+                limits,
                 history_table,
                 killer_table,
             );
 
             // Pop position after returning
             history.pop();
+            self.evaluator.update_accumulator(nnue, &board_copy, board);
 
             // Beta cutoff - opponent won't allow this position
             if score >= beta {
                 metrics.beta_cutoffs += 1;
 
                 // Update history table and killer table for quiet moves that cause beta cutoffs
-                let is_capture =
-                    board.piece_on(chess_move.to() as u8).is_some() || chess_move.is_en_passant();
-                if !is_capture {
+                if !board.is_capture(chess_move) {
                     history_table.increment(&chess_move, depth);
                     killer_table.store(ply, chess_move);
                 }
@@ -656,6 +874,7 @@ impl Minimax {
     fn alpha_beta(
         &self,
         board: &Board,
+        nnue: &mut Option<NnueAccumulator>,
         depth: u8,
         mut alpha: i32,
         mut beta: i32,
@@ -677,6 +896,11 @@ impl Minimax {
             return 0; // Repetition is a draw
         }
 
+        // Tablebase positions have an exact result
+        if let Some(score) = self.probe_tablebase(board, current_depth as usize, metrics) {
+            return score;
+        }
+
         // Store original alpha for bound determination
         let original_alpha = alpha;
 
@@ -718,7 +942,7 @@ impl Minimax {
         if move_buffer.is_empty() {
             let score = if board.in_check(board.side_to_move) {
                 // Losing position - adjust score by depth to prefer faster checkmates
-                -100_000 - (depth as i32)
+                -MATE_SCORE - (depth as i32)
             } else {
                 // Stalemate - return draw score
                 0
@@ -730,7 +954,7 @@ impl Minimax {
 
         // Leaf node - use quiescence search to resolve tactical sequences
         if depth == 0 {
-            let score = quiescence_search(board, alpha, beta, &self.evaluator);
+            let score = self.leaf_score(board, nnue, alpha, beta);
             tt.store(board.hash, depth, score, None, Bound::Exact);
             return score;
         }
@@ -748,9 +972,11 @@ impl Minimax {
 
             // Push position before recursing
             history.push(board_copy.hash);
+            self.evaluator.update_accumulator(nnue, board, &board_copy);
 
             let score = -self.alpha_beta(
                 &board_copy,
+                nnue,
                 depth - 1,
                 -beta,
                 -alpha,
@@ -763,6 +989,7 @@ impl Minimax {
 
             // Pop position after returning
             history.pop();
+            self.evaluator.update_accumulator(nnue, &board_copy, board);
 
             // Beta cutoff - opponent won't allow this position
             if score >= beta {
@@ -869,8 +1096,7 @@ impl Minimax {
         let [killer1, killer2] = killers.get(ply);
 
         moves[priority_index..].sort_by_key(|m| {
-            let is_capture = board.piece_on(m.to() as u8).is_some() || m.is_en_passant();
-            if is_capture {
+            if board.is_capture(*m) {
                 // Captures: MVV-LVA with large offset to sort before quiet moves
                 let victim = board
                     .piece_on(m.to() as u8)
@@ -893,9 +1119,7 @@ impl Minimax {
     }
 
     fn move_priority(board: &Board, chess_move: &ChessMove) -> i32 {
-        let is_capture =
-            board.piece_on(chess_move.to() as u8).is_some() || chess_move.is_en_passant();
-        if is_capture {
+        if board.is_capture(*chess_move) {
             let victim_value = if let Some((_, piece)) = board.piece_on(chess_move.to() as u8) {
                 Self::piece_value(piece)
             } else {
@@ -957,6 +1181,7 @@ mod tests {
 
         // CRITICAL: Compute the zobrist hash for the position
         board.hash = crate::search::compute_hash_board(&board);
+        board.refresh_accumulator();
 
         // Create a TT and metrics for the test
         let minimax = Minimax::new();
@@ -1006,6 +1231,106 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_node_limited_search_is_reproducible() {
+        let board =
+            Board::from_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3");
+        let params = SearchParams::fixed_nodes(32, 3_000);
+        let minimax = Minimax::new();
+
+        let mut runs = Vec::new();
+        for _ in 0..2 {
+            let mut tt = TranspositionTable::new_with_entries(1024);
+            let mut metrics = SearchMetrics::new();
+            let mut history = SearchHistory::new();
+            let best_move = minimax.find_best_move_iterative(
+                &board,
+                &params,
+                &mut history,
+                &mut tt,
+                &mut metrics,
+            );
+            runs.push((best_move, metrics.nodes_explored, metrics.score));
+        }
+
+        assert!(runs[0].0.is_some());
+        assert_eq!(runs[0], runs[1]);
+    }
+
+    #[test]
+    fn test_budget_spent_during_depth_one_keeps_move_and_score_together() {
+        let board =
+            Board::from_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3");
+        let minimax = Minimax::new();
+        // Depth 1 takes 27 nodes here: budgets that run out during the first root
+        // move, after it, and partway through the rest
+        for max_nodes in [1, 2, 10] {
+            let mut tt = TranspositionTable::new_with_entries(1024);
+            let mut metrics = SearchMetrics::new();
+            let mut history = SearchHistory::new();
+            let root_moves = minimax.find_root_moves_iterative(
+                &board,
+                &SearchParams::fixed_nodes(8, max_nodes),
+                &mut history,
+                &mut tt,
+                &mut metrics,
+            );
+
+            assert_eq!(metrics.completed_depth, 0, "budget {}", max_nodes);
+            let (best_move, score) = root_moves[0];
+            assert_eq!(score, metrics.score, "budget {}", max_nodes);
+            let mut legal_moves = Vec::new();
+            MoveGenerator::generate_legal_moves(&board, &mut legal_moves);
+            assert!(legal_moves.contains(&best_move));
+            assert!(root_moves.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+        }
+    }
+
+    #[test]
+    fn test_root_moves_are_scored_best_first() {
+        // Scholar's mate pattern: Qxf7# must come first, far ahead of the rest
+        let board =
+            Board::from_fen("r1bqkbnr/pppp1ppp/2n5/4p2Q/2B1P3/8/PPPP1PPP/RNB1K1NR w KQkq - 4 4");
+        let minimax = Minimax::new();
+        let mut tt = TranspositionTable::new_with_entries(1024);
+        let mut metrics = SearchMetrics::new();
+        let mut history = SearchHistory::new();
+        let root_moves = minimax.find_root_moves_iterative(
+            &board,
+            &SearchParams::fixed_nodes(3, 50_000),
+            &mut history,
+            &mut tt,
+            &mut metrics,
+        );
+
+        let mut legal_moves = Vec::new();
+        MoveGenerator::generate_legal_moves(&board, &mut legal_moves);
+        assert_eq!(root_moves.len(), legal_moves.len());
+        assert_eq!(root_moves[0].0, ChessMove::new(pos("h5"), pos("f7")));
+        assert!(root_moves.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+        assert!(root_moves[0].1 > root_moves[1].1 + 10_000);
+    }
+
+    #[test]
+    fn test_search_scores_tablebase_wins() {
+        use crate::tablebase::TB_WIN_SCORE;
+        use crate::tablebase::tests::QueenWinsTablebase;
+
+        // Qxd4 reaches KQvK right after a capture, where the tablebase is probed
+        let board = Board::from_fen("8/8/4k3/8/3r4/8/8/3QK3 w - - 0 1");
+        let mut minimax = Minimax::new();
+        minimax.set_tablebase(Some(Arc::new(QueenWinsTablebase)));
+
+        let mut tt = TranspositionTable::new_with_entries(1024);
+        let mut metrics = SearchMetrics::new();
+        let mut history = SearchHistory::new();
+        let best_move = minimax.find_best_move(&board, 2, &mut history, &mut tt, &mut metrics);
+
+        assert_eq!(best_move, Some(ChessMove::new(3, 27)));
+        assert!(metrics.tb_hits > 0);
+        assert!(metrics.score > TB_WIN_SCORE - MAX_PLY as i32);
+    }
+
     fn pos(s: &str) -> usize {
         let bytes = s.as_bytes();
         let file = (bytes[0] - b'a') as usize;
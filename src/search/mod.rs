@@ -1,10 +1,14 @@
 mod engine;
 mod history;
 mod minimax;
+mod polyglot;
 mod quiescence;
+mod skill;
 mod zobrist;
 
 pub use engine::ChessEngine;
 pub use history::SearchHistory;
-pub use minimax::{Minimax, SearchMetrics, SearchParams};
+pub use minimax::{MATE_SCORE, Minimax, SearchMetrics, SearchParams};
+pub use polyglot::{POLYGLOT_RANDOM, compute_polyglot_hash};
+pub use skill::{MAX_SKILL_LEVEL, Skill};
 pub use zobrist::{CastlingRight, ZobristTable, compute_hash_board};
@@ -1,5 +1,6 @@
 use crate::board::{Board, ChessMove, Color, Piece};
 use crate::eval::Evaluator;
+use crate::eval::nnue::NnueAccumulator;
 
 /// Generates noisy moves: captures, pawn promotions, and en passant.
 /// These are the only moves searched in quiescence search to resolve tactical sequences.
@@ -202,15 +203,22 @@ fn piece_value(piece: Piece) -> i32 {
 ///
 /// # Arguments
 /// * `board` - The current board position
+/// * `nnue` - The NNUE accumulator of `board`, if the evaluator uses NNUE
 /// * `alpha` - The current alpha bound (best score for the maximizing player)
 /// * `beta` - The current beta bound (best score for the minimizing player)
 /// * `evaluator` - The static evaluation function
 ///
 /// # Returns
 /// The evaluation score from the perspective of the side to move
-pub fn quiescence_search(board: &Board, mut alpha: i32, beta: i32, evaluator: &Evaluator) -> i32 {
+pub fn quiescence_search(
+    board: &Board,
+    nnue: &mut Option<NnueAccumulator>,
+    mut alpha: i32,
+    beta: i32,
+    evaluator: &Evaluator,
+) -> i32 {
     // Stand pat: evaluate the current position
-    let stand_pat = evaluator.evaluate(board);
+    let stand_pat = evaluator.evaluate_with_accumulator(board, nnue.as_ref());
 
     // Beta cutoff: position is already too good for the opponent
     if stand_pat >= beta {
@@ -238,9 +246,11 @@ pub fn quiescence_search(board: &Board, mut alpha: i32, beta: i32, evaluator: &E
     for chess_move in moves {
         let mut board_copy = *board;
         board_copy.make_move(chess_move);
+        evaluator.update_accumulator(nnue, board, &board_copy);
 
         // Recursively search with negated alpha/beta (negamax framework)
-        let score = -quiescence_search(&board_copy, -beta, -alpha, evaluator);
+        let score = -quiescence_search(&board_copy, nnue, -beta, -alpha, evaluator);
+        evaluator.update_accumulator(nnue, &board_copy, board);
 
         // Beta cutoff
         if score >= beta {
@@ -328,7 +338,7 @@ mod tests {
         let board = Board::startpos();
         let evaluator = Evaluator::new();
 
-        let score = quiescence_search(&board, i32::MIN + 1, i32::MAX, &evaluator);
+        let score = quiescence_search(&board, &mut None, i32::MIN + 1, i32::MAX, &evaluator);
 
         // Score should be close to 0 in the starting position
         assert!(
@@ -357,9 +367,10 @@ mod tests {
         board.occ_all = board.occ[Color::White as usize] | board.occ[Color::Black as usize];
 
         board.side_to_move = Color::White;
+        board.refresh_accumulator();
 
         let evaluator = Evaluator::new();
-        let score = quiescence_search(&board, i32::MIN + 1, i32::MAX, &evaluator);
+        let score = quiescence_search(&board, &mut None, i32::MIN + 1, i32::MAX, &evaluator);
 
         // After capturing the queen, white should be significantly ahead
         // Score should be positive and substantial
@@ -0,0 +1,203 @@
+//! Playing strength below the engine's best, for opponents people can beat.
+//!
+//! A skill level from 0 (weakest) to [`MAX_SKILL_LEVEL`] (full strength) combines
+//! depth and node caps, evaluation noise, no quiescence search at the lowest levels,
+//! and a deliberately imperfect choice among the best root moves.
+
+use crate::board::ChessMove;
+use crate::search::SearchParams;
+use rand::Rng;
+
+/// The full-strength level: no limits at all
+pub const MAX_SKILL_LEVEL: u8 = 20;
+
+/// Approximate Elo of level 0; each level adds `ELO_PER_LEVEL`
+const MIN_ELO: u32 = 800;
+const ELO_PER_LEVEL: u32 = 80;
+
+/// Node budget of level 0; each level searches 1.5 times as many nodes
+const MIN_NODES: f64 = 300.0;
+const NODES_GROWTH: f64 = 1.5;
+
+/// Below this level leaves are scored statically, so captures can be left hanging
+const QUIESCENCE_LEVEL: u8 = 6;
+
+/// How many of the best root moves a weakened engine chooses from
+const CANDIDATE_MOVES: usize = 4;
+
+const PAWN_VALUE: i32 = 100;
+
+/// A playing strength, from level 0 to [`MAX_SKILL_LEVEL`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Skill {
+    level: u8,
+}
+
+impl Default for Skill {
+    fn default() -> Self {
+        Self::full_strength()
+    }
+}
+
+impl Skill {
+    pub fn full_strength() -> Self {
+        Self {
+            level: MAX_SKILL_LEVEL,
+        }
+    }
+
+    /// Skill level 0-20; larger values are clamped to full strength.
+    pub fn from_level(level: u8) -> Self {
+        Self {
+            level: level.min(MAX_SKILL_LEVEL),
+        }
+    }
+
+    /// The level closest to a target Elo, from about 800 (level 0) to 2400 (level 20).
+    ///
+    /// The scale is approximate; it has not been calibrated against rated players.
+    pub fn from_elo(elo: u32) -> Self {
+        let level = (elo.saturating_sub(MIN_ELO) + ELO_PER_LEVEL / 2) / ELO_PER_LEVEL;
+        Self::from_level(level.min(MAX_SKILL_LEVEL as u32) as u8)
+    }
+
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
+    /// Approximate Elo of this level.
+    pub fn elo(&self) -> u32 {
+        MIN_ELO + self.level as u32 * ELO_PER_LEVEL
+    }
+
+    /// Whether this level plays below full strength.
+    pub fn is_limited(&self) -> bool {
+        self.level < MAX_SKILL_LEVEL
+    }
+
+    /// `params` capped to this level's depth and node budget. A node budget replaces
+    /// the minimum search time, so weak levels also move quickly.
+    pub fn limit_search(&self, params: &SearchParams) -> SearchParams {
+        if !self.is_limited() {
+            return *params;
+        }
+        let max_nodes = (MIN_NODES * NODES_GROWTH.powi(self.level as i32)) as u64;
+        SearchParams {
+            max_depth: params.max_depth.min(1 + self.level / 2),
+            min_search_time_ms: 0,
+            max_nodes: Some(
+                params
+                    .max_nodes
+                    .map_or(max_nodes, |nodes| nodes.min(max_nodes)),
+            ),
+        }
+    }
+
+    /// Largest random offset added to evaluations, in centipawns.
+    pub fn eval_noise(&self) -> i32 {
+        (MAX_SKILL_LEVEL - self.level) as i32 * 10
+    }
+
+    /// Whether captures are resolved with quiescence search at the leaves.
+    pub fn uses_quiescence(&self) -> bool {
+        self.level >= QUIESCENCE_LEVEL
+    }
+
+    /// Picks a move from root moves scored best first, sometimes preferring a
+    /// weaker one: each of the best few gets a random bonus that grows with its
+    /// distance from the top score and with the level's weakness.
+    pub fn pick_move<R: Rng>(
+        &self,
+        root_moves: &[(ChessMove, i32)],
+        rng: &mut R,
+    ) -> Option<ChessMove> {
+        let &(best_move, top_score) = root_moves.first()?;
+        if !self.is_limited() {
+            return Some(best_move);
+        }
+
+        let candidates = &root_moves[..root_moves.len().min(CANDIDATE_MOVES)];
+        let weakness = 120 - 2 * self.level as i32;
+        let spread = (top_score - candidates[candidates.len() - 1].1).min(PAWN_VALUE);
+
+        let mut best = (best_move, i32::MIN);
+        for &(chess_move, score) in candidates {
+            // Saturating: mate scores make the distance from the top huge
+            let push = weakness
+                .saturating_mul(top_score.saturating_sub(score))
+                .saturating_add(spread * rng.random_range(0..weakness))
+                / 128;
+            let value = score.saturating_add(push);
+            if value >= best.1 {
+                best = (chess_move, value);
+            }
+        }
+        Some(best.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_elo_maps_to_levels() {
+        assert_eq!(Skill::from_elo(0).level(), 0);
+        assert_eq!(Skill::from_elo(1_600).level(), 10);
+        assert_eq!(Skill::from_elo(1_630).level(), 10);
+        assert_eq!(Skill::from_elo(3_500), Skill::full_strength());
+        assert_eq!(Skill::from_level(7).elo(), 1_360);
+        assert_eq!(Skill::from_level(99), Skill::full_strength());
+    }
+
+    #[test]
+    fn test_limits_grow_with_level() {
+        let params = SearchParams::new(10, 5_000);
+        assert_eq!(Skill::full_strength().limit_search(&params).max_nodes, None);
+
+        let weakest = Skill::from_level(0).limit_search(&params);
+        let stronger = Skill::from_level(12).limit_search(&params);
+        assert_eq!(weakest.max_depth, 1);
+        assert_eq!(stronger.max_depth, 7);
+        assert!(weakest.max_nodes.unwrap() < stronger.max_nodes.unwrap());
+        assert_eq!(weakest.min_search_time_ms, 0);
+
+        assert!(!Skill::from_level(0).uses_quiescence());
+        assert!(Skill::from_level(15).uses_quiescence());
+        assert_eq!(Skill::full_strength().eval_noise(), 0);
+    }
+
+    #[test]
+    fn test_weak_levels_sometimes_pick_worse_moves() {
+        let moves: Vec<(ChessMove, i32)> = [(12, 28, 50), (11, 27, 40), (6, 21, 30), (1, 18, 20)]
+            .iter()
+            .map(|&(from, to, score)| (ChessMove::new(from, to), score))
+            .collect();
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let picks = |skill: Skill, rng: &mut StdRng| -> Vec<ChessMove> {
+            (0..200)
+                .map(|_| skill.pick_move(&moves, rng).unwrap())
+                .collect()
+        };
+        assert!(
+            picks(Skill::full_strength(), &mut rng)
+                .iter()
+                .all(|&m| m == moves[0].0)
+        );
+        assert!(
+            picks(Skill::from_level(0), &mut rng)
+                .iter()
+                .any(|&m| m != moves[0].0)
+        );
+
+        // Stronger levels never trade a queen for the random bonus
+        let blunder = [moves[0], (moves[1].0, -900)];
+        assert!(
+            (0..200)
+                .all(|_| Skill::from_level(15).pick_move(&blunder, &mut rng) == Some(moves[0].0))
+        );
+    }
+}
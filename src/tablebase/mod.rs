@@ -0,0 +1,236 @@
+//! Endgame tablebase probing.
+//!
+//! The search probes a [`Tablebase`] for win/draw/loss (WDL) results once few enough
+//! pieces remain, and the engine uses distance-to-zero (DTZ) at the root to choose a
+//! move that keeps the result under the fifty-move rule. See [`SyzygyTablebase`] for
+//! the on-disk Syzygy tables.
+
+mod syzygy;
+
+pub use syzygy::{SyzygyTablebase, material_name};
+
+use crate::board::{Board, CastlingRights, ChessMove, Piece};
+use crate::movegen::MoveGenerator;
+
+/// Scores at or beyond this (but below mate scores) are tablebase wins
+pub const TB_WIN_SCORE: i32 = 80_000;
+
+/// Win/draw/loss result from the side to move's perspective.
+///
+/// Cursed wins and blessed losses would be wins/losses without the fifty-move rule,
+/// but are draws with it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Wdl {
+    Loss = -2,
+    BlessedLoss = -1,
+    Draw = 0,
+    CursedWin = 1,
+    Win = 2,
+}
+
+impl Wdl {
+    /// The same result seen from the other side.
+    pub fn flip(self) -> Self {
+        match self {
+            Wdl::Loss => Wdl::Win,
+            Wdl::BlessedLoss => Wdl::CursedWin,
+            Wdl::Draw => Wdl::Draw,
+            Wdl::CursedWin => Wdl::BlessedLoss,
+            Wdl::Win => Wdl::Loss,
+        }
+    }
+
+    /// Search score for this result `ply` moves from the root.
+    /// Faster wins and slower losses score better; fifty-move draws score as draws.
+    pub fn to_score(self, ply: usize) -> i32 {
+        match self {
+            Wdl::Win => TB_WIN_SCORE - ply as i32,
+            Wdl::Loss => -TB_WIN_SCORE + ply as i32,
+            _ => 0,
+        }
+    }
+}
+
+/// A source of endgame tablebase results.
+pub trait Tablebase: Send + Sync {
+    /// Largest number of pieces (kings included) the tables cover.
+    fn max_pieces(&self) -> u32;
+
+    /// Win/draw/loss for the side to move, assuming the last move reset the fifty-move counter.
+    fn probe_wdl(&self, board: &Board) -> Option<Wdl>;
+
+    /// Distance to the next capture or pawn move (in plies) on the way to the WDL result:
+    /// positive when winning, negative when losing, 0 for a draw.
+    fn probe_dtz(&self, board: &Board) -> Option<i32>;
+
+    /// Whether the tables can be probed for this position at all.
+    fn covers(&self, board: &Board) -> bool {
+        board.castling == CastlingRights::empty() && board.occ_all.count_ones() <= self.max_pieces()
+    }
+}
+
+/// A root move ranked by the tablebase.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RootMove {
+    pub chess_move: ChessMove,
+    /// Result after the move, for the side making it, with the fifty-move rule applied
+    pub wdl: Wdl,
+    /// Plies until the next capture or pawn move: positive when winning, negative when losing
+    pub dtz: i32,
+}
+
+/// Ranks the legal moves of a tablebase position, best first.
+///
+/// Wins are ordered by shortest DTZ, so the engine makes progress instead of wandering
+/// in a won ending; losses are ordered by longest DTZ. A win whose DTZ would run past
+/// the fifty-move counter is ranked as a draw. Returns None if the position is not
+/// covered or any probe fails.
+pub fn rank_root_moves(tablebase: &dyn Tablebase, board: &Board) -> Option<Vec<RootMove>> {
+    if !tablebase.covers(board) {
+        return None;
+    }
+
+    let mut moves = Vec::with_capacity(64);
+    MoveGenerator::generate_legal_moves(board, &mut moves);
+
+    let mut ranked = Vec::with_capacity(moves.len());
+    for chess_move in moves {
+        let zeroing = chess_move.promotion_piece().is_some()
+            || board.is_capture(chess_move)
+            || board
+                .piece_on(chess_move.from() as u8)
+                .is_some_and(|(_, piece)| piece == Piece::Pawn);
+
+        let mut child = *board;
+        child.make_move(chess_move);
+
+        let mut replies = Vec::with_capacity(64);
+        MoveGenerator::generate_legal_moves(&child, &mut replies);
+        if replies.is_empty() {
+            let wdl = if child.in_check(child.side_to_move) {
+                Wdl::Win
+            } else {
+                Wdl::Draw
+            };
+            ranked.push(RootMove {
+                chess_move,
+                wdl,
+                dtz: if wdl == Wdl::Win { 1 } else { 0 },
+            });
+            continue;
+        }
+
+        let wdl = tablebase.probe_wdl(&child)?.flip();
+        let dtz = if zeroing {
+            // The move itself resets the counter
+            match wdl {
+                Wdl::Win | Wdl::CursedWin => 1,
+                Wdl::Loss | Wdl::BlessedLoss => -1,
+                Wdl::Draw => 0,
+            }
+        } else {
+            let child_dtz = tablebase.probe_dtz(&child)?;
+            match child_dtz.signum() {
+                -1 => -child_dtz + 1,
+                1 => -child_dtz - 1,
+                _ => 0,
+            }
+        };
+
+        // Without a reset the counter keeps running from where it is now
+        let clock = if zeroing {
+            0
+        } else {
+            board.halfmove_clock as i32
+        };
+        let wdl = match wdl {
+            Wdl::Win if dtz + clock > 100 => Wdl::CursedWin,
+            Wdl::Loss if -dtz + clock > 100 => Wdl::BlessedLoss,
+            other => other,
+        };
+
+        ranked.push(RootMove {
+            chess_move,
+            wdl,
+            dtz,
+        });
+    }
+
+    // Best result first; among wins the quickest conversion, among losses the slowest
+    ranked.sort_by_key(|m| (std::cmp::Reverse(m.wdl), m.dtz));
+    Some(ranked)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Test tablebase that knows a single rule: whoever has a queen wins.
+    /// DTZ grows with the distance between the kings, so closer kings convert faster.
+    pub(crate) struct QueenWinsTablebase;
+
+    impl Tablebase for QueenWinsTablebase {
+        fn max_pieces(&self) -> u32 {
+            4
+        }
+
+        fn probe_wdl(&self, board: &Board) -> Option<Wdl> {
+            let us = board.side_to_move;
+            let has_queen = |color| board.pieces_of(color, Piece::Queen) != 0;
+            Some(match (has_queen(us), has_queen(us.opponent())) {
+                (true, false) => Wdl::Win,
+                (false, true) => Wdl::Loss,
+                _ => Wdl::Draw,
+            })
+        }
+
+        fn probe_dtz(&self, board: &Board) -> Option<i32> {
+            let [white, black] = board.king_sq.map(|sq| sq as i32);
+            let distance = ((white % 8) - (black % 8)).abs() + ((white / 8) - (black / 8)).abs();
+            Some(match self.probe_wdl(board)? {
+                Wdl::Win => distance,
+                Wdl::Loss => -distance,
+                _ => 0,
+            })
+        }
+    }
+
+    #[test]
+    fn test_wdl_flip_and_score() {
+        assert_eq!(Wdl::Win.flip(), Wdl::Loss);
+        assert_eq!(Wdl::CursedWin.flip(), Wdl::BlessedLoss);
+        assert_eq!(Wdl::Draw.flip(), Wdl::Draw);
+        assert!(Wdl::Win.to_score(3) > Wdl::Win.to_score(5));
+        assert_eq!(Wdl::CursedWin.to_score(1), 0);
+    }
+
+    #[test]
+    fn test_rank_root_moves_keeps_the_win() {
+        // The queen on d5 is attacked by the black king; moving it away keeps the win
+        let board = Board::from_fen("8/8/3k4/3Q4/8/8/8/4K3 w - - 0 1");
+        let ranked = rank_root_moves(&QueenWinsTablebase, &board).unwrap();
+
+        let best = ranked[0];
+        assert_eq!(best.wdl, Wdl::Win);
+        assert!(
+            ranked
+                .iter()
+                .all(|m| m.wdl == Wdl::Win || m.wdl == Wdl::Draw)
+        );
+        assert!(
+            ranked
+                .iter()
+                .filter(|m| m.wdl == Wdl::Win)
+                .all(|m| m.dtz >= best.dtz)
+        );
+    }
+
+    #[test]
+    fn test_rank_root_moves_applies_fifty_move_rule() {
+        let board = Board::from_fen("8/8/3k4/3Q4/8/8/8/4K3 w - - 99 80");
+        let ranked = rank_root_moves(&QueenWinsTablebase, &board).unwrap();
+
+        assert!(ranked.iter().all(|m| m.wdl != Wdl::Win));
+        assert!(rank_root_moves(&QueenWinsTablebase, &Board::startpos()).is_none());
+    }
+}
@@ -0,0 +1,544 @@
+//! How Syzygy tables number the positions of a material.
+//!
+//! Symmetries are removed first: colors are swapped so the side the table lists first
+//! is white, the board is mirrored so the leading pawn (or piece) is on files a-d, and
+//! pawnless positions are also folded into the a1-d1-d4 triangle. Each group of like
+//! pieces is then ranked among the squares the earlier groups left free.
+
+use crate::board::{Board, Color, Piece};
+use once_cell::sync::Lazy;
+
+/// Pieces by Syzygy code minus one: pawn is 1 and king 6, and black pieces add 8
+const PIECES: [Piece; 6] = [
+    Piece::Pawn,
+    Piece::Knight,
+    Piece::Bishop,
+    Piece::Rook,
+    Piece::Queen,
+    Piece::King,
+];
+
+/// Placements of the three leading pieces of a pawnless table with a unique piece
+const UNIQUE_LEADERS: u64 = 31332;
+/// Placements of the two kings of a pawnless table without a unique piece
+const KING_PAIRS: u64 = 462;
+
+/// Syzygy code of a piece.
+pub(super) fn piece_code(color: Color, piece: Piece) -> u8 {
+    8 * color as u8 + piece as u8 + 1
+}
+
+/// Index tables shared by every table file.
+struct Maps {
+    /// Rank of each square below the a1-h8 diagonal
+    b1h1h7: [u64; 64],
+    /// Rank of each square of the a1-d1-d4 triangle, the diagonal last
+    a1d1d4: [u64; 64],
+    /// Rank of each legal king pair, by the first king's triangle rank
+    kk: [[u64; 64]; 10],
+    /// `binomial[k][n]` ways to choose k of n squares
+    binomial: [[u64; 64]; 7],
+    /// Rank of each pawn square, the ones nearest the a1 and h1 corners highest
+    pawns: [u64; 64],
+    /// First index of each leading pawn square, by number of leading pawns
+    lead_pawn_idx: [[u64; 64]; 6],
+    /// Indices taken by the leading pawns, by number of leading pawns and file
+    lead_pawns_size: [[u64; 4]; 6],
+}
+
+static MAPS: Lazy<Maps> = Lazy::new(Maps::new);
+
+/// Rank minus file: 0 on the a1-h8 diagonal, negative below it.
+fn off_diagonal(sq: usize) -> i32 {
+    (sq / 8) as i32 - (sq % 8) as i32
+}
+
+fn king_attacks(sq: usize) -> u64 {
+    let (rank, file) = ((sq / 8) as i32, (sq % 8) as i32);
+    let mut attacks = 0;
+    for dr in -1..=1 {
+        for df in -1..=1 {
+            let (r, f) = (rank + dr, file + df);
+            if (dr, df) != (0, 0) && (0..8).contains(&r) && (0..8).contains(&f) {
+                attacks |= 1u64 << (r * 8 + f);
+            }
+        }
+    }
+    attacks
+}
+
+impl Maps {
+    fn new() -> Self {
+        let mut maps = Maps {
+            b1h1h7: [0; 64],
+            a1d1d4: [0; 64],
+            kk: [[0; 64]; 10],
+            binomial: [[0; 64]; 7],
+            pawns: [0; 64],
+            lead_pawn_idx: [[0; 64]; 6],
+            lead_pawns_size: [[0; 4]; 6],
+        };
+
+        let mut code = 0;
+        for sq in 0..64 {
+            if off_diagonal(sq) < 0 {
+                maps.b1h1h7[sq] = code;
+                code += 1;
+            }
+        }
+
+        let mut code = 0;
+        for below in [true, false] {
+            for sq in 0..28 {
+                let on_side = if below {
+                    off_diagonal(sq) < 0
+                } else {
+                    off_diagonal(sq) == 0
+                };
+                if on_side && sq % 8 <= 3 {
+                    maps.a1d1d4[sq] = code;
+                    code += 1;
+                }
+            }
+        }
+
+        let mut code = 0;
+        for idx in 0..10 {
+            for s1 in 0..28 {
+                // Squares outside the triangle also map to 0; b1 is the real 0
+                if maps.a1d1d4[s1] != idx || (idx == 0 && s1 != 1) {
+                    continue;
+                }
+                let mut diagonal = Vec::new();
+                for s2 in 0..64 {
+                    if (king_attacks(s1) | 1u64 << s1) & 1u64 << s2 != 0 {
+                        continue;
+                    }
+                    if off_diagonal(s1) == 0 && off_diagonal(s2) > 0 {
+                        continue;
+                    }
+                    if off_diagonal(s1) == 0 && off_diagonal(s2) == 0 {
+                        diagonal.push(s2);
+                    } else {
+                        maps.kk[idx as usize][s2] = code;
+                        code += 1;
+                    }
+                }
+                for s2 in diagonal {
+                    maps.kk[idx as usize][s2] = code;
+                    code += 1;
+                }
+            }
+        }
+
+        for n in 0..64 {
+            maps.binomial[0][n] = 1;
+            for k in 1..7 {
+                if n > 0 {
+                    maps.binomial[k][n] = maps.binomial[k - 1][n - 1] + maps.binomial[k][n - 1];
+                }
+            }
+        }
+
+        let mut available = 47;
+        for lead_pawns in 1..6 {
+            for file in 0..4 {
+                let mut idx = 0;
+                for rank in 1..7 {
+                    let sq = rank * 8 + file;
+                    if lead_pawns == 1 {
+                        maps.pawns[sq] = available;
+                        maps.pawns[sq ^ 7] = available - 1;
+                        available = available.saturating_sub(2);
+                    }
+                    maps.lead_pawn_idx[lead_pawns][sq] = idx;
+                    idx += maps.binomial[lead_pawns - 1][maps.pawns[sq] as usize];
+                }
+                maps.lead_pawns_size[lead_pawns][file] = idx;
+            }
+        }
+
+        maps
+    }
+}
+
+/// Material of a table, white being the side its name lists first.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct Material {
+    /// `counts[color][piece]`
+    counts: [[u8; 6]; 2],
+}
+
+impl Material {
+    /// Material of a table name like `KRPvKR`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let (white, black) = name.split_once('v')?;
+        let mut counts = [[0; 6]; 2];
+        for (color, side) in [white, black].into_iter().enumerate() {
+            for letter in side.chars() {
+                let piece = match letter {
+                    'P' => Piece::Pawn,
+                    'N' => Piece::Knight,
+                    'B' => Piece::Bishop,
+                    'R' => Piece::Rook,
+                    'Q' => Piece::Queen,
+                    'K' => Piece::King,
+                    _ => return None,
+                };
+                counts[color][piece as usize] += 1;
+            }
+        }
+        (counts[0][Piece::King as usize] == 1 && counts[1][Piece::King as usize] == 1)
+            .then_some(Self { counts })
+    }
+
+    /// Material on the board, white first.
+    #[cfg(test)]
+    pub fn of(board: &Board) -> Self {
+        let mut counts = [[0; 6]; 2];
+        for color in [Color::White, Color::Black] {
+            for piece in PIECES {
+                counts[color as usize][piece as usize] =
+                    board.pieces_of(color, piece).count_ones() as u8;
+            }
+        }
+        Self { counts }
+    }
+
+    pub fn piece_count(&self) -> usize {
+        self.counts.iter().flatten().map(|&n| n as usize).sum()
+    }
+
+    pub fn has_pawns(&self) -> bool {
+        self.counts[0][0] + self.counts[1][0] > 0
+    }
+
+    /// Whether both sides have the same pieces, so black to move is stored as white to move.
+    pub fn is_symmetric(&self) -> bool {
+        self.counts[0] == self.counts[1]
+    }
+
+    /// Whether some side has exactly one pawn, knight, bishop, rook or queen.
+    pub fn has_unique_pieces(&self) -> bool {
+        self.counts.iter().any(|side| side[..5].contains(&1))
+    }
+
+    /// Color whose pawns lead the encoding: the one with fewer pawns, white on a tie.
+    pub fn lead_color(&self) -> Color {
+        let [white, black] = [self.counts[0][0], self.counts[1][0]];
+        if black == 0 || (white > 0 && black >= white) {
+            Color::White
+        } else {
+            Color::Black
+        }
+    }
+
+    /// Pawns of the leading color, then of the other.
+    pub fn pawn_counts(&self) -> [u8; 2] {
+        let lead = self.lead_color() as usize;
+        [self.counts[lead][0], self.counts[1 - lead][0]]
+    }
+
+    /// Codes of all pieces, white's first and each side from pawn to king.
+    pub fn codes(&self) -> Vec<u8> {
+        let mut codes = Vec::new();
+        for color in [Color::White, Color::Black] {
+            for piece in PIECES {
+                for _ in 0..self.counts[color as usize][piece as usize] {
+                    codes.push(piece_code(color, piece));
+                }
+            }
+        }
+        codes
+    }
+}
+
+/// Piece order and grouping of one part of a table: a side to move and, with pawns,
+/// the file of the leading pawn.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct Encoding {
+    /// Piece codes in the order squares are encoded
+    pub pieces: Vec<u8>,
+    /// Number of pieces in each group
+    pub group_len: Vec<usize>,
+    /// Multiplier of each group's rank; the last entry is the number of indices
+    pub group_idx: Vec<u64>,
+}
+
+impl Encoding {
+    /// Groups `pieces` and orders the groups: the leading group is encoded at position
+    /// `order[0]` and the other side's pawns, if both sides have some, at `order[1]`.
+    pub fn new(material: &Material, pieces: Vec<u8>, order: [u8; 2], file: usize) -> Self {
+        let maps = &*MAPS;
+        let has_pawns = material.has_pawns();
+        let mut first_len: i32 = if has_pawns {
+            0
+        } else if material.has_unique_pieces() {
+            3
+        } else {
+            2
+        };
+
+        let mut group_len = vec![1];
+        for i in 1..pieces.len() {
+            first_len -= 1;
+            if first_len > 0 || pieces[i] == pieces[i - 1] {
+                *group_len.last_mut().unwrap() += 1;
+            } else {
+                group_len.push(1);
+            }
+        }
+        let groups = group_len.len();
+
+        let both_pawns = has_pawns && material.pawn_counts()[1] > 0;
+        let mut next = if both_pawns { 2 } else { 1 };
+        let mut free_squares = 64 - group_len[0] - if both_pawns { group_len[1] } else { 0 };
+        let mut group_idx = vec![0; groups + 1];
+        let mut idx = 1u64;
+
+        let mut k = 0;
+        while next < groups || k == order[0] as usize || k == order[1] as usize {
+            if k == order[0] as usize {
+                group_idx[0] = idx;
+                idx *= if has_pawns {
+                    maps.lead_pawns_size[group_len[0]][file]
+                } else if material.has_unique_pieces() {
+                    UNIQUE_LEADERS
+                } else {
+                    KING_PAIRS
+                };
+            } else if k == order[1] as usize {
+                group_idx[1] = idx;
+                idx *= maps.binomial[group_len[1]][48 - group_len[0]];
+            } else {
+                group_idx[next] = idx;
+                idx *= maps.binomial[group_len[next]][free_squares];
+                free_squares -= group_len[next];
+                next += 1;
+            }
+            k += 1;
+        }
+        group_idx[groups] = idx;
+
+        Self {
+            pieces,
+            group_len,
+            group_idx,
+        }
+    }
+
+    /// Number of indices.
+    pub fn size(&self) -> u64 {
+        self.group_idx[self.group_len.len()]
+    }
+}
+
+/// Where a position is stored in a table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct Slot {
+    /// Side to move once colors are swapped to the table's orientation (0 is white)
+    pub stm: usize,
+    /// File of the leading pawn, counted from the nearer edge; 0 without pawns
+    pub file: usize,
+    pub index: u64,
+}
+
+/// Finds a position in a table. `encodings` holds each stored side's encoding by file;
+/// `black_stronger` says the board's black pieces match the table's white ones.
+pub(super) fn locate(
+    material: &Material,
+    encodings: &[Vec<Encoding>],
+    board: &Board,
+    black_stronger: bool,
+) -> Slot {
+    let maps = &*MAPS;
+    let flip = black_stronger || (material.is_symmetric() && board.side_to_move == Color::Black);
+    let (flip_color, flip_squares) = if flip { (8, 56) } else { (0, 0) };
+    let stm = flip as usize ^ board.side_to_move as usize;
+
+    let mut squares: Vec<usize> = Vec::with_capacity(8);
+    let mut pieces: Vec<u8> = Vec::with_capacity(8);
+    let mut lead_pawns = 0u64;
+    let mut file = 0;
+    if material.has_pawns() {
+        let lead_code = encodings[0][0].pieces[0] ^ flip_color;
+        let color = if lead_code & 8 == 0 {
+            Color::White
+        } else {
+            Color::Black
+        };
+        lead_pawns = board.pieces_of(color, Piece::Pawn);
+        let mut bits = lead_pawns;
+        while bits != 0 {
+            squares.push(bits.trailing_zeros() as usize ^ flip_squares);
+            pieces.push(encodings[0][0].pieces[0]);
+            bits &= bits - 1;
+        }
+        // The pawn nearest the a1 or h1 corner leads
+        let leader = (0..squares.len())
+            .max_by_key(|&i| maps.pawns[squares[i]])
+            .unwrap();
+        squares.swap(0, leader);
+        file = (squares[0] % 8).min(7 - squares[0] % 8);
+    }
+    let lead_count = squares.len();
+
+    let mut bits = board.occ_all & !lead_pawns;
+    while bits != 0 {
+        let sq = bits.trailing_zeros() as u8;
+        let (color, piece) = board.piece_on(sq).unwrap();
+        squares.push(sq as usize ^ flip_squares);
+        pieces.push(piece_code(color, piece) ^ flip_color);
+        bits &= bits - 1;
+    }
+
+    let encoding = &encodings[stm % encodings.len()][file];
+
+    // Put the pieces in the table's order
+    for i in lead_count..squares.len() {
+        if let Some(j) = (i..squares.len()).find(|&j| pieces[j] == encoding.pieces[i]) {
+            pieces.swap(i, j);
+            squares.swap(i, j);
+        }
+    }
+
+    if squares[0] % 8 > 3 {
+        squares.iter_mut().for_each(|sq| *sq ^= 7);
+    }
+
+    let mut index;
+    if material.has_pawns() {
+        index = maps.lead_pawn_idx[lead_count][squares[0]];
+        squares[1..lead_count].sort_by_key(|&sq| maps.pawns[sq]);
+        for (i, &sq) in squares.iter().enumerate().take(lead_count).skip(1) {
+            index += maps.binomial[i][maps.pawns[sq] as usize];
+        }
+    } else {
+        if squares[0] / 8 > 3 {
+            squares.iter_mut().for_each(|sq| *sq ^= 56);
+        }
+
+        // Mirror along the diagonal so the first leader off it lies below it
+        if let Some(i) = (0..encoding.group_len[0]).find(|&i| off_diagonal(squares[i]) != 0)
+            && off_diagonal(squares[i]) > 0
+        {
+            for sq in &mut squares[i..] {
+                *sq = ((*sq >> 3) | (*sq << 3)) & 63;
+            }
+        }
+
+        index = if material.has_unique_pieces() {
+            unique_leaders_index(&squares)
+        } else {
+            maps.kk[maps.a1d1d4[squares[0]] as usize][squares[1]]
+        };
+    }
+
+    index *= encoding.group_idx[0];
+    let mut start = encoding.group_len[0];
+    let mut remaining_pawns = material.has_pawns() && material.pawn_counts()[1] > 0;
+    for next in 1..encoding.group_len.len() {
+        let len = encoding.group_len[next];
+        squares[start..start + len].sort_unstable();
+        let mut n = 0;
+        for i in 0..len {
+            let sq = squares[start + i];
+            // Squares taken by earlier groups don't count
+            let adjust = squares[..start].iter().filter(|&&s| sq > s).count();
+            let pawn_offset = if remaining_pawns { 8 } else { 0 };
+            n += maps.binomial[i + 1][sq - adjust - pawn_offset];
+        }
+        remaining_pawns = false;
+        index += n * encoding.group_idx[next];
+        start += len;
+    }
+
+    Slot { stm, file, index }
+}
+
+/// Index of the three leading pieces of a pawnless table with a unique piece, the
+/// first already in the a1-d1-d4 triangle.
+fn unique_leaders_index(squares: &[usize]) -> u64 {
+    let maps = &*MAPS;
+    let [s0, s1, s2] = [squares[0], squares[1], squares[2]];
+    let adjust1 = (s1 > s0) as usize;
+    let adjust2 = (s2 > s0) as usize + (s2 > s1) as usize;
+    let rank = |sq: usize| (sq / 8) as u64;
+
+    if off_diagonal(s0) != 0 {
+        (maps.a1d1d4[s0] * 63 + (s1 - adjust1) as u64) * 62 + (s2 - adjust2) as u64
+    } else if off_diagonal(s1) != 0 {
+        (6 * 63 + rank(s0) * 28 + maps.b1h1h7[s1]) * 62 + (s2 - adjust2) as u64
+    } else if off_diagonal(s2) != 0 {
+        6 * 63 * 62
+            + 4 * 28 * 62
+            + rank(s0) * 7 * 28
+            + (rank(s1) - adjust1 as u64) * 28
+            + maps.b1h1h7[s2]
+    } else {
+        6 * 63 * 62
+            + 4 * 28 * 62
+            + 4 * 7 * 28
+            + rank(s0) * 7 * 6
+            + (rank(s1) - adjust1 as u64) * 6
+            + (rank(s2) - adjust2 as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_tables() {
+        let maps = &*MAPS;
+        // b1 c1 d1 c2 d2 d3, then the diagonal a1 b2 c3 d4
+        assert_eq!(maps.a1d1d4[1], 0);
+        assert_eq!(maps.a1d1d4[19], 5);
+        assert_eq!(maps.a1d1d4[0], 6);
+        assert_eq!(maps.a1d1d4[27], 9);
+        assert_eq!(maps.kk.iter().flatten().max(), Some(&(KING_PAIRS - 1)));
+        assert_eq!(maps.binomial[2][6], 15);
+        // a2 leads, then its mirror h2; e7 is last of the 48 pawn squares
+        assert_eq!(maps.pawns[8], 47);
+        assert_eq!(maps.pawns[15], 46);
+        assert_eq!(maps.pawns[52], 0);
+        assert_eq!(maps.lead_pawns_size[1], [6; 4]);
+    }
+
+    #[test]
+    fn test_encoding_sizes() {
+        let kqvk = Material::from_name("KQvK").unwrap();
+        let encoding = Encoding::new(&kqvk, vec![5, 6, 14], [0, 0xF], 0);
+        assert_eq!(encoding.group_len, vec![3]);
+        assert_eq!(encoding.size(), UNIQUE_LEADERS);
+
+        let knnvk = Material::from_name("KNNvK").unwrap();
+        assert!(!knnvk.has_unique_pieces());
+        let encoding = Encoding::new(&knnvk, vec![6, 14, 2, 2], [0, 0xF], 0);
+        assert_eq!(encoding.group_len, vec![2, 2]);
+        assert_eq!(encoding.size(), KING_PAIRS * 62 * 61 / 2);
+
+        let kpvk = Material::from_name("KPvK").unwrap();
+        let encoding = Encoding::new(&kpvk, vec![1, 6, 14], [0, 0xF], 2);
+        assert_eq!(encoding.size(), 6 * 63 * 62);
+    }
+
+    #[test]
+    fn test_mirrored_positions_share_an_index() {
+        let material = Material::from_name("KQvK").unwrap();
+        let encodings = vec![vec![Encoding::new(&material, vec![5, 6, 14], [0, 0xF], 0)]; 2];
+        let locate_fen = |fen: &str, black_stronger| {
+            locate(&material, &encodings, &Board::from_fen(fen), black_stronger)
+        };
+
+        let slot = locate_fen("8/8/8/8/8/2k5/8/1K1Q4 w - - 0 1", false);
+        // Mirrored across the center files, across the ranks, and along the diagonal
+        assert_eq!(locate_fen("8/8/8/8/8/5k2/8/4Q1K1 w - - 0 1", false), slot);
+        assert_eq!(locate_fen("1K1Q4/8/2k5/8/8/8/8/8 w - - 0 1", false), slot);
+        assert_eq!(locate_fen("8/8/8/8/Q7/2k5/K7/8 w - - 0 1", false), slot);
+        // Colors swapped: black has the queen and is to move
+        assert_eq!(locate_fen("1k1q4/8/2K5/8/8/8/8/8 b - - 0 1", true), slot);
+        assert!(slot.index < UNIQUE_LEADERS);
+    }
+}
@@ -0,0 +1,756 @@
+//! Builds small Syzygy tables for the tests.
+//!
+//! The official tables can't be downloaded in every build environment, so the fixtures
+//! in `tests/fixtures/syzygy` come from here: a retrograde solver works out every
+//! position's WDL and DTZ with the engine's move generator, and a writer compresses
+//! the results in the Syzygy file format. Regenerate them with
+//! `cargo test --release -- --ignored regenerate_syzygy_fixtures`.
+
+use super::encoding::{Encoding, Material, Slot, locate, piece_code};
+use super::table::{LEAF, LOSS_PLIES, MAPPED, SINGLE_VALUE, STM, WIN_PLIES};
+use super::{DTZ_MAGIC, SyzygyTablebase, WDL_MAGIC};
+use crate::board::{Board, Color, Piece};
+use crate::movegen::MoveGenerator;
+use crate::tablebase::{Tablebase, Wdl};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Fixture tables with the side to move their DTZ table stores, in an order where
+/// captures and promotions only lead to tables generated earlier
+pub(super) const FIXTURE_TABLES: [(&str, Color); 6] = [
+    ("KNvK", Color::White),
+    ("KBvK", Color::White),
+    ("KRvK", Color::Black),
+    ("KQvK", Color::White),
+    ("KPvK", Color::White),
+    ("KNNvK", Color::Black),
+];
+
+/// log2 of the bytes in a block
+const BLOCK_SHIFT: u8 = 6;
+/// log2 of the values between sparse index entries
+const SPAN_SHIFT: u8 = 10;
+/// Most values in a block; leaves room for a sparse index entry past the end
+const MAX_BLOCK_VALUES: usize = 64_000;
+/// Most values one symbol may expand to
+const MAX_SYMBOL_VALUES: usize = 256;
+/// Pairs seen fewer times than this are not worth a symbol
+const MIN_PAIR_COUNT: u32 = 8;
+
+/// Marks a position no legal position maps to, or an unsolved one
+const UNKNOWN: i8 = i8::MIN;
+const UNKNOWN_DTZ: i16 = i16::MIN;
+
+pub(super) fn fixture_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/syzygy")
+}
+
+/// Piece order of the writer's tables: what the encoding needs to lead, then like
+/// pieces together.
+fn piece_order(material: &Material) -> Vec<u8> {
+    let mut rest = material.codes();
+    let mut take = |code: u8, order: &mut Vec<u8>| {
+        let i = rest.iter().position(|&c| c == code).unwrap();
+        order.push(rest.remove(i));
+    };
+
+    let mut order = Vec::new();
+    if material.has_pawns() {
+        let lead = material.lead_color();
+        let [lead_pawns, other_pawns] = material.pawn_counts();
+        for _ in 0..lead_pawns {
+            take(piece_code(lead, Piece::Pawn), &mut order);
+        }
+        for _ in 0..other_pawns {
+            take(piece_code(lead.opponent(), Piece::Pawn), &mut order);
+        }
+    } else {
+        if material.has_unique_pieces() {
+            let codes = material.codes();
+            let unique = *codes
+                .iter()
+                .find(|&&c| c & 7 != 6 && codes.iter().filter(|&&d| d == c).count() == 1)
+                .unwrap();
+            take(unique, &mut order);
+        }
+        take(piece_code(Color::White, Piece::King), &mut order);
+        take(piece_code(Color::Black, Piece::King), &mut order);
+    }
+    order.extend(rest);
+    order
+}
+
+/// Encodings of both sides to move, by file.
+fn writer_encodings(material: &Material) -> Vec<Vec<Encoding>> {
+    let pieces = piece_order(material);
+    let both_pawns = material.has_pawns() && material.pawn_counts()[1] > 0;
+    let order = [0, if both_pawns { 1 } else { 0xF }];
+    let files = if material.has_pawns() { 4 } else { 1 };
+    let by_file: Vec<Encoding> = (0..files)
+        .map(|file| Encoding::new(material, pieces.clone(), order, file))
+        .collect();
+    vec![by_file.clone(), by_file]
+}
+
+/// How a move from a solved position turns out.
+#[derive(Clone, Copy)]
+enum Child {
+    /// Same material: the position's flat index, and whether the move is a pawn move
+    Same(usize, bool),
+    /// Capture or promotion, with its result for the side making it
+    Converted(Wdl),
+}
+
+/// Every position of a material with its WDL and DTZ.
+struct Solution {
+    material: Material,
+    encodings: Vec<Vec<Encoding>>,
+    /// Flat index of the first position of each side to move and file
+    offsets: Vec<Vec<usize>>,
+    /// WDL as in `Wdl`, for the side to move
+    wdl: Vec<i8>,
+    /// DTZ in plies, signed as `probe_dtz` returns it
+    dtz: Vec<i16>,
+}
+
+impl Solution {
+    fn flat(offsets: &[Vec<usize>], slot: Slot) -> usize {
+        offsets[slot.stm][slot.file] + slot.index as usize
+    }
+
+    fn solve(material: Material, smaller: &SyzygyTablebase) -> Self {
+        let encodings = writer_encodings(&material);
+        let mut offsets = Vec::new();
+        let mut total = 0;
+        for side in &encodings {
+            offsets.push(
+                side.iter()
+                    .map(|encoding| {
+                        total += encoding.size() as usize;
+                        total - encoding.size() as usize
+                    })
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        // One board for every index a legal position maps to
+        let codes = material.codes();
+        let mut boards: Vec<Option<Board>> = vec![None; total];
+        let mut squares = vec![0u8; codes.len()];
+        place(&codes, &mut squares, 0, &mut |squares| {
+            for stm in [Color::White, Color::Black] {
+                let board = build_board(&codes, squares, stm);
+                if board.in_check(stm.opponent()) {
+                    continue;
+                }
+                let slot = locate(&material, &encodings, &board, false);
+                boards[Self::flat(&offsets, slot)].get_or_insert(board);
+            }
+        });
+
+        let mut children: Vec<Vec<Child>> = vec![Vec::new(); total];
+        let mut mated = vec![false; total];
+        let mut wdl = vec![UNKNOWN; total];
+        let mut moves = Vec::with_capacity(64);
+        for (i, board) in boards.iter().enumerate() {
+            let Some(board) = board else { continue };
+            MoveGenerator::generate_legal_moves(board, &mut moves);
+            if moves.is_empty() {
+                mated[i] = board.in_check(board.side_to_move);
+                wdl[i] = if mated[i] { Wdl::Loss } else { Wdl::Draw } as i8;
+            }
+            for &chess_move in &moves {
+                let pawn_move = board
+                    .piece_on(chess_move.from() as u8)
+                    .is_some_and(|(_, piece)| piece == Piece::Pawn);
+                let mut child = *board;
+                child.make_move(chess_move);
+                children[i].push(if Material::of(&child) == material {
+                    let slot = locate(&material, &encodings, &child, false);
+                    let flat = Self::flat(&offsets, slot);
+                    assert!(boards[flat].is_some(), "{} has no index", child.to_fen());
+                    Child::Same(flat, pawn_move)
+                } else if child.occ_all.count_ones() == 2 {
+                    Child::Converted(Wdl::Draw)
+                } else {
+                    let result = smaller.probe_wdl(&child).expect("smaller table missing");
+                    Child::Converted(result.flip())
+                });
+            }
+        }
+
+        // WDL: iterate until nothing changes; whatever is still open is a draw
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for i in 0..total {
+                if boards[i].is_none() || wdl[i] != UNKNOWN {
+                    continue;
+                }
+                let mut best = Wdl::Loss as i8;
+                let mut all_known = true;
+                for &child in &children[i] {
+                    let value = match child {
+                        Child::Converted(result) => result as i8,
+                        Child::Same(j, _) if wdl[j] == UNKNOWN => {
+                            all_known = false;
+                            continue;
+                        }
+                        Child::Same(j, _) => -wdl[j],
+                    };
+                    best = best.max(value);
+                }
+                if best == Wdl::Win as i8 || all_known {
+                    wdl[i] = best;
+                    changed = true;
+                }
+            }
+        }
+        for i in 0..total {
+            if boards[i].is_some() && wdl[i] == UNKNOWN {
+                wdl[i] = Wdl::Draw as i8;
+            }
+        }
+
+        // DTZ: pass k settles positions k plies from zeroing, from the previous pass's
+        // values, so each win takes its quickest way
+        let mut dtz = vec![UNKNOWN_DTZ; total];
+        for i in 0..total {
+            if boards[i].is_some() && (wdl[i] == Wdl::Draw as i8 || mated[i]) {
+                dtz[i] = if mated[i] { -1 } else { 0 };
+            }
+        }
+        for pass in 1.. {
+            let previous = dtz.clone();
+            let mut open = 0;
+            for i in 0..total {
+                if boards[i].is_none() || previous[i] != UNKNOWN_DTZ {
+                    continue;
+                }
+                open += 1;
+                if wdl[i] == Wdl::Win as i8 {
+                    let best = children[i]
+                        .iter()
+                        .filter_map(|&child| match child {
+                            Child::Converted(Wdl::Win) => Some(1),
+                            Child::Same(j, true) if wdl[j] == Wdl::Loss as i8 => Some(1),
+                            Child::Same(j, false) if mated[j] => Some(1),
+                            Child::Same(j, false)
+                                if wdl[j] == Wdl::Loss as i8 && previous[j] != UNKNOWN_DTZ =>
+                            {
+                                Some(1 - previous[j])
+                            }
+                            _ => None,
+                        })
+                        .min();
+                    if let Some(best) = best.filter(|&best| best <= pass) {
+                        dtz[i] = best;
+                    }
+                } else {
+                    let mut longest = Some(-1);
+                    for &child in &children[i] {
+                        longest = match child {
+                            Child::Same(j, false) if previous[j] == UNKNOWN_DTZ => None,
+                            Child::Same(j, false) => longest.map(|l: i16| l.min(-previous[j] - 1)),
+                            _ => longest,
+                        };
+                    }
+                    if let Some(longest) = longest {
+                        dtz[i] = longest;
+                    }
+                }
+            }
+            if open == 0 {
+                break;
+            }
+            assert!(pass < 1000, "DTZ didn't converge");
+        }
+        assert!(
+            dtz.iter().all(|&d| d == UNKNOWN_DTZ || d.abs() <= 100),
+            "the fixtures have no fifty-move results"
+        );
+
+        Self {
+            material,
+            encodings,
+            offsets,
+            wdl,
+            dtz,
+        }
+    }
+
+    /// The WDL table file.
+    fn wdl_file(&self) -> Vec<u8> {
+        let sides = if self.material.is_symmetric() { 1 } else { 2 };
+        let parts = (0..self.encodings[0].len())
+            .map(|file| {
+                (0..sides)
+                    .map(|side| {
+                        let values = self.part_values(side, file, |i| {
+                            (self.wdl[i] != UNKNOWN).then(|| (self.wdl[i] + 2) as u16)
+                        });
+                        compress(&values, 0)
+                    })
+                    .collect()
+            })
+            .collect();
+        write_file(WDL_MAGIC, &self.material, &self.encodings[0], parts, &[])
+    }
+
+    /// The DTZ table file, storing positions with `side` to move.
+    fn dtz_file(&self, side: Color) -> Vec<u8> {
+        let side = side as usize;
+        let flags = MAPPED | WIN_PLIES | LOSS_PLIES | if side == 1 { STM } else { 0 };
+        let mut parts = Vec::new();
+        let mut maps = Vec::new();
+        for file in 0..self.encodings[0].len() {
+            let range = self.offsets[side][file]
+                ..self.offsets[side][file] + self.encodings[side][file].size() as usize;
+
+            // Map the distances of each result to ranks, the most common first
+            let mut lists = Vec::new();
+            let mut ranks: Vec<HashMap<i16, u16>> = Vec::new();
+            for result in [Wdl::Win, Wdl::Loss] {
+                let mut counts: HashMap<i16, usize> = HashMap::new();
+                for i in range.clone() {
+                    if self.wdl[i] == result as i8 {
+                        *counts.entry(self.dtz[i].abs() - 1).or_default() += 1;
+                    }
+                }
+                let mut values: Vec<(i16, usize)> = counts.into_iter().collect();
+                values.sort_by_key(|&(value, count)| (Reverse(count), value));
+                assert!(values.len() < 256 && values.iter().all(|&(v, _)| v < 256));
+                lists.push(values.iter().map(|&(v, _)| v as u8).collect::<Vec<u8>>());
+                ranks.push(
+                    values
+                        .iter()
+                        .enumerate()
+                        .map(|(rank, &(value, _))| (value, rank as u16))
+                        .collect(),
+                );
+            }
+            lists.extend([Vec::new(), Vec::new()]);
+            maps.push(lists);
+
+            let values = self.part_values(side, file, |i| match self.wdl[i] {
+                w if w == Wdl::Win as i8 => Some(ranks[0][&(self.dtz[i] - 1)]),
+                w if w == Wdl::Loss as i8 => Some(ranks[1][&(-self.dtz[i] - 1)]),
+                _ => None,
+            });
+            parts.push(vec![compress(&values, flags)]);
+        }
+        write_file(
+            DTZ_MAGIC,
+            &self.material,
+            &self.encodings[side],
+            parts,
+            &maps,
+        )
+    }
+
+    /// Values of one part; indices `value` has nothing for repeat the previous value.
+    fn part_values(
+        &self,
+        side: usize,
+        file: usize,
+        value: impl Fn(usize) -> Option<u16>,
+    ) -> Vec<u16> {
+        let start = self.offsets[side][file];
+        let size = self.encodings[side][file].size() as usize;
+        let mut values = Vec::with_capacity(size);
+        let mut last = 0;
+        for i in start..start + size {
+            last = value(i).unwrap_or(last);
+            values.push(last);
+        }
+        values
+    }
+}
+
+/// Calls `visit` with every placement of `codes` on distinct squares, pawns off the
+/// back ranks and like pieces in increasing square order.
+fn place(codes: &[u8], squares: &mut [u8], n: usize, visit: &mut impl FnMut(&[u8])) {
+    if n == codes.len() {
+        visit(squares);
+        return;
+    }
+    let first = if n > 0 && codes[n] == codes[n - 1] {
+        squares[n - 1] + 1
+    } else {
+        0
+    };
+    for sq in first..64 {
+        if squares[..n].contains(&sq) || (codes[n] & 7 == 1 && !(8..56).contains(&sq)) {
+            continue;
+        }
+        squares[n] = sq;
+        place(codes, squares, n + 1, visit);
+    }
+}
+
+fn build_board(codes: &[u8], squares: &[u8], side_to_move: Color) -> Board {
+    const PIECES: [Piece; 6] = [
+        Piece::Pawn,
+        Piece::Knight,
+        Piece::Bishop,
+        Piece::Rook,
+        Piece::Queen,
+        Piece::King,
+    ];
+    let mut board = Board::new_empty();
+    for (&code, &sq) in codes.iter().zip(squares) {
+        let color = if code & 8 == 0 {
+            Color::White
+        } else {
+            Color::Black
+        };
+        let piece = PIECES[(code & 7) as usize - 1];
+        board.pieces[color as usize][piece as usize] |= 1u64 << sq;
+        board.occ[color as usize] |= 1u64 << sq;
+        if piece == Piece::King {
+            board.king_sq[color as usize] = sq;
+        }
+    }
+    board.occ_all = board.occ[0] | board.occ[1];
+    board.side_to_move = side_to_move;
+    board
+}
+
+/// One compressed part, ready to be laid out in a file.
+struct Part {
+    /// Flags and compression settings
+    header: Vec<u8>,
+    sparse_index: Vec<u8>,
+    block_lengths: Vec<u8>,
+    blocks: Vec<u8>,
+}
+
+/// A symbol: a leaf holding a value, or a pair of symbols.
+#[derive(Clone, Copy)]
+struct Symbol {
+    left: u32,
+    right: u32,
+    values: usize,
+}
+
+/// Compresses values the way Syzygy tables do: pairs of symbols become new symbols,
+/// the symbols are Huffman coded, and the codes are packed into blocks.
+fn compress(values: &[u16], flags: u8) -> Part {
+    if values.iter().all(|&v| v == values[0]) {
+        return Part {
+            header: vec![flags | SINGLE_VALUE, values[0] as u8],
+            sparse_index: Vec::new(),
+            block_lengths: Vec::new(),
+            blocks: Vec::new(),
+        };
+    }
+
+    let mut symbols: Vec<Symbol> = Vec::new();
+    let mut leaf_of: HashMap<u16, u32> = HashMap::new();
+    let mut sequence: Vec<u32> = values
+        .iter()
+        .map(|&value| {
+            *leaf_of.entry(value).or_insert_with(|| {
+                symbols.push(Symbol {
+                    left: value as u32,
+                    right: LEAF as u32,
+                    values: 1,
+                });
+                symbols.len() as u32 - 1
+            })
+        })
+        .collect();
+
+    // Replace the most common adjacent pair with a new symbol until none is common
+    while symbols.len() < LEAF as usize {
+        let mut counts: HashMap<(u32, u32), u32> = HashMap::new();
+        for pair in sequence.windows(2) {
+            if symbols[pair[0] as usize].values + symbols[pair[1] as usize].values
+                <= MAX_SYMBOL_VALUES
+            {
+                *counts.entry((pair[0], pair[1])).or_default() += 1;
+            }
+        }
+        let Some((&(left, right), &count)) = counts
+            .iter()
+            .max_by_key(|&(&pair, &count)| (count, Reverse(pair)))
+        else {
+            break;
+        };
+        if count < MIN_PAIR_COUNT {
+            break;
+        }
+        symbols.push(Symbol {
+            left,
+            right,
+            values: symbols[left as usize].values + symbols[right as usize].values,
+        });
+        let pair_symbol = symbols.len() as u32 - 1;
+        let mut replaced = Vec::with_capacity(sequence.len());
+        let mut i = 0;
+        while i < sequence.len() {
+            if i + 1 < sequence.len() && (sequence[i], sequence[i + 1]) == (left, right) {
+                replaced.push(pair_symbol);
+                i += 2;
+            } else {
+                replaced.push(sequence[i]);
+                i += 1;
+            }
+        }
+        sequence = replaced;
+    }
+
+    // Huffman code lengths of the symbols left in the sequence
+    let mut frequency = vec![0u64; symbols.len()];
+    for &sym in &sequence {
+        frequency[sym as usize] += 1;
+    }
+    let mut lengths = vec![0u32; symbols.len()];
+    let coded: Vec<usize> = (0..symbols.len()).filter(|&s| frequency[s] > 0).collect();
+    if coded.len() == 1 {
+        lengths[coded[0]] = 1;
+    } else {
+        let mut parent: Vec<usize> = Vec::new();
+        let mut heap: BinaryHeap<Reverse<(u64, usize)>> = BinaryHeap::new();
+        for (node, &sym) in coded.iter().enumerate() {
+            heap.push(Reverse((frequency[sym], node)));
+            parent.push(usize::MAX);
+        }
+        while heap.len() > 1 {
+            let Reverse((a, x)) = heap.pop().unwrap();
+            let Reverse((b, y)) = heap.pop().unwrap();
+            parent.push(usize::MAX);
+            let node = parent.len() - 1;
+            parent[x] = node;
+            parent[y] = node;
+            heap.push(Reverse((a + b, node)));
+        }
+        for (node, &sym) in coded.iter().enumerate() {
+            let mut depth = 0;
+            let mut at = node;
+            while parent[at] != usize::MAX {
+                at = parent[at];
+                depth += 1;
+            }
+            lengths[sym] = depth;
+        }
+    }
+    let max_len = coded.iter().map(|&s| lengths[s]).max().unwrap();
+    let min_len = coded.iter().map(|&s| lengths[s]).min().unwrap();
+    assert!(max_len <= 32, "code too long");
+
+    // Canonical codes: the longest codes get the lowest symbol numbers and codes
+    let mut order = coded.clone();
+    order.sort_by_key(|&s| (Reverse(lengths[s]), s));
+    order.extend((0..symbols.len()).filter(|&s| frequency[s] == 0));
+    let mut renumber = vec![0u32; symbols.len()];
+    for (new, &old) in order.iter().enumerate() {
+        renumber[old] = new as u32;
+    }
+    let count_of = |len: u32| coded.iter().filter(|&&s| lengths[s] == len).count() as u64;
+    let lens = (max_len - min_len + 1) as usize;
+    let mut lowest_sym = vec![0u64; lens];
+    let mut base = vec![0u64; lens];
+    for l in (0..lens - 1).rev() {
+        let longer = count_of(min_len + l as u32 + 1);
+        lowest_sym[l] = lowest_sym[l + 1] + longer;
+        assert_eq!((base[l + 1] + longer) % 2, 0, "incomplete code");
+        base[l] = (base[l + 1] + longer) / 2;
+    }
+    let code_of = |sym: usize| {
+        let l = (lengths[sym] - min_len) as usize;
+        (base[l] + renumber[sym] as u64 - lowest_sym[l], lengths[sym])
+    };
+
+    // Pack the codes into blocks
+    let block_bytes = 1usize << BLOCK_SHIFT;
+    let mut blocks: Vec<u8> = Vec::new();
+    let mut block_values: Vec<usize> = Vec::new();
+    let mut block = BitWriter::default();
+    let mut values_in_block = 0;
+    for &sym in &sequence {
+        let (code, len) = code_of(sym as usize);
+        let sym_values = symbols[sym as usize].values;
+        if block.bits + len as usize > 8 * block_bytes
+            || values_in_block + sym_values > MAX_BLOCK_VALUES
+        {
+            blocks.extend(block.finish(block_bytes));
+            block_values.push(values_in_block);
+            block = BitWriter::default();
+            values_in_block = 0;
+        }
+        block.push(code, len);
+        values_in_block += sym_values;
+    }
+    blocks.extend(block.finish(block_bytes));
+    block_values.push(values_in_block);
+
+    // Each sparse index entry locates the value in the middle of its span
+    let span = 1usize << SPAN_SHIFT;
+    let mut starts = vec![0];
+    for &n in &block_values {
+        starts.push(starts.last().unwrap() + n);
+    }
+    let mut sparse_index = Vec::new();
+    for k in 0..values.len().div_ceil(span) {
+        let target = k * span + span / 2;
+        let block = (0..block_values.len())
+            .find(|&b| target < starts[b + 1])
+            .unwrap_or(block_values.len() - 1);
+        sparse_index.extend((block as u32).to_le_bytes());
+        sparse_index.extend(u16::try_from(target - starts[block]).unwrap().to_le_bytes());
+    }
+
+    let mut header = vec![
+        flags,
+        BLOCK_SHIFT,
+        SPAN_SHIFT,
+        0, // no padding entries
+    ];
+    header.extend((block_values.len() as u32).to_le_bytes());
+    header.extend([max_len as u8, min_len as u8]);
+    for &lowest in &lowest_sym {
+        header.extend((lowest as u16).to_le_bytes());
+    }
+    header.extend((symbols.len() as u16).to_le_bytes());
+    for &old in &order {
+        let symbol = symbols[old];
+        let (left, right) = if symbol.right == LEAF as u32 {
+            (symbol.left, LEAF as u32)
+        } else {
+            (
+                renumber[symbol.left as usize],
+                renumber[symbol.right as usize],
+            )
+        };
+        header.extend([
+            left as u8,
+            ((left >> 8) | (right & 0xF) << 4) as u8,
+            (right >> 4) as u8,
+        ]);
+    }
+    if symbols.len() % 2 == 1 {
+        header.push(0);
+    }
+
+    Part {
+        header,
+        sparse_index,
+        block_lengths: block_values
+            .iter()
+            .flat_map(|&n| ((n - 1) as u16).to_le_bytes())
+            .collect(),
+        blocks,
+    }
+}
+
+/// Writes codes most significant bit first.
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    bits: usize,
+}
+
+impl BitWriter {
+    fn push(&mut self, code: u64, len: u32) {
+        for bit in (0..len).rev() {
+            if self.bits.is_multiple_of(8) {
+                self.bytes.push(0);
+            }
+            if code >> bit & 1 != 0 {
+                *self.bytes.last_mut().unwrap() |= 0x80 >> (self.bits % 8);
+            }
+            self.bits += 1;
+        }
+    }
+
+    fn finish(mut self, len: usize) -> Vec<u8> {
+        self.bytes.resize(len, 0);
+        self.bytes
+    }
+}
+
+/// Lays out a table file: header, piece order, part settings, the DTZ map, sparse
+/// indices, block lengths and 64-byte aligned blocks.
+fn write_file(
+    magic: [u8; 4],
+    material: &Material,
+    encodings: &[Encoding],
+    parts: Vec<Vec<Part>>,
+    maps: &[Vec<Vec<u8>>],
+) -> Vec<u8> {
+    let both_pawns = material.has_pawns() && material.pawn_counts()[1] > 0;
+    let mut bytes = magic.to_vec();
+    bytes.push(!material.is_symmetric() as u8 | (material.has_pawns() as u8) << 1);
+    for encoding in encodings {
+        bytes.push(0);
+        if both_pawns {
+            bytes.push(0x11);
+        }
+        bytes.extend(encoding.pieces.iter().map(|&code| code | code << 4));
+    }
+    let align = |bytes: &mut Vec<u8>, alignment: usize| {
+        bytes.resize(bytes.len().next_multiple_of(alignment), 0);
+    };
+    align(&mut bytes, 2);
+
+    for file_parts in &parts {
+        for part in file_parts {
+            bytes.extend(&part.header);
+        }
+    }
+    for lists in maps {
+        for list in lists {
+            bytes.push(list.len() as u8);
+            bytes.extend(list);
+        }
+    }
+    align(&mut bytes, 2);
+    for file_parts in &parts {
+        for part in file_parts {
+            bytes.extend(&part.sparse_index);
+        }
+    }
+    for file_parts in &parts {
+        for part in file_parts {
+            bytes.extend(&part.block_lengths);
+        }
+    }
+    for file_parts in &parts {
+        for part in file_parts {
+            align(&mut bytes, 64);
+            bytes.extend(&part.blocks);
+        }
+    }
+    bytes
+}
+
+/// Solves and writes the fixture tables.
+#[test]
+#[ignore = "slow in debug builds; run in release to regenerate the fixtures"]
+fn regenerate_syzygy_fixtures() {
+    let dir = fixture_dir();
+    std::fs::create_dir_all(&dir).unwrap();
+    for (name, dtz_side) in FIXTURE_TABLES {
+        let smaller = SyzygyTablebase::open(dir.to_str().unwrap()).unwrap();
+        let material = Material::from_name(name).unwrap();
+        assert!(!material.is_symmetric());
+        let solution = Solution::solve(material, &smaller);
+
+        // Known longest wins, white to move: mate in 10 with a queen, 16 with a rook
+        let white_to_move = &solution.dtz[..solution.offsets[1][0]];
+        match name {
+            "KQvK" => assert_eq!(white_to_move.iter().max(), Some(&19)),
+            "KRvK" => assert_eq!(white_to_move.iter().max(), Some(&31)),
+            _ => {}
+        }
+
+        std::fs::write(dir.join(format!("{}.rtbw", name)), solution.wdl_file()).unwrap();
+        std::fs::write(
+            dir.join(format!("{}.rtbz", name)),
+            solution.dtz_file(dtz_side),
+        )
+        .unwrap();
+    }
+}
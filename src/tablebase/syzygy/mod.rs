@@ -0,0 +1,597 @@
+//! Syzygy endgame tablebases.
+//!
+//! Tables are named by material, stronger side first: `KQvK.rtbw` holds WDL results
+//! and `KQvK.rtbz` holds DTZ for king and queen against king. The layout and
+//! compression follow the Syzygy generator; see [`table`] and [`encoding`].
+
+mod encoding;
+#[cfg(test)]
+mod generate;
+mod table;
+
+use crate::board::{Board, Color, Piece};
+use crate::movegen::MoveGenerator;
+use crate::tablebase::{Tablebase, Wdl};
+use encoding::Material;
+use once_cell::sync::OnceCell;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use table::{DtzLookup, Table, TableKind};
+
+/// First four bytes of every `.rtbw` file
+const WDL_MAGIC: [u8; 4] = [0x71, 0xE8, 0x23, 0x5D];
+/// First four bytes of every `.rtbz` file
+const DTZ_MAGIC: [u8; 4] = [0xD7, 0x66, 0x0C, 0xA5];
+
+/// Largest tables the Syzygy format defines
+const MAX_TABLE_PIECES: u32 = 7;
+
+/// Piece letters in the order Syzygy file names list them
+const NAME_ORDER: [(Piece, char); 6] = [
+    (Piece::King, 'K'),
+    (Piece::Queen, 'Q'),
+    (Piece::Rook, 'R'),
+    (Piece::Bishop, 'B'),
+    (Piece::Knight, 'N'),
+    (Piece::Pawn, 'P'),
+];
+
+/// A table file, read and parsed the first time a position with its material is probed.
+struct TableFile {
+    path: PathBuf,
+    kind: TableKind,
+    material: Material,
+    table: OnceCell<Result<Table, String>>,
+}
+
+impl TableFile {
+    /// The parsed table, or None if the file is unreadable or corrupt.
+    fn get(&self) -> Option<&Table> {
+        self.table
+            .get_or_init(|| Table::load(&self.path, self.kind, self.material.clone()))
+            .as_ref()
+            .ok()
+    }
+}
+
+/// Syzygy tablebase files found in a directory.
+///
+/// Probes resolve captures with a small search, as the tables store arbitrary values
+/// for positions where a capture is best. A file is read into memory the first time
+/// it is probed; if it turns out to be corrupt, probes of its material return None
+/// and the search falls back to its normal evaluation.
+pub struct SyzygyTablebase {
+    dir: PathBuf,
+    wdl_tables: HashMap<String, TableFile>,
+    dtz_tables: HashMap<String, TableFile>,
+    max_pieces: u32,
+}
+
+impl SyzygyTablebase {
+    /// Scans a directory for `.rtbw` and `.rtbz` files, checking each file's header.
+    pub fn open(dir: &str) -> std::io::Result<Self> {
+        let mut tablebase = Self {
+            dir: PathBuf::from(dir),
+            wdl_tables: HashMap::new(),
+            dtz_tables: HashMap::new(),
+            max_pieces: 0,
+        };
+
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            let (Some(stem), Some(extension)) = (
+                path.file_stem().and_then(|s| s.to_str()),
+                path.extension().and_then(|s| s.to_str()),
+            ) else {
+                continue;
+            };
+            let (magic, kind, tables) = match extension {
+                "rtbw" => (WDL_MAGIC, TableKind::Wdl, &mut tablebase.wdl_tables),
+                "rtbz" => (DTZ_MAGIC, TableKind::Dtz, &mut tablebase.dtz_tables),
+                _ => continue,
+            };
+            let (Some(pieces), Some(material)) = (piece_count(stem), Material::from_name(stem))
+            else {
+                continue;
+            };
+            if pieces > MAX_TABLE_PIECES {
+                continue;
+            }
+
+            let mut header = [0u8; 4];
+            std::fs::File::open(&path)?.read_exact(&mut header)?;
+            if header != magic {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("{} is not a Syzygy table", path.display()),
+                ));
+            }
+
+            tables.insert(
+                stem.to_string(),
+                TableFile {
+                    path,
+                    kind,
+                    material,
+                    table: OnceCell::new(),
+                },
+            );
+            // The search only probes WDL tables, so DTZ tables alone don't cover a position
+            if kind == TableKind::Wdl {
+                tablebase.max_pieces = tablebase.max_pieces.max(pieces);
+            }
+        }
+
+        Ok(tablebase)
+    }
+
+    pub fn directory(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Number of WDL tables found.
+    pub fn num_wdl_tables(&self) -> usize {
+        self.wdl_tables.len()
+    }
+
+    /// Number of DTZ tables found.
+    pub fn num_dtz_tables(&self) -> usize {
+        self.dtz_tables.len()
+    }
+
+    /// Whether a WDL table exists for the position's material.
+    pub fn has_wdl_table(&self, board: &Board) -> bool {
+        Self::find_table(&self.wdl_tables, board).is_some()
+    }
+
+    /// Whether a DTZ table exists for the position's material.
+    pub fn has_dtz_table(&self, board: &Board) -> bool {
+        Self::find_table(&self.dtz_tables, board).is_some()
+    }
+
+    /// The table file for the position's material, and whether it is stored with
+    /// colors swapped (black has the pieces the name lists first).
+    fn find_table<'a>(
+        tables: &'a HashMap<String, TableFile>,
+        board: &Board,
+    ) -> Option<(&'a TableFile, bool)> {
+        // Only one color orientation of each table is stored
+        if let Some(file) = tables.get(&material_name(board, Color::White)) {
+            return Some((file, false));
+        }
+        tables
+            .get(&material_name(board, Color::Black))
+            .map(|file| (file, true))
+    }
+
+    /// WDL stored for the position, which is only meaningful when no capture is best.
+    fn probe_wdl_table(&self, board: &Board) -> Option<Wdl> {
+        if board.occ_all.count_ones() == 2 {
+            // Bare kings
+            return Some(Wdl::Draw);
+        }
+        let (file, black_stronger) = Self::find_table(&self.wdl_tables, board)?;
+        file.get()?.wdl(board, black_stronger)
+    }
+
+    /// WDL of the position, searching captures (and, with `check_zeroing`, pawn moves)
+    /// whose results the table doesn't store. Also returns whether such a move is best,
+    /// in which case the DTZ table holds no useful value.
+    fn search(&self, board: &Board, check_zeroing: bool) -> Option<(Wdl, bool)> {
+        let mut moves = Vec::with_capacity(64);
+        MoveGenerator::generate_legal_moves(board, &mut moves);
+
+        let mut best = Wdl::Loss;
+        let mut searched = 0;
+        for &chess_move in &moves {
+            let zeroing = board.is_capture(chess_move)
+                || (check_zeroing && is_pawn_move(board, chess_move.from() as u8));
+            if !zeroing {
+                continue;
+            }
+            searched += 1;
+
+            let mut child = *board;
+            child.make_move(chess_move);
+            let (value, _) = self.search(&child, false)?;
+            let value = value.flip();
+            if value > best {
+                best = value;
+                if value == Wdl::Win {
+                    return Some((value, true));
+                }
+            }
+        }
+
+        // When every move was searched the table isn't needed; this also covers mate
+        let all_searched = searched > 0 && searched == moves.len();
+        let value = if all_searched {
+            best
+        } else {
+            self.probe_wdl_table(board)?
+        };
+        if best >= value {
+            return Some((best, best > Wdl::Draw || all_searched));
+        }
+        Some((value, false))
+    }
+}
+
+/// Whether the piece on `sq` is a pawn.
+fn is_pawn_move(board: &Board, sq: u8) -> bool {
+    board
+        .piece_on(sq)
+        .is_some_and(|(_, piece)| piece == Piece::Pawn)
+}
+
+/// DTZ of a position whose best move resets the fifty-move counter.
+fn dtz_before_zeroing(wdl: Wdl) -> i32 {
+    match wdl {
+        Wdl::Win => 1,
+        Wdl::CursedWin => 101,
+        Wdl::Draw => 0,
+        Wdl::BlessedLoss => -101,
+        Wdl::Loss => -1,
+    }
+}
+
+impl Tablebase for SyzygyTablebase {
+    fn max_pieces(&self) -> u32 {
+        self.max_pieces
+    }
+
+    fn probe_wdl(&self, board: &Board) -> Option<Wdl> {
+        if !self.covers(board) {
+            return None;
+        }
+        self.search(board, false).map(|(wdl, _)| wdl)
+    }
+
+    fn probe_dtz(&self, board: &Board) -> Option<i32> {
+        if !self.covers(board) {
+            return None;
+        }
+        let (wdl, zeroing_best) = self.search(board, true)?;
+        if wdl == Wdl::Draw {
+            return Some(0);
+        }
+        if zeroing_best {
+            return Some(dtz_before_zeroing(wdl));
+        }
+
+        let (file, black_stronger) = Self::find_table(&self.dtz_tables, board)?;
+        let sign = if wdl > Wdl::Draw { 1 } else { -1 };
+        match file.get()?.dtz(board, black_stronger, wdl)? {
+            DtzLookup::Plies(plies) => {
+                let cursed = matches!(wdl, Wdl::CursedWin | Wdl::BlessedLoss);
+                return Some((plies + if cursed { 100 } else { 0 }) * sign);
+            }
+            DtzLookup::OtherSide => {}
+        }
+
+        // The table stores the other side to move: take the best DTZ over the moves
+        let mut moves = Vec::with_capacity(64);
+        MoveGenerator::generate_legal_moves(board, &mut moves);
+        let mut min_dtz = i32::MAX;
+        for chess_move in moves {
+            let zeroing =
+                board.is_capture(chess_move) || is_pawn_move(board, chess_move.from() as u8);
+            let mut child = *board;
+            child.make_move(chess_move);
+
+            let mut dtz = if zeroing {
+                // The move's own DTZ, from the sign of the result it leads to
+                -dtz_before_zeroing(self.search(&child, false)?.0)
+            } else {
+                -self.probe_dtz(&child)?
+            };
+            if dtz == 1 && child.in_check(child.side_to_move) {
+                let mut replies = Vec::with_capacity(64);
+                MoveGenerator::generate_legal_moves(&child, &mut replies);
+                if replies.is_empty() {
+                    min_dtz = 1;
+                }
+            }
+            if !zeroing {
+                dtz += dtz.signum();
+            }
+            if dtz < min_dtz && dtz.signum() == sign {
+                min_dtz = dtz;
+            }
+        }
+        // No legal moves: mated
+        Some(if min_dtz == i32::MAX { -1 } else { min_dtz })
+    }
+}
+
+/// Syzygy table name for the position's material with `first`'s pieces first, e.g. `KRPvKR`.
+pub fn material_name(board: &Board, first: Color) -> String {
+    let side = |color| {
+        NAME_ORDER
+            .iter()
+            .map(|&(piece, letter)| {
+                let count = board.pieces_of(color, piece).count_ones() as usize;
+                letter.to_string().repeat(count)
+            })
+            .collect::<String>()
+    };
+    format!("{}v{}", side(first), side(first.opponent()))
+}
+
+/// Number of pieces in a table name like `KQvK`, or None if it isn't one.
+fn piece_count(name: &str) -> Option<u32> {
+    let (strong, weak) = name.split_once('v')?;
+    let valid =
+        |side: &str| side.starts_with('K') && side[1..].chars().all(|c| "QRBNP".contains(c));
+    (valid(strong) && valid(weak)).then(|| (strong.len() + weak.len()) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tablebase::rank_root_moves;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn fixtures() -> SyzygyTablebase {
+        SyzygyTablebase::open(generate::fixture_dir().to_str().unwrap()).unwrap()
+    }
+
+    /// A random legal position with `material` (e.g. `KQvK`, white's pieces first).
+    fn random_position(rng: &mut StdRng, material: &str, side_to_move: Color) -> Board {
+        loop {
+            let mut squares = ['1'; 64];
+            for (color, side) in material.split('v').enumerate() {
+                for letter in side.chars() {
+                    let sq = loop {
+                        let sq = rng.random_range(0..64);
+                        let back_rank = !(8..56).contains(&sq);
+                        if squares[sq] == '1' && !(letter == 'P' && back_rank) {
+                            break sq;
+                        }
+                    };
+                    squares[sq] = if color == 0 {
+                        letter
+                    } else {
+                        letter.to_ascii_lowercase()
+                    };
+                }
+            }
+            let ranks: Vec<String> = (0..8)
+                .rev()
+                .map(|rank| squares[rank * 8..rank * 8 + 8].iter().collect())
+                .collect();
+            let stm = if side_to_move == Color::White {
+                'w'
+            } else {
+                'b'
+            };
+            let board = Board::from_fen(&format!("{} {} - - 0 1", ranks.join("/"), stm));
+            if !board.in_check(side_to_move.opponent()) {
+                return board;
+            }
+        }
+    }
+
+    /// The position with colors swapped and the board flipped top to bottom.
+    fn color_flipped(board: &Board) -> Board {
+        let fen = board.to_fen();
+        let fields: Vec<&str> = fen.split(' ').collect();
+        let placement: Vec<String> = fields[0]
+            .split('/')
+            .rev()
+            .map(|rank| {
+                rank.chars()
+                    .map(|c| match c.is_ascii_uppercase() {
+                        true => c.to_ascii_lowercase(),
+                        false => c.to_ascii_uppercase(),
+                    })
+                    .collect()
+            })
+            .collect();
+        let stm = if fields[1] == "w" { "b" } else { "w" };
+        Board::from_fen(&format!("{} {} - - 0 1", placement.join("/"), stm))
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_material_name() {
+        let board = Board::from_fen("8/8/4k3/8/3r4/8/2P5/2R1K3 w - - 0 1");
+        assert_eq!(material_name(&board, Color::White), "KRPvKR");
+        assert_eq!(material_name(&board, Color::Black), "KRvKRP");
+        assert_eq!(piece_count("KRPvKR"), Some(5));
+        assert_eq!(piece_count("KQvX"), None);
+        assert_eq!(piece_count("notes"), None);
+    }
+
+    #[test]
+    fn test_open_finds_tables_in_either_orientation() {
+        let dir = temp_dir("rusty_chess_syzygy_open");
+        std::fs::write(dir.join("KQvK.rtbw"), WDL_MAGIC).unwrap();
+        std::fs::write(dir.join("KQvK.rtbz"), DTZ_MAGIC).unwrap();
+        std::fs::write(dir.join("KRPvKR.rtbw"), WDL_MAGIC).unwrap();
+        std::fs::write(dir.join("README.txt"), "not a table").unwrap();
+
+        let tablebase = SyzygyTablebase::open(dir.to_str().unwrap()).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(tablebase.num_wdl_tables(), 2);
+        assert_eq!(tablebase.num_dtz_tables(), 1);
+        assert_eq!(tablebase.max_pieces(), 5);
+
+        // Black has the queen, so the KQvK table is found with colors swapped
+        let board = Board::from_fen("8/8/4k3/8/3q4/8/8/4K3 w - - 0 1");
+        assert!(tablebase.has_wdl_table(&board));
+        assert!(tablebase.has_dtz_table(&board));
+        assert!(!tablebase.has_wdl_table(&Board::startpos()));
+    }
+
+    #[test]
+    fn test_open_rejects_bad_header() {
+        let dir = temp_dir("rusty_chess_syzygy_bad_header");
+        std::fs::write(dir.join("KQvK.rtbw"), DTZ_MAGIC).unwrap();
+
+        let result = SyzygyTablebase::open(dir.to_str().unwrap());
+        std::fs::remove_dir_all(&dir).ok();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_max_pieces_counts_wdl_tables_only() {
+        let dir = temp_dir("rusty_chess_syzygy_max_pieces");
+        std::fs::write(dir.join("KQvK.rtbw"), WDL_MAGIC).unwrap();
+        std::fs::write(dir.join("KRPvKR.rtbz"), DTZ_MAGIC).unwrap();
+
+        let tablebase = SyzygyTablebase::open(dir.to_str().unwrap()).unwrap();
+        assert_eq!(tablebase.max_pieces(), 3);
+        // The file holds nothing past its magic, so probing it fails instead of guessing
+        let board = Board::from_fen("8/8/4k3/8/3Q4/8/8/4K3 b - - 0 1");
+        assert_eq!(tablebase.probe_wdl(&board), None);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_fixtures_cover_four_pieces() {
+        let tablebase = fixtures();
+        assert_eq!(tablebase.num_wdl_tables(), generate::FIXTURE_TABLES.len());
+        assert_eq!(tablebase.num_dtz_tables(), generate::FIXTURE_TABLES.len());
+        assert_eq!(tablebase.max_pieces(), 4);
+    }
+
+    #[test]
+    fn test_bare_kings_and_minor_pieces_draw() {
+        let tablebase = fixtures();
+        for fen in [
+            "8/8/4k3/8/8/8/8/4K3 w - - 0 1",
+            "8/8/4k3/8/8/8/8/2B1K3 b - - 0 1",
+            "8/8/4k3/8/3n4/8/8/4K3 w - - 0 1",
+            "8/8/4k3/8/8/8/8/1NN1K3 w - - 0 1",
+        ] {
+            let board = Board::from_fen(fen);
+            assert_eq!(tablebase.probe_wdl(&board), Some(Wdl::Draw), "{}", fen);
+            assert_eq!(tablebase.probe_dtz(&board), Some(0), "{}", fen);
+        }
+    }
+
+    #[test]
+    fn test_queen_and_rook_win_unless_lost_at_once() {
+        let tablebase = fixtures();
+        let mut rng = StdRng::seed_from_u64(7);
+        for material in ["KQvK", "KRvK"] {
+            for i in 0..300 {
+                let strong_to_move = i % 2 == 0;
+                let stm = if strong_to_move {
+                    Color::White
+                } else {
+                    Color::Black
+                };
+                let board = random_position(&mut rng, material, stm);
+
+                // The weak side only escapes by taking the piece or being stalemated
+                let mut moves = Vec::new();
+                MoveGenerator::generate_legal_moves(&board, &mut moves);
+                let expected = if strong_to_move {
+                    Wdl::Win
+                } else if moves.is_empty() {
+                    if board.in_check(stm) {
+                        Wdl::Loss
+                    } else {
+                        Wdl::Draw
+                    }
+                } else if moves.iter().any(|&m| board.is_capture(m)) {
+                    Wdl::Draw
+                } else {
+                    Wdl::Loss
+                };
+                assert_eq!(
+                    tablebase.probe_wdl(&board),
+                    Some(expected),
+                    "{}",
+                    board.to_fen()
+                );
+                assert_eq!(
+                    tablebase.probe_wdl(&color_flipped(&board)),
+                    Some(expected),
+                    "{}",
+                    board.to_fen()
+                );
+            }
+        }
+    }
+
+    /// Results that follow from the rules alone, so any correct table must agree
+    const KNOWN_RESULTS: [(&str, Wdl, i32); 10] = [
+        // Qh8 and Ra8 mate
+        ("k7/8/1K6/8/8/8/8/7Q w - - 0 1", Wdl::Win, 1),
+        ("k6Q/8/1K6/8/8/8/8/8 b - - 0 1", Wdl::Loss, -1),
+        ("7k/8/6K1/8/8/8/8/R7 w - - 0 1", Wdl::Win, 1),
+        // Mated by two knights, and the same with colors swapped
+        ("k7/8/KNN5/8/8/8/8/8 b - - 0 1", Wdl::Loss, -1),
+        ("8/8/8/8/8/knn5/8/K7 w - - 0 1", Wdl::Loss, -1),
+        // Promotion is a zeroing move
+        ("7k/3KP3/8/8/8/8/8/8 w - - 0 1", Wdl::Win, 1),
+        ("8/8/8/8/8/8/3kp3/7K b - - 0 1", Wdl::Win, 1),
+        // The king takes the pawn, or holds the corner against a rook pawn
+        ("8/8/8/8/8/8/3kP3/7K b - - 0 1", Wdl::Draw, 0),
+        ("k7/8/K7/P7/8/8/8/8 w - - 0 1", Wdl::Draw, 0),
+        // With its king on the sixth in front of the pawn White wins, but the king
+        // must step aside (1... Kd8 2. Kf7) before the pawn can move
+        ("4k3/8/4K3/4P3/8/8/8/8 b - - 0 1", Wdl::Loss, -4),
+    ];
+
+    fn assert_known_results(tablebase: &SyzygyTablebase) {
+        for (fen, wdl, dtz) in KNOWN_RESULTS {
+            let board = Board::from_fen(fen);
+            assert_eq!(tablebase.probe_wdl(&board), Some(wdl), "{}", fen);
+            assert_eq!(tablebase.probe_dtz(&board), Some(dtz), "{}", fen);
+        }
+    }
+
+    #[test]
+    fn test_known_results() {
+        assert_known_results(&fixtures());
+    }
+
+    #[test]
+    #[ignore = "needs the official KQvK, KRvK, KPvK and KNNvK tables in SYZYGY_PATH"]
+    fn test_known_results_in_official_tables() {
+        let path = std::env::var("SYZYGY_PATH").expect("SYZYGY_PATH is not set");
+        let tablebase = SyzygyTablebase::open(&path).unwrap();
+        assert_known_results(&tablebase);
+    }
+
+    #[test]
+    fn test_dtz_agrees_with_the_best_move() {
+        // Each position's DTZ must follow from its children's, whichever side the
+        // DTZ table stores
+        let tablebase = fixtures();
+        let mut rng = StdRng::seed_from_u64(11);
+        for material in ["KQvK", "KRvK", "KPvK", "KNNvK"] {
+            for i in 0..40 {
+                let stm = if i % 2 == 0 {
+                    Color::White
+                } else {
+                    Color::Black
+                };
+                let board = random_position(&mut rng, material, stm);
+                let Some(best) = rank_root_moves(&tablebase, &board)
+                    .unwrap()
+                    .first()
+                    .copied()
+                else {
+                    continue;
+                };
+                let fen = board.to_fen();
+                assert_eq!(tablebase.probe_wdl(&board), Some(best.wdl), "{}", fen);
+                assert_eq!(tablebase.probe_dtz(&board), Some(best.dtz), "{}", fen);
+            }
+        }
+    }
+}
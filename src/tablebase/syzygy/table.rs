@@ -0,0 +1,484 @@
+//! Parsing and decompressing Syzygy table files.
+//!
+//! A file holds one or both sides to move of a material and, with pawns, one part per
+//! file of the leading pawn. Each part is compressed on its own: runs of values are
+//! replaced by symbols that expand through a tree of pairs, and the symbols are
+//! Huffman coded into fixed-size blocks. A sparse index points at the block holding
+//! every `span`-th value, so a probe decodes a single block.
+
+use super::encoding::{Encoding, Material, Slot, locate};
+use super::{DTZ_MAGIC, WDL_MAGIC};
+use crate::board::Board;
+use crate::tablebase::Wdl;
+
+/// File header flag: the table stores both sides to move
+const SPLIT: u8 = 1;
+/// File header flag: the material has pawns
+const HAS_PAWNS: u8 = 2;
+
+/// Part flag: which side to move a DTZ part stores (set for black)
+pub(super) const STM: u8 = 1;
+/// Part flag: DTZ values are ranks into a map of actual distances
+pub(super) const MAPPED: u8 = 2;
+/// Part flag: winning DTZ values are in plies rather than moves
+pub(super) const WIN_PLIES: u8 = 4;
+/// Part flag: losing DTZ values are in plies rather than moves
+pub(super) const LOSS_PLIES: u8 = 8;
+/// Part flag: the DTZ map holds 16-bit values
+pub(super) const WIDE: u8 = 16;
+/// Part flag: every position has the same value
+pub(super) const SINGLE_VALUE: u8 = 128;
+
+/// Marks a leaf in the tree of pairs
+pub(super) const LEAF: u16 = 0xFFF;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum TableKind {
+    Wdl,
+    Dtz,
+}
+
+/// Result of looking a position up in a DTZ table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum DtzLookup {
+    /// Plies to the next capture or pawn move, before the sign of the result is applied
+    Plies(i32),
+    /// The table only stores the other side to move
+    OtherSide,
+}
+
+/// One compressed part of a table: a side to move and a leading pawn file.
+struct Part {
+    flags: u8,
+    /// The only value, when `SINGLE_VALUE` is set
+    single_value: u16,
+    block_size: usize,
+    span: u64,
+    min_sym_len: u32,
+    /// First symbol of each code length, shortest first
+    lowest_sym: Vec<u16>,
+    /// Smallest code of each length, left-aligned in 64 bits
+    base64: Vec<u64>,
+    /// Number of values each symbol expands to, minus one
+    symlen: Vec<u8>,
+    /// The pair each symbol expands to; a leaf holds its value on the left
+    btree: Vec<(u16, u16)>,
+    /// Block and offset within it of the value in the middle of each span
+    sparse_index: Vec<(u32, u16)>,
+    /// Number of values in each block, minus one
+    block_lengths: Vec<u16>,
+    num_blocks: usize,
+    /// File offset of the first block
+    data: usize,
+    /// Offsets into the DTZ map of the win, loss, cursed win and blessed loss lists
+    map_idx: [u16; 4],
+}
+
+/// A parsed table file.
+pub(super) struct Table {
+    material: Material,
+    /// Encodings by stored side to move and leading pawn file
+    encodings: Vec<Vec<Encoding>>,
+    parts: Vec<Vec<Part>>,
+    /// File offset of the DTZ map
+    map: usize,
+    bytes: Vec<u8>,
+}
+
+/// Bounds-checked little-endian reads from a table file.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8], String> {
+        let bytes = self
+            .bytes
+            .get(self.pos..self.pos + len)
+            .ok_or("table file is truncated")?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    /// Moves to the next multiple of `alignment` from the start of the file.
+    fn align(&mut self, alignment: usize) {
+        self.pos = self.pos.next_multiple_of(alignment);
+    }
+}
+
+/// Reads big-endian bytes, treating anything past the end of the file as zeros.
+fn read_be(bytes: &[u8], pos: usize, len: usize) -> u64 {
+    (0..len).fold(0, |value, i| {
+        value << 8 | bytes.get(pos + i).copied().unwrap_or(0) as u64
+    })
+}
+
+impl Table {
+    pub fn load(
+        path: &std::path::Path,
+        kind: TableKind,
+        material: Material,
+    ) -> Result<Self, String> {
+        let bytes =
+            std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::parse(kind, material, bytes).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn parse(kind: TableKind, material: Material, bytes: Vec<u8>) -> Result<Self, String> {
+        let magic = match kind {
+            TableKind::Wdl => WDL_MAGIC,
+            TableKind::Dtz => DTZ_MAGIC,
+        };
+        if !bytes.starts_with(&magic) {
+            return Err("not a Syzygy table".to_string());
+        }
+        let mut reader = Reader {
+            bytes: &bytes,
+            pos: magic.len(),
+        };
+
+        let flags = reader.u8()?;
+        if (flags & SPLIT != 0) == material.is_symmetric()
+            || (flags & HAS_PAWNS != 0) != material.has_pawns()
+        {
+            return Err("header doesn't match the material in the file name".to_string());
+        }
+
+        let sides = if kind == TableKind::Wdl && !material.is_symmetric() {
+            2
+        } else {
+            1
+        };
+        let files = if material.has_pawns() { 4 } else { 1 };
+        let both_pawns = material.has_pawns() && material.pawn_counts()[1] > 0;
+        let mut codes = material.codes();
+        codes.sort_unstable();
+
+        let mut encodings: Vec<Vec<Encoding>> = vec![Vec::new(); sides];
+        for file in 0..files {
+            let first = reader.u8()?;
+            let second = if both_pawns { reader.u8()? } else { 0xFF };
+            let orders = [[first & 0xF, second & 0xF], [first >> 4, second >> 4]];
+
+            let mut pieces = vec![Vec::new(); sides];
+            for _ in 0..material.piece_count() {
+                let byte = reader.u8()?;
+                pieces[0].push(byte & 0xF);
+                if sides == 2 {
+                    pieces[1].push(byte >> 4);
+                }
+            }
+
+            for (side, pieces) in pieces.into_iter().enumerate() {
+                let mut sorted = pieces.clone();
+                sorted.sort_unstable();
+                if sorted != codes {
+                    return Err("pieces don't match the material in the file name".to_string());
+                }
+                let encoding = Encoding::new(&material, pieces, orders[side], file);
+                if encoding.group_idx.contains(&0) {
+                    return Err("invalid group order".to_string());
+                }
+                encodings[side].push(encoding);
+            }
+        }
+        reader.align(2);
+
+        let mut parts: Vec<Vec<Part>> = (0..sides).map(|_| Vec::new()).collect();
+        for file in 0..files {
+            for (side_parts, side_encodings) in parts.iter_mut().zip(&encodings) {
+                side_parts.push(read_part(&mut reader, side_encodings[file].size())?);
+            }
+        }
+
+        let map = reader.pos;
+        if kind == TableKind::Dtz {
+            for part in &mut parts[0] {
+                if part.flags & MAPPED == 0 {
+                    continue;
+                }
+                let wide = part.flags & WIDE != 0;
+                if wide {
+                    reader.align(2);
+                }
+                for i in 0..4 {
+                    if wide {
+                        part.map_idx[i] = ((reader.pos - map) / 2 + 1) as u16;
+                        let len = reader.u16()? as usize;
+                        reader.take(2 * len)?;
+                    } else {
+                        part.map_idx[i] = (reader.pos - map + 1) as u16;
+                        let len = reader.u8()? as usize;
+                        reader.take(len)?;
+                    }
+                }
+            }
+            reader.align(2);
+        }
+
+        for file in 0..files {
+            for side_parts in &mut parts {
+                let part = &mut side_parts[file];
+                for entry in &mut part.sparse_index {
+                    *entry = (reader.u32()?, reader.u16()?);
+                }
+            }
+        }
+        for file in 0..files {
+            for side_parts in &mut parts {
+                for length in &mut side_parts[file].block_lengths {
+                    *length = reader.u16()?;
+                }
+            }
+        }
+        for file in 0..files {
+            for side_parts in &mut parts {
+                let part = &mut side_parts[file];
+                reader.align(64);
+                part.data = reader.pos;
+                reader.pos += part.num_blocks * part.block_size;
+            }
+        }
+
+        Ok(Self {
+            material,
+            encodings,
+            parts,
+            map,
+            bytes,
+        })
+    }
+
+    /// Where a position is stored.
+    pub fn locate(&self, board: &Board, black_stronger: bool) -> Slot {
+        locate(&self.material, &self.encodings, board, black_stronger)
+    }
+
+    /// Win/draw/loss of a position without captures that matter.
+    pub fn wdl(&self, board: &Board, black_stronger: bool) -> Option<Wdl> {
+        let slot = self.locate(board, black_stronger);
+        let part = &self.parts[slot.stm % self.parts.len()][slot.file];
+        Some(match self.decompress(part, slot.index)? {
+            0 => Wdl::Loss,
+            1 => Wdl::BlessedLoss,
+            2 => Wdl::Draw,
+            3 => Wdl::CursedWin,
+            4 => Wdl::Win,
+            _ => return None,
+        })
+    }
+
+    /// Distance to zeroing of a position whose result is `wdl`, which must not be a draw.
+    pub fn dtz(&self, board: &Board, black_stronger: bool, wdl: Wdl) -> Option<DtzLookup> {
+        let slot = self.locate(board, black_stronger);
+        let part = &self.parts[0][slot.file];
+        let symmetric = self.material.is_symmetric() && !self.material.has_pawns();
+        if (part.flags & STM) as usize != slot.stm && !symmetric {
+            return Some(DtzLookup::OtherSide);
+        }
+
+        let mut value = self.decompress(part, slot.index)? as usize;
+        if part.flags & MAPPED != 0 {
+            let list = match wdl {
+                Wdl::Win | Wdl::Draw => 0,
+                Wdl::Loss => 1,
+                Wdl::CursedWin => 2,
+                Wdl::BlessedLoss => 3,
+            };
+            let idx = part.map_idx[list] as usize + value;
+            value = if part.flags & WIDE != 0 {
+                let pos = self.map + 2 * idx;
+                u16::from_le_bytes(self.bytes.get(pos..pos + 2)?.try_into().unwrap()) as usize
+            } else {
+                *self.bytes.get(self.map + idx)? as usize
+            };
+        }
+
+        let plies = match wdl {
+            Wdl::Win => part.flags & WIN_PLIES != 0,
+            Wdl::Loss => part.flags & LOSS_PLIES != 0,
+            _ => false,
+        };
+        if !plies {
+            value *= 2;
+        }
+        Some(DtzLookup::Plies(value as i32 + 1))
+    }
+
+    /// The value stored at `index`, or None if the data is corrupt.
+    fn decompress(&self, part: &Part, index: u64) -> Option<u16> {
+        if part.flags & SINGLE_VALUE != 0 {
+            return Some(part.single_value);
+        }
+
+        // Start from the sparse index entry nearest the value and walk to its block
+        let &(block, offset) = part.sparse_index.get((index / part.span) as usize)?;
+        let mut block = block as usize;
+        let mut offset = offset as i64 + (index % part.span) as i64 - (part.span / 2) as i64;
+        while offset < 0 {
+            block = block.checked_sub(1)?;
+            offset += *part.block_lengths.get(block)? as i64 + 1;
+        }
+        while offset > *part.block_lengths.get(block)? as i64 {
+            offset -= part.block_lengths[block] as i64 + 1;
+            block += 1;
+        }
+
+        // Decode symbols until the one that covers the value
+        let mut pos = part.data + block * part.block_size;
+        let mut buffer = read_be(&self.bytes, pos, 8);
+        pos += 8;
+        let mut buffer_bits = 64;
+        let mut sym;
+        loop {
+            let mut len = 0;
+            while buffer < part.base64[len] {
+                len += 1;
+            }
+            let bits = len as u32 + part.min_sym_len;
+            sym = ((buffer - part.base64[len]) >> (64 - bits)) as usize
+                + part.lowest_sym[len] as usize;
+            let sym_values = *part.symlen.get(sym)? as i64 + 1;
+            if offset < sym_values {
+                break;
+            }
+            offset -= sym_values;
+            buffer <<= bits;
+            buffer_bits -= bits;
+            if buffer_bits <= 32 {
+                buffer_bits += 32;
+                buffer |= read_be(&self.bytes, pos, 4) << (64 - buffer_bits);
+                pos += 4;
+            }
+        }
+
+        // Descend the pairs to the leaf holding the value
+        while part.symlen[sym] != 0 {
+            let (left, right) = part.btree[sym];
+            let left_values = part.symlen[left as usize] as i64 + 1;
+            if offset < left_values {
+                sym = left as usize;
+            } else {
+                offset -= left_values;
+                sym = right as usize;
+            }
+        }
+        Some(part.btree[sym].0)
+    }
+}
+
+/// Reads the compression settings of a part with `size` values.
+fn read_part(reader: &mut Reader, size: u64) -> Result<Part, String> {
+    let flags = reader.u8()?;
+    let mut part = Part {
+        flags,
+        single_value: 0,
+        block_size: 0,
+        span: 1,
+        min_sym_len: 0,
+        lowest_sym: Vec::new(),
+        base64: Vec::new(),
+        symlen: Vec::new(),
+        btree: Vec::new(),
+        sparse_index: Vec::new(),
+        block_lengths: Vec::new(),
+        num_blocks: 0,
+        data: 0,
+        map_idx: [0; 4],
+    };
+    if flags & SINGLE_VALUE != 0 {
+        part.single_value = reader.u8()? as u16;
+        return Ok(part);
+    }
+
+    let block_shift = reader.u8()?;
+    let span_shift = reader.u8()?;
+    let padding = reader.u8()? as usize;
+    let num_blocks = reader.u32()? as usize;
+    let max_sym_len = reader.u8()? as u32;
+    let min_sym_len = reader.u8()? as u32;
+    if block_shift > 24 || span_shift > 40 || span_shift == 0 {
+        return Err("invalid block size".to_string());
+    }
+    if min_sym_len == 0 || min_sym_len > max_sym_len || max_sym_len > 32 {
+        return Err("invalid symbol lengths".to_string());
+    }
+    part.block_size = 1 << block_shift;
+    part.span = 1 << span_shift;
+    part.min_sym_len = min_sym_len;
+
+    let lengths = (max_sym_len - min_sym_len + 1) as usize;
+    for _ in 0..lengths {
+        part.lowest_sym.push(reader.u16()?);
+    }
+
+    let sym_count = reader.u16()? as usize;
+    for _ in 0..sym_count {
+        let lr = reader.take(3)?;
+        let left = (lr[1] as u16 & 0xF) << 8 | lr[0] as u16;
+        let right = (lr[2] as u16) << 4 | lr[1] as u16 >> 4;
+        part.btree.push((left, right));
+    }
+    reader.take(sym_count & 1)?;
+
+    // Canonical Huffman: longer codes are numerically smaller
+    part.base64 = vec![0; lengths];
+    for i in (0..lengths - 1).rev() {
+        let codes = part.lowest_sym[i]
+            .checked_sub(part.lowest_sym[i + 1])
+            .ok_or("invalid symbol table")?;
+        part.base64[i] = (part.base64[i + 1] + codes as u64) / 2;
+    }
+    for (i, base) in part.base64.iter_mut().enumerate() {
+        *base <<= 64 - i as u32 - min_sym_len;
+    }
+
+    part.symlen = vec![0; sym_count];
+    let mut visited = vec![false; sym_count];
+    for sym in 0..sym_count {
+        if !visited[sym] {
+            set_symlen(&mut part, sym, &mut visited)?;
+        }
+    }
+
+    part.sparse_index = vec![(0, 0); size.div_ceil(part.span) as usize];
+    // Padding entries after the last block keep the sparse index in range
+    part.block_lengths = vec![0; num_blocks + padding];
+    part.num_blocks = num_blocks;
+    Ok(part)
+}
+
+/// Counts the values each symbol expands to, children first.
+fn set_symlen(part: &mut Part, sym: usize, visited: &mut [bool]) -> Result<(), String> {
+    visited[sym] = true;
+    let (left, right) = part.btree[sym];
+    if right == LEAF {
+        return Ok(());
+    }
+    let (left, right) = (left as usize, right as usize);
+    if left >= visited.len() || right >= visited.len() {
+        return Err("invalid symbol tree".to_string());
+    }
+    for child in [left, right] {
+        if !visited[child] {
+            set_symlen(part, child, visited)?;
+        }
+    }
+    part.symlen[sym] = part.symlen[left]
+        .wrapping_add(part.symlen[right])
+        .wrapping_add(1);
+    Ok(())
+}
@@ -1,46 +1,66 @@
 use crate::{
     board::Color,
-    terminal::{BlackOpeningBook, ChessEngineSettings, DisplaySettings, WhiteOpeningBook},
+    terminal::{
+        BlackOpeningBook, ChessEngineSettings, DisplaySettings, GameMode, PlayerSettings,
+        WhiteOpeningBook,
+    },
 };
 
 pub fn print_instructions(settings: &ChessEngineSettings, display: &DisplaySettings) {
-    let color_str = match settings.player_color {
-        Color::White => "White",
-        Color::Black => "Black",
-    };
-
-    // Determine the AI's opening book based on player color
-    let opening_book_str = match settings.player_color {
-        Color::White => {
-            // AI plays Black
-            match settings.black_opening_book {
-                BlackOpeningBook::None => "None",
-            }
-        }
-        Color::Black => {
-            // AI plays White
-            match settings.white_opening_book {
-                WhiteOpeningBook::None => "None",
-                WhiteOpeningBook::LondonSystem => "London System",
-                WhiteOpeningBook::ColleSystem => "Colle System",
-            }
-        }
-    };
-
     println!("┌─────────────────────────────────────────┐");
     println!("│            Game Settings                │");
     println!("├─────────────────────────────────────────┤");
-    println!("│  Player color:     {:>19}  │", color_str);
-    println!("│  AI search depth:  {:>15} ply  │", settings.search_depth);
-    println!("│  AI opening book:  {:>19}  │", opening_book_str);
+    for color in [Color::White, Color::Black] {
+        let label = format!("{:?}:", color);
+        println!(
+            "│  {:<7}{:>30}  │",
+            label,
+            player_description(settings.player(color))
+        );
+        if let Some(book) = opening_book_name(settings, color) {
+            println!("│  {:<7}{:>30}  │", "Book:", book);
+        }
+    }
+    let clock = match settings.time_control {
+        Some(time_control) => time_control.to_string(),
+        None => "Untimed".to_string(),
+    };
+    println!("│  {:<7}{:>30}  │", "Clock:", clock);
+    if settings.mode == GameMode::EngineVsEngine {
+        println!(
+            "│  Delay:  {:>28.1}s  │",
+            settings.move_delay_ms as f64 / 1000.0
+        );
+        println!("└─────────────────────────────────────────┘");
+        println!();
+        return;
+    }
     println!("├─────────────────────────────────────────┤");
     println!("│            Commands                     │");
     println!("├─────────────────────────────────────────┤");
-    println!("│  e2,e4    - Make a move (from,to)       │");
+    println!("│  Nf3, e4  - Make a move (SAN)           │");
+    println!("│  g1f3     - ...or UCI, e7e8n promotes   │");
+    println!("│  g1,f3    - ...or from,to coordinates   │");
     println!("│  moves    - Show all legal moves        │");
-    println!("│  undo     - Undo last move pair         │");
+    println!("│  hint     - Suggest a move              │");
+    println!(
+        "│  coach    - Grade your moves      [{}] │",
+        if display.coach_mode { "ON " } else { "OFF" }
+    );
+    println!("│  undo     - Take back your last move    │");
+    if settings.time_control.is_none() {
+        println!("│  back     - Step back one move          │");
+        println!("│  forward  - Replay the next move        │");
+        println!("│  goto n   - Go to the position at ply n │");
+    }
+    println!("│  flip     - Turn the board around       │");
     println!("│  fen      - Show current FEN            │");
     println!("│  eval     - Show position evaluation    │");
+    if settings.time_control.is_some() {
+        println!("│  clock    - Show both clocks            │");
+    }
+    println!("│  save f   - Save the game to file f     │");
+    println!("│  load f   - Continue a game saved in f  │");
     println!("│  resign   - Resign the game             │");
     println!("│  quit     - Exit the game               │");
     println!("├─────────────────────────────────────────┤");
@@ -75,3 +95,32 @@ pub fn print_instructions(settings: &ChessEngineSettings, display: &DisplaySetti
     println!("└─────────────────────────────────────────┘");
     println!();
 }
+
+fn player_description(player: &PlayerSettings) -> String {
+    match player {
+        PlayerSettings::Human => "Human".to_string(),
+        PlayerSettings::Engine { search_depth } => format!("Engine, depth {}", search_depth),
+        PlayerSettings::Uci { move_time_ms, .. } => {
+            format!("UCI engine, {}s/move", move_time_ms / 1000)
+        }
+    }
+}
+
+/// The built-in engine's book for `color`, or None when no built-in engine plays it.
+fn opening_book_name(settings: &ChessEngineSettings, color: Color) -> Option<&'static str> {
+    if !matches!(settings.player(color), PlayerSettings::Engine { .. }) {
+        return None;
+    }
+    Some(match color {
+        Color::White => match settings.white_opening_book {
+            WhiteOpeningBook::None => "None",
+            WhiteOpeningBook::LondonSystem => "London System",
+            WhiteOpeningBook::ColleSystem => "Colle System",
+        },
+        Color::Black => match settings.black_opening_book {
+            BlackOpeningBook::None => "None",
+            BlackOpeningBook::Sicilian => "Sicilian Defense",
+            BlackOpeningBook::KingsIndian => "King's Indian",
+        },
+    })
+}
@@ -5,6 +5,7 @@ mod settings;
 pub use instructions::print_instructions;
 pub use introduction::print_introduction;
 pub use settings::{
-    BlackOpeningBook, ChessEngineSettings, DisplaySettings, WhiteOpeningBook,
-    get_chess_engine_settings,
+    BlackOpeningBook, ChessEngineSettings, DisplaySettings, GameMode, PlayerSettings,
+    WhiteOpeningBook, book_learning_path_from_args, eval_config_from_args, eval_params_from_args,
+    get_chess_engine_settings, resume_path_from_args,
 };
@@ -1,11 +1,16 @@
-use crate::board::{Board, Color};
+use crate::board::utils::no_color_requested;
+use crate::board::{Board, BoardFromFenError, BoardStyle, Color};
+use crate::engine::TimeControl;
+use crate::eval::{EvalConfig, EvalParams};
+use serde::{Deserialize, Serialize};
 use std::{
     env,
     io::{self, Write},
+    time::Duration,
 };
 
 /// Opening book options for when the AI plays as White.
-#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub enum WhiteOpeningBook {
     #[default]
     None,
@@ -14,45 +19,165 @@ pub enum WhiteOpeningBook {
 }
 
 /// Opening book options for when the AI plays as Black.
-/// Currently no opening books are implemented for Black,
-/// but this enum makes it easy to add them in the future.
-#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub enum BlackOpeningBook {
     #[default]
     None,
-    // Future opening books can be added here, e.g.:
-    // Sicilian,
-    // KingsIndian,
+    Sicilian,
+    KingsIndian,
 }
 
+/// How the two sides of a terminal game are played.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum GameMode {
+    #[default]
+    HumanVsEngine,
+    HumanVsHuman,
+    /// Two engines play while the user watches
+    EngineVsEngine,
+}
+
+/// Who plays one side of a terminal game.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum PlayerSettings {
+    Human,
+    /// The built-in engine searching to `search_depth`
+    Engine {
+        search_depth: u8,
+    },
+    /// An external UCI engine started with `command`, given `move_time_ms` per move
+    Uci {
+        command: String,
+        move_time_ms: u64,
+    },
+}
+
+#[derive(Clone)]
 pub struct ChessEngineSettings {
-    pub player_color: Color,
-    pub search_depth: u8,
+    pub mode: GameMode,
+    pub white: PlayerSettings,
+    pub black: PlayerSettings,
     pub starting_position: Board,
+    /// Book for the built-in engine when it plays White
     pub white_opening_book: WhiteOpeningBook,
+    /// Book for the built-in engine when it plays Black
     pub black_opening_book: BlackOpeningBook,
+    /// Pause after each move when no human is playing, so the game can be followed
+    pub move_delay_ms: u64,
+    /// Clocks for both sides, or None for an untimed game
+    pub time_control: Option<TimeControl>,
+}
+
+impl ChessEngineSettings {
+    pub fn player(&self, color: Color) -> &PlayerSettings {
+        match color {
+            Color::White => &self.white,
+            Color::Black => &self.black,
+        }
+    }
+
+    /// The human's color when a single human plays against an engine.
+    pub fn human_color(&self) -> Option<Color> {
+        match (&self.white, &self.black) {
+            (PlayerSettings::Human, PlayerSettings::Human) => None,
+            (PlayerSettings::Human, _) => Some(Color::White),
+            (_, PlayerSettings::Human) => Some(Color::Black),
+            _ => None,
+        }
+    }
 }
 
-#[derive(Clone, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct DisplaySettings {
     pub show_search_stats: bool,
     pub show_tt_info: bool,
     pub show_eval: bool,
     pub show_move_analysis: bool,
+    /// Grade each of the human's moves against the engine's best move
+    pub coach_mode: bool,
+    /// Draw pieces as letters instead of chess symbols
+    pub ascii_pieces: bool,
+    /// Plain text board without ANSI colors
+    pub no_color: bool,
+    /// Turn the board around from its usual side: the human's, or White's
+    pub flip_board: bool,
+    pub hide_coordinates: bool,
+    /// Show each side's extra material above and below the board
+    pub show_captured: bool,
 }
 
 pub fn get_chess_engine_settings() -> ChessEngineSettings {
-    let player_color: Color = get_player_color();
-    let search_depth: u8 = get_search_depth();
+    let mode = get_game_mode();
+    let (white, black, move_delay_ms) = match mode {
+        GameMode::HumanVsEngine => match get_player_color() {
+            Color::White => (PlayerSettings::Human, get_engine_player(Color::Black), 0),
+            Color::Black => (get_engine_player(Color::White), PlayerSettings::Human, 0),
+        },
+        GameMode::HumanVsHuman => (PlayerSettings::Human, PlayerSettings::Human, 0),
+        GameMode::EngineVsEngine => {
+            let white = get_engine_player(Color::White);
+            let black = get_engine_player(Color::Black);
+            (white, black, get_move_delay())
+        }
+    };
+    let time_control = get_time_control();
     let starting_position: Board = get_starting_position();
-    let (white_opening_book, black_opening_book) = get_opening_book_settings(player_color);
+
+    let white_opening_book = match white {
+        PlayerSettings::Engine { .. } => get_white_opening_book(),
+        _ => WhiteOpeningBook::None,
+    };
+    let black_opening_book = match black {
+        PlayerSettings::Engine { .. } => get_black_opening_book(),
+        _ => BlackOpeningBook::None,
+    };
 
     ChessEngineSettings {
-        player_color,
-        search_depth,
+        mode,
+        white,
+        black,
         starting_position,
         white_opening_book,
         black_opening_book,
+        move_delay_ms,
+        time_control,
+    }
+}
+
+fn get_game_mode() -> GameMode {
+    println!("┌─────────────────────────────────────────┐");
+    println!("│             Game Mode                   │");
+    println!("├─────────────────────────────────────────┤");
+    println!("│  [1] Human vs Engine                    │");
+    println!("│  [2] Human vs Human  - Same terminal    │");
+    println!("│  [3] Engine vs Engine - Watch a game    │");
+    println!("└─────────────────────────────────────────┘");
+
+    loop {
+        print!("  > ");
+        io::stdout().flush().unwrap();
+
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .expect("Failed to read input");
+
+        match input.trim() {
+            "1" => {
+                println!("  ✓ Human vs Engine\n");
+                return GameMode::HumanVsEngine;
+            }
+            "2" => {
+                println!("  ✓ Human vs Human\n");
+                return GameMode::HumanVsHuman;
+            }
+            "3" => {
+                println!("  ✓ Engine vs Engine\n");
+                return GameMode::EngineVsEngine;
+            }
+            _ => println!("  ✗ Invalid choice. Enter 1, 2 or 3."),
+        }
     }
 }
 
@@ -87,14 +212,24 @@ fn get_player_color() -> Color {
     }
 }
 
-fn get_search_depth() -> u8 {
+/// Asks for the strength of the engine playing `color`, or for an external UCI engine.
+fn get_engine_player(color: Color) -> PlayerSettings {
+    match get_search_depth(color) {
+        Some(search_depth) => PlayerSettings::Engine { search_depth },
+        None => get_uci_engine(),
+    }
+}
+
+/// Reads a search depth from 1 to 10, or None when the user picks a UCI engine instead.
+fn get_search_depth(color: Color) -> Option<u8> {
     println!("┌─────────────────────────────────────────┐");
-    println!("│         AI Difficulty (1-10)            │");
+    println!("│{:^41}│", format!("{:?} Engine Difficulty (1-10)", color));
     println!("├─────────────────────────────────────────┤");
     println!("│  1-3   Beginner    - Fast, weak play    │");
     println!("│  4-5   Intermediate - Balanced          │");
     println!("│  6-7   Advanced    - Strong, slower     │");
     println!("│  8-10  Expert      - Very strong        │");
+    println!("│  u     External UCI engine              │");
     println!("├─────────────────────────────────────────┤");
     println!("│  Recommended: 5                         │");
     println!("└─────────────────────────────────────────┘");
@@ -108,7 +243,11 @@ fn get_search_depth() -> u8 {
             .read_line(&mut input)
             .expect("Failed to read input");
 
-        match input.trim().parse::<u8>() {
+        let input = input.trim();
+        if input.eq_ignore_ascii_case("u") {
+            return None;
+        }
+        match input.parse::<u8>() {
             Ok(depth) if (1..=10).contains(&depth) => {
                 let difficulty = match depth {
                     1..=3 => "Beginner",
@@ -117,9 +256,156 @@ fn get_search_depth() -> u8 {
                     _ => "Expert",
                 };
                 println!("  ✓ Difficulty: {} (depth {})\n", difficulty, depth);
-                return depth;
+                return Some(depth);
+            }
+            _ => println!("  ✗ Invalid choice. Enter a number 1-10 or 'u'."),
+        }
+    }
+}
+
+fn get_uci_engine() -> PlayerSettings {
+    println!("┌─────────────────────────────────────────┐");
+    println!("│         External UCI Engine             │");
+    println!("├─────────────────────────────────────────┤");
+    println!("│  Enter the command that starts the      │");
+    println!("│  engine, e.g. /usr/bin/stockfish        │");
+    println!("└─────────────────────────────────────────┘");
+
+    let command = loop {
+        print!("  command> ");
+        io::stdout().flush().unwrap();
+
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .expect("Failed to read input");
+
+        match input.trim() {
+            "" => println!("  ✗ Enter a command."),
+            command => break command.to_string(),
+        }
+    };
+
+    println!("  Seconds per move (1-60):");
+    loop {
+        print!("  > ");
+        io::stdout().flush().unwrap();
+
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .expect("Failed to read input");
+
+        match input.trim().parse::<u64>() {
+            Ok(seconds) if (1..=60).contains(&seconds) => {
+                println!("  ✓ UCI engine: {} ({}s per move)\n", command, seconds);
+                return PlayerSettings::Uci {
+                    command,
+                    move_time_ms: seconds * 1000,
+                };
+            }
+            _ => println!("  ✗ Invalid choice. Enter a number 1-60."),
+        }
+    }
+}
+
+fn get_move_delay() -> u64 {
+    println!("┌─────────────────────────────────────────┐");
+    println!("│       Delay Between Moves (seconds)     │");
+    println!("├─────────────────────────────────────────┤");
+    println!("│  0     - As fast as the engines play    │");
+    println!("│  1-10  - Pause to follow the game       │");
+    println!("└─────────────────────────────────────────┘");
+
+    loop {
+        print!("  > ");
+        io::stdout().flush().unwrap();
+
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .expect("Failed to read input");
+
+        match input.trim().parse::<u64>() {
+            Ok(seconds) if seconds <= 10 => {
+                println!("  ✓ Delay: {}s\n", seconds);
+                return seconds * 1000;
+            }
+            _ => println!("  ✗ Invalid choice. Enter a number 0-10."),
+        }
+    }
+}
+
+fn get_time_control() -> Option<TimeControl> {
+    println!("┌─────────────────────────────────────────┐");
+    println!("│            Time Control                 │");
+    println!("├─────────────────────────────────────────┤");
+    println!("│  [n] None      - Untimed game           │");
+    println!("│  [c] Classical - 90 min + 30s per move  │");
+    println!("│  [r] Rapid     - 15 min + 10s per move  │");
+    println!("│  [b] Blitz     - 5 min + 3s per move    │");
+    println!("│  [f] Fischer   - Custom increment       │");
+    println!("│  [d] Bronstein - Custom delay           │");
+    println!("└─────────────────────────────────────────┘");
+
+    loop {
+        print!("  > ");
+        io::stdout().flush().unwrap();
+
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .expect("Failed to read input");
+
+        let time_control = match input.trim().to_lowercase().as_str() {
+            "n" | "none" => {
+                println!("  ✓ Untimed game\n");
+                return None;
+            }
+            "c" | "classical" => TimeControl::classical(),
+            "r" | "rapid" => TimeControl::rapid(),
+            "b" | "blitz" => TimeControl::blitz(),
+            "f" | "fischer" => {
+                let minutes = get_number("Minutes per side (1-180):", 1, 180);
+                let increment = get_number("Increment in seconds (0-60):", 0, 60);
+                TimeControl::fischer(
+                    Duration::from_secs(minutes * 60),
+                    Duration::from_secs(increment),
+                )
+            }
+            "d" | "bronstein" => {
+                let minutes = get_number("Minutes per side (1-180):", 1, 180);
+                let delay = get_number("Delay in seconds (0-60):", 0, 60);
+                TimeControl::bronstein(
+                    Duration::from_secs(minutes * 60),
+                    Duration::from_secs(delay),
+                )
             }
-            _ => println!("  ✗ Invalid choice. Enter a number 1-10."),
+            _ => {
+                println!("  ✗ Invalid choice. Enter 'n', 'c', 'r', 'b', 'f' or 'd'.");
+                continue;
+            }
+        };
+        println!("  ✓ Time control: {}\n", time_control);
+        return Some(time_control);
+    }
+}
+
+/// Asks for a whole number between `min` and `max`.
+fn get_number(label: &str, min: u64, max: u64) -> u64 {
+    println!("  {}", label);
+    loop {
+        print!("  > ");
+        io::stdout().flush().unwrap();
+
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .expect("Failed to read input");
+
+        match input.trim().parse::<u64>() {
+            Ok(number) if (min..=max).contains(&number) => return number,
+            _ => println!("  ✗ Invalid choice. Enter a number {}-{}.", min, max),
         }
     }
 }
@@ -183,42 +469,25 @@ fn get_fen_position() -> Board {
             return Board::startpos();
         }
 
-        // Try to parse the FEN
-        let board = Board::from_fen(input);
-
-        // Validate the board has kings for both sides
-        if board.king_sq[0] >= 64 || board.king_sq[1] >= 64 {
-            println!("  ✗ Invalid FEN: Both sides must have a king.");
-            continue;
-        }
-
-        println!("  ✓ FEN loaded successfully!\n");
-        return board;
-    }
-}
-
-fn get_opening_book_settings(player_color: Color) -> (WhiteOpeningBook, BlackOpeningBook) {
-    // Determine which color the AI is playing
-    let ai_color = match player_color {
-        Color::White => Color::Black,
-        Color::Black => Color::White,
-    };
-
-    match ai_color {
-        Color::White => {
-            let white_book = get_white_opening_book();
-            (white_book, BlackOpeningBook::None)
-        }
-        Color::Black => {
-            let black_book = get_black_opening_book();
-            (WhiteOpeningBook::None, black_book)
+        match Board::try_from_fen(input) {
+            Ok(board) => {
+                println!("  ✓ FEN loaded successfully!\n");
+                return board;
+            }
+            Err(BoardFromFenError::Parse(e)) => println!("  ✗ Invalid FEN: {}", e),
+            Err(BoardFromFenError::Invalid(errors)) => {
+                println!("  ✗ Illegal position:");
+                for error in errors {
+                    println!("    - {}", error);
+                }
+            }
         }
     }
 }
 
 fn get_white_opening_book() -> WhiteOpeningBook {
     println!("┌─────────────────────────────────────────┐");
-    println!("│      AI Opening Book (White)            │");
+    println!("│     Engine Opening Book (White)         │");
     println!("├─────────────────────────────────────────┤");
     println!("│  [n] None         - No opening book     │");
     println!("│  [l] London System - d4, Bf4, e3 setup  │");
@@ -254,11 +523,11 @@ fn get_white_opening_book() -> WhiteOpeningBook {
 
 fn get_black_opening_book() -> BlackOpeningBook {
     println!("┌─────────────────────────────────────────┐");
-    println!("│      AI Opening Book (Black)            │");
+    println!("│     Engine Opening Book (Black)         │");
     println!("├─────────────────────────────────────────┤");
-    println!("│  [n] None - No opening book             │");
-    println!("│                                         │");
-    println!("│  (More opening books coming soon!)      │");
+    println!("│  [n] None          - No opening book    │");
+    println!("│  [s] Sicilian      - 1. e4 c5, Najdorf  │");
+    println!("│  [k] King's Indian - Nf6, g6, Bg7, d6   │");
     println!("└─────────────────────────────────────────┘");
 
     loop {
@@ -275,15 +544,45 @@ fn get_black_opening_book() -> BlackOpeningBook {
                 println!("  ✓ No opening book selected\n");
                 return BlackOpeningBook::None;
             }
-            _ => println!("  ✗ Invalid choice. Enter 'n'."),
+            "s" | "sicilian" => {
+                println!("  ✓ Sicilian Defense opening book selected\n");
+                return BlackOpeningBook::Sicilian;
+            }
+            "k" | "kid" | "kings indian" => {
+                println!("  ✓ King's Indian Defense opening book selected\n");
+                return BlackOpeningBook::KingsIndian;
+            }
+            _ => println!("  ✗ Invalid choice. Enter 'n', 's', or 'k'."),
         }
     }
 }
 
 impl DisplaySettings {
+    /// Nothing enabled, with the board drawn without colors if `NO_COLOR` is set.
+    pub fn from_env() -> Self {
+        Self {
+            no_color: no_color_requested(),
+            ..Self::default()
+        }
+    }
+
+    /// Applies a board drawing flag (`--ascii`, `--no-color`, `--flip`,
+    /// `--no-coordinates` or `--captured`), returning false if `arg` isn't one.
+    pub fn parse_board_flag(&mut self, arg: &str) -> bool {
+        match arg {
+            "--ascii" => self.ascii_pieces = true,
+            "--no-color" => self.no_color = true,
+            "--flip" => self.flip_board = true,
+            "--no-coordinates" => self.hide_coordinates = true,
+            "--captured" => self.show_captured = true,
+            _ => return false,
+        }
+        true
+    }
+
     pub fn from_args() -> Self {
         let args: Vec<String> = env::args().collect();
-        let mut settings = Self::default();
+        let mut settings = Self::from_env();
 
         for arg in &args[1..] {
             match arg.as_str() {
@@ -291,6 +590,8 @@ impl DisplaySettings {
                 "--tt" | "-t" => settings.show_tt_info = true,
                 "--eval" | "-e" => settings.show_eval = true,
                 "--analysis" | "-a" => settings.show_move_analysis = true,
+                "--coach" | "-c" => settings.coach_mode = true,
+                flag if settings.parse_board_flag(flag) => {}
                 "--verbose" | "-v" => {
                     settings.show_search_stats = true;
                     settings.show_tt_info = true;
@@ -311,6 +612,61 @@ impl DisplaySettings {
     pub fn any_enabled(&self) -> bool {
         self.show_search_stats || self.show_tt_info || self.show_eval || self.show_move_analysis
     }
+
+    /// How to draw the board. It is shown from `human`'s side when only one side is
+    /// human, and from White's otherwise, unless flipped.
+    pub fn board_style(&self, human: Option<Color>) -> BoardStyle {
+        BoardStyle {
+            ascii: self.ascii_pieces,
+            color: !self.no_color,
+            flipped: (human == Some(Color::Black)) != self.flip_board,
+            coordinates: !self.hide_coordinates,
+            captured_material: self.show_captured,
+        }
+    }
+}
+
+/// Loads the eval config passed with `--eval-config <path>`, if any.
+pub fn eval_config_from_args() -> Result<Option<EvalConfig>, String> {
+    match path_from_args("--eval-config")? {
+        Some(path) => EvalConfig::load(&path)
+            .map(Some)
+            .map_err(|e| format!("Failed to load eval config '{}': {}", path, e)),
+        None => Ok(None),
+    }
+}
+
+/// Loads the tuned parameter file passed with `--eval-params <path>`, if any.
+pub fn eval_params_from_args() -> Result<Option<EvalParams>, String> {
+    match path_from_args("--eval-params")? {
+        Some(path) => EvalParams::load(&path)
+            .map(Some)
+            .map_err(|e| format!("Failed to load eval params '{}': {}", path, e)),
+        None => Ok(None),
+    }
+}
+
+/// Returns the book learning file passed with `--book-learning <path>`, if any.
+pub fn book_learning_path_from_args() -> Result<Option<String>, String> {
+    path_from_args("--book-learning")
+}
+
+/// Returns the saved game passed with `--resume <path>`, if any.
+pub fn resume_path_from_args() -> Result<Option<String>, String> {
+    path_from_args("--resume")
+}
+
+fn path_from_args(flag: &str) -> Result<Option<String>, String> {
+    let args: Vec<String> = env::args().collect();
+
+    match args.iter().position(|arg| arg == flag) {
+        Some(i) => args
+            .get(i + 1)
+            .cloned()
+            .map(Some)
+            .ok_or_else(|| format!("{} requires a path", flag)),
+        None => Ok(None),
+    }
 }
 
 fn print_usage() {
@@ -327,6 +683,21 @@ OPTIONS:
     -e, --eval       Show position evaluation before/after moves
     -a, --analysis   Show move analysis (position change, material delta)
     -v, --verbose    Enable all display options
+    -c, --coach      Grade each of your moves (best, good, inaccuracy, mistake, blunder)
+    --ascii          Draw pieces as letters (K, q) instead of chess symbols
+    --no-color       Draw the board without colors (also set by the NO_COLOR variable)
+    --flip           Turn the board around; it normally faces the human player
+    --no-coordinates Hide the file letters and rank numbers
+    --captured       Show each side's extra material beside the board
+    --eval-config <path>
+                     Load evaluator weights from a JSON eval config
+    --eval-params <path>
+                     Load tuned evaluation constants (written by the tune binary)
+    --book-learning <path>
+                     Load the engine's opening book from this file if it exists, and save
+                     it with this game's result after the game (book learning).
+                     Needs exactly one built-in engine in the game
+    --resume <path>  Continue a game saved with the in-game `save` command
     -h, --help       Print this help message
 
 EXAMPLES:
@@ -337,6 +708,9 @@ EXAMPLES:
 IN-GAME COMMANDS:
     You can also toggle these options during gameplay using:
     stats, tt, eval, analysis, verbose
+    Save and continue games with: save <file>, load <file>
+    Ask for a suggested move with: hint; toggle move grading with: coach
+    Turn the board around with: flip
 "#
     );
 }